@@ -0,0 +1,143 @@
+//! Fetch and deserialize program accounts over RPC.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use solana_client::rpc_client::RpcClient;
+
+use crate::pda::{
+    bonding_curve_pda, compliance_gate_pda, conditional_order_pda, creator_stats_pda, dca_schedule_pda, fee_share_stake_pda, fee_share_vault_pda,
+    funding_round_pda, global_config_pda, global_stats_pda, holder_snapshot_pda,
+    insurance_fund_pda, insurance_payout_pda, kyc_attestation_pda, limit_order_pda, otc_deal_pda, payment_stream_pda, project_update_pda,
+    round_position_pda, snapshot_registry_pda, sol_vesting_pda, token_lock_pda, trade_delegate_pda, trader_position_pda,
+    update_feed_pda, vesting_pda,
+};
+use crate::{
+    BondingCurve, ClientError, ComplianceGate, ConditionalOrder, CreatorStats, DcaSchedule, FeeShareStake, FeeShareVault, FundingRound,
+    GlobalConfig, GlobalStats, HolderSnapshot, InsuranceFund, InsurancePayout, KycAttestation, LimitOrder, OtcDeal,
+    PaymentStream, ProjectUpdate, ProjectUpdateFeed, Result, RoundPosition, SnapshotRegistry,
+    SolVestingSchedule, TokenLock, TradeDelegate, TraderPosition, VestingSchedule,
+};
+
+/// Fetch any program account and deserialize it (discriminator checked)
+pub fn fetch<T: AccountDeserialize>(client: &RpcClient, address: &Pubkey) -> Result<T> {
+    let data = client
+        .get_account_data(address)
+        .map_err(|_| ClientError::AccountNotFound(*address))?;
+    Ok(T::try_deserialize(&mut data.as_slice())?)
+}
+
+pub fn fetch_global_config(client: &RpcClient) -> Result<GlobalConfig> {
+    fetch(client, &global_config_pda().0)
+}
+
+pub fn fetch_global_stats(client: &RpcClient) -> Result<GlobalStats> {
+    fetch(client, &global_stats_pda().0)
+}
+
+pub fn fetch_creator_stats(client: &RpcClient, creator: &Pubkey) -> Result<CreatorStats> {
+    fetch(client, &creator_stats_pda(creator).0)
+}
+
+pub fn fetch_bonding_curve(client: &RpcClient, mint: &Pubkey) -> Result<BondingCurve> {
+    fetch(client, &bonding_curve_pda(mint).0)
+}
+
+pub fn fetch_trader_position(
+    client: &RpcClient,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<TraderPosition> {
+    fetch(client, &trader_position_pda(mint, owner).0)
+}
+
+pub fn fetch_vesting_schedule(
+    client: &RpcClient,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+) -> Result<VestingSchedule> {
+    fetch(client, &vesting_pda(mint, beneficiary).0)
+}
+
+pub fn fetch_token_lock(client: &RpcClient, mint: &Pubkey, owner: &Pubkey, index: u64) -> Result<TokenLock> {
+    fetch(client, &token_lock_pda(mint, owner, index).0)
+}
+
+pub fn fetch_otc_deal(client: &RpcClient, seller: &Pubkey, id: u64) -> Result<OtcDeal> {
+    fetch(client, &otc_deal_pda(seller, id).0)
+}
+
+pub fn fetch_funding_round(client: &RpcClient, mint: &Pubkey) -> Result<FundingRound> {
+    fetch(client, &funding_round_pda(mint).0)
+}
+
+pub fn fetch_round_position(client: &RpcClient, mint: &Pubkey, investor: &Pubkey) -> Result<RoundPosition> {
+    fetch(client, &round_position_pda(mint, investor).0)
+}
+
+pub fn fetch_snapshot_registry(client: &RpcClient, mint: &Pubkey) -> Result<SnapshotRegistry> {
+    fetch(client, &snapshot_registry_pda(mint).0)
+}
+
+pub fn fetch_holder_snapshot(client: &RpcClient, mint: &Pubkey, epoch: u64) -> Result<HolderSnapshot> {
+    fetch(client, &holder_snapshot_pda(mint, epoch).0)
+}
+
+pub fn fetch_update_feed(client: &RpcClient, project: &Pubkey) -> Result<ProjectUpdateFeed> {
+    fetch(client, &update_feed_pda(project).0)
+}
+
+pub fn fetch_project_update(client: &RpcClient, project: &Pubkey, sequence: u64) -> Result<ProjectUpdate> {
+    fetch(client, &project_update_pda(project, sequence).0)
+}
+
+pub fn fetch_kyc_attestation(client: &RpcClient, verifier: &Pubkey, wallet: &Pubkey) -> Result<KycAttestation> {
+    fetch(client, &kyc_attestation_pda(verifier, wallet).0)
+}
+
+pub fn fetch_trade_delegate(client: &RpcClient, owner: &Pubkey, session_key: &Pubkey) -> Result<TradeDelegate> {
+    fetch(client, &trade_delegate_pda(owner, session_key).0)
+}
+
+pub fn fetch_limit_order(client: &RpcClient, owner: &Pubkey, order_id: u64) -> Result<LimitOrder> {
+    fetch(client, &limit_order_pda(owner, order_id).0)
+}
+
+pub fn fetch_conditional_order(client: &RpcClient, owner: &Pubkey, order_id: u64) -> Result<ConditionalOrder> {
+    fetch(client, &conditional_order_pda(owner, order_id).0)
+}
+
+pub fn fetch_dca_schedule(client: &RpcClient, owner: &Pubkey, mint: &Pubkey) -> Result<DcaSchedule> {
+    fetch(client, &dca_schedule_pda(owner, mint).0)
+}
+
+pub fn fetch_compliance_gate(client: &RpcClient, mint: &Pubkey) -> Result<ComplianceGate> {
+    fetch(client, &compliance_gate_pda(mint).0)
+}
+
+pub fn fetch_payment_stream(client: &RpcClient, payer: &Pubkey, id: u64) -> Result<PaymentStream> {
+    fetch(client, &payment_stream_pda(payer, id).0)
+}
+
+pub fn fetch_sol_vesting_schedule(
+    client: &RpcClient,
+    funder: &Pubkey,
+    beneficiary: &Pubkey,
+) -> Result<SolVestingSchedule> {
+    fetch(client, &sol_vesting_pda(funder, beneficiary).0)
+}
+
+pub fn fetch_fee_share_vault(client: &RpcClient) -> Result<FeeShareVault> {
+    fetch(client, &fee_share_vault_pda().0)
+}
+
+pub fn fetch_fee_share_stake(client: &RpcClient, owner: &Pubkey) -> Result<FeeShareStake> {
+    fetch(client, &fee_share_stake_pda(owner).0)
+}
+
+pub fn fetch_insurance_fund(client: &RpcClient) -> Result<InsuranceFund> {
+    fetch(client, &insurance_fund_pda().0)
+}
+
+pub fn fetch_insurance_payout(client: &RpcClient, payout_id: u64) -> Result<InsurancePayout> {
+    fetch(client, &insurance_payout_pda(payout_id).0)
+}
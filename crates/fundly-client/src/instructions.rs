@@ -0,0 +1,2162 @@
+//! Typed instruction builders.
+//!
+//! Each builder derives every PDA and associated token account itself, so
+//! callers only supply the wallets and amounts involved.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::{self, spl_token::native_mint};
+use fundly::{BondingCurve, OrderTrigger, StreamTerms, SwapDirection};
+
+use crate::pda::{
+    bonding_curve_pda, buyer_record_pda, compliance_gate_pda, conditional_order_pda, creator_allocation_pda, creator_stats_pda, curve_oracle_pda, dca_schedule_pda,
+    event_authority_pda, fee_share_stake_pda, fee_share_vault_pda, funding_round_pda, global_config_pda,
+    global_stats_pda, graduation_archive_pda, holder_snapshot_pda, insurance_fund_pda, jackpot_pda, insurance_payout_pda, kyc_attestation_pda,
+    limit_order_pda, live_candle_pda, migration_authority_pda, migration_vault_pda, otc_deal_pda,
+    payment_stream_pda, project_update_pda, randomness_request_pda, reflection_claim_pda, reflection_distribution_pda,
+    reflection_pool_pda, reflection_vault_pda, round_position_pda, sol_vault_pda, sol_vesting_pda,
+    token_lock_pda, trade_delegate_pda, trade_guard_pda, trader_position_pda, update_feed_pda, user_points_pda, vesting_pda,
+};
+
+// The curve's native-mint ATA, used as its vault once `enable_wsol_vault` has run
+fn curve_wsol_vault(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&bonding_curve_pda(mint).0, &native_mint::ID)
+}
+
+/// Optional features of a curve that add accounts to the routes trading against it
+/// (delegated trades, orders, DCA, curve-to-curve swaps and baskets)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CurveExtras {
+    /// The curve takes reflections on trades
+    pub reflections: bool,
+    /// The curve feeds a jackpot from its buys
+    pub jackpot: bool,
+    /// The curve holds its SOL side as wSOL; sells pay out to the trader's native-mint ATA
+    pub wsol_vault: bool,
+}
+
+impl CurveExtras {
+    pub fn of(curve: &BondingCurve) -> Self {
+        Self {
+            reflections: curve.has_reflections(),
+            jackpot: curve.has_jackpot(),
+            wsol_vault: curve.has_wsol_vault(),
+        }
+    }
+
+    fn wsol_vault(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.wsol_vault.then(|| curve_wsol_vault(mint))
+    }
+
+    fn wsol_account(&self, trader: &Pubkey) -> Option<Pubkey> {
+        self.wsol_vault.then(|| get_associated_token_address(trader, &native_mint::ID))
+    }
+
+    fn reflection_vault(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.reflections.then(|| reflection_vault_pda(mint).0)
+    }
+
+    fn jackpot(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.jackpot.then(|| jackpot_pda(mint).0)
+    }
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `initial_buy_sol` optionally executes the creator's dev buy in the same instruction
+/// `trading_start_time` optionally keeps the curve closed until an announced unix time
+pub fn initialize_bonding_curve(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    treasury: &Pubkey,
+    token_supply: u64,
+    initial_buy_sol: Option<u64>,
+    trading_start_time: Option<i64>,
+) -> Instruction {
+    build(
+        initialize_bonding_curve_accounts(creator, mint, treasury),
+        fundly::instruction::InitializeBondingCurve {
+            token_supply,
+            initial_buy_sol,
+            trading_start_time,
+        },
+    )
+}
+
+/// `initialize_bonding_curve` on a platform pricing its launch fee in USD, converted at the
+/// `sol_usd_price` Pyth update
+pub fn initialize_bonding_curve_usd_fee(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    treasury: &Pubkey,
+    token_supply: u64,
+    sol_usd_price: &Pubkey,
+) -> Instruction {
+    build(
+        fundly::accounts::InitializeBondingCurve {
+            sol_usd_price: Some(*sol_usd_price),
+            ..initialize_bonding_curve_accounts(creator, mint, treasury)
+        },
+        fundly::instruction::InitializeBondingCurve {
+            token_supply,
+            initial_buy_sol: None,
+            trading_start_time: None,
+        },
+    )
+}
+
+fn initialize_bonding_curve_accounts(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    treasury: &Pubkey,
+) -> fundly::accounts::InitializeBondingCurve {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    fundly::accounts::InitializeBondingCurve {
+        bonding_curve,
+        mint: *mint,
+        sol_vault: sol_vault_pda(mint).0,
+        bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+        curve_oracle: curve_oracle_pda(mint).0,
+        creator_token_account: get_associated_token_address(creator, mint),
+        graduation_archive: graduation_archive_pda(mint).0,
+        global_config: global_config_pda().0,
+        global_stats: global_stats_pda().0,
+        creator_stats: creator_stats_pda(creator).0,
+        treasury: *treasury,
+        sol_usd_price: None,
+        creator: *creator,
+        system_program: system_program::ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        rent: anchor_lang::solana_program::sysvar::rent::ID,
+    }
+}
+
+/// Gate buys on the curve behind attestations from the platform's KYC verifier
+pub fn enable_kyc_gating(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::EnableKycGating {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            global_config: global_config_pda().0,
+            creator: *creator,
+        },
+        fundly::instruction::EnableKycGating {},
+    )
+}
+
+/// Cap the net SOL any wallet may put into the curve; only valid before the first trade
+pub fn set_max_sol_per_wallet(creator: &Pubkey, mint: &Pubkey, max_sol_per_wallet: u64) -> Instruction {
+    build(
+        fundly::accounts::SetMaxSolPerWallet {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            creator: *creator,
+        },
+        fundly::instruction::SetMaxSolPerWallet { max_sol_per_wallet },
+    )
+}
+
+/// Scale the curve's virtual SOL by `weights_bps` over the quarters of a `duration` second
+/// window opened by finalize_launch; only valid before the launch is finalized
+pub fn enable_liquidity_bootstrapping(creator: &Pubkey, mint: &Pubkey, weights_bps: [u16; 4], duration: i64) -> Instruction {
+    build(
+        fundly::accounts::EnableLiquidityBootstrapping {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            creator: *creator,
+        },
+        fundly::instruction::EnableLiquidityBootstrapping { weights_bps, duration },
+    )
+}
+
+/// Set the creator's royalty on every trade; only valid before the first trade
+pub fn set_creator_royalty(creator: &Pubkey, mint: &Pubkey, royalty_bps: u16) -> Instruction {
+    build(
+        fundly::accounts::SetCreatorRoyalty {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            creator: *creator,
+        },
+        fundly::instruction::SetCreatorRoyalty { royalty_bps },
+    )
+}
+
+/// Permissionless: pays the curve's accrued royalties to `creator`
+pub fn claim_creator_royalties(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::ClaimCreatorRoyalties {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            creator: *creator,
+        },
+        fundly::instruction::ClaimCreatorRoyalties {},
+    )
+}
+
+/// Escrow `allocation_bps` of the supply for the creator until `release_delay` seconds after
+/// migration; only valid before the launch is finalized
+pub fn escrow_creator_allocation(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    allocation_bps: u16,
+    release_delay: i64,
+) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    build(
+        fundly::accounts::EscrowCreatorAllocation {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            creator_allocation_escrow: creator_allocation_pda(mint).0,
+            creator: *creator,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        fundly::instruction::EscrowCreatorAllocation { allocation_bps, release_delay },
+    )
+}
+
+/// Permissionless: pays the released creator allocation to `creator`'s token account
+pub fn claim_creator_allocation(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::ClaimCreatorAllocation {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            creator_allocation_escrow: creator_allocation_pda(mint).0,
+            creator_token_account: get_associated_token_address(creator, mint),
+            creator: *creator,
+            token_program: token::ID,
+        },
+        fundly::instruction::ClaimCreatorAllocation {},
+    )
+}
+
+/// Admin only: feeds `fee_share_bps` of the curve's buy fees to a jackpot drawn every
+/// `buys_per_draw` buys among the recent buyers at random
+pub fn enable_jackpot(authority: &Pubkey, mint: &Pubkey, fee_share_bps: u16, buys_per_draw: u64) -> Instruction {
+    build(
+        fundly::accounts::EnableJackpot {
+            global_config: global_config_pda().0,
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            jackpot: jackpot_pda(mint).0,
+            authority: *authority,
+            payer: *authority,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::EnableJackpot { fee_share_bps, buys_per_draw },
+    )
+}
+
+/// Pay the winner of the curve's latest jackpot draw
+pub fn claim_jackpot(mint: &Pubkey, winner: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::ClaimJackpot {
+            jackpot: jackpot_pda(mint).0,
+            winner: *winner,
+        },
+        fundly::instruction::ClaimJackpot {},
+    )
+}
+
+/// Open a draw for `consumer` against a Switchboard randomness account committed in the
+/// previous slot
+pub fn request_randomness(
+    payer: &Pubkey,
+    consumer: &Pubkey,
+    kind: fundly::RandomnessConsumerKind,
+    randomness_account: &Pubkey,
+) -> Instruction {
+    build(
+        request_randomness_accounts(payer, consumer, randomness_account),
+        fundly::instruction::RequestRandomness { kind },
+    )
+}
+
+/// `request_randomness` replacing an expired request bound to `previous_randomness_account`,
+/// whose value must not have been revealed
+pub fn replace_randomness_request(
+    payer: &Pubkey,
+    consumer: &Pubkey,
+    kind: fundly::RandomnessConsumerKind,
+    randomness_account: &Pubkey,
+    previous_randomness_account: &Pubkey,
+) -> Instruction {
+    build(
+        fundly::accounts::RequestRandomness {
+            previous_randomness_account: Some(*previous_randomness_account),
+            ..request_randomness_accounts(payer, consumer, randomness_account)
+        },
+        fundly::instruction::RequestRandomness { kind },
+    )
+}
+
+fn request_randomness_accounts(
+    payer: &Pubkey,
+    consumer: &Pubkey,
+    randomness_account: &Pubkey,
+) -> fundly::accounts::RequestRandomness {
+    fundly::accounts::RequestRandomness {
+        randomness_request: randomness_request_pda(consumer).0,
+        consumer: *consumer,
+        randomness_account: *randomness_account,
+        previous_randomness_account: None,
+        global_config: global_config_pda().0,
+        payer: *payer,
+        system_program: system_program::ID,
+    }
+}
+
+/// Deliver the revealed value to `consumer`
+pub fn fulfill_randomness(consumer: &Pubkey, randomness_account: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::FulfillRandomness {
+            randomness_request: randomness_request_pda(consumer).0,
+            consumer: *consumer,
+            randomness_account: *randomness_account,
+        },
+        fundly::instruction::FulfillRandomness {},
+    )
+}
+
+/// Reflect `reflection_bps` of every trade's tokens to holders; only valid before the launch
+/// is finalized
+pub fn enable_reflections(creator: &Pubkey, mint: &Pubkey, reflection_bps: u16) -> Instruction {
+    build(
+        fundly::accounts::EnableReflections {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            reflection_pool: reflection_pool_pda(mint).0,
+            reflection_vault: reflection_vault_pda(mint).0,
+            creator: *creator,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        fundly::instruction::EnableReflections { reflection_bps },
+    )
+}
+
+/// Permissionless: shares the accrued reflections among the holders in snapshot `epoch`
+pub fn distribute_reflections(payer: &Pubkey, mint: &Pubkey, epoch: u64) -> Instruction {
+    build(
+        fundly::accounts::DistributeReflections {
+            reflection_pool: reflection_pool_pda(mint).0,
+            reflection_vault: reflection_vault_pda(mint).0,
+            holder_snapshot: holder_snapshot_pda(mint, epoch).0,
+            reflection_distribution: reflection_distribution_pda(mint, epoch).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::DistributeReflections { epoch },
+    )
+}
+
+/// `proof` links the holder's `balance` to snapshot `epoch`'s root; the tokens go to the
+/// holder's associated token account
+pub fn claim_reflections(
+    holder: &Pubkey,
+    mint: &Pubkey,
+    epoch: u64,
+    balance: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        fundly::accounts::ClaimReflections {
+            reflection_pool: reflection_pool_pda(mint).0,
+            reflection_vault: reflection_vault_pda(mint).0,
+            holder_snapshot: holder_snapshot_pda(mint, epoch).0,
+            reflection_distribution: reflection_distribution_pda(mint, epoch).0,
+            reflection_claim: reflection_claim_pda(mint, epoch, holder).0,
+            holder_token_account: get_associated_token_address(holder, mint),
+            holder: *holder,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        fundly::instruction::ClaimReflections { epoch, balance, proof },
+    )
+}
+
+pub fn issue_kyc_attestation(verifier: &Pubkey, wallet: &Pubkey, expires_at: i64) -> Instruction {
+    build(
+        fundly::accounts::IssueKycAttestation {
+            global_config: global_config_pda().0,
+            kyc_attestation: kyc_attestation_pda(verifier, wallet).0,
+            verifier: *verifier,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::IssueKycAttestation { wallet: *wallet, expires_at },
+    )
+}
+
+pub fn revoke_kyc_attestation(verifier: &Pubkey, wallet: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::RevokeKycAttestation {
+            kyc_attestation: kyc_attestation_pda(verifier, wallet).0,
+            verifier: *verifier,
+        },
+        fundly::instruction::RevokeKycAttestation {},
+    )
+}
+
+/// Hold the curve's SOL side as wSOL; only valid before the first trade
+pub fn enable_wsol_vault(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::EnableWsolVault {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            native_mint: native_mint::ID,
+            curve_wsol_vault: curve_wsol_vault(mint),
+            creator: *creator,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::EnableWsolVault {},
+    )
+}
+
+/// Open a launched curve to buys once its supply is deposited and mint authorities revoked
+/// (anyone may send it). `creator_allocation` must be set once the creator escrowed an allocation
+pub fn finalize_launch(mint: &Pubkey, creator_allocation: bool) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    build(
+        fundly::accounts::FinalizeLaunch {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            creator_allocation_escrow: creator_allocation.then(|| creator_allocation_pda(mint).0),
+        },
+        fundly::instruction::FinalizeLaunch {},
+    )
+}
+
+/// Hold the curve's SOL side in the curve account itself; only valid before the first trade
+pub fn enable_inline_sol(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::EnableInlineSol {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            creator: *creator,
+        },
+        fundly::instruction::EnableInlineSol {},
+    )
+}
+
+/// Only valid while the creator's dev buy is the curve's sole trade
+/// `wsol_vault` must be set for curves that hold their SOL side as wSOL, and
+/// `creator_allocation` for curves with an escrowed creator allocation
+pub fn cancel_bonding_curve(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    treasury: &Pubkey,
+    wsol_vault: bool,
+    creator_allocation: bool,
+) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    build(
+        fundly::accounts::CancelBondingCurve {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_wsol_vault: wsol_vault.then(|| curve_wsol_vault(mint)),
+            curve_oracle: curve_oracle_pda(mint).0,
+            creator_allocation_escrow: creator_allocation.then(|| creator_allocation_pda(mint).0),
+            creator_token_account: get_associated_token_address(creator, mint),
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            treasury: *treasury,
+            creator: *creator,
+            token_program: token::ID,
+        },
+        fundly::instruction::CancelBondingCurve {},
+    )
+}
+
+fn buy_accounts(buyer: &Pubkey, mint: &Pubkey) -> fundly::accounts::BuyTokens {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    fundly::accounts::BuyTokens {
+        bonding_curve,
+        mint: *mint,
+        bonding_curve_sol_vault: sol_vault_pda(mint).0,
+        bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+        curve_oracle: curve_oracle_pda(mint).0,
+        live_candle: live_candle_pda(mint).0,
+        user_points: user_points_pda(buyer).0,
+        trade_guard: trade_guard_pda(mint, buyer).0,
+        buyer_token_account: get_associated_token_address(buyer, mint),
+        buyer_record: Some(buyer_record_pda(mint, buyer).0),
+        trader_position: None,
+        competition: None,
+        competition_entry: None,
+        curve_wsol_vault: None,
+        migration_sol_vault: None,
+        migration_token_account: None,
+        migration_authority: None,
+        creator_stats: None,
+        treasury: None,
+        kyc_attestation: None,
+        compliance_gate: None,
+        reflection_vault: None,
+        jackpot: None,
+        global_config: global_config_pda().0,
+        global_stats: global_stats_pda().0,
+        buyer: *buyer,
+        payer: *buyer,
+        system_program: system_program::ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        event_authority: event_authority_pda().0,
+        program: fundly::ID,
+    }
+}
+
+fn sell_accounts(seller: &Pubkey, mint: &Pubkey) -> fundly::accounts::SellTokens {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    fundly::accounts::SellTokens {
+        bonding_curve,
+        mint: *mint,
+        bonding_curve_sol_vault: sol_vault_pda(mint).0,
+        bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+        curve_oracle: curve_oracle_pda(mint).0,
+        live_candle: live_candle_pda(mint).0,
+        user_points: user_points_pda(seller).0,
+        trade_guard: trade_guard_pda(mint, seller).0,
+        seller_token_account: get_associated_token_address(seller, mint),
+        trader_position: None,
+        competition: None,
+        competition_entry: None,
+        curve_wsol_vault: None,
+        seller_wsol_account: None,
+        buyer_record: None,
+        reflection_vault: None,
+        global_config: global_config_pda().0,
+        global_stats: global_stats_pda().0,
+        seller: *seller,
+        payer: *seller,
+        system_program: system_program::ID,
+        token_program: token::ID,
+        event_authority: event_authority_pda().0,
+        program: fundly::ID,
+    }
+}
+
+pub fn buy_tokens(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        buy_accounts(buyer, mint),
+        fundly::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps },
+    )
+}
+
+/// Accounts a buy needs to migrate the curve it graduates
+#[derive(Clone, Copy, Debug)]
+pub struct BuyMigration<'a> {
+    /// Creator of the curve, whose stats record the migration
+    pub creator: Pubkey,
+    pub treasury: Pubkey,
+    /// Recipients of the global config's treasury split in slot order (empty when none is
+    /// configured)
+    pub treasury_split: &'a [Pubkey],
+}
+
+/// Like `buy_tokens`, but migrates the curve in the same transaction if this buy crosses
+/// the migration threshold (the migration token account must already exist)
+pub fn buy_tokens_and_migrate(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    migration: &BuyMigration,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    let migration_authority = migration_authority_pda().0;
+    let mut instruction = build(
+        fundly::accounts::BuyTokens {
+            migration_sol_vault: Some(migration_vault_pda(mint).0),
+            migration_token_account: Some(get_associated_token_address(&migration_authority, mint)),
+            migration_authority: Some(migration_authority),
+            creator_stats: Some(creator_stats_pda(&migration.creator).0),
+            treasury: Some(migration.treasury),
+            ..buy_accounts(buyer, mint)
+        },
+        fundly::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps },
+    );
+    // The recipients follow the holder-discount slot, which the program ID leaves empty
+    if !migration.treasury_split.is_empty() {
+        instruction.accounts.push(AccountMeta::new_readonly(fundly::ID, false));
+        instruction
+            .accounts
+            .extend(migration.treasury_split.iter().map(|recipient| AccountMeta::new(*recipient, false)));
+    }
+    instruction
+}
+
+/// Buy from a curve that takes reflections, whose share of the tokens goes to its vault
+pub fn buy_tokens_reflecting(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::BuyTokens {
+            reflection_vault: Some(reflection_vault_pda(mint).0),
+            ..buy_accounts(buyer, mint)
+        },
+        fundly::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps },
+    )
+}
+
+/// Buy from a curve with a jackpot, entering this buyer into its draws
+pub fn buy_tokens_with_jackpot(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::BuyTokens {
+            jackpot: Some(jackpot_pda(mint).0),
+            ..buy_accounts(buyer, mint)
+        },
+        fundly::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps },
+    )
+}
+
+/// Buy from a curve that holds its SOL side as wSOL (still paid in lamports)
+pub fn buy_tokens_wsol(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::BuyTokens {
+            curve_wsol_vault: Some(curve_wsol_vault(mint)),
+            ..buy_accounts(buyer, mint)
+        },
+        fundly::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps },
+    )
+}
+
+/// Buy on a KYC-gated curve, presenting the buyer's attestation from `verifier`
+pub fn buy_tokens_attested(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    verifier: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::BuyTokens {
+            kyc_attestation: Some(kyc_attestation_pda(verifier, buyer).0),
+            ..buy_accounts(buyer, mint)
+        },
+        fundly::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps },
+    )
+}
+
+/// Buy on a compliance-gated curve, presenting the mint's gate and, when the gate
+/// requires one, the buyer's attestation from `verifier`
+pub fn buy_tokens_gated(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    verifier: Option<&Pubkey>,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::BuyTokens {
+            kyc_attestation: verifier.map(|verifier| kyc_attestation_pda(verifier, buyer).0),
+            compliance_gate: Some(compliance_gate_pda(mint).0),
+            ..buy_accounts(buyer, mint)
+        },
+        fundly::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps },
+    )
+}
+
+/// Buy in a transaction sponsored by `relayer`, which must sign and pay the fees; the buyer
+/// still signs for its funds and pays the relayer `relayer_tip` lamports
+pub fn buy_tokens_relayed(
+    relayer: &Pubkey,
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+    relayer_tip: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::BuyTokens { payer: *relayer, ..buy_accounts(buyer, mint) },
+        fundly::instruction::BuyTokensRelayed { sol_amount, min_tokens_out, max_price_impact_bps, relayer_tip },
+    )
+}
+
+/// Buy with another token via `route`, a Jupiter swap instruction whose output is the buyer's
+/// wSOL associated token account. That account must exist when the buy executes; it is
+/// closed to unwrap the SOL
+pub fn buy_tokens_via_jupiter(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    route: Instruction,
+    min_sol_received: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    let mut instruction = build(
+        buy_accounts(buyer, mint),
+        fundly::instruction::BuyTokensViaJupiter {
+            route_data: route.data,
+            min_sol_received,
+            min_tokens_out,
+            max_price_impact_bps,
+        },
+    );
+    instruction.accounts.push(AccountMeta::new_readonly(route.program_id, false));
+    instruction
+        .accounts
+        .push(AccountMeta::new(get_associated_token_address(buyer, &native_mint::ID), false));
+    instruction.accounts.extend(route.accounts);
+    instruction
+}
+
+/// Authorize `session_key` to trade for `owner` within limits, depositing `deposit` lamports
+/// for it to spend on buys
+pub fn authorize_trade_delegate(
+    owner: &Pubkey,
+    session_key: &Pubkey,
+    max_sol_per_trade: u64,
+    expires_at: i64,
+    allowed_mints: Vec<Pubkey>,
+    deposit: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::AuthorizeTradeDelegate {
+            trade_delegate: trade_delegate_pda(owner, session_key).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::AuthorizeTradeDelegate {
+            session_key: *session_key,
+            max_sol_per_trade,
+            expires_at,
+            allowed_mints,
+            deposit,
+        },
+    )
+}
+
+pub fn revoke_trade_delegate(owner: &Pubkey, session_key: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::RevokeTradeDelegate {
+            trade_delegate: trade_delegate_pda(owner, session_key).0,
+            owner: *owner,
+        },
+        fundly::instruction::RevokeTradeDelegate {},
+    )
+}
+
+/// Buy for `owner`, signed by its session key and paid from the delegate's deposit
+pub fn delegated_buy(
+    session_key: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    extras: CurveExtras,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    build(
+        fundly::accounts::DelegatedBuy {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_oracle: curve_oracle_pda(mint).0,
+            live_candle: live_candle_pda(mint).0,
+            user_points: user_points_pda(owner).0,
+            trade_guard: trade_guard_pda(mint, owner).0,
+            owner_token_account: get_associated_token_address(owner, mint),
+            buyer_record: Some(buyer_record_pda(mint, owner).0),
+            trade_delegate: trade_delegate_pda(owner, session_key).0,
+            curve_wsol_vault: extras.wsol_vault(mint),
+            kyc_attestation: None,
+            compliance_gate: None,
+            reflection_vault: extras.reflection_vault(mint),
+            jackpot: extras.jackpot(mint),
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            owner: *owner,
+            session_key: *session_key,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::DelegatedBuy { sol_amount, min_tokens_out, max_price_impact_bps },
+    )
+}
+
+/// Sell `owner`'s tokens, signed by its session key; the delegate account must be approved
+/// as SPL delegate of the owner's token account
+pub fn delegated_sell(
+    session_key: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    extras: CurveExtras,
+    token_amount: u64,
+    min_sol_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    build(
+        fundly::accounts::DelegatedSell {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_oracle: curve_oracle_pda(mint).0,
+            live_candle: live_candle_pda(mint).0,
+            user_points: user_points_pda(owner).0,
+            trade_guard: trade_guard_pda(mint, owner).0,
+            owner_token_account: get_associated_token_address(owner, mint),
+            trade_delegate: trade_delegate_pda(owner, session_key).0,
+            curve_wsol_vault: extras.wsol_vault(mint),
+            owner_wsol_account: extras.wsol_account(owner),
+            reflection_vault: extras.reflection_vault(mint),
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            owner: *owner,
+            session_key: *session_key,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::DelegatedSell { token_amount, min_sol_out, max_price_impact_bps },
+    )
+}
+
+/// Place a limit order escrowing `amount` lamports (buys) or tokens (sells) until the curve
+/// price crosses `price` (lamports per whole token)
+pub fn place_limit_order(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    order_id: u64,
+    side: SwapDirection,
+    price: u64,
+    amount: u64,
+    expires_at: i64,
+) -> Instruction {
+    let limit_order = limit_order_pda(owner, order_id).0;
+    build(
+        fundly::accounts::PlaceLimitOrder {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            limit_order,
+            escrow_token_account: get_associated_token_address(&limit_order, mint),
+            owner_token_account: (side == SwapDirection::Sell)
+                .then(|| get_associated_token_address(owner, mint)),
+            owner: *owner,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::PlaceLimitOrder { order_id, side, price, amount, expires_at },
+    )
+}
+
+/// Fill `owner`'s limit order; `keeper` pays for any accounts the trade creates
+pub fn fill_limit_order(
+    keeper: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    extras: CurveExtras,
+    order_id: u64,
+    side: SwapDirection,
+) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    let limit_order = limit_order_pda(owner, order_id).0;
+    build(
+        fundly::accounts::FillLimitOrder {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_oracle: curve_oracle_pda(mint).0,
+            live_candle: live_candle_pda(mint).0,
+            user_points: user_points_pda(owner).0,
+            trade_guard: trade_guard_pda(mint, owner).0,
+            owner_token_account: get_associated_token_address(owner, mint),
+            buyer_record: Some(buyer_record_pda(mint, owner).0),
+            limit_order,
+            escrow_token_account: get_associated_token_address(&limit_order, mint),
+            kyc_attestation: None,
+            compliance_gate: None,
+            curve_wsol_vault: extras.wsol_vault(mint),
+            owner_wsol_account: extras.wsol_account(owner).filter(|_| side == SwapDirection::Sell),
+            reflection_vault: extras.reflection_vault(mint),
+            jackpot: extras.jackpot(mint),
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            owner: *owner,
+            keeper: *keeper,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::FillLimitOrder {},
+    )
+}
+
+/// Cancel `owner`'s limit order, signed by the owner or, once it has expired, anyone
+pub fn cancel_limit_order(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    order_id: u64,
+    side: SwapDirection,
+) -> Instruction {
+    let limit_order = limit_order_pda(owner, order_id).0;
+    build(
+        fundly::accounts::CancelLimitOrder {
+            mint: *mint,
+            limit_order,
+            escrow_token_account: get_associated_token_address(&limit_order, mint),
+            owner_token_account: (side == SwapDirection::Sell)
+                .then(|| get_associated_token_address(owner, mint)),
+            owner: *owner,
+            authority: *authority,
+            token_program: token::ID,
+        },
+        fundly::instruction::CancelLimitOrder {},
+    )
+}
+
+/// Sell `token_amount` tokens of `sell_mint` and buy `buy_mint` with the proceeds, receiving
+/// at least `min_tokens_out`
+pub fn swap_between_curves(
+    trader: &Pubkey,
+    sell_mint: &Pubkey,
+    sell_extras: CurveExtras,
+    buy_mint: &Pubkey,
+    buy_extras: CurveExtras,
+    token_amount: u64,
+    min_tokens_out: u64,
+) -> Instruction {
+    let sell_bonding_curve = bonding_curve_pda(sell_mint).0;
+    let buy_bonding_curve = bonding_curve_pda(buy_mint).0;
+    build(
+        fundly::accounts::SwapBetweenCurves {
+            sell_bonding_curve,
+            sell_mint: *sell_mint,
+            sell_curve_sol_vault: sol_vault_pda(sell_mint).0,
+            sell_curve_token_account: get_associated_token_address(&sell_bonding_curve, sell_mint),
+            sell_curve_oracle: curve_oracle_pda(sell_mint).0,
+            sell_live_candle: live_candle_pda(sell_mint).0,
+            sell_trade_guard: trade_guard_pda(sell_mint, trader).0,
+            seller_token_account: get_associated_token_address(trader, sell_mint),
+            buy_bonding_curve,
+            buy_mint: *buy_mint,
+            buy_curve_sol_vault: sol_vault_pda(buy_mint).0,
+            buy_curve_token_account: get_associated_token_address(&buy_bonding_curve, buy_mint),
+            buy_curve_oracle: curve_oracle_pda(buy_mint).0,
+            buy_live_candle: live_candle_pda(buy_mint).0,
+            buy_trade_guard: trade_guard_pda(buy_mint, trader).0,
+            buyer_token_account: get_associated_token_address(trader, buy_mint),
+            buyer_record: Some(buyer_record_pda(buy_mint, trader).0),
+            user_points: user_points_pda(trader).0,
+            kyc_attestation: None,
+            compliance_gate: None,
+            sell_curve_wsol_vault: sell_extras.wsol_vault(sell_mint),
+            trader_wsol_account: sell_extras.wsol_account(trader),
+            buy_curve_wsol_vault: buy_extras.wsol_vault(buy_mint),
+            sell_reflection_vault: sell_extras.reflection_vault(sell_mint),
+            buy_reflection_vault: buy_extras.reflection_vault(buy_mint),
+            buy_jackpot: buy_extras.jackpot(buy_mint),
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            trader: *trader,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::SwapBetweenCurves { token_amount, min_tokens_out },
+    )
+}
+
+/// Buy several curves at once, splitting `sol_amount` between them
+/// `legs` are (mint, weight in bps, min_tokens_out, extras); weights must sum to 10_000
+pub fn buy_basket(buyer: &Pubkey, sol_amount: u64, legs: &[(Pubkey, u16, u64, CurveExtras)]) -> Instruction {
+    let mut instruction = build(
+        fundly::accounts::BuyBasket {
+            user_points: user_points_pda(buyer).0,
+            kyc_attestation: None,
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            buyer: *buyer,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::BuyBasket {
+            sol_amount,
+            weights: legs.iter().map(|(_, weight, _, _)| *weight).collect(),
+            min_tokens_out: legs.iter().map(|(_, _, min_tokens_out, _)| *min_tokens_out).collect(),
+        },
+    );
+    // Optional accounts a curve doesn't have are passed as the program ID
+    let optional = |address: Option<Pubkey>| AccountMeta::new(address.unwrap_or(fundly::ID), false);
+    for (mint, _, _, extras) in legs {
+        let bonding_curve = bonding_curve_pda(mint).0;
+        instruction.accounts.extend([
+            AccountMeta::new(bonding_curve, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(sol_vault_pda(mint).0, false),
+            AccountMeta::new(get_associated_token_address(&bonding_curve, mint), false),
+            AccountMeta::new(curve_oracle_pda(mint).0, false),
+            AccountMeta::new(live_candle_pda(mint).0, false),
+            AccountMeta::new(trade_guard_pda(mint, buyer).0, false),
+            AccountMeta::new(get_associated_token_address(buyer, mint), false),
+            AccountMeta::new(buyer_record_pda(mint, buyer).0, false),
+            optional(extras.wsol_vault(mint)),
+            optional(extras.reflection_vault(mint)),
+            optional(extras.jackpot(mint)),
+        ]);
+    }
+    instruction
+}
+
+/// Escrow `token_amount` tokens and `keeper_tip` lamports in a stop-loss or take-profit sell
+pub fn place_conditional_order(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    order_id: u64,
+    trigger: OrderTrigger,
+    token_amount: u64,
+    slippage_bps: u16,
+    keeper_tip: u64,
+) -> Instruction {
+    let conditional_order = conditional_order_pda(owner, order_id).0;
+    build(
+        fundly::accounts::PlaceConditionalOrder {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            conditional_order,
+            escrow_token_account: get_associated_token_address(&conditional_order, mint),
+            owner_token_account: get_associated_token_address(owner, mint),
+            owner: *owner,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::PlaceConditionalOrder { order_id, trigger, token_amount, slippage_bps, keeper_tip },
+    )
+}
+
+/// Execute `owner`'s triggered conditional order; `keeper` receives its tip
+pub fn execute_conditional_order(
+    keeper: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    extras: CurveExtras,
+    order_id: u64,
+) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    let conditional_order = conditional_order_pda(owner, order_id).0;
+    build(
+        fundly::accounts::ExecuteConditionalOrder {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_oracle: curve_oracle_pda(mint).0,
+            live_candle: live_candle_pda(mint).0,
+            user_points: user_points_pda(owner).0,
+            trade_guard: trade_guard_pda(mint, owner).0,
+            owner_token_account: get_associated_token_address(owner, mint),
+            conditional_order,
+            escrow_token_account: get_associated_token_address(&conditional_order, mint),
+            curve_wsol_vault: extras.wsol_vault(mint),
+            owner_wsol_account: extras.wsol_account(owner),
+            reflection_vault: extras.reflection_vault(mint),
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            owner: *owner,
+            keeper: *keeper,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::ExecuteConditionalOrder {},
+    )
+}
+
+pub fn cancel_conditional_order(owner: &Pubkey, mint: &Pubkey, order_id: u64) -> Instruction {
+    let conditional_order = conditional_order_pda(owner, order_id).0;
+    build(
+        fundly::accounts::CancelConditionalOrder {
+            mint: *mint,
+            conditional_order,
+            escrow_token_account: get_associated_token_address(&conditional_order, mint),
+            owner_token_account: get_associated_token_address(owner, mint),
+            owner: *owner,
+            token_program: token::ID,
+        },
+        fundly::instruction::CancelConditionalOrder {},
+    )
+}
+
+/// Schedule recurring buys of `amount_per_buy` lamports every `interval` seconds out of
+/// `deposit`, tipping the executing keeper `keeper_tip` per buy
+pub fn create_dca_schedule(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount_per_buy: u64,
+    interval: i64,
+    max_price: u64,
+    keeper_tip: u64,
+    deposit: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::CreateDcaSchedule {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            dca_schedule: dca_schedule_pda(owner, mint).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::CreateDcaSchedule { amount_per_buy, interval, max_price, keeper_tip, deposit },
+    )
+}
+
+/// Execute `owner`'s due DCA buy; `keeper` pays for any accounts the trade creates
+pub fn execute_dca_buy(keeper: &Pubkey, owner: &Pubkey, mint: &Pubkey, extras: CurveExtras) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    build(
+        fundly::accounts::ExecuteDcaBuy {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_oracle: curve_oracle_pda(mint).0,
+            live_candle: live_candle_pda(mint).0,
+            user_points: user_points_pda(owner).0,
+            trade_guard: trade_guard_pda(mint, owner).0,
+            owner_token_account: get_associated_token_address(owner, mint),
+            buyer_record: Some(buyer_record_pda(mint, owner).0),
+            dca_schedule: dca_schedule_pda(owner, mint).0,
+            curve_wsol_vault: extras.wsol_vault(mint),
+            kyc_attestation: None,
+            compliance_gate: None,
+            reflection_vault: extras.reflection_vault(mint),
+            jackpot: extras.jackpot(mint),
+            global_config: global_config_pda().0,
+            global_stats: global_stats_pda().0,
+            owner: *owner,
+            keeper: *keeper,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::ExecuteDcaBuy {},
+    )
+}
+
+pub fn cancel_dca_schedule(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::CancelDcaSchedule {
+            dca_schedule: dca_schedule_pda(owner, mint).0,
+            owner: *owner,
+        },
+        fundly::instruction::CancelDcaSchedule {},
+    )
+}
+
+/// Buy with slippage in basis points from `quoted_price` (lamports per whole token)
+pub fn buy_tokens_bps(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    sol_amount: u64,
+    quoted_price: u64,
+    slippage_bps: u16,
+) -> Instruction {
+    build(
+        buy_accounts(buyer, mint),
+        fundly::instruction::BuyTokensBps { sol_amount, quoted_price, slippage_bps },
+    )
+}
+
+pub fn sell_tokens(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    token_amount: u64,
+    min_sol_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        sell_accounts(seller, mint),
+        fundly::instruction::SellTokens { token_amount, min_sol_out, max_price_impact_bps },
+    )
+}
+
+/// Sell and credit the payout to the seller's buyer record, freeing room under a per-wallet
+/// cap; the seller must have bought from the curve before
+pub fn sell_tokens_recorded(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    token_amount: u64,
+    min_sol_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::SellTokens {
+            buyer_record: Some(buyer_record_pda(mint, seller).0),
+            ..sell_accounts(seller, mint)
+        },
+        fundly::instruction::SellTokens { token_amount, min_sol_out, max_price_impact_bps },
+    )
+}
+
+/// Sell in a transaction sponsored by `relayer`, which must sign and pay the fees; the
+/// relayer is paid `relayer_tip` lamports out of the proceeds
+pub fn sell_tokens_relayed(
+    relayer: &Pubkey,
+    seller: &Pubkey,
+    mint: &Pubkey,
+    token_amount: u64,
+    min_sol_out: u64,
+    max_price_impact_bps: Option<u16>,
+    relayer_tip: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::SellTokens { payer: *relayer, ..sell_accounts(seller, mint) },
+        fundly::instruction::SellTokensRelayed { token_amount, min_sol_out, max_price_impact_bps, relayer_tip },
+    )
+}
+
+/// Sell to a curve that takes reflections, whose share of the tokens goes to its vault
+pub fn sell_tokens_reflecting(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    token_amount: u64,
+    min_sol_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::SellTokens {
+            reflection_vault: Some(reflection_vault_pda(mint).0),
+            ..sell_accounts(seller, mint)
+        },
+        fundly::instruction::SellTokens { token_amount, min_sol_out, max_price_impact_bps },
+    )
+}
+
+/// Sell to a curve that holds its SOL side as wSOL; the payout goes to `seller_wsol_account`
+pub fn sell_tokens_wsol(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    seller_wsol_account: &Pubkey,
+    token_amount: u64,
+    min_sol_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Instruction {
+    build(
+        fundly::accounts::SellTokens {
+            curve_wsol_vault: Some(curve_wsol_vault(mint)),
+            seller_wsol_account: Some(*seller_wsol_account),
+            ..sell_accounts(seller, mint)
+        },
+        fundly::instruction::SellTokens { token_amount, min_sol_out, max_price_impact_bps },
+    )
+}
+
+/// Sell with slippage in basis points from `quoted_price` (lamports per whole token)
+pub fn sell_tokens_bps(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    token_amount: u64,
+    quoted_price: u64,
+    slippage_bps: u16,
+) -> Instruction {
+    build(
+        sell_accounts(seller, mint),
+        fundly::instruction::SellTokensBps { token_amount, quoted_price, slippage_bps },
+    )
+}
+
+/// Sell the seller's whole balance; the amount is read on-chain
+pub fn sell_all_tokens(seller: &Pubkey, mint: &Pubkey, quoted_price: u64, slippage_bps: u16) -> Instruction {
+    build(
+        sell_accounts(seller, mint),
+        fundly::instruction::SellAllTokens { quoted_price, slippage_bps },
+    )
+}
+
+fn swap_accounts(user: &Pubkey, mint: &Pubkey, direction: SwapDirection) -> fundly::accounts::Swap {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    fundly::accounts::Swap {
+        bonding_curve: Some(bonding_curve),
+        mint: *mint,
+        bonding_curve_sol_vault: Some(sol_vault_pda(mint).0),
+        bonding_curve_token_account: Some(get_associated_token_address(&bonding_curve, mint)),
+        graduation_archive: None,
+        curve_oracle: curve_oracle_pda(mint).0,
+        live_candle: live_candle_pda(mint).0,
+        user_points: user_points_pda(user).0,
+        trade_guard: trade_guard_pda(mint, user).0,
+        user_token_account: get_associated_token_address(user, mint),
+        buyer_record: (direction == SwapDirection::Buy).then(|| buyer_record_pda(mint, user).0),
+        trader_position: None,
+        competition: None,
+        competition_entry: None,
+        kyc_attestation: None,
+        compliance_gate: None,
+        reflection_vault: None,
+        jackpot: None,
+        curve_wsol_vault: None,
+        user_wsol_account: None,
+        platform_token_account: None,
+        global_config: global_config_pda().0,
+        global_stats: global_stats_pda().0,
+        user: *user,
+        system_program: system_program::ID,
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        event_authority: event_authority_pda().0,
+        program: fundly::ID,
+    }
+}
+
+/// Swap against the bonding curve (pre-migration); only buys pass the buyer record
+/// Post-migration swaps additionally need the Raydium accounts appended as remaining accounts
+pub fn swap(
+    user: &Pubkey,
+    mint: &Pubkey,
+    direction: SwapDirection,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    build(
+        swap_accounts(user, mint, direction),
+        fundly::instruction::Swap { direction, amount_in, min_amount_out },
+    )
+}
+
+/// `swap` on a curve that takes reflections, whose share of the tokens goes to its vault
+pub fn swap_reflecting(
+    user: &Pubkey,
+    mint: &Pubkey,
+    direction: SwapDirection,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::Swap {
+            reflection_vault: Some(reflection_vault_pda(mint).0),
+            ..swap_accounts(user, mint, direction)
+        },
+        fundly::instruction::Swap { direction, amount_in, min_amount_out },
+    )
+}
+
+/// `swap` buying from a curve with a jackpot, entering this buyer into its draws
+pub fn swap_with_jackpot(
+    user: &Pubkey,
+    mint: &Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    let direction = SwapDirection::Buy;
+    build(
+        fundly::accounts::Swap {
+            jackpot: Some(jackpot_pda(mint).0),
+            ..swap_accounts(user, mint, direction)
+        },
+        fundly::instruction::Swap { direction, amount_in, min_amount_out },
+    )
+}
+
+/// `swap` on a curve that holds its SOL side as wSOL; buys are still paid in lamports and
+/// sells pay out to the user's native-mint token account
+pub fn swap_wsol(
+    user: &Pubkey,
+    mint: &Pubkey,
+    direction: SwapDirection,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::Swap {
+            curve_wsol_vault: Some(curve_wsol_vault(mint)),
+            user_wsol_account: (direction == SwapDirection::Sell)
+                .then(|| get_associated_token_address(user, &native_mint::ID)),
+            ..swap_accounts(user, mint, direction)
+        },
+        fundly::instruction::Swap { direction, amount_in, min_amount_out },
+    )
+}
+
+/// `swap` on the curve with the user's platform token account, for the holder fee discount
+pub fn swap_discounted(
+    user: &Pubkey,
+    mint: &Pubkey,
+    direction: SwapDirection,
+    platform_token_account: &Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::Swap {
+            platform_token_account: Some(*platform_token_account),
+            ..swap_accounts(user, mint, direction)
+        },
+        fundly::instruction::Swap { direction, amount_in, min_amount_out },
+    )
+}
+
+/// Swap after `close_bonding_curve`: the pool is read from the graduation archive, and the
+/// Raydium accounts must be appended as remaining accounts
+pub fn swap_closed_curve(
+    user: &Pubkey,
+    mint: &Pubkey,
+    direction: SwapDirection,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    build(
+        fundly::accounts::Swap {
+            bonding_curve: None,
+            bonding_curve_sol_vault: None,
+            bonding_curve_token_account: None,
+            graduation_archive: Some(graduation_archive_pda(mint).0),
+            buyer_record: None,
+            ..swap_accounts(user, mint, direction)
+        },
+        fundly::instruction::Swap { direction, amount_in, min_amount_out },
+    )
+}
+
+/// Read-only: simulate it and decode the return data as a `fundly::CurveState`
+pub fn get_curve_state(mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::GetCurveState {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            global_config: global_config_pda().0,
+        },
+        fundly::instruction::GetCurveState {},
+    )
+}
+
+/// Read-only: simulate it and decode the return data as a `fundly::CurveUsdValue`
+pub fn get_curve_usd_value(mint: &Pubkey, sol_usd_price: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::GetCurveUsdValue {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            global_config: global_config_pda().0,
+            sol_usd_price: *sol_usd_price,
+        },
+        fundly::instruction::GetCurveUsdValue {},
+    )
+}
+
+/// Start tracking `owner`'s position on a curve
+pub fn open_trader_position(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::OpenTraderPosition {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            trader_position: trader_position_pda(mint, owner).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::OpenTraderPosition {},
+    )
+}
+
+pub fn close_trader_position(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::CloseTraderPosition {
+            trader_position: trader_position_pda(mint, owner).0,
+            mint: *mint,
+            owner: *owner,
+        },
+        fundly::instruction::CloseTraderPosition {},
+    )
+}
+
+/// `sequence` must be the feed's current `update_count` (0 for a project's first update)
+pub fn post_update(owner: &Pubkey, project: &Pubkey, sequence: u64, content_hash: [u8; 32], uri: String) -> Instruction {
+    build(
+        fundly::accounts::PostUpdate {
+            project_state: *project,
+            update_feed: update_feed_pda(project).0,
+            project_update: project_update_pda(project, sequence).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::PostUpdate { content_hash, uri },
+    )
+}
+
+pub fn initialize_vesting(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    total_amount: u64,
+    start_time: i64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+    release_interval: i64,
+) -> Instruction {
+    let vesting_schedule = vesting_pda(mint, creator).0;
+    build(
+        fundly::accounts::InitializeVesting {
+            vesting_schedule,
+            mint: *mint,
+            vesting_vault: get_associated_token_address(&vesting_schedule, mint),
+            creator: *creator,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::InitializeVesting {
+            total_amount,
+            start_time,
+            cliff_duration,
+            vesting_duration,
+            release_interval,
+        },
+    )
+}
+
+/// `destination` is any existing token account the beneficiary owns (default: their ATA)
+/// `amount` claims part of the unlocked tokens (default: everything claimable)
+pub fn claim_vested_tokens(
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+    destination: Option<&Pubkey>,
+    amount: Option<u64>,
+) -> Instruction {
+    claim_vested_tokens_as(beneficiary, beneficiary, mint, destination, amount)
+}
+
+/// Claim signed by `claimer`, the beneficiary or the delegate set with `set_claim_delegate`.
+/// `destination` must still be a token account the beneficiary owns (default: their ATA)
+pub fn claim_vested_tokens_as(
+    claimer: &Pubkey,
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+    destination: Option<&Pubkey>,
+    amount: Option<u64>,
+) -> Instruction {
+    let vesting_schedule = vesting_pda(mint, beneficiary).0;
+    build(
+        fundly::accounts::ClaimVestedTokens {
+            vesting_schedule,
+            mint: *mint,
+            vesting_vault: get_associated_token_address(&vesting_schedule, mint),
+            destination_token_account: destination
+                .copied()
+                .unwrap_or_else(|| get_associated_token_address(beneficiary, mint)),
+            beneficiary: *beneficiary,
+            claimer: *claimer,
+            token_program: token::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::ClaimVestedTokens { amount },
+    )
+}
+
+/// `total_amount` lamports are moved from `funder` into the schedule
+pub fn initialize_sol_vesting(
+    funder: &Pubkey,
+    beneficiary: &Pubkey,
+    total_amount: u64,
+    start_time: i64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+    release_interval: i64,
+) -> Instruction {
+    build(
+        fundly::accounts::InitializeSolVesting {
+            sol_vesting_schedule: sol_vesting_pda(funder, beneficiary).0,
+            beneficiary: *beneficiary,
+            funder: *funder,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::InitializeSolVesting {
+            total_amount,
+            start_time,
+            cliff_duration,
+            vesting_duration,
+            release_interval,
+        },
+    )
+}
+
+/// `amount` claims part of the unlocked lamports (default: everything claimable)
+pub fn claim_vested_sol(funder: &Pubkey, beneficiary: &Pubkey, amount: Option<u64>) -> Instruction {
+    build(
+        fundly::accounts::ClaimVestedSol {
+            sol_vesting_schedule: sol_vesting_pda(funder, beneficiary).0,
+            beneficiary: *beneficiary,
+        },
+        fundly::instruction::ClaimVestedSol { amount },
+    )
+}
+
+/// Locks `amount` from the owner's ATA under lock number `index`
+pub fn lock_tokens(owner: &Pubkey, mint: &Pubkey, index: u64, amount: u64, unlock_time: i64) -> Instruction {
+    let token_lock = token_lock_pda(mint, owner, index).0;
+    build(
+        fundly::accounts::LockTokens {
+            token_lock,
+            mint: *mint,
+            lock_vault: get_associated_token_address(&token_lock, mint),
+            owner_token_account: get_associated_token_address(owner, mint),
+            owner: *owner,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::LockTokens { index, amount, unlock_time },
+    )
+}
+
+pub fn extend_lock(owner: &Pubkey, mint: &Pubkey, index: u64, new_unlock_time: i64) -> Instruction {
+    build(
+        fundly::accounts::ExtendLock {
+            token_lock: token_lock_pda(mint, owner, index).0,
+            owner: *owner,
+        },
+        fundly::instruction::ExtendLock { new_unlock_time },
+    )
+}
+
+/// `destination` is any token account the owner holds for the mint (default: their ATA)
+pub fn withdraw_after_unlock(owner: &Pubkey, mint: &Pubkey, index: u64, destination: Option<&Pubkey>) -> Instruction {
+    let token_lock = token_lock_pda(mint, owner, index).0;
+    build(
+        fundly::accounts::WithdrawAfterUnlock {
+            token_lock,
+            mint: *mint,
+            lock_vault: get_associated_token_address(&token_lock, mint),
+            destination_token_account: destination
+                .copied()
+                .unwrap_or_else(|| get_associated_token_address(owner, mint)),
+            owner: *owner,
+            token_program: token::ID,
+        },
+        fundly::instruction::WithdrawAfterUnlock {},
+    )
+}
+
+pub fn create_otc_deal(
+    seller: &Pubkey,
+    mint: &Pubkey,
+    id: u64,
+    buyer: &Pubkey,
+    token_amount: u64,
+    sol_amount: u64,
+) -> Instruction {
+    let otc_deal = otc_deal_pda(seller, id).0;
+    build(
+        fundly::accounts::CreateOtcDeal {
+            otc_deal,
+            mint: *mint,
+            escrow_vault: get_associated_token_address(&otc_deal, mint),
+            seller: *seller,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::CreateOtcDeal { id, buyer: *buyer, token_amount, sol_amount },
+    )
+}
+
+/// Deposits the token leg from the seller's ATA
+pub fn deposit_otc_tokens(seller: &Pubkey, mint: &Pubkey, id: u64) -> Instruction {
+    let otc_deal = otc_deal_pda(seller, id).0;
+    build(
+        fundly::accounts::DepositOtcTokens {
+            otc_deal,
+            mint: *mint,
+            escrow_vault: get_associated_token_address(&otc_deal, mint),
+            seller_token_account: get_associated_token_address(seller, mint),
+            seller: *seller,
+            token_program: token::ID,
+        },
+        fundly::instruction::DepositOtcTokens {},
+    )
+}
+
+pub fn deposit_otc_sol(buyer: &Pubkey, seller: &Pubkey, id: u64) -> Instruction {
+    build(
+        fundly::accounts::DepositOtcSol {
+            otc_deal: otc_deal_pda(seller, id).0,
+            buyer: *buyer,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::DepositOtcSol {},
+    )
+}
+
+/// Delivers the tokens to the buyer's ATA, which must already exist
+pub fn settle_otc_deal(seller: &Pubkey, buyer: &Pubkey, mint: &Pubkey, id: u64) -> Instruction {
+    let otc_deal = otc_deal_pda(seller, id).0;
+    build(
+        fundly::accounts::SettleOtcDeal {
+            otc_deal,
+            mint: *mint,
+            escrow_vault: get_associated_token_address(&otc_deal, mint),
+            buyer_token_account: get_associated_token_address(buyer, mint),
+            seller: *seller,
+            token_program: token::ID,
+        },
+        fundly::instruction::SettleOtcDeal {},
+    )
+}
+
+/// `party` is the seller or the buyer; a deposited token leg is refunded to the seller's ATA
+pub fn cancel_otc_deal(party: &Pubkey, seller: &Pubkey, buyer: &Pubkey, mint: &Pubkey, id: u64) -> Instruction {
+    let otc_deal = otc_deal_pda(seller, id).0;
+    build(
+        fundly::accounts::CancelOtcDeal {
+            otc_deal,
+            mint: *mint,
+            escrow_vault: get_associated_token_address(&otc_deal, mint),
+            seller_token_account: get_associated_token_address(seller, mint),
+            seller: *seller,
+            buyer: *buyer,
+            party: *party,
+            token_program: token::ID,
+        },
+        fundly::instruction::CancelOtcDeal {},
+    )
+}
+
+/// The allocation is taken from the creator's ATA
+pub fn create_funding_round(
+    creator: &Pubkey,
+    mint: &Pubkey,
+    discount_bps: u16,
+    price_cap: u64,
+    token_allocation: u64,
+    deadline: i64,
+) -> Instruction {
+    let funding_round = funding_round_pda(mint).0;
+    build(
+        fundly::accounts::CreateFundingRound {
+            funding_round,
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            round_vault: get_associated_token_address(&funding_round, mint),
+            creator_token_account: get_associated_token_address(creator, mint),
+            creator: *creator,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::CreateFundingRound { discount_bps, price_cap, token_allocation, deadline },
+    )
+}
+
+pub fn invest_in_round(investor: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        fundly::accounts::InvestInRound {
+            funding_round: funding_round_pda(mint).0,
+            round_position: round_position_pda(mint, investor).0,
+            bonding_curve: bonding_curve_pda(mint).0,
+            investor: *investor,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::InvestInRound { amount },
+    )
+}
+
+/// Surplus allocation tokens go back to the creator's ATA
+pub fn convert_funding_round(mint: &Pubkey, creator: &Pubkey) -> Instruction {
+    let funding_round = funding_round_pda(mint).0;
+    build(
+        fundly::accounts::ConvertFundingRound {
+            funding_round,
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            round_vault: get_associated_token_address(&funding_round, mint),
+            creator_token_account: get_associated_token_address(creator, mint),
+            creator: *creator,
+            token_program: token::ID,
+        },
+        fundly::instruction::ConvertFundingRound {},
+    )
+}
+
+pub fn claim_round_tokens(investor: &Pubkey, mint: &Pubkey) -> Instruction {
+    let funding_round = funding_round_pda(mint).0;
+    build(
+        fundly::accounts::ClaimRoundTokens {
+            funding_round,
+            round_position: round_position_pda(mint, investor).0,
+            mint: *mint,
+            round_vault: get_associated_token_address(&funding_round, mint),
+            investor_token_account: get_associated_token_address(investor, mint),
+            investor: *investor,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::ClaimRoundTokens {},
+    )
+}
+
+pub fn refund_round_deposit(investor: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::RefundRoundDeposit {
+            funding_round: funding_round_pda(mint).0,
+            round_position: round_position_pda(mint, investor).0,
+            bonding_curve: bonding_curve_pda(mint).0,
+            investor: *investor,
+        },
+        fundly::instruction::RefundRoundDeposit {},
+    )
+}
+
+pub fn reclaim_round_tokens(creator: &Pubkey, mint: &Pubkey) -> Instruction {
+    let funding_round = funding_round_pda(mint).0;
+    build(
+        fundly::accounts::ReclaimRoundTokens {
+            funding_round,
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            round_vault: get_associated_token_address(&funding_round, mint),
+            creator_token_account: get_associated_token_address(creator, mint),
+            creator: *creator,
+            token_program: token::ID,
+        },
+        fundly::instruction::ReclaimRoundTokens {},
+    )
+}
+
+pub fn enable_rage_quit(creator: &Pubkey, mint: &Pubkey, milestone_count: u8, milestone_interval: i64) -> Instruction {
+    build(
+        fundly::accounts::EnableRageQuit { funding_round: funding_round_pda(mint).0, creator: *creator },
+        fundly::instruction::EnableRageQuit { milestone_count, milestone_interval },
+    )
+}
+
+pub fn release_round_milestone(mint: &Pubkey, creator: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::ReleaseRoundMilestone { funding_round: funding_round_pda(mint).0, creator: *creator },
+        fundly::instruction::ReleaseRoundMilestone {},
+    )
+}
+
+/// Burns from the holder's ATA
+pub fn rage_quit(holder: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        fundly::accounts::RageQuit {
+            funding_round: funding_round_pda(mint).0,
+            mint: *mint,
+            holder_token_account: get_associated_token_address(holder, mint),
+            holder: *holder,
+            token_program: token::ID,
+        },
+        fundly::instruction::RageQuit { amount },
+    )
+}
+
+pub fn create_sol_stream(payer: &Pubkey, id: u64, terms: StreamTerms) -> Instruction {
+    build(
+        fundly::accounts::CreateSolStream {
+            payment_stream: payment_stream_pda(payer, id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::CreateSolStream { id, terms },
+    )
+}
+
+/// Escrows the tokens from the payer's ATA
+pub fn create_token_stream(payer: &Pubkey, mint: &Pubkey, id: u64, terms: StreamTerms) -> Instruction {
+    let payment_stream = payment_stream_pda(payer, id).0;
+    build(
+        fundly::accounts::CreateTokenStream {
+            payment_stream,
+            mint: *mint,
+            stream_vault: get_associated_token_address(&payment_stream, mint),
+            payer_token_account: get_associated_token_address(payer, mint),
+            payer: *payer,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+        },
+        fundly::instruction::CreateTokenStream { id, terms },
+    )
+}
+
+/// `mint` is set for token streams, which pay into the payee's ATA
+/// `amount` withdraws part of the streamed funds (default: everything available)
+pub fn withdraw_from_stream(
+    payee: &Pubkey,
+    payer: &Pubkey,
+    id: u64,
+    mint: Option<&Pubkey>,
+    amount: Option<u64>,
+) -> Instruction {
+    let payment_stream = payment_stream_pda(payer, id).0;
+    build(
+        fundly::accounts::WithdrawFromStream {
+            payment_stream,
+            stream_vault: mint.map(|mint| get_associated_token_address(&payment_stream, mint)),
+            payee_token_account: mint.map(|mint| get_associated_token_address(payee, mint)),
+            payee: *payee,
+            token_program: mint.map(|_| token::ID),
+        },
+        fundly::instruction::WithdrawFromStream { amount },
+    )
+}
+
+/// `mint` is set for token streams, which settle between the payee's and payer's ATAs
+pub fn cancel_stream(payer: &Pubkey, payee: &Pubkey, id: u64, mint: Option<&Pubkey>) -> Instruction {
+    let payment_stream = payment_stream_pda(payer, id).0;
+    build(
+        fundly::accounts::CancelStream {
+            payment_stream,
+            stream_vault: mint.map(|mint| get_associated_token_address(&payment_stream, mint)),
+            payee_token_account: mint.map(|mint| get_associated_token_address(payee, mint)),
+            payer_token_account: mint.map(|mint| get_associated_token_address(payer, mint)),
+            payee: *payee,
+            payer: *payer,
+            token_program: mint.map(|_| token::ID),
+        },
+        fundly::instruction::CancelStream {},
+    )
+}
+
+/// `delegate: None` revokes the current claim delegate
+pub fn set_claim_delegate(beneficiary: &Pubkey, mint: &Pubkey, delegate: Option<Pubkey>) -> Instruction {
+    build(
+        fundly::accounts::SetClaimDelegate {
+            vesting_schedule: vesting_pda(mint, beneficiary).0,
+            mint: *mint,
+            beneficiary: *beneficiary,
+        },
+        fundly::instruction::SetClaimDelegate { delegate },
+    )
+}
+
+/// `route_fee_share` must be set once fee sharing is enabled in the global config, and
+/// `route_insurance` once `insurance_fee_bps` is
+/// `wsol_vault` must be set for curves that hold their SOL side as wSOL; their fees go to
+/// the treasury's wSOL account
+/// `treasury_split` lists the recipients of the global config's treasury split in slot order
+/// (empty when none is configured)
+pub fn withdraw_platform_fees(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    treasury: &Pubkey,
+    route_fee_share: bool,
+    route_insurance: bool,
+    wsol_vault: bool,
+    treasury_split: &[Pubkey],
+) -> Instruction {
+    let mut instruction = build(
+        fundly::accounts::WithdrawPlatformFees {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            global_config: global_config_pda().0,
+            authority: *authority,
+            treasury: *treasury,
+            fee_share_vault: route_fee_share.then(|| fee_share_vault_pda().0),
+            curve_wsol_vault: wsol_vault.then(|| curve_wsol_vault(mint)),
+            treasury_wsol_account: wsol_vault
+                .then(|| get_associated_token_address(treasury, &native_mint::ID)),
+            system_program: system_program::ID,
+            token_program: wsol_vault.then_some(token::ID),
+            insurance_fund: route_insurance.then(|| insurance_fund_pda().0),
+        },
+        fundly::instruction::WithdrawPlatformFees {},
+    );
+    instruction
+        .accounts
+        .extend(treasury_split.iter().map(|recipient| AccountMeta::new(*recipient, false)));
+    instruction
+}
+
+/// Pay a proposed insurance payout once its delay has passed (anyone may crank it)
+pub fn execute_payout(payout_id: u64, recipient: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::ExecutePayout {
+            insurance_fund: insurance_fund_pda().0,
+            payout: insurance_payout_pda(payout_id).0,
+            recipient: *recipient,
+        },
+        fundly::instruction::ExecutePayout {},
+    )
+}
+
+/// Stake `amount` of the platform token (`stake_mint`) for a share of trading fees
+pub fn stake_platform_token(owner: &Pubkey, stake_mint: &Pubkey, amount: u64) -> Instruction {
+    let fee_share_vault = fee_share_vault_pda().0;
+    build(
+        fundly::accounts::StakePlatformToken {
+            fee_share_vault,
+            stake_vault: get_associated_token_address(&fee_share_vault, stake_mint),
+            fee_share_stake: fee_share_stake_pda(owner).0,
+            owner_token_account: get_associated_token_address(owner, stake_mint),
+            owner: *owner,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        fundly::instruction::StakePlatformToken { amount },
+    )
+}
+
+pub fn unstake(owner: &Pubkey, stake_mint: &Pubkey, amount: u64) -> Instruction {
+    let fee_share_vault = fee_share_vault_pda().0;
+    build(
+        fundly::accounts::Unstake {
+            fee_share_vault,
+            stake_vault: get_associated_token_address(&fee_share_vault, stake_mint),
+            fee_share_stake: fee_share_stake_pda(owner).0,
+            owner_token_account: get_associated_token_address(owner, stake_mint),
+            owner: *owner,
+            token_program: token::ID,
+        },
+        fundly::instruction::Unstake { amount },
+    )
+}
+
+pub fn claim_fee_share(owner: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::ClaimFeeShare {
+            fee_share_vault: fee_share_vault_pda().0,
+            fee_share_stake: fee_share_stake_pda(owner).0,
+            owner: *owner,
+        },
+        fundly::instruction::ClaimFeeShare {},
+    )
+}
+
+/// Write a migrated curve's graduation archive (admin only); `raydium_pool` must be the pool
+/// recorded on the curve
+pub fn finalize_migration(authority: &Pubkey, mint: &Pubkey, raydium_pool: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::FinalizeMigration {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            graduation_archive: graduation_archive_pda(mint).0,
+            raydium_pool: *raydium_pool,
+            global_config: global_config_pda().0,
+            authority: *authority,
+            payer: *authority,
+            system_program: system_program::ID,
+            event_authority: event_authority_pda().0,
+            program: fundly::ID,
+        },
+        fundly::instruction::FinalizeMigration {},
+    )
+}
+
+/// `rent_payer` is whoever paid for the record on the buyer's first buy
+pub fn close_buyer_record(buyer: &Pubkey, mint: &Pubkey, rent_payer: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::CloseBuyerRecord {
+            buyer_record: buyer_record_pda(mint, buyer).0,
+            mint: *mint,
+            graduation_archive: graduation_archive_pda(mint).0,
+            buyer: *buyer,
+            rent_payer: *rent_payer,
+        },
+        fundly::instruction::CloseBuyerRecord {},
+    )
+}
+
+/// Close a migrated curve's accounts once its graduation archive exists (admin only)
+pub fn close_bonding_curve(authority: &Pubkey, mint: &Pubkey, treasury: &Pubkey, creator: &Pubkey) -> Instruction {
+    let bonding_curve = bonding_curve_pda(mint).0;
+    build(
+        fundly::accounts::CloseBondingCurve {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            graduation_archive: graduation_archive_pda(mint).0,
+            global_config: global_config_pda().0,
+            authority: *authority,
+            treasury: *treasury,
+            creator: *creator,
+            token_program: token::ID,
+        },
+        fundly::instruction::CloseBondingCurve {},
+    )
+}
+
+/// Bring a global config written by an older program version up to the current layout
+pub fn upgrade_global_config(authority: &Pubkey) -> Instruction {
+    build(upgrade_global_config_accounts(authority), fundly::instruction::UpgradeGlobalConfig {})
+}
+
+/// `upgrade_global_config` under its original name
+pub fn migrate_global_config(authority: &Pubkey) -> Instruction {
+    build(upgrade_global_config_accounts(authority), fundly::instruction::MigrateGlobalConfig {})
+}
+
+fn upgrade_global_config_accounts(authority: &Pubkey) -> fundly::accounts::UpgradeGlobalConfig {
+    fundly::accounts::UpgradeGlobalConfig {
+        global_config: global_config_pda().0,
+        authority: *authority,
+        payer: *authority,
+        system_program: system_program::ID,
+    }
+}
+
+/// Rewrite `mint`'s bonding curve in the current layout, with `payer` covering the extra rent
+pub fn upgrade_bonding_curve(payer: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        fundly::accounts::UpgradeBondingCurve {
+            bonding_curve: bonding_curve_pda(mint).0,
+            mint: *mint,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        fundly::instruction::UpgradeBondingCurve {},
+    )
+}
@@ -0,0 +1,37 @@
+//! Off-chain client for the fundly program.
+//!
+//! Provides typed instruction builders, PDA derivation, account fetching and
+//! quote math that mirrors the on-chain curve, so bots and backends never
+//! hand-roll instruction encoding.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use fundly::ID as PROGRAM_ID;
+pub use fundly::{BUY_AND_MIGRATE_CU_LIMIT, BUY_TOKENS_CU_LIMIT, SELL_TOKENS_CU_LIMIT};
+pub use fundly::{
+    BondingCurve, ComplianceGate, ConditionalOrder, CreatorStats, CurveState, DcaSchedule, FeeShareStake, FeeShareVault, FundingRound,
+    GlobalConfig, GlobalStats, HolderSnapshot, InsuranceFund, InsurancePayout, KycAttestation, LimitOrder, OtcDeal,
+    PaymentStream, ProjectUpdate, ProjectUpdateFeed, RoundPosition, SnapshotRegistry,
+    SolVestingSchedule, StreamTerms, TokenLock, TradeDelegate, TraderPosition, VestingSchedule,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("rpc error: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[error("account {0} not found")]
+    AccountNotFound(anchor_lang::prelude::Pubkey),
+    #[error("failed to deserialize account: {0}")]
+    Deserialize(#[from] anchor_lang::error::Error),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(error))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
@@ -0,0 +1,243 @@
+//! PDA derivation for every account the program owns.
+
+use anchor_lang::prelude::Pubkey;
+
+pub fn global_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"global_config"], &fundly::ID)
+}
+
+/// Signer used by the program to emit events via self-CPI
+pub fn event_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &fundly::ID)
+}
+
+pub fn global_stats_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"global_stats"], &fundly::ID)
+}
+
+pub fn king_of_the_hill_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"king_of_the_hill"], &fundly::ID)
+}
+
+pub fn pending_config_update_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pending_config_update"], &fundly::ID)
+}
+
+pub fn project_pda(owner: &Pubkey, symbol: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"project", owner.as_ref(), symbol.as_bytes()], &fundly::ID)
+}
+
+/// Mint created by `create_project_mint`, derivable as soon as the project exists
+pub fn project_mint_pda(project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"project_mint", project.as_ref()], &fundly::ID)
+}
+
+pub fn creator_stats_pda(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator_stats", creator.as_ref()], &fundly::ID)
+}
+
+pub fn bonding_curve_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bonding_curve", mint.as_ref()], &fundly::ID)
+}
+
+pub fn sol_vault_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sol_vault", mint.as_ref()], &fundly::ID)
+}
+
+pub fn curve_oracle_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"curve_oracle", mint.as_ref()], &fundly::ID)
+}
+
+pub fn live_candle_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"live_candle", mint.as_ref()], &fundly::ID)
+}
+
+pub fn candle_pda(mint: &Pubkey, bucket_start: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"candle", mint.as_ref(), &bucket_start.to_le_bytes()],
+        &fundly::ID,
+    )
+}
+
+pub fn buyer_record_pda(mint: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"buyer_record", mint.as_ref(), buyer.as_ref()], &fundly::ID)
+}
+
+pub fn trader_position_pda(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"trader_position", mint.as_ref(), owner.as_ref()], &fundly::ID)
+}
+
+pub fn competition_pda(competition_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"competition", &competition_id.to_le_bytes()], &fundly::ID)
+}
+
+pub fn competition_entry_pda(competition: &Pubkey, trader: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"competition_entry", competition.as_ref(), trader.as_ref()],
+        &fundly::ID,
+    )
+}
+
+pub fn user_points_pda(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"user_points", user.as_ref()], &fundly::ID)
+}
+
+pub fn trade_guard_pda(mint: &Pubkey, trader: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"trade_guard", mint.as_ref(), trader.as_ref()], &fundly::ID)
+}
+
+pub fn badge_collection_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"badge_collection", mint.as_ref()], &fundly::ID)
+}
+
+pub fn badge_mint_pda(mint: &Pubkey, buyer: &Pubkey, kind: fundly::BadgeKind) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"badge_mint", mint.as_ref(), buyer.as_ref(), &[kind as u8]],
+        &fundly::ID,
+    )
+}
+
+pub fn badge_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"badge_authority"], &fundly::ID)
+}
+
+pub fn vesting_pda(mint: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vesting", mint.as_ref(), beneficiary.as_ref()], &fundly::ID)
+}
+
+pub fn token_lock_pda(mint: &Pubkey, owner: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"token_lock", mint.as_ref(), owner.as_ref(), &index.to_le_bytes()],
+        &fundly::ID,
+    )
+}
+
+pub fn otc_deal_pda(seller: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"otc_deal", seller.as_ref(), &id.to_le_bytes()], &fundly::ID)
+}
+
+pub fn funding_round_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"funding_round", mint.as_ref()], &fundly::ID)
+}
+
+pub fn round_position_pda(mint: &Pubkey, investor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"round_position", mint.as_ref(), investor.as_ref()], &fundly::ID)
+}
+
+pub fn snapshot_registry_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"snapshot_registry", mint.as_ref()], &fundly::ID)
+}
+
+pub fn holder_snapshot_pda(mint: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"holder_snapshot", mint.as_ref(), &epoch.to_le_bytes()], &fundly::ID)
+}
+
+pub fn update_feed_pda(project: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"update_feed", project.as_ref()], &fundly::ID)
+}
+
+pub fn project_update_pda(project: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"project_update", project.as_ref(), &sequence.to_le_bytes()], &fundly::ID)
+}
+
+pub fn kyc_attestation_pda(verifier: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"kyc_attestation", verifier.as_ref(), wallet.as_ref()], &fundly::ID)
+}
+
+pub fn trade_delegate_pda(owner: &Pubkey, session_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"trade_delegate", owner.as_ref(), session_key.as_ref()], &fundly::ID)
+}
+
+pub fn limit_order_pda(owner: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"limit_order", owner.as_ref(), &order_id.to_le_bytes()], &fundly::ID)
+}
+
+pub fn conditional_order_pda(owner: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"conditional_order", owner.as_ref(), &order_id.to_le_bytes()], &fundly::ID)
+}
+
+pub fn dca_schedule_pda(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"dca_schedule", owner.as_ref(), mint.as_ref()], &fundly::ID)
+}
+
+pub fn compliance_gate_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"compliance_gate", mint.as_ref()], &fundly::ID)
+}
+
+pub fn payment_stream_pda(payer: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"payment_stream", payer.as_ref(), &id.to_le_bytes()], &fundly::ID)
+}
+
+pub fn sol_vesting_pda(funder: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sol_vesting", funder.as_ref(), beneficiary.as_ref()], &fundly::ID)
+}
+
+pub fn migration_vault_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"migration_vault", mint.as_ref()], &fundly::ID)
+}
+
+pub fn migration_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"migration_authority"], &fundly::ID)
+}
+
+pub fn creator_allocation_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator_allocation", mint.as_ref()], &fundly::ID)
+}
+
+pub fn jackpot_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"jackpot", mint.as_ref()], &fundly::ID)
+}
+
+pub fn randomness_request_pda(consumer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"randomness_request", consumer.as_ref()], &fundly::ID)
+}
+
+pub fn reflection_pool_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reflection_pool", mint.as_ref()], &fundly::ID)
+}
+
+pub fn reflection_vault_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reflection_vault", mint.as_ref()], &fundly::ID)
+}
+
+pub fn reflection_distribution_pda(mint: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"reflection_distribution", mint.as_ref(), &epoch.to_le_bytes()],
+        &fundly::ID,
+    )
+}
+
+pub fn reflection_claim_pda(mint: &Pubkey, epoch: u64, holder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"reflection_claim", mint.as_ref(), &epoch.to_le_bytes(), holder.as_ref()],
+        &fundly::ID,
+    )
+}
+
+pub fn graduation_archive_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"graduation_archive", mint.as_ref()], &fundly::ID)
+}
+
+pub fn lp_burn_info_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"lp_burn_info", mint.as_ref()], &fundly::ID)
+}
+
+pub fn lp_lock_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"lp_lock", mint.as_ref()], &fundly::ID)
+}
+
+pub fn fee_share_vault_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_share_vault"], &fundly::ID)
+}
+
+pub fn fee_share_stake_pda(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_share_stake", owner.as_ref()], &fundly::ID)
+}
+
+pub fn insurance_fund_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"insurance_fund"], &fundly::ID)
+}
+
+pub fn insurance_payout_pda(payout_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"insurance_payout", &payout_id.to_le_bytes()], &fundly::ID)
+}
@@ -0,0 +1,71 @@
+//! Off-chain quotes using the same pricing code as the program.
+
+use anchor_lang::Result;
+use fundly::curve::{self, Trade};
+
+use crate::{BondingCurve, GlobalConfig};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuyQuote {
+    pub tokens_out: u64,
+    pub fee: u64,
+    pub royalty: u64,
+    pub price_after: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SellQuote {
+    pub sol_out: u64,
+    pub fee: u64,
+    pub royalty: u64,
+    pub price_after: u64,
+}
+
+/// Curve price in lamports per whole token at unix time `now`, as limit orders compare it
+/// (prices of liquidity bootstrapping curves fall over their window)
+pub fn spot_price(bonding_curve: &BondingCurve, now: i64) -> Result<u64> {
+    curve::spot_price(bonding_curve.reserves_at(now)?)
+}
+
+/// Tokens received for spending `sol_amount` lamports (fee and creator royalty included)
+pub fn quote_buy(
+    bonding_curve: &BondingCurve,
+    global_config: &GlobalConfig,
+    sol_amount: u64,
+    now: i64,
+) -> Result<BuyQuote> {
+    let fee = curve::fee_for_amount(sol_amount, global_config.buy_fee_bps_for(bonding_curve)?)?;
+    let royalty = bonding_curve.creator_royalty_for(sol_amount)?;
+    let sol_in = sol_amount - fee - royalty;
+    let reserves = bonding_curve.reserves_at(now)?;
+    Ok(BuyQuote {
+        tokens_out: curve::tokens_out_for_sol(reserves, sol_in)?,
+        fee,
+        royalty,
+        price_after: curve::price_after_trade(reserves, Trade::Buy { sol_in })?,
+    })
+}
+
+/// Lamports received for selling `token_amount` tokens (after fee and creator royalty)
+pub fn quote_sell(
+    bonding_curve: &BondingCurve,
+    global_config: &GlobalConfig,
+    token_amount: u64,
+    now: i64,
+) -> Result<SellQuote> {
+    let reserves = bonding_curve.reserves_at(now)?;
+    let sol_out_before_fee = curve::sol_out_for_tokens(reserves, token_amount)?;
+    let fee = curve::fee_for_amount(sol_out_before_fee, global_config.sell_fee_bps_for(bonding_curve))?;
+    let royalty = bonding_curve.creator_royalty_for(sol_out_before_fee)?;
+    Ok(SellQuote {
+        sol_out: sol_out_before_fee - fee - royalty,
+        fee,
+        royalty,
+        price_after: curve::price_after_trade(reserves, Trade::Sell { tokens_in: token_amount })?,
+    })
+}
+
+/// Minimum output accepting at most `slippage_bps` below `expected`
+pub fn with_slippage(expected: u64, slippage_bps: u16) -> u64 {
+    ((expected as u128) * (10_000 - slippage_bps.min(10_000) as u128) / 10_000) as u64
+}
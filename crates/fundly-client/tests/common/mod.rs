@@ -0,0 +1,615 @@
+//! Shared harness for tests that run the compiled program under `solana-program-test`.
+//!
+//! Build the program before running them: `anchor build && cargo test -p fundly-client`
+
+#![allow(dead_code)]
+
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{system_program, AccountDeserialize, AccountSerialize, AnchorDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::{self, spl_token};
+use fundly_client::{instructions, pda};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+pub const VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
+pub const VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+pub const INITIAL_TOKEN_SUPPLY: u64 = 1_000_000_000_000_000;
+pub const MIGRATION_THRESHOLD_SOL: u64 = 85_000_000_000;
+pub const FEE_BPS: u16 = 100;
+// The Switchboard queue and authority random draws are configured to trust
+pub const RANDOMNESS_QUEUE: Pubkey = Pubkey::new_from_array([5; 32]);
+pub const RANDOMNESS_AUTHORITY: Pubkey = Pubkey::new_from_array([6; 32]);
+
+/// A launched curve: `admin` is the platform authority and the curve's creator
+pub struct Launch {
+    pub context: ProgramTestContext,
+    pub admin: Keypair,
+    pub trader: Keypair,
+    pub treasury: Pubkey,
+    pub mint: Pubkey,
+}
+
+fn wallet(lamports: u64) -> Account {
+    Account::new(lamports, 0, &system_program::ID)
+}
+
+fn packed<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+// A 6-decimal mint with the full initial supply issued, as curves are launched from
+fn supply_mint(mint_authority: Option<Pubkey>) -> Account {
+    packed(spl_token::state::Mint {
+        mint_authority: COption::from(mint_authority),
+        supply: INITIAL_TOKEN_SUPPLY,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    })
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    packed(spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    })
+}
+
+pub fn initialize_global_config_ix(authority: &Pubkey, treasury: &Pubkey) -> Instruction {
+    initialize_global_config_with_ix(authority, treasury, |_| {})
+}
+
+/// Like `initialize_global_config_ix`, with `configure` adjusting the defaults
+pub fn initialize_global_config_with_ix(
+    authority: &Pubkey,
+    treasury: &Pubkey,
+    configure: impl FnOnce(&mut fundly::GlobalConfigParams),
+) -> Instruction {
+    let mut params = fundly::GlobalConfigParams {
+        treasury: *treasury,
+        virtual_sol_reserves: VIRTUAL_SOL_RESERVES,
+        virtual_token_reserves: VIRTUAL_TOKEN_RESERVES,
+        initial_token_supply: INITIAL_TOKEN_SUPPLY,
+        buy_fee_bps: FEE_BPS,
+        migration_threshold_sol: MIGRATION_THRESHOLD_SOL,
+        raydium_amm_program: Pubkey::new_unique(),
+        migration_keeper_reward: 0,
+        lp_fee_creator_share_bps: 5_000,
+        config_update_delay: 0,
+        boost_lamports_per_hour: 0,
+        points_per_sol_bought: 0,
+        points_per_sol_sold: 0,
+        round_trip_slot_window: 0,
+        fee_tiers: [fundly::FeeTier::default(); 4],
+        sell_fee_bps: FEE_BPS,
+        platform_token_mint: Pubkey::default(),
+        holder_discount_tiers: [fundly::HolderDiscountTier::default(); 4],
+        fee_share_bps: 0,
+        launch_fee_lamports: 0,
+        launch_deposit_lamports: 0,
+        max_launches_per_window: 0,
+        launch_window_seconds: 0,
+        migration_operator: Pubkey::default(),
+        insurance_fee_bps: 0,
+        buyback_bps: 0,
+        kyc_verifier: Pubkey::default(),
+        jupiter_program: Pubkey::default(),
+        token_decimals: 6,
+        randomness_queue: RANDOMNESS_QUEUE,
+        randomness_authority: RANDOMNESS_AUTHORITY,
+        sol_usd_price_account: Pubkey::default(),
+        max_price_age_seconds: 60,
+        max_price_confidence_bps: 200,
+        launch_fee_usd: 0,
+    };
+    configure(&mut params);
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::InitializeGlobalConfig {
+            global_config: pda::global_config_pda().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::InitializeGlobalConfig { params }.data(),
+    }
+}
+
+pub fn initialize_global_stats_ix(authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::InitializeGlobalStats {
+            global_config: pda::global_config_pda().0,
+            global_stats: pda::global_stats_pda().0,
+            authority: *authority,
+            payer: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::InitializeGlobalStats {}.data(),
+    }
+}
+
+pub fn set_treasury_split_ix(authority: &Pubkey, splits: Vec<fundly::TreasurySplit>) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::UpdateGlobalConfig {
+            global_config: pda::global_config_pda().0,
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::SetTreasurySplit { splits }.data(),
+    }
+}
+
+pub fn initialize_insurance_fund_ix(authority: &Pubkey, payout_delay: i64) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::InitializeInsuranceFund {
+            global_config: pda::global_config_pda().0,
+            insurance_fund: pda::insurance_fund_pda().0,
+            authority: *authority,
+            payer: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::InitializeInsuranceFund { payout_delay }.data(),
+    }
+}
+
+pub fn propose_payout_ix(authority: &Pubkey, payout_id: u64, recipient: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::ProposePayout {
+            global_config: pda::global_config_pda().0,
+            insurance_fund: pda::insurance_fund_pda().0,
+            payout: pda::insurance_payout_pda(payout_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::ProposePayout {
+            recipient: *recipient,
+            amount,
+            reason: "Compensation for incident".to_string(),
+        }
+        .data(),
+    }
+}
+
+/// Merkle root over `holders` as `create_snapshot` expects, carrying odd nodes up unchanged
+pub fn snapshot_root(holders: &[(Pubkey, u64)]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = holders.iter().map(|(holder, balance)| fundly::snapshot::leaf(holder, *balance)).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| if pair.len() == 2 { fundly::snapshot::parent(&pair[0], &pair[1]) } else { pair[0] })
+            .collect();
+    }
+    level[0]
+}
+
+pub fn create_snapshot_ix(authority: &Pubkey, mint: &Pubkey, epoch: u64, holders: &[(Pubkey, u64)], slot: u64) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::CreateSnapshot {
+            global_config: pda::global_config_pda().0,
+            mint: *mint,
+            snapshot_registry: pda::snapshot_registry_pda(mint).0,
+            holder_snapshot: pda::holder_snapshot_pda(mint, epoch).0,
+            authority: *authority,
+            payer: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::CreateSnapshot {
+            merkle_root: snapshot_root(holders),
+            slot,
+            holder_count: holders.len() as u64,
+            total_balance: holders.iter().map(|(_, balance)| balance).sum(),
+        }
+        .data(),
+    }
+}
+
+/// Pre-migration buyback; migrated curves also need the curve's wSOL account and Raydium accounts
+pub fn buyback_and_burn_ix(authority: &Pubkey, mint: &Pubkey, min_tokens_out: u64) -> Instruction {
+    let bonding_curve = pda::bonding_curve_pda(mint).0;
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::BuybackAndBurn {
+            global_config: pda::global_config_pda().0,
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: pda::sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_wsol_account: None,
+            authority: *authority,
+            token_program: token::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::BuybackAndBurn { min_tokens_out }.data(),
+    }
+}
+
+pub fn set_compliance_flags_ix(
+    authority: &Pubkey,
+    project: &Pubkey,
+    mint: &Pubkey,
+    flags: u16,
+    max_buy_lamports: u64,
+) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::SetComplianceFlags {
+            global_config: pda::global_config_pda().0,
+            project_state: *project,
+            mint: *mint,
+            bonding_curve: pda::bonding_curve_pda(mint).0,
+            compliance_gate: pda::compliance_gate_pda(mint).0,
+            authority: *authority,
+            payer: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::SetComplianceFlags { flags, max_buy_lamports }.data(),
+    }
+}
+
+/// Give `address` a system-owned balance of `lamports`
+pub fn airdrop(context: &mut ProgramTestContext, address: &Pubkey, lamports: u64) {
+    context.set_account(address, &wallet(lamports).into());
+}
+
+/// Write a project owned by `owner` for `mint` straight into the bank, skipping the category
+/// setup `initialize_project` needs
+pub fn create_project(context: &mut ProgramTestContext, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let mut name = [0; 32];
+    name[..4].copy_from_slice(b"Test");
+    let mut symbol = [0; 10];
+    symbol[..4].copy_from_slice(b"TEST");
+    let project = fundly::ProjectState {
+        owner: *owner,
+        mint: *mint,
+        name_len: 4,
+        name,
+        symbol_len: 4,
+        symbol,
+        created_at: 0,
+        total_supply: INITIAL_TOKEN_SUPPLY,
+        category: 0,
+        boosted_until: 0,
+        compliance_flags: 0,
+        mint_source: fundly::MintSource::Keypair,
+    };
+    let mut data = Vec::with_capacity(fundly::ProjectState::MAX_SIZE);
+    project.try_serialize(&mut data).unwrap();
+    let address = pda::project_pda(owner, "TEST").0;
+    context.set_account(
+        &address,
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: fundly::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+    address
+}
+
+/// Create an empty token account for `mint` owned by `owner` at a fresh (non-ATA) address
+pub fn create_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let address = Pubkey::new_unique();
+    context.set_account(&address, &token_account(*mint, *owner, 0).into());
+    address
+}
+
+pub async fn mint_supply(context: &mut ProgramTestContext, mint: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*mint).await.unwrap().expect("mint exists");
+    spl_token::state::Mint::unpack(&account.data).unwrap().supply
+}
+
+/// The keeper crank; the client has no builder since bots migrate through `buy_tokens_and_migrate`
+pub fn migrate_to_raydium_ix(keeper: &Pubkey, mint: &Pubkey, creator: &Pubkey, treasury: &Pubkey) -> Instruction {
+    let bonding_curve = pda::bonding_curve_pda(mint).0;
+    let migration_authority = pda::migration_authority_pda().0;
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::MigrateToRaydium {
+            bonding_curve,
+            mint: *mint,
+            bonding_curve_sol_vault: pda::sol_vault_pda(mint).0,
+            bonding_curve_token_account: get_associated_token_address(&bonding_curve, mint),
+            curve_wsol_vault: None,
+            migration_sol_vault: pda::migration_vault_pda(mint).0,
+            migration_token_account: get_associated_token_address(&migration_authority, mint),
+            migration_authority,
+            global_config: pda::global_config_pda().0,
+            global_stats: pda::global_stats_pda().0,
+            creator_stats: pda::creator_stats_pda(creator).0,
+            payer: *keeper,
+            treasury: *treasury,
+            creator: *creator,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+            event_authority: pda::event_authority_pda().0,
+            program: fundly::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::MigrateToRaydium {}.data(),
+    }
+}
+
+/// Start the program with a funded admin and trader and a mint whose supply the admin holds
+pub async fn start() -> Launch {
+    let admin = Keypair::new();
+    let trader = Keypair::new();
+    let treasury = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(true);
+    program_test.add_program("fundly", fundly::ID, None);
+    program_test.add_account(admin.pubkey(), wallet(100_000_000_000));
+    program_test.add_account(trader.pubkey(), wallet(200_000_000_000));
+    program_test.add_account(treasury, wallet(1_000_000_000));
+    program_test.add_account(mint, supply_mint(None));
+    program_test.add_account(
+        get_associated_token_address(&admin.pubkey(), &mint),
+        token_account(mint, admin.pubkey(), INITIAL_TOKEN_SUPPLY),
+    );
+    let context = program_test.start_with_context().await;
+
+    Launch { context, admin, trader, treasury, mint }
+}
+
+/// `start`, then set up the platform and launch a curve for the mint
+pub async fn launch() -> Launch {
+    launch_with(|_| {}).await
+}
+
+/// `launch` with `configure` adjusting the global config
+pub async fn launch_with(configure: impl FnOnce(&mut fundly::GlobalConfigParams)) -> Launch {
+    let mut launch = start().await;
+    let admin = launch.admin.insecure_clone();
+    let ixs = [
+        initialize_global_config_with_ix(&admin.pubkey(), &launch.treasury, configure),
+        initialize_global_stats_ix(&admin.pubkey()),
+        instructions::initialize_bonding_curve(
+            &admin.pubkey(),
+            &launch.mint,
+            &launch.treasury,
+            INITIAL_TOKEN_SUPPLY,
+            None,
+            None,
+        ),
+        instructions::finalize_launch(&launch.mint, false),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &admin).await.unwrap();
+    }
+    launch
+}
+
+/// Launch another curve on the running platform, for a new mint whose supply the admin holds
+pub async fn launch_another(launch: &mut Launch) -> Pubkey {
+    let admin = launch.admin.insecure_clone();
+    let mint = Pubkey::new_unique();
+    launch.context.set_account(&mint, &supply_mint(None).into());
+    launch.context.set_account(
+        &get_associated_token_address(&admin.pubkey(), &mint),
+        &token_account(mint, admin.pubkey(), INITIAL_TOKEN_SUPPLY).into(),
+    );
+    let ix = instructions::initialize_bonding_curve(&admin.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    send(&mut launch.context, instructions::finalize_launch(&mint, false), &admin).await.unwrap();
+    mint
+}
+
+/// Replace `mint` with a fresh full-supply mint whose mint authority is `mint_authority`
+pub fn set_mint_authority(context: &mut ProgramTestContext, mint: &Pubkey, mint_authority: Option<Pubkey>) {
+    context.set_account(mint, &supply_mint(mint_authority).into());
+}
+
+pub fn transaction(context: &ProgramTestContext, instruction: Instruction, signer: &Keypair) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signer.pubkey()),
+        &[signer],
+        context.last_blockhash,
+    )
+}
+
+pub async fn send(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signer: &Keypair,
+) -> Result<(), BanksClientError> {
+    // A fresh blockhash keeps identical instructions from being deduplicated
+    context.last_blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash).await?;
+    let transaction = transaction(context, instruction, signer);
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Send with `relayer` paying the transaction fee and co-signing alongside `signer`
+pub async fn send_sponsored(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    relayer: &Keypair,
+    signer: &Keypair,
+) -> Result<(), BanksClientError> {
+    context.last_blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash).await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&relayer.pubkey()),
+        &[relayer, signer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Simulate a view instruction and decode its return data
+pub async fn view<T: AnchorDeserialize>(context: &mut ProgramTestContext, instruction: Instruction, signer: &Keypair) -> T {
+    let transaction = transaction(context, instruction, signer);
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.expect("return data");
+    T::try_from_slice(&return_data.data).unwrap()
+}
+
+/// Assert the transaction failed with `code` (a program or Anchor error number)
+pub fn assert_error(result: Result<(), BanksClientError>, code: u32) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, code, "expected error {code}, got {actual}")
+        }
+        other => panic!("expected custom error {code}, got {other:?}"),
+    }
+}
+
+pub fn program_error(code: fundly::ErrorCode) -> u32 {
+    anchor_lang::error::ERROR_CODE_OFFSET + code as u32
+}
+
+pub fn anchor_error(code: anchor_lang::error::ErrorCode) -> u32 {
+    code as u32
+}
+
+pub async fn lamports(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    context.banks_client.get_balance(*address).await.unwrap()
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*address).await.unwrap().expect("token account exists");
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+/// Create an empty associated token account for `mint` owned by `owner`
+pub fn create_associated_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let address = get_associated_token_address(owner, mint);
+    context.set_account(&address, &token_account(*mint, *owner, 0).into());
+    address
+}
+
+pub async fn fetch<T: AccountDeserialize>(context: &mut ProgramTestContext, address: &Pubkey) -> T {
+    let account = context.banks_client.get_account(*address).await.unwrap().expect("account exists");
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn bonding_curve(context: &mut ProgramTestContext, mint: &Pubkey) -> fundly::BondingCurve {
+    fetch(context, &pda::bonding_curve_pda(mint).0).await
+}
+
+/// Overwrite `mint`'s bonding curve state, keeping the account's discriminator and lamports
+pub async fn set_bonding_curve(context: &mut ProgramTestContext, mint: &Pubkey, curve: &fundly::BondingCurve) {
+    let address = pda::bonding_curve_pda(mint).0;
+    let mut account = context.banks_client.get_account(address).await.unwrap().expect("account exists");
+    account.data[8..].copy_from_slice(bytemuck::bytes_of(curve));
+    context.set_account(&address, &account.into());
+}
+
+/// Move the cluster clock to `unix_timestamp`
+pub async fn set_time(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+/// Write a Switchboard randomness account committed in `seed_slot` and revealing `value` in
+/// `reveal_slot` (0 = not revealed yet)
+pub fn set_switchboard_randomness(
+    context: &mut ProgramTestContext,
+    address: &Pubkey,
+    seed_slot: u64,
+    reveal_slot: u64,
+    value: [u8; 32],
+) {
+    set_switchboard_randomness_from(context, address, &RANDOMNESS_QUEUE, seed_slot, reveal_slot, value);
+}
+
+/// `set_switchboard_randomness` for an account on `queue`
+pub fn set_switchboard_randomness_from(
+    context: &mut ProgramTestContext,
+    address: &Pubkey,
+    queue: &Pubkey,
+    seed_slot: u64,
+    reveal_slot: u64,
+    value: [u8; 32],
+) {
+    // discriminator, authority, queue, seed slothash, seed slot, oracle, reveal slot, value
+    let mut data = fundly::randomness::RANDOMNESS_ACCOUNT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(RANDOMNESS_AUTHORITY.as_ref());
+    data.extend_from_slice(queue.as_ref());
+    data.extend_from_slice(&[0; 32]);
+    data.extend_from_slice(&seed_slot.to_le_bytes());
+    data.extend_from_slice(&[0; 32]);
+    data.extend_from_slice(&reveal_slot.to_le_bytes());
+    data.extend_from_slice(&value);
+    context.set_account(
+        address,
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: fundly::randomness::SWITCHBOARD_ON_DEMAND_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+/// Write a fully verified Pyth SOL/USD price update of `price` * 10^-8 dollars
+pub fn set_sol_usd_price(context: &mut ProgramTestContext, address: &Pubkey, price: i64, conf: u64, publish_time: i64) {
+    // discriminator, write authority, verification level (Full), then the price message
+    let mut data = fundly::oracle::PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[0; 32]);
+    data.push(1);
+    data.extend_from_slice(&fundly::oracle::SOL_USD_FEED_ID);
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&conf.to_le_bytes());
+    data.extend_from_slice(&(-8i32).to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes());
+    // prev_publish_time, ema_price, ema_conf, posted_slot
+    data.extend_from_slice(&[0; 32]);
+    context.set_account(
+        address,
+        &Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: fundly::oracle::PYTH_RECEIVER_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+pub async fn now(context: &mut ProgramTestContext) -> i64 {
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp
+}
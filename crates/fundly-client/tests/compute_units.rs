@@ -0,0 +1,83 @@
+//! Compute-unit ceilings for curve trades.
+//!
+//! Bots size their compute budget from `BUY_TOKENS_CU_LIMIT`,
+//! `SELL_TOKENS_CU_LIMIT` and `BUY_AND_MIGRATE_CU_LIMIT`, so a change that
+//! pushes a trade over them fails here. Build with `cu-log` to see where the units go in the program logs.
+//!
+//! These tests load the compiled program, so build it first:
+//! `anchor build && cargo test -p fundly-client --test compute_units -- --nocapture`
+
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::{create_associated_token_account, launch, send, token_balance, transaction, Launch};
+use fundly_client::{instructions, pda, BUY_AND_MIGRATE_CU_LIMIT, BUY_TOKENS_CU_LIMIT, SELL_TOKENS_CU_LIMIT};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+
+const BUY_LAMPORTS: u64 = 1_000_000_000;
+// Enough to cross the 85 SOL migration threshold after the 1% fee
+const GRADUATING_BUY_LAMPORTS: u64 = 87_000_000_000;
+
+// Simulate `instruction` and return the compute units it consumed
+async fn compute_units(context: &mut ProgramTestContext, instruction: Instruction, signer: &Keypair) -> u64 {
+    let transaction = transaction(context, instruction, signer);
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    let details = simulation.simulation_details.unwrap();
+    for log in &details.logs {
+        println!("{log}");
+    }
+    details.units_consumed
+}
+
+#[tokio::test]
+async fn first_buy_within_limit() {
+    let Launch { mut context, mint, trader, .. } = launch().await;
+
+    let buy = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    let units = compute_units(&mut context, buy, &trader).await;
+    println!("first buy: {units} CU (limit {BUY_TOKENS_CU_LIMIT})");
+    assert!(units <= BUY_TOKENS_CU_LIMIT as u64);
+}
+
+#[tokio::test]
+async fn repeat_buy_within_limit() {
+    let Launch { mut context, mint, trader, .. } = launch().await;
+    let buy = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut context, buy, &trader).await.unwrap();
+
+    let buy = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    let units = compute_units(&mut context, buy, &trader).await;
+    println!("repeat buy: {units} CU (limit {BUY_TOKENS_CU_LIMIT})");
+    assert!(units <= BUY_TOKENS_CU_LIMIT as u64);
+}
+
+#[tokio::test]
+async fn sell_within_limit() {
+    let Launch { mut context, mint, trader, .. } = launch().await;
+    let buy = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut context, buy, &trader).await.unwrap();
+
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let balance = token_balance(&mut context, &trader_tokens).await;
+    let sell = instructions::sell_tokens(&trader.pubkey(), &mint, balance / 2, 0, None);
+    let units = compute_units(&mut context, sell, &trader).await;
+    println!("sell: {units} CU (limit {SELL_TOKENS_CU_LIMIT})");
+    assert!(units <= SELL_TOKENS_CU_LIMIT as u64);
+}
+
+#[tokio::test]
+async fn threshold_crossing_buy_with_migration_within_limit() {
+    let Launch { mut context, admin, trader, treasury, mint } = launch().await;
+    create_associated_token_account(&mut context, &mint, &pda::migration_authority_pda().0);
+
+    let migration = instructions::BuyMigration { creator: admin.pubkey(), treasury, treasury_split: &[] };
+    let buy = instructions::buy_tokens_and_migrate(&trader.pubkey(), &mint, &migration, GRADUATING_BUY_LAMPORTS, 0, None);
+    let units = compute_units(&mut context, buy, &trader).await;
+    println!("buy and migrate: {units} CU (limit {BUY_AND_MIGRATE_CU_LIMIT})");
+    assert!(units <= BUY_AND_MIGRATE_CU_LIMIT as u64);
+}
@@ -0,0 +1,2700 @@
+//! End-to-end curve lifecycle: launch, trades, graduation, migration, fee
+//! withdrawal and vesting claims, plus the attacks each step must reject.
+//!
+//! These tests load the compiled program, so build it first:
+//! `anchor build && cargo test -p fundly-client --test lifecycle`
+
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::{Discriminator, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use common::*;
+use fundly::{CurveState, ErrorCode, MigrationStatus, OrderTrigger, SwapDirection, MIGRATION_FEE_LAMPORTS};
+use fundly_client::instructions::{self, CurveExtras};
+use fundly_client::{pda, quote};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const BUY_LAMPORTS: u64 = 1_000_000_000;
+// Enough to cross the 85 SOL migration threshold after the 1% fee
+const GRADUATING_BUY_LAMPORTS: u64 = 87_000_000_000;
+
+// Swap every occurrence of `address` in the instruction for `replacement`, keeping its flags
+fn replace_account(mut instruction: Instruction, address: &Pubkey, replacement: Pubkey) -> Instruction {
+    for meta in instruction.accounts.iter_mut().filter(|meta| meta.pubkey == *address) {
+        meta.pubkey = replacement;
+    }
+    instruction
+}
+
+fn fee(amount: u64) -> u64 {
+    fundly::curve::fee_for_amount(amount, FEE_BPS).unwrap()
+}
+
+async fn buy(launch: &mut Launch, lamports: u64) {
+    let ix = instructions::buy_tokens(&launch.trader.pubkey(), &launch.mint, lamports, 0, None);
+    send(&mut launch.context, ix, &launch.trader).await.unwrap();
+}
+
+async fn graduate(launch: &mut Launch) {
+    buy(launch, GRADUATING_BUY_LAMPORTS).await;
+    let admin = launch.admin.insecure_clone();
+    let ix = migrate_to_raydium_ix(&admin.pubkey(), &launch.mint, &admin.pubkey(), &launch.treasury);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+}
+
+#[tokio::test]
+async fn launch_moves_supply_onto_the_curve() {
+    let mut launch = launch().await;
+
+    let curve = bonding_curve(&mut launch.context, &launch.mint).await;
+    assert_eq!(curve.mint, launch.mint);
+    assert_eq!(curve.creator, launch.admin.pubkey());
+    assert_eq!(curve.real_token_reserves, INITIAL_TOKEN_SUPPLY);
+    assert_eq!(curve.virtual_sol_reserves, VIRTUAL_SOL_RESERVES);
+    assert!(!curve.is_complete() && !curve.is_migrated());
+
+    let curve_tokens = get_associated_token_address(&pda::bonding_curve_pda(&launch.mint).0, &launch.mint);
+    assert_eq!(token_balance(&mut launch.context, &curve_tokens).await, INITIAL_TOKEN_SUPPLY);
+}
+
+#[tokio::test]
+async fn launch_rejects_wrong_treasury() {
+    let mut launch = start().await;
+    let admin = launch.admin.insecure_clone();
+    send(&mut launch.context, initialize_global_config_ix(&admin.pubkey(), &launch.treasury), &admin)
+        .await
+        .unwrap();
+    send(&mut launch.context, initialize_global_stats_ix(&admin.pubkey()), &admin)
+        .await
+        .unwrap();
+
+    let ix = instructions::initialize_bonding_curve(
+        &admin.pubkey(),
+        &launch.mint,
+        &Pubkey::new_unique(),
+        INITIAL_TOKEN_SUPPLY,
+        None,
+        None,
+    );
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::InvalidTreasury));
+}
+
+#[tokio::test]
+async fn launch_requires_the_standard_supply_and_decimals() {
+    let mut launch = start().await;
+    let admin = launch.admin.insecure_clone();
+    let ix = initialize_global_config_with_ix(&admin.pubkey(), &launch.treasury, |config| config.token_decimals = 9);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    send(&mut launch.context, initialize_global_stats_ix(&admin.pubkey()), &admin)
+        .await
+        .unwrap();
+
+    // Seeding the curve with part of the supply would skew its price against the virtual reserves
+    let ix = instructions::initialize_bonding_curve(
+        &admin.pubkey(),
+        &launch.mint,
+        &launch.treasury,
+        INITIAL_TOKEN_SUPPLY / 2,
+        None,
+        None,
+    );
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::NonstandardTokenSupply),
+    );
+
+    // The test mint has 6 decimals, not the configured 9
+    let ix = instructions::initialize_bonding_curve(
+        &admin.pubkey(),
+        &launch.mint,
+        &launch.treasury,
+        INITIAL_TOKEN_SUPPLY,
+        None,
+        None,
+    );
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::NonstandardTokenDecimals),
+    );
+}
+
+#[tokio::test]
+async fn usd_launch_fee_is_charged_at_a_fresh_confident_oracle_price() {
+    let mut launch = start().await;
+    let admin = launch.admin.insecure_clone();
+    let price_account = Pubkey::new_unique();
+    let ix = initialize_global_config_with_ix(&admin.pubkey(), &launch.treasury, |config| {
+        config.sol_usd_price_account = price_account;
+        config.launch_fee_usd = 5_000_000;
+    });
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    send(&mut launch.context, initialize_global_stats_ix(&admin.pubkey()), &admin)
+        .await
+        .unwrap();
+    let current_time = now(&mut launch.context).await;
+    let launch_ix = |launch: &Launch| {
+        instructions::initialize_bonding_curve_usd_fee(
+            &admin.pubkey(),
+            &launch.mint,
+            &launch.treasury,
+            INITIAL_TOKEN_SUPPLY,
+            &price_account,
+        )
+    };
+
+    let ix = instructions::initialize_bonding_curve(&admin.pubkey(), &launch.mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None);
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::SolUsdPriceRequired));
+    // $150.00000000 per SOL, published over a minute ago
+    set_sol_usd_price(&mut launch.context, &price_account, 15_000_000_000, 1_000_000, current_time - 61);
+    let ix = launch_ix(&launch);
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::StalePrice));
+    // Confidence of +-$4.50 is 3% of the price
+    set_sol_usd_price(&mut launch.context, &price_account, 15_000_000_000, 450_000_000, current_time);
+    let ix = launch_ix(&launch);
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::PriceConfidenceTooWide));
+
+    set_sol_usd_price(&mut launch.context, &price_account, 15_000_000_000, 1_000_000, current_time);
+    let treasury_before = lamports(&mut launch.context, &launch.treasury).await;
+    let ix = launch_ix(&launch);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    // $5 at $150 per SOL, rounded up
+    assert_eq!(lamports(&mut launch.context, &launch.treasury).await, treasury_before + 33_333_334);
+
+    send(&mut launch.context, instructions::finalize_launch(&launch.mint, false), &admin).await.unwrap();
+    let ix = instructions::get_curve_usd_value(&launch.mint, &price_account);
+    let value: fundly::CurveUsdValue = view(&mut launch.context, ix, &admin).await;
+    let state: CurveState = view(&mut launch.context, instructions::get_curve_state(&launch.mint), &admin).await;
+    assert_eq!(value.sol_usd, 150_000_000);
+    assert_eq!(value.price_per_token as u128, state.price_lamports_per_token as u128 * 150_000_000 / 1_000_000_000);
+    assert_eq!(value.real_sol_reserves, 0);
+}
+
+#[tokio::test]
+async fn global_config_upgrades_from_the_unversioned_layout() {
+    // Discriminator through `secondary_payouts`, the last field before `version` was added
+    const V0_LEN: usize = 276;
+    const LP_FEE_CREATOR_SHARE_OFFSET: usize = 146;
+    let mut launch = launch().await;
+    let admin = launch.admin.insecure_clone();
+    let address = pda::global_config_pda().0;
+    let mut account = launch.context.banks_client.get_account(address).await.unwrap().unwrap();
+    account.data.truncate(V0_LEN);
+    account.data[LP_FEE_CREATOR_SHARE_OFFSET..LP_FEE_CREATOR_SHARE_OFFSET + 2]
+        .copy_from_slice(&2_500u16.to_le_bytes());
+    launch.context.set_account(&address, &account.into());
+
+    send(&mut launch.context, instructions::migrate_global_config(&admin.pubkey()), &admin).await.unwrap();
+    let config: fundly::GlobalConfig = fetch(&mut launch.context, &address).await;
+    assert_eq!(config.version, fundly::GlobalConfig::CURRENT_VERSION);
+    // Configured values survive; only fields the old layout lacked get defaults
+    assert_eq!(config.lp_fee_creator_share_bps, 2_500);
+    assert_eq!(config.buy_fee_bps, FEE_BPS);
+    assert_eq!(config.sell_fee_bps, FEE_BPS);
+    assert_eq!(config.migration_operator, admin.pubkey());
+    assert_eq!(config.token_decimals, 6);
+    assert_eq!(config.max_price_age_seconds, 60);
+
+    assert_error(
+        send(&mut launch.context, instructions::upgrade_global_config(&admin.pubkey()), &admin).await,
+        program_error(ErrorCode::ConfigAlreadyCurrent),
+    );
+}
+
+#[tokio::test]
+async fn buys_wait_until_the_launch_is_finalized() {
+    let mut launch = start().await;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ixs = [
+        initialize_global_config_ix(&admin.pubkey(), &launch.treasury),
+        initialize_global_stats_ix(&admin.pubkey()),
+        instructions::initialize_bonding_curve(&admin.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &admin).await.unwrap();
+    }
+
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::LaunchNotFinalized),
+    );
+
+    // A creator still able to mint could dilute buyers
+    set_mint_authority(&mut launch.context, &mint, Some(admin.pubkey()));
+    assert_error(
+        send(&mut launch.context, instructions::finalize_launch(&mint, false), &trader).await,
+        program_error(ErrorCode::MintAuthorityNotRevoked),
+    );
+
+    // Handing the authority to the curve is as good as revoking it
+    set_mint_authority(&mut launch.context, &mint, Some(pda::bonding_curve_pda(&mint).0));
+    send(&mut launch.context, instructions::finalize_launch(&mint, false), &trader).await.unwrap();
+    assert!(bonding_curve(&mut launch.context, &mint).await.is_launch_finalized());
+    assert_error(
+        send(&mut launch.context, instructions::finalize_launch(&mint, false), &trader).await,
+        program_error(ErrorCode::LaunchAlreadyFinalized),
+    );
+
+    buy(&mut launch, BUY_LAMPORTS).await;
+}
+
+#[tokio::test]
+async fn liquidity_bootstrapping_weights_ease_the_price_down_over_the_window() {
+    let mut launch = start().await;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ixs = [
+        initialize_global_config_ix(&admin.pubkey(), &launch.treasury),
+        initialize_global_stats_ix(&admin.pubkey()),
+        instructions::initialize_bonding_curve(&admin.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &admin).await.unwrap();
+    }
+
+    let weights = [40_000, 30_000, 20_000, 15_000];
+    let ix = instructions::enable_liquidity_bootstrapping(&trader.pubkey(), &mint, weights, 4_000);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    // Weights that rise again would let early buyers sell back for more than they paid
+    let ix = instructions::enable_liquidity_bootstrapping(&admin.pubkey(), &mint, [40_000, 20_000, 30_000, 15_000], 4_000);
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::InvalidLbpSchedule));
+    let ix = instructions::enable_liquidity_bootstrapping(&admin.pubkey(), &mint, weights, 4_000);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    send(&mut launch.context, instructions::finalize_launch(&mint, false), &admin).await.unwrap();
+    let ix = instructions::enable_liquidity_bootstrapping(&admin.pubkey(), &mint, weights, 4_000);
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::LbpUnavailable));
+
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    let start = curve.lbp_start_time;
+    let opening_price = 4 * curve.virtual_sol_reserves as u128 * 1_000_000
+        / (curve.virtual_token_reserves + curve.real_token_reserves) as u128;
+    let state: CurveState = view(&mut launch.context, instructions::get_curve_state(&mint), &trader).await;
+    assert_eq!(state.lbp_weight_bps, 40_000);
+    assert_eq!(state.price_lamports_per_token as u128, opening_price);
+
+    // A sniper's early buy is priced at the opening weight
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let sol_before = lamports(&mut launch.context, &trader.pubkey()).await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let tokens = token_balance(&mut launch.context, &trader_tokens).await;
+
+    // Halfway through the second quarter the weight is between its checkpoints
+    set_time(&mut launch.context, start + 1_500).await;
+    let state: CurveState = view(&mut launch.context, instructions::get_curve_state(&mint), &trader).await;
+    assert_eq!(state.lbp_weight_bps, 25_000);
+
+    // Once the window ends the curve is the plain constant product, and the early tokens
+    // sell back for less than they cost
+    set_time(&mut launch.context, start + 4_000).await;
+    let state: CurveState = view(&mut launch.context, instructions::get_curve_state(&mint), &trader).await;
+    assert_eq!(state.lbp_weight_bps, 10_000);
+    let ix = instructions::sell_tokens(&trader.pubkey(), &mint, tokens, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert!(lamports(&mut launch.context, &trader.pubkey()).await < sol_before - BUY_LAMPORTS / 2);
+}
+
+#[tokio::test]
+async fn buy_then_sell_round_trip() {
+    let mut launch = launch().await;
+    let trader = launch.trader.pubkey();
+    let trader_tokens = get_associated_token_address(&trader, &launch.mint);
+
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let tokens = token_balance(&mut launch.context, &trader_tokens).await;
+    assert!(tokens > 0);
+
+    let curve = bonding_curve(&mut launch.context, &launch.mint).await;
+    assert_eq!(curve.real_sol_reserves, BUY_LAMPORTS - fee(BUY_LAMPORTS));
+    assert_eq!(curve.real_token_reserves, INITIAL_TOKEN_SUPPLY - tokens);
+    assert_eq!(curve.accumulated_fees, fee(BUY_LAMPORTS));
+    assert_eq!(curve.trade_count, 1);
+    assert_eq!(curve.holder_count, 1);
+
+    let before = lamports(&mut launch.context, &trader).await;
+    let ix = instructions::sell_tokens(&trader, &launch.mint, tokens, 0, None);
+    send(&mut launch.context, ix, &launch.trader).await.unwrap();
+    // Add back the signature fee the trader paid for the sell
+    let received = lamports(&mut launch.context, &trader).await + 5_000 - before;
+
+    // Fees on both legs mean the round trip always loses
+    assert!(received < BUY_LAMPORTS);
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, 0);
+    let curve = bonding_curve(&mut launch.context, &launch.mint).await;
+    assert_eq!(curve.real_token_reserves, INITIAL_TOKEN_SUPPLY);
+    assert_eq!(curve.trade_count, 2);
+    assert_eq!(curve.holder_count, 0);
+}
+
+#[tokio::test]
+async fn buy_rejects_slippage() {
+    let mut launch = launch().await;
+    let ix = instructions::buy_tokens(&launch.trader.pubkey(), &launch.mint, BUY_LAMPORTS, u64::MAX, None);
+    assert_error(
+        send(&mut launch.context, ix, &launch.trader).await,
+        program_error(ErrorCode::SlippageExceeded),
+    );
+}
+
+#[tokio::test]
+async fn buy_rejects_fake_sol_vault() {
+    let mut launch = launch().await;
+    let ix = replace_account(
+        instructions::buy_tokens(&launch.trader.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None),
+        &pda::sol_vault_pda(&launch.mint).0,
+        Pubkey::new_unique(),
+    );
+    assert_error(
+        send(&mut launch.context, ix, &launch.trader).await,
+        anchor_error(AnchorErrorCode::ConstraintSeeds),
+    );
+}
+
+#[tokio::test]
+async fn sell_rejects_fake_sol_vault() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+
+    // An attacker-controlled account can't stand in for the curve's vault
+    let ix = replace_account(
+        instructions::sell_tokens(&launch.trader.pubkey(), &launch.mint, 1_000_000, 0, None),
+        &pda::sol_vault_pda(&launch.mint).0,
+        launch.trader.pubkey(),
+    );
+    assert_error(
+        send(&mut launch.context, ix, &launch.trader).await,
+        anchor_error(AnchorErrorCode::ConstraintSeeds),
+    );
+}
+
+#[tokio::test]
+async fn selling_the_curve_to_zero_keeps_the_vault_rent_exempt() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let trader = launch.trader.insecure_clone();
+    let vault = pda::sol_vault_pda(&mint).0;
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let rent_exempt = solana_sdk::rent::Rent::default().minimum_balance(0);
+
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    assert_eq!(curve.vault_rent_reserve, rent_exempt);
+
+    // Drain half the reserves out of band: selling everything could then only be paid out
+    // of the rent reserve, so it is refused rather than paid
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    let mut account = launch.context.banks_client.get_account(vault).await.unwrap().unwrap();
+    let full = account.lamports;
+    account.lamports = curve.vault_rent_reserve + curve.accumulated_fees + curve.real_sol_reserves / 2;
+    launch.context.set_account(&vault, &account.clone().into());
+    let ix = instructions::sell_tokens(&trader.pubkey(), &mint, balance, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::InsufficientSOL),
+    );
+
+    // With the vault whole again the trader sells everything; what's left still covers rent
+    account.lamports = full;
+    launch.context.set_account(&vault, &account.into());
+    let ix = instructions::sell_tokens(&trader.pubkey(), &mint, balance, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, 0);
+    assert_eq!(curve.real_token_reserves, INITIAL_TOKEN_SUPPLY);
+    let vault_lamports = lamports(&mut launch.context, &vault).await;
+    assert!(vault_lamports >= curve.vault_rent_reserve + curve.accumulated_fees + curve.real_sol_reserves);
+    assert!(vault_lamports - curve.accumulated_fees >= rent_exempt);
+}
+
+#[tokio::test]
+async fn migration_requires_threshold() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+
+    let admin = launch.admin.insecure_clone();
+    let ix = migrate_to_raydium_ix(&admin.pubkey(), &launch.mint, &admin.pubkey(), &launch.treasury);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::ThresholdNotReached),
+    );
+}
+
+#[tokio::test]
+async fn graduation_migrates_reserves() {
+    let mut launch = launch().await;
+    let treasury_before = lamports(&mut launch.context, &launch.treasury).await;
+
+    graduate(&mut launch).await;
+
+    let raised = GRADUATING_BUY_LAMPORTS - fee(GRADUATING_BUY_LAMPORTS);
+    let curve = bonding_curve(&mut launch.context, &launch.mint).await;
+    assert!(curve.is_migrated());
+    assert_eq!(curve.graduation_sol_reserves, raised);
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.real_token_reserves, 0);
+
+    let migration_vault = pda::migration_vault_pda(&launch.mint).0;
+    assert_eq!(
+        lamports(&mut launch.context, &migration_vault).await,
+        raised - MIGRATION_FEE_LAMPORTS
+    );
+    let migration_tokens = get_associated_token_address(&pda::migration_authority_pda().0, &launch.mint);
+    assert_eq!(
+        token_balance(&mut launch.context, &migration_tokens).await,
+        curve.graduation_token_reserves
+    );
+    assert_eq!(
+        lamports(&mut launch.context, &launch.treasury).await,
+        treasury_before + MIGRATION_FEE_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn threshold_crossing_buy_migrates_in_the_same_transaction() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let migration_tokens = create_associated_token_account(&mut launch.context, &mint, &pda::migration_authority_pda().0);
+    let migration = instructions::BuyMigration { creator: admin.pubkey(), treasury: launch.treasury, treasury_split: &[] };
+    let buy_and_migrate = |lamports| instructions::buy_tokens_and_migrate(&trader.pubkey(), &mint, &migration, lamports, 0, None);
+
+    // Below the threshold the migration accounts are simply unused
+    send(&mut launch.context, buy_and_migrate(BUY_LAMPORTS), &trader).await.unwrap();
+    assert!(!bonding_curve(&mut launch.context, &mint).await.is_migrated());
+
+    send(&mut launch.context, buy_and_migrate(GRADUATING_BUY_LAMPORTS), &trader).await.unwrap();
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    assert!(curve.is_migrated());
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.real_token_reserves, 0);
+    let raised = BUY_LAMPORTS - fee(BUY_LAMPORTS) + GRADUATING_BUY_LAMPORTS - fee(GRADUATING_BUY_LAMPORTS);
+    assert_eq!(curve.graduation_sol_reserves, raised);
+    assert_eq!(
+        lamports(&mut launch.context, &pda::migration_vault_pda(&mint).0).await,
+        raised - MIGRATION_FEE_LAMPORTS
+    );
+    assert_eq!(token_balance(&mut launch.context, &migration_tokens).await, curve.graduation_token_reserves);
+
+    // No trade can land at curve prices after the crossing buy
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::AlreadyMigrated),
+    );
+}
+
+#[tokio::test]
+async fn curve_state_reports_graduation_progress() {
+    let mut launch = launch().await;
+    let trader = launch.trader.insecure_clone();
+
+    let state: CurveState = view(&mut launch.context, instructions::get_curve_state(&launch.mint), &trader).await;
+    assert_eq!(state.graduation_progress_bps, 0);
+    assert_eq!(state.migration_threshold_sol, MIGRATION_THRESHOLD_SOL);
+    assert_eq!(state.buy_fee_bps, FEE_BPS);
+
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let raised = BUY_LAMPORTS - fee(BUY_LAMPORTS);
+    let state: CurveState = view(&mut launch.context, instructions::get_curve_state(&launch.mint), &trader).await;
+    assert_eq!(state.real_sol_reserves, raised);
+    assert_eq!(state.graduation_progress_bps as u64, raised * 10_000 / MIGRATION_THRESHOLD_SOL);
+    assert_eq!(state.trade_count, 1);
+
+    graduate(&mut launch).await;
+    let state: CurveState = view(&mut launch.context, instructions::get_curve_state(&launch.mint), &trader).await;
+    assert!(state.migrated);
+    assert_eq!(state.migration_status, MigrationStatus::FundsMoved);
+}
+
+#[tokio::test]
+async fn migration_rejects_wrong_treasury() {
+    let mut launch = launch().await;
+    buy(&mut launch, GRADUATING_BUY_LAMPORTS).await;
+
+    let admin = launch.admin.insecure_clone();
+    let ix = migrate_to_raydium_ix(&admin.pubkey(), &launch.mint, &admin.pubkey(), &Pubkey::new_unique());
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InvalidTreasury),
+    );
+}
+
+#[tokio::test]
+async fn double_migration_rejected() {
+    let mut launch = launch().await;
+    graduate(&mut launch).await;
+
+    let admin = launch.admin.insecure_clone();
+    let ix = migrate_to_raydium_ix(&admin.pubkey(), &launch.mint, &admin.pubkey(), &launch.treasury);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::AlreadyMigrated),
+    );
+}
+
+#[tokio::test]
+async fn trading_closes_after_migration() {
+    let mut launch = launch().await;
+    graduate(&mut launch).await;
+
+    let ix = instructions::buy_tokens(&launch.trader.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &launch.trader).await,
+        program_error(ErrorCode::AlreadyMigrated),
+    );
+    let ix = instructions::sell_tokens(&launch.trader.pubkey(), &launch.mint, 1_000_000, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &launch.trader).await,
+        program_error(ErrorCode::AlreadyMigrated),
+    );
+}
+
+#[tokio::test]
+async fn swap_trades_the_curve_then_waits_for_the_recorded_pool() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let trader = launch.trader.insecure_clone();
+    let ix = instructions::swap(&trader.pubkey(), &mint, SwapDirection::Buy, BUY_LAMPORTS, 0);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let record = pda::buyer_record_pda(&mint, &trader.pubkey()).0;
+    let buyer_record: fundly::BuyerRecord = fetch(&mut launch.context, &record).await;
+    assert_eq!(buyer_record.rent_payer, trader.pubkey());
+
+    // Sells don't need the buyer record
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    let ix = instructions::swap(&trader.pubkey(), &mint, SwapDirection::Sell, balance / 2, 0);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, balance - balance / 2);
+
+    // After migration the curve's pool field holds the migration vault until the pool is recorded
+    graduate(&mut launch).await;
+    let ix = instructions::swap(&trader.pubkey(), &mint, SwapDirection::Sell, 1_000, 0);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::RaydiumPoolNotRecorded),
+    );
+}
+
+#[tokio::test]
+async fn closed_curve_swaps_route_from_the_graduation_archive() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    graduate(&mut launch).await;
+
+    // The archive has to be written before the curve can be closed
+    let close = instructions::close_bonding_curve(&admin.pubkey(), &mint, &launch.treasury, &admin.pubkey());
+    assert_error(
+        send(&mut launch.context, close.clone(), &admin).await,
+        anchor_error(AnchorErrorCode::AccountNotInitialized),
+    );
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    let ix = instructions::finalize_migration(&admin.pubkey(), &mint, &curve.raydium_pool);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+
+    let treasury_before = lamports(&mut launch.context, &launch.treasury).await;
+    send(&mut launch.context, close, &admin).await.unwrap();
+    let curve_address = pda::bonding_curve_pda(&mint).0;
+    assert_eq!(lamports(&mut launch.context, &curve_address).await, 0);
+    assert_eq!(lamports(&mut launch.context, &pda::sol_vault_pda(&mint).0).await, 0);
+    assert!(lamports(&mut launch.context, &launch.treasury).await > treasury_before);
+
+    // Swaps keep working without the curve: they get as far as the Raydium accounts
+    let ix = instructions::swap_closed_curve(&trader.pubkey(), &mint, SwapDirection::Sell, 1_000, 0);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::InvalidRaydiumAccounts),
+    );
+}
+
+#[tokio::test]
+async fn bonding_curve_upgrades_from_the_borsh_layout() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let trader = launch.trader.insecure_clone();
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+
+    // The curve as the last Borsh layout stored it (300 bytes), with a buy fee override set
+    let mut data = fundly::BondingCurve::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(curve.mint.as_ref());
+    data.extend_from_slice(curve.creator.as_ref());
+    for value in [
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        curve.real_sol_reserves,
+        curve.real_token_reserves,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&[curve.complete, curve.migrated]);
+    data.extend_from_slice(curve.raydium_pool.as_ref());
+    data.push(curve.bump);
+    data.extend_from_slice(&curve.created_at.to_le_bytes());
+    data.extend_from_slice(&curve.graduated_at.to_le_bytes());
+    for value in [
+        curve.unique_buyers,
+        curve.total_fees_collected,
+        curve.graduation_sol_reserves,
+        curve.graduation_token_reserves,
+        curve.accumulated_fees,
+        curve.trade_count,
+        curve.holder_count,
+        curve.trending_score,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&curve.trending_updated_at.to_le_bytes());
+    data.extend_from_slice(&curve.dev_buy_sol.to_le_bytes());
+    data.extend_from_slice(&curve.dev_buy_tokens.to_le_bytes());
+    data.push(curve.flag_reason);
+    data.extend_from_slice(&curve.flagged_at.to_le_bytes());
+    data.extend_from_slice(&[1, 0x2c, 0x01]); // Some(300)
+    data.push(0); // None
+    data.extend_from_slice(&curve.launch_deposit.to_le_bytes());
+    data.extend_from_slice(&curve.trading_start_time.to_le_bytes());
+    data.push(curve.migration_status);
+    for value in [curve.migration_sol_withdrawn, curve.migration_tokens_withdrawn, curve.vault_rent_reserve] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.push(curve.wsol_vault);
+    assert_eq!(data.len(), 300);
+
+    let address = pda::bonding_curve_pda(&mint).0;
+    let mut account = launch.context.banks_client.get_account(address).await.unwrap().unwrap();
+    account.data = data;
+    launch.context.set_account(&address, &account.into());
+
+    send(&mut launch.context, instructions::upgrade_bonding_curve(&trader.pubkey(), &mint), &trader).await.unwrap();
+    let upgraded = bonding_curve(&mut launch.context, &mint).await;
+    assert_eq!(upgraded.real_sol_reserves, curve.real_sol_reserves);
+    assert_eq!(upgraded.real_token_reserves, curve.real_token_reserves);
+    assert_eq!(upgraded.unique_buyers, curve.unique_buyers);
+    assert_eq!(upgraded.vault_rent_reserve, curve.vault_rent_reserve);
+    assert_eq!(upgraded.bump, curve.bump);
+    assert_eq!(upgraded.buy_fee_bps_override, 300);
+    assert_eq!(upgraded.sell_fee_bps_override, fundly::BondingCurve::NO_FEE_OVERRIDE);
+    assert!(upgraded.is_launch_finalized());
+    let account = launch.context.banks_client.get_account(address).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), fundly::BondingCurve::MAX_SIZE);
+
+    // The upgraded curve trades as before
+    buy(&mut launch, BUY_LAMPORTS).await;
+    assert_error(
+        send(&mut launch.context, instructions::upgrade_bonding_curve(&trader.pubkey(), &mint), &trader).await,
+        program_error(ErrorCode::CurveAlreadyCurrent),
+    );
+}
+
+#[tokio::test]
+async fn inline_sol_curve_trades_without_touching_the_vault() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let curve_address = pda::bonding_curve_pda(&mint).0;
+    let vault = pda::sol_vault_pda(&mint).0;
+
+    let ix = instructions::enable_inline_sol(&trader.pubkey(), &mint);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = instructions::enable_inline_sol(&creator.pubkey(), &mint);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.inline_sol, 1);
+
+    // Buys land on the curve account; the vault keeps only its rent
+    let vault_before = lamports(&mut launch.context, &vault).await;
+    let curve_before = lamports(&mut launch.context, &curve_address).await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    assert_eq!(lamports(&mut launch.context, &vault).await, vault_before);
+    assert_eq!(lamports(&mut launch.context, &curve_address).await, curve_before + BUY_LAMPORTS);
+
+    // It can't be switched on once the curve has traded
+    let ix = instructions::enable_inline_sol(&creator.pubkey(), &mint);
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        program_error(ErrorCode::InlineSolUnavailable),
+    );
+
+    // Sells and fee withdrawals pay out of the curve without dipping below its floor
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let tokens = token_balance(&mut launch.context, &trader_tokens).await;
+    let ix = instructions::sell_tokens(&trader.pubkey(), &mint, tokens, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let admin = launch.admin.insecure_clone();
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &mint, &launch.treasury, false, false, false, &[]);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    assert_eq!(curve.accumulated_fees, 0);
+    assert_eq!(
+        lamports(&mut launch.context, &curve_address).await,
+        curve_before + curve.real_sol_reserves + curve.creator_royalties
+    );
+    assert_eq!(lamports(&mut launch.context, &vault).await, vault_before);
+}
+
+#[tokio::test]
+async fn fee_withdrawal_pays_treasury() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let treasury_before = lamports(&mut launch.context, &launch.treasury).await;
+
+    let admin = launch.admin.insecure_clone();
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &launch.mint, &launch.treasury, false, false, false, &[]);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+
+    assert_eq!(
+        lamports(&mut launch.context, &launch.treasury).await,
+        treasury_before + fee(BUY_LAMPORTS)
+    );
+    let curve = bonding_curve(&mut launch.context, &launch.mint).await;
+    assert_eq!(curve.accumulated_fees, 0);
+    assert_eq!(curve.real_sol_reserves, BUY_LAMPORTS - fee(BUY_LAMPORTS));
+
+    // Nothing left to take a second time
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &launch.mint, &launch.treasury, false, false, false, &[]);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::NoFeesToWithdraw),
+    );
+}
+
+#[tokio::test]
+async fn fee_withdrawal_follows_treasury_split() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let (ops, dao) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let admin = launch.admin.insecure_clone();
+    let splits = vec![
+        fundly::TreasurySplit { recipient: ops, weight_bps: 6_000 },
+        fundly::TreasurySplit { recipient: dao, weight_bps: 4_000 },
+    ];
+    send(&mut launch.context, set_treasury_split_ix(&admin.pubkey(), splits), &admin).await.unwrap();
+    let treasury_before = lamports(&mut launch.context, &launch.treasury).await;
+
+    // Recipients left out of the remaining accounts
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &launch.mint, &launch.treasury, false, false, false, &[]);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InvalidRemainingAccounts),
+    );
+
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &launch.mint, &launch.treasury, false, false, false, &[ops, dao]);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+
+    let fees = fee(BUY_LAMPORTS);
+    assert_eq!(lamports(&mut launch.context, &ops).await, fees * 6_000 / 10_000);
+    assert_eq!(lamports(&mut launch.context, &dao).await, fees - fees * 6_000 / 10_000);
+    assert_eq!(lamports(&mut launch.context, &launch.treasury).await, treasury_before);
+}
+
+#[tokio::test]
+async fn migration_fee_follows_treasury_split() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    create_associated_token_account(&mut launch.context, &mint, &pda::migration_authority_pda().0);
+    let (ops, dao) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let splits = vec![
+        fundly::TreasurySplit { recipient: ops, weight_bps: 6_000 },
+        fundly::TreasurySplit { recipient: dao, weight_bps: 4_000 },
+    ];
+    send(&mut launch.context, set_treasury_split_ix(&admin.pubkey(), splits), &admin).await.unwrap();
+    let treasury_before = lamports(&mut launch.context, &launch.treasury).await;
+
+    let split = [ops, dao];
+    let migration = instructions::BuyMigration { creator: admin.pubkey(), treasury: launch.treasury, treasury_split: &split };
+    let ix = instructions::buy_tokens_and_migrate(&trader.pubkey(), &mint, &migration, GRADUATING_BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert!(bonding_curve(&mut launch.context, &mint).await.is_migrated());
+
+    let ops_share = MIGRATION_FEE_LAMPORTS * 6_000 / 10_000;
+    assert_eq!(lamports(&mut launch.context, &ops).await, ops_share);
+    assert_eq!(lamports(&mut launch.context, &dao).await, MIGRATION_FEE_LAMPORTS - ops_share);
+    assert_eq!(lamports(&mut launch.context, &launch.treasury).await, treasury_before);
+}
+
+#[tokio::test]
+async fn treasury_split_must_total_100_percent() {
+    let mut launch = launch().await;
+    let admin = launch.admin.insecure_clone();
+    let splits = vec![
+        fundly::TreasurySplit { recipient: Pubkey::new_unique(), weight_bps: 6_000 },
+        fundly::TreasurySplit { recipient: Pubkey::new_unique(), weight_bps: 3_000 },
+    ];
+    assert_error(
+        send(&mut launch.context, set_treasury_split_ix(&admin.pubkey(), splits), &admin).await,
+        program_error(ErrorCode::InvalidTreasurySplit),
+    );
+}
+
+#[tokio::test]
+async fn insurance_fund_collects_fees_and_pays_after_delay() {
+    const PAYOUT_DELAY: i64 = 86_400;
+    let mut launch = launch_with(|config| config.insurance_fee_bps = 1_000).await;
+    let admin = launch.admin.insecure_clone();
+    send(&mut launch.context, initialize_insurance_fund_ix(&admin.pubkey(), PAYOUT_DELAY), &admin).await.unwrap();
+    buy(&mut launch, BUY_LAMPORTS).await;
+
+    // The fund's slice is mandatory once configured
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &launch.mint, &launch.treasury, false, false, false, &[]);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InsuranceFundRequired),
+    );
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &launch.mint, &launch.treasury, false, true, false, &[]);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+
+    let deposited = fee(BUY_LAMPORTS) / 10;
+    let fund: fundly::InsuranceFund = fetch(&mut launch.context, &pda::insurance_fund_pda().0).await;
+    assert_eq!(fund.total_deposited, deposited);
+
+    // Payouts can't exceed what the fund holds
+    let victim = Pubkey::new_unique();
+    let ix = propose_payout_ix(&admin.pubkey(), 0, &victim, deposited + 1);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InsufficientInsuranceFunds),
+    );
+    send(&mut launch.context, propose_payout_ix(&admin.pubkey(), 0, &victim, deposited), &admin).await.unwrap();
+
+    let trader = launch.trader.insecure_clone();
+    assert_error(
+        send(&mut launch.context, instructions::execute_payout(0, &victim), &trader).await,
+        program_error(ErrorCode::PayoutNotReady),
+    );
+
+    let proposed_at = now(&mut launch.context).await;
+    set_time(&mut launch.context, proposed_at + PAYOUT_DELAY).await;
+    send(&mut launch.context, instructions::execute_payout(0, &victim), &trader).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &victim).await, deposited);
+
+    let fund: fundly::InsuranceFund = fetch(&mut launch.context, &pda::insurance_fund_pda().0).await;
+    assert_eq!(fund.total_paid_out, deposited);
+    assert_eq!(fund.pending_amount, 0);
+    assert_error(
+        send(&mut launch.context, instructions::execute_payout(0, &victim), &trader).await,
+        program_error(ErrorCode::PayoutAlreadyExecuted),
+    );
+}
+
+#[tokio::test]
+async fn buyback_burns_tokens_with_fees_before_migration() {
+    let mut launch = launch_with(|config| config.buyback_bps = 5_000).await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let before = bonding_curve(&mut launch.context, &launch.mint).await;
+
+    let admin = launch.admin.insecure_clone();
+    send(&mut launch.context, buyback_and_burn_ix(&admin.pubkey(), &launch.mint, 1), &admin).await.unwrap();
+
+    let spent = fee(BUY_LAMPORTS) / 2;
+    let after = bonding_curve(&mut launch.context, &launch.mint).await;
+    let burned = before.real_token_reserves - after.real_token_reserves;
+    assert!(burned > 0);
+    assert_eq!(after.accumulated_fees, before.accumulated_fees - spent);
+    assert_eq!(after.real_sol_reserves, before.real_sol_reserves + spent);
+    assert_eq!(mint_supply(&mut launch.context, &launch.mint).await, INITIAL_TOKEN_SUPPLY - burned);
+
+    let curve_tokens = get_associated_token_address(&pda::bonding_curve_pda(&launch.mint).0, &launch.mint);
+    assert_eq!(token_balance(&mut launch.context, &curve_tokens).await, after.real_token_reserves);
+}
+
+#[tokio::test]
+async fn buyback_is_gated_like_a_curve_buy() {
+    let mut launch = launch_with(|config| config.buyback_bps = 5_000).await;
+    let mint = launch.mint;
+    let admin = launch.admin.insecure_clone();
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+
+    let not_started = fundly::BondingCurve { trading_start_time: i64::MAX, ..curve };
+    set_bonding_curve(&mut launch.context, &mint, &not_started).await;
+    assert_error(
+        send(&mut launch.context, buyback_and_burn_ix(&admin.pubkey(), &mint, 1), &admin).await,
+        program_error(ErrorCode::TradingNotStarted),
+    );
+
+    let flagged = fundly::BondingCurve { flag_reason: 1, ..curve };
+    set_bonding_curve(&mut launch.context, &mint, &flagged).await;
+    assert_error(
+        send(&mut launch.context, buyback_and_burn_ix(&admin.pubkey(), &mint, 1), &admin).await,
+        program_error(ErrorCode::CurveFlagged),
+    );
+
+    set_bonding_curve(&mut launch.context, &mint, &curve).await;
+    send(&mut launch.context, buyback_and_burn_ix(&admin.pubkey(), &mint, 1), &admin).await.unwrap();
+}
+
+#[tokio::test]
+async fn buyback_requires_configuration() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+
+    let admin = launch.admin.insecure_clone();
+    assert_error(
+        send(&mut launch.context, buyback_and_burn_ix(&admin.pubkey(), &launch.mint, 0), &admin).await,
+        program_error(ErrorCode::BuybackDisabled),
+    );
+}
+
+#[tokio::test]
+async fn fee_withdrawal_rejects_wrong_treasury() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+
+    let admin = launch.admin.insecure_clone();
+    let ix = instructions::withdraw_platform_fees(&admin.pubkey(), &launch.mint, &Pubkey::new_unique(), false, false, false, &[]);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InvalidTreasury),
+    );
+}
+
+#[tokio::test]
+async fn fee_withdrawal_rejects_stranger() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+
+    let trader = launch.trader.insecure_clone();
+    let ix = instructions::withdraw_platform_fees(&trader.pubkey(), &launch.mint, &launch.treasury, false, false, false, &[]);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::Unauthorized),
+    );
+}
+
+#[tokio::test]
+async fn vesting_releases_linearly_after_cliff() {
+    let mut launch = launch().await;
+    let creator = launch.admin.insecure_clone();
+    let creator_tokens = get_associated_token_address(&creator.pubkey(), &launch.mint);
+
+    // The creator buys in, then locks the tokens
+    let ix = instructions::buy_tokens(&creator.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let locked = token_balance(&mut launch.context, &creator_tokens).await;
+
+    let start = now(&mut launch.context).await;
+    let ix = instructions::initialize_vesting(&creator.pubkey(), &launch.mint, locked, start, 100, 1_000, 1);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let vesting_vault = get_associated_token_address(&pda::vesting_pda(&launch.mint, &creator.pubkey()).0, &launch.mint);
+    let ix = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &creator_tokens,
+        &vesting_vault,
+        &creator.pubkey(),
+        &[],
+        locked,
+    )
+    .unwrap();
+    send(&mut launch.context, ix, &creator).await.unwrap();
+
+    let claim = || instructions::claim_vested_tokens(&creator.pubkey(), &launch.mint, None, None);
+    assert_error(
+        send(&mut launch.context, claim(), &creator).await,
+        program_error(ErrorCode::CliffNotReached),
+    );
+
+    set_time(&mut launch.context, start + 500).await;
+    send(&mut launch.context, claim(), &creator).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &creator_tokens).await, locked / 2);
+
+    set_time(&mut launch.context, start + 1_000).await;
+    send(&mut launch.context, claim(), &creator).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &creator_tokens).await, locked);
+    assert_eq!(token_balance(&mut launch.context, &vesting_vault).await, 0);
+
+    assert_error(
+        send(&mut launch.context, claim(), &creator).await,
+        program_error(ErrorCode::NoTokensToCllaim),
+    );
+}
+
+#[tokio::test]
+async fn vesting_claims_partially_into_another_account() {
+    let mut launch = launch().await;
+    let creator = launch.admin.insecure_clone();
+    // The whole supply sits on the curve, so the creator buys the tokens it locks
+    let ix = instructions::buy_tokens(&creator.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let start = now(&mut launch.context).await;
+    let ix = instructions::initialize_vesting(&creator.pubkey(), &launch.mint, 1_000, start, 0, 10, 1);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let vesting_vault = get_associated_token_address(&pda::vesting_pda(&launch.mint, &creator.pubkey()).0, &launch.mint);
+    let creator_tokens = get_associated_token_address(&creator.pubkey(), &launch.mint);
+    let ix = spl_token::instruction::transfer(&spl_token::ID, &creator_tokens, &vesting_vault, &creator.pubkey(), &[], 1_000)
+        .unwrap();
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    set_time(&mut launch.context, start + 5).await;
+
+    let cold_wallet = create_token_account(&mut launch.context, &launch.mint, &creator.pubkey());
+    let claim = |amount| instructions::claim_vested_tokens(&creator.pubkey(), &launch.mint, Some(&cold_wallet), Some(amount));
+    send(&mut launch.context, claim(200), &creator).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &cold_wallet).await, 200);
+
+    // Only 300 of the 500 unlocked remain
+    assert_error(
+        send(&mut launch.context, claim(301), &creator).await,
+        program_error(ErrorCode::ClaimExceedsUnlocked),
+    );
+    send(&mut launch.context, claim(300), &creator).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &cold_wallet).await, 500);
+
+    // The destination must belong to the beneficiary
+    let foreign = create_token_account(&mut launch.context, &launch.mint, &launch.trader.pubkey());
+    set_time(&mut launch.context, start + 10).await;
+    let ix = instructions::claim_vested_tokens(&creator.pubkey(), &launch.mint, Some(&foreign), None);
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        anchor_error(AnchorErrorCode::ConstraintTokenOwner),
+    );
+}
+
+#[tokio::test]
+async fn vesting_claim_rejects_other_beneficiary() {
+    let mut launch = launch().await;
+    // Gives the trader a token account to claim into
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let creator = launch.admin.insecure_clone();
+    let start = now(&mut launch.context).await;
+    let ix = instructions::initialize_vesting(&creator.pubkey(), &launch.mint, 1_000, start, 0, 10, 1);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+
+    // The schedule is derived from the signing beneficiary, so the creator's can't be passed
+    let ix = replace_account(
+        instructions::claim_vested_tokens(&launch.trader.pubkey(), &launch.mint, None, None),
+        &pda::vesting_pda(&launch.mint, &launch.trader.pubkey()).0,
+        pda::vesting_pda(&launch.mint, &creator.pubkey()).0,
+    );
+    assert_error(
+        send(&mut launch.context, ix, &launch.trader).await,
+        anchor_error(AnchorErrorCode::ConstraintSeeds),
+    );
+}
+
+#[tokio::test]
+async fn claim_delegate_claims_into_beneficiary_account() {
+    let mut launch = launch().await;
+    // Gives the trader (acting as the bot) a token account of its own
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let creator = launch.admin.insecure_clone();
+    let bot = launch.trader.insecure_clone();
+    // The whole supply sits on the curve, so the creator buys the tokens it locks
+    let ix = instructions::buy_tokens(&creator.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let start = now(&mut launch.context).await;
+    let ix = instructions::initialize_vesting(&creator.pubkey(), &launch.mint, 1_000, start, 0, 10, 1);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let vesting_vault = get_associated_token_address(&pda::vesting_pda(&launch.mint, &creator.pubkey()).0, &launch.mint);
+    let creator_tokens = get_associated_token_address(&creator.pubkey(), &launch.mint);
+    let ix = spl_token::instruction::transfer(&spl_token::ID, &creator_tokens, &vesting_vault, &creator.pubkey(), &[], 1_000)
+        .unwrap();
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let before = token_balance(&mut launch.context, &creator_tokens).await;
+    set_time(&mut launch.context, start + 5).await;
+
+    let claim = |amount| {
+        instructions::claim_vested_tokens_as(&bot.pubkey(), &creator.pubkey(), &launch.mint, None, Some(amount))
+    };
+    assert_error(
+        send(&mut launch.context, claim(100), &bot).await,
+        program_error(ErrorCode::Unauthorized),
+    );
+
+    let ix = instructions::set_claim_delegate(&creator.pubkey(), &launch.mint, Some(bot.pubkey()));
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    send(&mut launch.context, claim(100), &bot).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &creator_tokens).await, before + 100);
+
+    // The delegate can't redirect the tokens to itself
+    let bot_tokens = get_associated_token_address(&bot.pubkey(), &launch.mint);
+    let ix = instructions::claim_vested_tokens_as(&bot.pubkey(), &creator.pubkey(), &launch.mint, Some(&bot_tokens), None);
+    assert_error(
+        send(&mut launch.context, ix, &bot).await,
+        anchor_error(AnchorErrorCode::ConstraintTokenOwner),
+    );
+
+    // Only the beneficiary can change the delegate
+    let ix = replace_account(
+        instructions::set_claim_delegate(&bot.pubkey(), &launch.mint, None),
+        &pda::vesting_pda(&launch.mint, &bot.pubkey()).0,
+        pda::vesting_pda(&launch.mint, &creator.pubkey()).0,
+    );
+    assert_error(
+        send(&mut launch.context, ix, &bot).await,
+        anchor_error(AnchorErrorCode::ConstraintSeeds),
+    );
+
+    let ix = instructions::set_claim_delegate(&creator.pubkey(), &launch.mint, None);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    assert_error(
+        send(&mut launch.context, claim(100), &bot).await,
+        program_error(ErrorCode::Unauthorized),
+    );
+}
+
+#[tokio::test]
+async fn sol_vesting_releases_lamports_linearly() {
+    let mut launch = launch().await;
+    let funder = launch.admin.insecure_clone();
+    let founder = launch.trader.insecure_clone();
+    let schedule = pda::sol_vesting_pda(&funder.pubkey(), &founder.pubkey()).0;
+    let start = now(&mut launch.context).await;
+    let ix = instructions::initialize_sol_vesting(&funder.pubkey(), &founder.pubkey(), 10_000_000, start, 100, 1_000, 1);
+    send(&mut launch.context, ix, &funder).await.unwrap();
+    let locked = lamports(&mut launch.context, &schedule).await;
+
+    let claim = |amount| instructions::claim_vested_sol(&funder.pubkey(), &founder.pubkey(), amount);
+    assert_error(
+        send(&mut launch.context, claim(None), &founder).await,
+        program_error(ErrorCode::CliffNotReached),
+    );
+
+    set_time(&mut launch.context, start + 500).await;
+    assert_error(
+        send(&mut launch.context, claim(Some(5_000_001)), &founder).await,
+        program_error(ErrorCode::ClaimExceedsUnlocked),
+    );
+    send(&mut launch.context, claim(None), &founder).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &schedule).await, locked - 5_000_000);
+
+    // Only the beneficiary claims
+    let ix = replace_account(
+        instructions::claim_vested_sol(&funder.pubkey(), &funder.pubkey(), None),
+        &pda::sol_vesting_pda(&funder.pubkey(), &funder.pubkey()).0,
+        schedule,
+    );
+    assert_error(
+        send(&mut launch.context, ix, &funder).await,
+        anchor_error(AnchorErrorCode::ConstraintSeeds),
+    );
+
+    set_time(&mut launch.context, start + 1_000).await;
+    send(&mut launch.context, claim(None), &founder).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &schedule).await, locked - 10_000_000);
+    let state: fundly::SolVestingSchedule = fetch(&mut launch.context, &schedule).await;
+    assert_eq!(state.claimed_amount, 10_000_000);
+}
+
+#[tokio::test]
+async fn token_locker_holds_until_unlock_time() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let owner = launch.trader.insecure_clone();
+    let owner_tokens = get_associated_token_address(&owner.pubkey(), &launch.mint);
+    let balance = token_balance(&mut launch.context, &owner_tokens).await;
+    let start = now(&mut launch.context).await;
+
+    // Two independent locks of the same mint
+    let ix = instructions::lock_tokens(&owner.pubkey(), &launch.mint, 0, balance / 2, start + 100);
+    send(&mut launch.context, ix, &owner).await.unwrap();
+    let ix = instructions::lock_tokens(&owner.pubkey(), &launch.mint, 1, balance / 4, start + 100);
+    send(&mut launch.context, ix, &owner).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &owner_tokens).await, balance - balance / 2 - balance / 4);
+
+    let ix = instructions::extend_lock(&owner.pubkey(), &launch.mint, 0, start + 50);
+    assert_error(
+        send(&mut launch.context, ix, &owner).await,
+        program_error(ErrorCode::InvalidLockDuration),
+    );
+    let ix = instructions::extend_lock(&owner.pubkey(), &launch.mint, 0, start + 1_000);
+    send(&mut launch.context, ix, &owner).await.unwrap();
+
+    set_time(&mut launch.context, start + 100).await;
+    let withdraw = |index| instructions::withdraw_after_unlock(&owner.pubkey(), &launch.mint, index, None);
+    assert_error(
+        send(&mut launch.context, withdraw(0), &owner).await,
+        program_error(ErrorCode::TokensStillLocked),
+    );
+    send(&mut launch.context, withdraw(1), &owner).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &owner_tokens).await, balance - balance / 2);
+
+    set_time(&mut launch.context, start + 1_000).await;
+    send(&mut launch.context, withdraw(0), &owner).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &owner_tokens).await, balance);
+    let token_lock = pda::token_lock_pda(&launch.mint, &owner.pubkey(), 0).0;
+    assert_eq!(lamports(&mut launch.context, &token_lock).await, 0);
+}
+
+#[tokio::test]
+async fn otc_deal_settles_both_legs_atomically() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let seller = launch.trader.insecure_clone();
+    let buyer = launch.admin.insecure_clone();
+    let seller_tokens = get_associated_token_address(&seller.pubkey(), &launch.mint);
+    let buyer_tokens = get_associated_token_address(&buyer.pubkey(), &launch.mint);
+    let balance = token_balance(&mut launch.context, &seller_tokens).await;
+    let buyer_balance = token_balance(&mut launch.context, &buyer_tokens).await;
+    let (amount, price) = (balance / 2, 2_000_000_000);
+
+    let ix = instructions::create_otc_deal(&seller.pubkey(), &launch.mint, 7, &buyer.pubkey(), amount, price);
+    send(&mut launch.context, ix, &seller).await.unwrap();
+    let settle = || instructions::settle_otc_deal(&seller.pubkey(), &buyer.pubkey(), &launch.mint, 7);
+    assert_error(
+        send(&mut launch.context, settle(), &buyer).await,
+        program_error(ErrorCode::OtcDealNotFunded),
+    );
+
+    let ix = instructions::deposit_otc_tokens(&seller.pubkey(), &launch.mint, 7);
+    send(&mut launch.context, ix, &seller).await.unwrap();
+    let ix = instructions::deposit_otc_sol(&buyer.pubkey(), &seller.pubkey(), 7);
+    send(&mut launch.context, ix, &buyer).await.unwrap();
+
+    // Fully funded deals can't be backed out of
+    let ix = instructions::cancel_otc_deal(&seller.pubkey(), &seller.pubkey(), &buyer.pubkey(), &launch.mint, 7);
+    assert_error(
+        send(&mut launch.context, ix, &seller).await,
+        program_error(ErrorCode::OtcDealFullyFunded),
+    );
+
+    let seller_lamports = lamports(&mut launch.context, &seller.pubkey()).await;
+    send(&mut launch.context, settle(), &buyer).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &buyer_tokens).await, buyer_balance + amount);
+    assert_eq!(token_balance(&mut launch.context, &seller_tokens).await, balance - amount);
+    // The SOL leg plus the deal's and escrow's rent
+    assert!(lamports(&mut launch.context, &seller.pubkey()).await > seller_lamports + price);
+    assert_eq!(lamports(&mut launch.context, &pda::otc_deal_pda(&seller.pubkey(), 7).0).await, 0);
+}
+
+#[tokio::test]
+async fn otc_deal_cancel_refunds_deposited_leg() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let seller = launch.trader.insecure_clone();
+    let buyer = launch.admin.insecure_clone();
+    let seller_tokens = get_associated_token_address(&seller.pubkey(), &launch.mint);
+    let balance = token_balance(&mut launch.context, &seller_tokens).await;
+
+    let ix = instructions::create_otc_deal(&seller.pubkey(), &launch.mint, 0, &buyer.pubkey(), balance, 1_000_000_000);
+    send(&mut launch.context, ix, &seller).await.unwrap();
+    let ix = instructions::deposit_otc_tokens(&seller.pubkey(), &launch.mint, 0);
+    send(&mut launch.context, ix, &seller).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &seller_tokens).await, 0);
+
+    // Either counterparty can back out before the SOL leg arrives
+    let ix = instructions::cancel_otc_deal(&buyer.pubkey(), &seller.pubkey(), &buyer.pubkey(), &launch.mint, 0);
+    send(&mut launch.context, ix, &buyer).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &seller_tokens).await, balance);
+    assert_eq!(lamports(&mut launch.context, &pda::otc_deal_pda(&seller.pubkey(), 0).0).await, 0);
+}
+
+#[tokio::test]
+async fn funding_round_converts_at_discount_after_graduation() {
+    let mut launch = launch().await;
+    let creator = launch.admin.insecure_clone();
+    let investor = launch.trader.insecure_clone();
+    let creator_tokens = get_associated_token_address(&creator.pubkey(), &launch.mint);
+    let ix = instructions::buy_tokens(&creator.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let allocation = token_balance(&mut launch.context, &creator_tokens).await;
+
+    let deadline = now(&mut launch.context).await + 1_000;
+    let ix = instructions::create_funding_round(&creator.pubkey(), &launch.mint, 2_000, 0, allocation, deadline);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let ix = instructions::invest_in_round(&investor.pubkey(), &launch.mint, BUY_LAMPORTS);
+    send(&mut launch.context, ix, &investor).await.unwrap();
+
+    let mint = launch.mint;
+    let convert = || instructions::convert_funding_round(&mint, &creator.pubkey());
+    assert_error(
+        send(&mut launch.context, convert(), &investor).await,
+        program_error(ErrorCode::FundingRoundNotGraduated),
+    );
+
+    graduate(&mut launch).await;
+    let ix = instructions::invest_in_round(&investor.pubkey(), &launch.mint, BUY_LAMPORTS);
+    assert_error(
+        send(&mut launch.context, ix, &investor).await,
+        program_error(ErrorCode::FundingRoundClosed),
+    );
+
+    let creator_lamports = lamports(&mut launch.context, &creator.pubkey()).await;
+    send(&mut launch.context, convert(), &investor).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &creator.pubkey()).await, creator_lamports + BUY_LAMPORTS);
+
+    // 20% below the price liquidity was seeded at
+    let curve = bonding_curve(&mut launch.context, &launch.mint).await;
+    let migration_price = fundly::curve::spot_price(fundly::curve::Reserves {
+        sol: curve.graduation_sol_reserves as u128,
+        token: curve.graduation_token_reserves as u128,
+    })
+    .unwrap();
+    let round: fundly::FundingRound = fetch(&mut launch.context, &pda::funding_round_pda(&launch.mint).0).await;
+    assert_eq!(round.conversion_price, migration_price * 8 / 10);
+    let expected = fundly::curve::tokens_for_deposit(BUY_LAMPORTS, round.conversion_price).unwrap();
+    // Unneeded allocation went back to the creator
+    assert_eq!(token_balance(&mut launch.context, &creator_tokens).await, allocation - expected);
+
+    let investor_tokens = get_associated_token_address(&investor.pubkey(), &launch.mint);
+    let before = token_balance(&mut launch.context, &investor_tokens).await;
+    let ix = instructions::claim_round_tokens(&investor.pubkey(), &launch.mint);
+    send(&mut launch.context, ix, &investor).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &investor_tokens).await, before + expected);
+}
+
+#[tokio::test]
+async fn funding_round_refunds_when_curve_misses_deadline() {
+    let mut launch = launch().await;
+    let creator = launch.admin.insecure_clone();
+    let investor = launch.trader.insecure_clone();
+    let creator_tokens = get_associated_token_address(&creator.pubkey(), &launch.mint);
+    let ix = instructions::buy_tokens(&creator.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let allocation = token_balance(&mut launch.context, &creator_tokens).await;
+
+    let deadline = now(&mut launch.context).await + 1_000;
+    let ix = instructions::create_funding_round(&creator.pubkey(), &launch.mint, 2_000, 0, allocation, deadline);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let ix = instructions::invest_in_round(&investor.pubkey(), &launch.mint, BUY_LAMPORTS);
+    send(&mut launch.context, ix, &investor).await.unwrap();
+
+    let refund = || instructions::refund_round_deposit(&investor.pubkey(), &launch.mint);
+    assert_error(
+        send(&mut launch.context, refund(), &investor).await,
+        program_error(ErrorCode::FundingRoundNotFailed),
+    );
+
+    set_time(&mut launch.context, deadline).await;
+    let round = pda::funding_round_pda(&launch.mint).0;
+    let escrowed = lamports(&mut launch.context, &round).await;
+    send(&mut launch.context, refund(), &investor).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &round).await, escrowed - BUY_LAMPORTS);
+    let position = pda::round_position_pda(&launch.mint, &investor.pubkey()).0;
+    assert_eq!(lamports(&mut launch.context, &position).await, 0);
+
+    let ix = instructions::reclaim_round_tokens(&creator.pubkey(), &launch.mint);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &creator_tokens).await, allocation);
+}
+
+#[tokio::test]
+async fn project_updates_are_sequenced_and_owner_only() {
+    let mut launch = launch().await;
+    let owner = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let project = create_project(&mut launch.context, &owner.pubkey(), &Pubkey::default());
+
+    for (sequence, uri) in ["ipfs://first", "ipfs://second"].into_iter().enumerate() {
+        let hash = [sequence as u8 + 1; 32];
+        let ix = instructions::post_update(&owner.pubkey(), &project, sequence as u64, hash, uri.to_string());
+        send(&mut launch.context, ix, &owner).await.unwrap();
+        let update: fundly::ProjectUpdate =
+            fetch(&mut launch.context, &pda::project_update_pda(&project, sequence as u64).0).await;
+        assert_eq!(update.sequence, sequence as u64);
+        assert_eq!(update.content_hash, hash);
+        assert_eq!(update.uri(), uri);
+    }
+    let feed: fundly::ProjectUpdateFeed = fetch(&mut launch.context, &pda::update_feed_pda(&project).0).await;
+    assert_eq!(feed.update_count, 2);
+
+    // Posted updates can't be overwritten, and only the owner posts
+    let ix = instructions::post_update(&owner.pubkey(), &project, 1, [9; 32], "ipfs://edited".to_string());
+    assert_error(
+        send(&mut launch.context, ix, &owner).await,
+        anchor_error(AnchorErrorCode::ConstraintSeeds),
+    );
+    let ix = instructions::post_update(&trader.pubkey(), &project, 2, [9; 32], "ipfs://fake".to_string());
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = instructions::post_update(&owner.pubkey(), &project, 2, [0; 32], "ipfs://third".to_string());
+    assert_error(
+        send(&mut launch.context, ix, &owner).await,
+        program_error(ErrorCode::InvalidContentHash),
+    );
+}
+
+#[tokio::test]
+async fn kyc_gated_curve_requires_valid_attestation_to_buy() {
+    let verifier = Keypair::new();
+    let mut launch = launch_with(|config| config.kyc_verifier = verifier.pubkey()).await;
+    airdrop(&mut launch.context, &verifier.pubkey(), 1_000_000_000);
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+
+    let ix = instructions::enable_kyc_gating(&trader.pubkey(), &launch.mint);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = instructions::enable_kyc_gating(&creator.pubkey(), &launch.mint);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    assert_eq!(bonding_curve(&mut launch.context, &launch.mint).await.kyc_required, 1);
+
+    let ix = instructions::buy_tokens(&trader.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::KycAttestationRequired),
+    );
+
+    // Only the configured verifier attests
+    let expires_at = now(&mut launch.context).await + 100;
+    let ix = instructions::issue_kyc_attestation(&trader.pubkey(), &trader.pubkey(), expires_at);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = instructions::issue_kyc_attestation(&verifier.pubkey(), &trader.pubkey(), expires_at);
+    send(&mut launch.context, ix, &verifier).await.unwrap();
+
+    let mint = launch.mint;
+    let attested_buy = || instructions::buy_tokens_attested(&trader.pubkey(), &mint, &verifier.pubkey(), BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, attested_buy(), &trader).await.unwrap();
+    // Gating can't be switched on or off once trading started
+    let ix = instructions::enable_kyc_gating(&creator.pubkey(), &mint);
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        program_error(ErrorCode::KycGatingUnavailable),
+    );
+
+    set_time(&mut launch.context, expires_at).await;
+    assert_error(
+        send(&mut launch.context, attested_buy(), &trader).await,
+        program_error(ErrorCode::KycAttestationRequired),
+    );
+    // Holders can always sell
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    let ix = instructions::sell_tokens(&trader.pubkey(), &mint, balance, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+
+    let ix = instructions::revoke_kyc_attestation(&verifier.pubkey(), &trader.pubkey());
+    send(&mut launch.context, ix, &verifier).await.unwrap();
+    let attestation = pda::kyc_attestation_pda(&verifier.pubkey(), &trader.pubkey()).0;
+    assert_eq!(lamports(&mut launch.context, &attestation).await, 0);
+}
+
+#[tokio::test]
+async fn session_key_trades_within_its_delegated_limits() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let owner = launch.trader.insecure_clone();
+    let session = Keypair::new();
+    airdrop(&mut launch.context, &session.pubkey(), 1_000_000_000);
+    let delegate = pda::trade_delegate_pda(&owner.pubkey(), &session.pubkey()).0;
+
+    let expires_at = now(&mut launch.context).await + 100;
+    let ix = instructions::authorize_trade_delegate(&owner.pubkey(), &session.pubkey(), BUY_LAMPORTS, expires_at, vec![Pubkey::new_unique(); 5], 0);
+    assert_error(
+        send(&mut launch.context, ix, &owner).await,
+        program_error(ErrorCode::InvalidTradeDelegate),
+    );
+    let ix = instructions::authorize_trade_delegate(&owner.pubkey(), &session.pubkey(), BUY_LAMPORTS, expires_at, vec![mint], 2 * BUY_LAMPORTS);
+    send(&mut launch.context, ix, &owner).await.unwrap();
+
+    let ix = instructions::delegated_buy(&session.pubkey(), &owner.pubkey(), &mint, CurveExtras::default(), BUY_LAMPORTS + 1, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &session).await,
+        program_error(ErrorCode::TradeDelegateLimitExceeded),
+    );
+    let budget = lamports(&mut launch.context, &delegate).await;
+    let ix = instructions::delegated_buy(&session.pubkey(), &owner.pubkey(), &mint, CurveExtras::default(), BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &session).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &delegate).await, budget - BUY_LAMPORTS);
+    let owner_tokens = get_associated_token_address(&owner.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &owner_tokens).await;
+    assert!(balance > 0);
+
+    // Sells need the owner's SPL approval, and pay the owner
+    let ix = instructions::delegated_sell(&session.pubkey(), &owner.pubkey(), &mint, CurveExtras::default(), balance / 2, 0, None);
+    assert!(send(&mut launch.context, ix, &session).await.is_err());
+    let ix = spl_token::instruction::approve(&spl_token::ID, &owner_tokens, &delegate, &owner.pubkey(), &[], balance).unwrap();
+    send(&mut launch.context, ix, &owner).await.unwrap();
+    let owner_before = lamports(&mut launch.context, &owner.pubkey()).await;
+    let ix = instructions::delegated_sell(&session.pubkey(), &owner.pubkey(), &mint, CurveExtras::default(), balance / 2, 0, None);
+    send(&mut launch.context, ix, &session).await.unwrap();
+    assert!(lamports(&mut launch.context, &owner.pubkey()).await > owner_before);
+    assert_eq!(token_balance(&mut launch.context, &owner_tokens).await, balance - balance / 2);
+
+    set_time(&mut launch.context, expires_at).await;
+    let ix = instructions::delegated_buy(&session.pubkey(), &owner.pubkey(), &mint, CurveExtras::default(), BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &session).await,
+        program_error(ErrorCode::TradeDelegateExpired),
+    );
+
+    // Revoking refunds the unspent deposit
+    let ix = instructions::revoke_trade_delegate(&owner.pubkey(), &session.pubkey());
+    send(&mut launch.context, ix, &owner).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &delegate).await, 0);
+}
+
+#[tokio::test]
+async fn limit_orders_fill_once_the_price_crosses_their_limit() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let trader = launch.trader.insecure_clone();
+    let keeper = launch.admin.insecure_clone();
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let expires_at = now(&mut launch.context).await + 100;
+
+    // A buy order under the current price rests until the price falls
+    buy(&mut launch, 2 * BUY_LAMPORTS).await;
+    let current_time = now(&mut launch.context).await;
+    let high = quote::spot_price(&bonding_curve(&mut launch.context, &mint).await, current_time).unwrap();
+    let ix = instructions::place_limit_order(&trader.pubkey(), &mint, 1, SwapDirection::Buy, high - 1, BUY_LAMPORTS, expires_at);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let ix = instructions::fill_limit_order(&keeper.pubkey(), &trader.pubkey(), &mint, CurveExtras::default(), 1, SwapDirection::Buy);
+    assert_error(
+        send(&mut launch.context, ix.clone(), &keeper).await,
+        program_error(ErrorCode::LimitPriceNotReached),
+    );
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    let sell = instructions::sell_tokens(&trader.pubkey(), &mint, balance, 0, None);
+    send(&mut launch.context, sell, &trader).await.unwrap();
+    send(&mut launch.context, ix, &keeper).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &pda::limit_order_pda(&trader.pubkey(), 1).0).await, 0);
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    assert!(balance > 0);
+
+    // A sell order under the current price fills straight away and pays the owner
+    let current_time = now(&mut launch.context).await;
+    let spot = quote::spot_price(&bonding_curve(&mut launch.context, &mint).await, current_time).unwrap();
+    let ix = instructions::place_limit_order(&trader.pubkey(), &mint, 2, SwapDirection::Sell, spot / 2, balance / 2, expires_at);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, balance - balance / 2);
+    let trader_before = lamports(&mut launch.context, &trader.pubkey()).await;
+    let ix = instructions::fill_limit_order(&keeper.pubkey(), &trader.pubkey(), &mint, CurveExtras::default(), 2, SwapDirection::Sell);
+    send(&mut launch.context, ix, &keeper).await.unwrap();
+    assert!(lamports(&mut launch.context, &trader.pubkey()).await > trader_before);
+
+    // One above it rests until expiry, after which anyone can cancel it
+    let remaining = balance - balance / 2;
+    let ix = instructions::place_limit_order(&trader.pubkey(), &mint, 3, SwapDirection::Sell, 2 * spot, remaining, expires_at);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, 0);
+    let ix = instructions::fill_limit_order(&keeper.pubkey(), &trader.pubkey(), &mint, CurveExtras::default(), 3, SwapDirection::Sell);
+    assert_error(
+        send(&mut launch.context, ix.clone(), &keeper).await,
+        program_error(ErrorCode::LimitPriceNotReached),
+    );
+    let cancel = instructions::cancel_limit_order(&keeper.pubkey(), &trader.pubkey(), &mint, 3, SwapDirection::Sell);
+    assert_error(
+        send(&mut launch.context, cancel.clone(), &keeper).await,
+        program_error(ErrorCode::Unauthorized),
+    );
+    set_time(&mut launch.context, expires_at).await;
+    assert_error(
+        send(&mut launch.context, ix, &keeper).await,
+        program_error(ErrorCode::LimitOrderExpired),
+    );
+    send(&mut launch.context, cancel, &keeper).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, remaining);
+    assert_eq!(lamports(&mut launch.context, &pda::limit_order_pda(&trader.pubkey(), 3).0).await, 0);
+}
+
+#[tokio::test]
+async fn swap_between_curves_rotates_into_another_launch() {
+    let mut launch = launch().await;
+    let old_mint = launch.mint;
+    let new_mint = launch_another(&mut launch).await;
+    let trader = launch.trader.insecure_clone();
+    let plain = CurveExtras::default();
+    let old_tokens = get_associated_token_address(&trader.pubkey(), &old_mint);
+    let new_tokens = get_associated_token_address(&trader.pubkey(), &new_mint);
+
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let balance = token_balance(&mut launch.context, &old_tokens).await;
+    let ix = instructions::swap_between_curves(&trader.pubkey(), &old_mint, plain, &old_mint, plain, balance, 0);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::SameCurveSwap),
+    );
+
+    // The single slippage check is on what the rotation finally buys
+    let ix = instructions::swap_between_curves(&trader.pubkey(), &old_mint, plain, &new_mint, plain, balance, u64::MAX);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::SlippageExceeded),
+    );
+
+    let trader_before = lamports(&mut launch.context, &trader.pubkey()).await;
+    let old_before = bonding_curve(&mut launch.context, &old_mint).await;
+    let ix = instructions::swap_between_curves(&trader.pubkey(), &old_mint, plain, &new_mint, plain, balance, 1);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &old_tokens).await, 0);
+    assert!(token_balance(&mut launch.context, &new_tokens).await > 0);
+
+    // Every lamport the sale paid out went into the new curve
+    let old_after = bonding_curve(&mut launch.context, &old_mint).await;
+    let new_after = bonding_curve(&mut launch.context, &new_mint).await;
+    let sol_out = (old_before.real_sol_reserves - old_after.real_sol_reserves)
+        - (old_after.accumulated_fees - old_before.accumulated_fees);
+    assert_eq!(new_after.real_sol_reserves + new_after.accumulated_fees, sol_out);
+    assert!(lamports(&mut launch.context, &trader.pubkey()).await < trader_before);
+}
+
+#[tokio::test]
+async fn buy_basket_splits_sol_across_curves_by_weight() {
+    let mut launch = launch().await;
+    let first = launch.mint;
+    let second = launch_another(&mut launch).await;
+    let trader = launch.trader.insecure_clone();
+    let plain = CurveExtras::default();
+
+    // Weights must account for the whole amount
+    let ix = instructions::buy_basket(&trader.pubkey(), BUY_LAMPORTS, &[(first, 5_000, 0, plain), (second, 4_000, 0, plain)]);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::InvalidBasket),
+    );
+
+    // Each curve has its own slippage floor, and missing one fails the whole basket
+    let ix = instructions::buy_basket(&trader.pubkey(), BUY_LAMPORTS, &[(first, 7_500, 1, plain), (second, 2_500, u64::MAX, plain)]);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::SlippageExceeded),
+    );
+
+    let first_before = bonding_curve(&mut launch.context, &first).await;
+    let second_before = bonding_curve(&mut launch.context, &second).await;
+    let ix = instructions::buy_basket(&trader.pubkey(), BUY_LAMPORTS, &[(first, 7_500, 1, plain), (second, 2_500, 1, plain)]);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+
+    let first_after = bonding_curve(&mut launch.context, &first).await;
+    let second_after = bonding_curve(&mut launch.context, &second).await;
+    let spent = |before: &fundly::BondingCurve, after: &fundly::BondingCurve| {
+        (after.real_sol_reserves + after.accumulated_fees) - (before.real_sol_reserves + before.accumulated_fees)
+    };
+    assert_eq!(spent(&first_before, &first_after), BUY_LAMPORTS * 3 / 4);
+    assert_eq!(spent(&second_before, &second_after), BUY_LAMPORTS / 4);
+    for mint in [first, second] {
+        let tokens = get_associated_token_address(&trader.pubkey(), &mint);
+        assert!(token_balance(&mut launch.context, &tokens).await > 0);
+    }
+
+    // The buyer records created by the first basket are reused by the next one
+    let ix = instructions::buy_basket(&trader.pubkey(), BUY_LAMPORTS, &[(first, 5_000, 1, plain), (second, 5_000, 1, plain)]);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(bonding_curve(&mut launch.context, &first).await.unique_buyers, first_after.unique_buyers);
+    assert_eq!(bonding_curve(&mut launch.context, &second).await.unique_buyers, 1);
+}
+
+#[tokio::test]
+async fn conditional_orders_sell_once_the_price_crosses_their_trigger() {
+    const TIP: u64 = 10_000;
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let trader = launch.trader.insecure_clone();
+    let keeper = launch.admin.insecure_clone();
+    let seller = Keypair::new();
+    airdrop(&mut launch.context, &seller.pubkey(), 2 * BUY_LAMPORTS);
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+
+    buy(&mut launch, 2 * BUY_LAMPORTS).await;
+    let ix = instructions::buy_tokens(&seller.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &seller).await.unwrap();
+    let current_time = now(&mut launch.context).await;
+    let spot = quote::spot_price(&bonding_curve(&mut launch.context, &mint).await, current_time).unwrap();
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+
+    // A stop-loss just under the price and a take-profit well above it
+    let stop_loss = OrderTrigger::StopLoss { price: spot - 1 };
+    let ix = instructions::place_conditional_order(&trader.pubkey(), &mint, 1, stop_loss, balance / 2, 5_000, TIP);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let take_profit = OrderTrigger::TakeProfit { price: 2 * spot };
+    let ix = instructions::place_conditional_order(&trader.pubkey(), &mint, 2, take_profit, balance - balance / 2, 0, TIP);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, 0);
+    let execute_stop_loss = instructions::execute_conditional_order(&keeper.pubkey(), &trader.pubkey(), &mint, CurveExtras::default(), 1);
+    assert_error(
+        send(&mut launch.context, execute_stop_loss.clone(), &keeper).await,
+        program_error(ErrorCode::OrderNotTriggered),
+    );
+
+    // Another holder selling drops the price through the stop
+    let seller_tokens = get_associated_token_address(&seller.pubkey(), &mint);
+    let seller_balance = token_balance(&mut launch.context, &seller_tokens).await;
+    let ix = instructions::sell_tokens(&seller.pubkey(), &mint, seller_balance, 0, None);
+    send(&mut launch.context, ix, &seller).await.unwrap();
+    let trader_before = lamports(&mut launch.context, &trader.pubkey()).await;
+    send(&mut launch.context, execute_stop_loss, &keeper).await.unwrap();
+    assert!(lamports(&mut launch.context, &trader.pubkey()).await > trader_before);
+    assert_eq!(lamports(&mut launch.context, &pda::conditional_order_pda(&trader.pubkey(), 1).0).await, 0);
+
+    // The take-profit never triggered; cancelling returns its tokens
+    let ix = instructions::execute_conditional_order(&keeper.pubkey(), &trader.pubkey(), &mint, CurveExtras::default(), 2);
+    assert_error(
+        send(&mut launch.context, ix, &keeper).await,
+        program_error(ErrorCode::OrderNotTriggered),
+    );
+    let ix = instructions::cancel_conditional_order(&trader.pubkey(), &mint, 2);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, balance - balance / 2);
+}
+
+#[tokio::test]
+async fn dca_schedule_buys_on_its_interval_until_the_curve_completes() {
+    const INTERVAL: i64 = 3_600;
+    const TIP: u64 = 10_000;
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let keeper = launch.admin.insecure_clone();
+    let owner = Keypair::new();
+    airdrop(&mut launch.context, &owner.pubkey(), 10 * BUY_LAMPORTS);
+    let schedule = pda::dca_schedule_pda(&owner.pubkey(), &mint).0;
+    let owner_tokens = get_associated_token_address(&owner.pubkey(), &mint);
+
+    let ix = instructions::create_dca_schedule(&owner.pubkey(), &mint, BUY_LAMPORTS, INTERVAL, 0, TIP, BUY_LAMPORTS);
+    assert_error(
+        send(&mut launch.context, ix, &owner).await,
+        program_error(ErrorCode::InvalidDcaSchedule),
+    );
+    let ix = instructions::create_dca_schedule(&owner.pubkey(), &mint, BUY_LAMPORTS, INTERVAL, 0, TIP, 3 * (BUY_LAMPORTS + TIP));
+    send(&mut launch.context, ix, &owner).await.unwrap();
+
+    // The first buy is due at once, then one per interval
+    let start = now(&mut launch.context).await;
+    let budget = lamports(&mut launch.context, &schedule).await;
+    let ix = instructions::execute_dca_buy(&keeper.pubkey(), &owner.pubkey(), &mint, CurveExtras::default());
+    send(&mut launch.context, ix.clone(), &keeper).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &schedule).await, budget - BUY_LAMPORTS - TIP);
+    assert!(token_balance(&mut launch.context, &owner_tokens).await > 0);
+    assert_error(
+        send(&mut launch.context, ix.clone(), &keeper).await,
+        program_error(ErrorCode::DcaBuyNotDue),
+    );
+    set_time(&mut launch.context, start + INTERVAL).await;
+    send(&mut launch.context, ix.clone(), &keeper).await.unwrap();
+    let state: fundly::DcaSchedule = fetch(&mut launch.context, &schedule).await;
+    assert_eq!(state.buys_executed, 2);
+
+    // Once the curve completes, the next crank closes the schedule and refunds the owner
+    buy(&mut launch, GRADUATING_BUY_LAMPORTS - 2 * BUY_LAMPORTS).await;
+    let tokens = token_balance(&mut launch.context, &owner_tokens).await;
+    let owner_before = lamports(&mut launch.context, &owner.pubkey()).await;
+    let remaining = lamports(&mut launch.context, &schedule).await;
+    set_time(&mut launch.context, start + 2 * INTERVAL).await;
+    send(&mut launch.context, ix, &keeper).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &schedule).await, 0);
+    assert_eq!(lamports(&mut launch.context, &owner.pubkey()).await, owner_before + remaining);
+    assert_eq!(token_balance(&mut launch.context, &owner_tokens).await, tokens);
+}
+
+#[tokio::test]
+async fn jupiter_buys_only_route_through_the_configured_program() {
+    let jupiter = Pubkey::new_unique();
+    let route = |program_id| Instruction { program_id, accounts: vec![], data: vec![] };
+
+    // Routing is off until a Jupiter program is configured
+    let mut launch = launch().await;
+    let trader = launch.trader.insecure_clone();
+    let ix = instructions::buy_tokens_via_jupiter(&trader.pubkey(), &launch.mint, route(Pubkey::default()), 0, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::InvalidJupiterRoute),
+    );
+
+    let mut launch = launch_with(|config| config.jupiter_program = jupiter).await;
+    let trader = launch.trader.insecure_clone();
+    let ix = instructions::buy_tokens_via_jupiter(&trader.pubkey(), &launch.mint, route(Pubkey::new_unique()), 0, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::InvalidJupiterRoute),
+    );
+}
+
+#[tokio::test]
+async fn relayer_sponsors_trades_for_an_unfunded_wallet() {
+    const TIP: u64 = 5_000;
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let relayer = Keypair::new();
+    airdrop(&mut launch.context, &relayer.pubkey(), 1_000_000_000);
+    // The new wallet holds exactly what it trades and tips, nothing for fees or rent
+    let wallet = Keypair::new();
+    airdrop(&mut launch.context, &wallet.pubkey(), BUY_LAMPORTS + TIP);
+
+    let ix = instructions::buy_tokens_relayed(&relayer.pubkey(), &relayer.pubkey(), &mint, BUY_LAMPORTS, 0, None, TIP);
+    assert_error(
+        send(&mut launch.context, ix, &relayer).await,
+        program_error(ErrorCode::InvalidRelayer),
+    );
+    let ix = instructions::buy_tokens_relayed(&relayer.pubkey(), &wallet.pubkey(), &mint, BUY_LAMPORTS, 0, None, TIP);
+    send_sponsored(&mut launch.context, ix, &relayer, &wallet).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &wallet.pubkey()).await, 0);
+    let wallet_tokens = get_associated_token_address(&wallet.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &wallet_tokens).await;
+    assert!(balance > 0);
+
+    // The tip on a sell comes out of the proceeds
+    let before = bonding_curve(&mut launch.context, &mint).await;
+    let ix = instructions::sell_tokens_relayed(&relayer.pubkey(), &wallet.pubkey(), &mint, balance, 0, None, TIP);
+    send_sponsored(&mut launch.context, ix, &relayer, &wallet).await.unwrap();
+    let after = bonding_curve(&mut launch.context, &mint).await;
+    let sol_out = (before.real_sol_reserves - after.real_sol_reserves)
+        - (after.accumulated_fees - before.accumulated_fees);
+    assert_eq!(lamports(&mut launch.context, &wallet.pubkey()).await, sol_out - TIP);
+}
+
+#[tokio::test]
+async fn graduation_archive_records_the_pool_and_frees_buyer_records() {
+    let mut launch = launch().await;
+    let mint = launch.mint;
+    let admin = launch.admin.insecure_clone();
+    // A relayer sponsors the wallet's first buy, so it pays the buyer record's rent
+    let relayer = Keypair::new();
+    airdrop(&mut launch.context, &relayer.pubkey(), 1_000_000_000);
+    let wallet = Keypair::new();
+    airdrop(&mut launch.context, &wallet.pubkey(), BUY_LAMPORTS);
+    let ix = instructions::buy_tokens_relayed(&relayer.pubkey(), &wallet.pubkey(), &mint, BUY_LAMPORTS, 0, None, 0);
+    send_sponsored(&mut launch.context, ix, &relayer, &wallet).await.unwrap();
+    let record = pda::buyer_record_pda(&mint, &wallet.pubkey()).0;
+    let buyer_record: fundly::BuyerRecord = fetch(&mut launch.context, &record).await;
+    assert_eq!(buyer_record.rent_payer, relayer.pubkey());
+
+    // Records can't be closed until the archive exists
+    let close = |rent_payer: &Pubkey| instructions::close_buyer_record(&wallet.pubkey(), &mint, rent_payer);
+    assert_error(
+        send_sponsored(&mut launch.context, close(&relayer.pubkey()), &relayer, &wallet).await,
+        anchor_error(AnchorErrorCode::AccountNotInitialized),
+    );
+
+    graduate(&mut launch).await;
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    let ix = instructions::finalize_migration(&admin.pubkey(), &mint, &Pubkey::new_unique());
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InvalidRaydiumPool),
+    );
+    let ix = instructions::finalize_migration(&admin.pubkey(), &mint, &curve.raydium_pool);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    let archive: fundly::GraduationArchive = fetch(&mut launch.context, &pda::graduation_archive_pda(&mint).0).await;
+    assert_eq!(archive.raydium_pool, curve.raydium_pool);
+    assert_eq!(archive.unique_buyers, curve.unique_buyers);
+    assert_eq!(archive.graduated_at, curve.graduated_at);
+
+    // The rent goes back to the relayer, not the wallet that was sponsored
+    assert_error(
+        send_sponsored(&mut launch.context, close(&wallet.pubkey()), &relayer, &wallet).await,
+        program_error(ErrorCode::Unauthorized),
+    );
+    let record_rent = lamports(&mut launch.context, &record).await;
+    let relayer_before = lamports(&mut launch.context, &relayer.pubkey()).await;
+    send_sponsored(&mut launch.context, close(&relayer.pubkey()), &relayer, &wallet).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &record).await, 0);
+    // Less the fee for the transaction's two signatures
+    assert_eq!(lamports(&mut launch.context, &relayer.pubkey()).await, relayer_before + record_rent - 10_000);
+}
+
+#[tokio::test]
+async fn creator_royalty_accrues_on_trades_until_claimed() {
+    let mut launch = launch().await;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+
+    let ix = instructions::set_creator_royalty(&creator.pubkey(), &mint, fundly::BondingCurve::MAX_CREATOR_ROYALTY_BPS + 1);
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        program_error(ErrorCode::InvalidCreatorRoyalty),
+    );
+    let ix = instructions::set_creator_royalty(&trader.pubkey(), &mint, 100);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = instructions::set_creator_royalty(&creator.pubkey(), &mint, 100);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+
+    let ix = instructions::claim_creator_royalties(&creator.pubkey(), &mint);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::NoRoyaltiesToClaim),
+    );
+
+    // Royalties come on top of the platform fee, on both sides
+    let before = bonding_curve(&mut launch.context, &mint).await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let after_buy = bonding_curve(&mut launch.context, &mint).await;
+    assert_eq!(after_buy.creator_royalties, BUY_LAMPORTS / 100);
+    assert_eq!(
+        after_buy.real_sol_reserves - before.real_sol_reserves,
+        BUY_LAMPORTS - fee(BUY_LAMPORTS) - BUY_LAMPORTS / 100
+    );
+    let ix = instructions::set_creator_royalty(&creator.pubkey(), &mint, 0);
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        program_error(ErrorCode::CreatorRoyaltyUnavailable),
+    );
+
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    let ix = instructions::sell_tokens(&trader.pubkey(), &mint, balance, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let after_sell = bonding_curve(&mut launch.context, &mint).await;
+    let sol_out_before_fee = after_buy.real_sol_reserves - after_sell.real_sol_reserves;
+    assert_eq!(after_sell.creator_royalties - after_buy.creator_royalties, sol_out_before_fee / 100);
+
+    // Anyone can settle them to the creator
+    let creator_before = lamports(&mut launch.context, &creator.pubkey()).await;
+    let ix = instructions::claim_creator_royalties(&creator.pubkey(), &mint);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(
+        lamports(&mut launch.context, &creator.pubkey()).await - creator_before,
+        after_sell.creator_royalties
+    );
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.creator_royalties, 0);
+}
+
+#[tokio::test]
+async fn creator_allocation_stays_escrowed_until_after_migration() {
+    let mut launch = start().await;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ixs = [
+        initialize_global_config_ix(&creator.pubkey(), &launch.treasury),
+        initialize_global_stats_ix(&creator.pubkey()),
+        instructions::initialize_bonding_curve(&creator.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &creator).await.unwrap();
+    }
+
+    let ix = instructions::escrow_creator_allocation(
+        &creator.pubkey(),
+        &mint,
+        fundly::BondingCurve::MAX_CREATOR_ALLOCATION_BPS + 1,
+        0,
+    );
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        program_error(ErrorCode::InvalidCreatorAllocation),
+    );
+    let release_delay = 3_600;
+    let ix = instructions::escrow_creator_allocation(&creator.pubkey(), &mint, 500, release_delay);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+
+    // The allocation comes out of the curve's reserves and still counts toward the supply
+    let allocation = INITIAL_TOKEN_SUPPLY / 20;
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    assert_eq!(curve.creator_allocation, allocation);
+    assert_eq!(curve.real_token_reserves, INITIAL_TOKEN_SUPPLY - allocation);
+    let escrow = pda::creator_allocation_pda(&mint).0;
+    assert_eq!(token_balance(&mut launch.context, &escrow).await, allocation);
+
+    // Finalizing checks the escrow actually holds the allocation
+    assert_error(
+        send(&mut launch.context, instructions::finalize_launch(&mint, false), &trader).await,
+        program_error(ErrorCode::CreatorAllocationEscrowRequired),
+    );
+    send(&mut launch.context, instructions::finalize_launch(&mint, true), &trader).await.unwrap();
+    assert!(bonding_curve(&mut launch.context, &mint).await.is_launch_finalized());
+
+    let ix = instructions::claim_creator_allocation(&creator.pubkey(), &mint);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::NotMigrated));
+
+    graduate(&mut launch).await;
+    let ix = instructions::claim_creator_allocation(&creator.pubkey(), &mint);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::CreatorAllocationLocked),
+    );
+
+    // Anyone can release it to the creator once the timelock has run
+    let graduated_at = bonding_curve(&mut launch.context, &mint).await.graduated_at;
+    set_time(&mut launch.context, graduated_at + release_delay).await;
+    let creator_tokens = get_associated_token_address(&creator.pubkey(), &mint);
+    let before = token_balance(&mut launch.context, &creator_tokens).await;
+    let ix = instructions::claim_creator_allocation(&creator.pubkey(), &mint);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &creator_tokens).await - before, allocation);
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.creator_allocation, 0);
+    assert!(launch.context.banks_client.get_account(escrow).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn reflections_accrue_on_trades_and_are_claimed_against_snapshots() {
+    let mut launch = start().await;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ixs = [
+        initialize_global_config_ix(&creator.pubkey(), &launch.treasury),
+        initialize_global_stats_ix(&creator.pubkey()),
+        instructions::initialize_bonding_curve(&creator.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None),
+        instructions::enable_reflections(&creator.pubkey(), &mint, 100),
+        instructions::finalize_launch(&mint, false),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &creator).await.unwrap();
+    }
+
+    // Trades that leave out the vault would skip the reflection
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::ReflectionVaultRequired),
+    );
+    let ix = instructions::buy_tokens_reflecting(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+
+    let curve = bonding_curve(&mut launch.context, &mint).await;
+    let tokens_out = INITIAL_TOKEN_SUPPLY - curve.real_token_reserves;
+    let vault = pda::reflection_vault_pda(&mint).0;
+    let reflected = token_balance(&mut launch.context, &vault).await;
+    assert_eq!(reflected, fundly::curve::fee_for_amount(tokens_out, 100).unwrap());
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    assert_eq!(balance, tokens_out - reflected);
+
+    // Only the trader holds tokens outside program accounts
+    let holders = [(trader.pubkey(), balance)];
+    launch.context.warp_to_slot(100).unwrap();
+    let ix = create_snapshot_ix(&creator.pubkey(), &mint, 0, &holders, 50);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    send(&mut launch.context, instructions::distribute_reflections(&trader.pubkey(), &mint, 0), &trader)
+        .await
+        .unwrap();
+    let distribution: fundly::ReflectionDistribution =
+        fetch(&mut launch.context, &pda::reflection_distribution_pda(&mint, 0).0).await;
+    assert!(distribution.amount <= reflected && reflected - distribution.amount <= 1);
+
+    let ix = instructions::claim_reflections(&trader.pubkey(), &mint, 0, balance + 1, vec![]);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::InvalidSnapshotProof),
+    );
+    let ix = instructions::claim_reflections(&trader.pubkey(), &mint, 0, balance, vec![]);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &trader_tokens).await, balance + distribution.amount);
+
+    // Each epoch is claimed once
+    let ix = instructions::claim_reflections(&trader.pubkey(), &mint, 0, balance, vec![]);
+    assert!(send(&mut launch.context, ix, &trader).await.is_err());
+}
+
+#[tokio::test]
+async fn reflection_claims_are_capped_at_the_epoch_distribution() {
+    let mut launch = start().await;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ixs = [
+        initialize_global_config_ix(&creator.pubkey(), &launch.treasury),
+        initialize_global_stats_ix(&creator.pubkey()),
+        instructions::initialize_bonding_curve(&creator.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None),
+        instructions::enable_reflections(&creator.pubkey(), &mint, 100),
+        instructions::finalize_launch(&mint, false),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &creator).await.unwrap();
+    }
+    let ix = instructions::buy_tokens_reflecting(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+
+    // A snapshot whose total leaves out one of its two holders
+    let other = Keypair::new();
+    airdrop(&mut launch.context, &other.pubkey(), 1_000_000_000);
+    create_associated_token_account(&mut launch.context, &mint, &other.pubkey());
+    let holders = [(trader.pubkey(), balance), (other.pubkey(), balance)];
+    let mut ix = create_snapshot_ix(&creator.pubkey(), &mint, 0, &holders, 50);
+    ix.data = fundly::instruction::CreateSnapshot {
+        merkle_root: snapshot_root(&holders),
+        slot: 50,
+        holder_count: 2,
+        total_balance: balance,
+    }
+    .data();
+    launch.context.warp_to_slot(100).unwrap();
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    send(&mut launch.context, instructions::distribute_reflections(&trader.pubkey(), &mint, 0), &trader)
+        .await
+        .unwrap();
+    let distribution: fundly::ReflectionDistribution =
+        fetch(&mut launch.context, &pda::reflection_distribution_pda(&mint, 0).0).await;
+
+    let trader_leaf = fundly::snapshot::leaf(&trader.pubkey(), balance);
+    let other_leaf = fundly::snapshot::leaf(&other.pubkey(), balance);
+    let ix = instructions::claim_reflections(&trader.pubkey(), &mint, 0, balance, vec![other_leaf]);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let distribution_after: fundly::ReflectionDistribution =
+        fetch(&mut launch.context, &pda::reflection_distribution_pda(&mint, 0).0).await;
+    assert_eq!(distribution_after.claimed, distribution.amount);
+
+    // The second holder's claim would take tokens the epoch never received
+    let ix = instructions::claim_reflections(&other.pubkey(), &mint, 0, balance, vec![trader_leaf]);
+    assert_error(
+        send(&mut launch.context, ix, &other).await,
+        program_error(ErrorCode::ReflectionsOverclaimed),
+    );
+}
+
+#[tokio::test]
+async fn swap_trades_reflection_and_jackpot_curves() {
+    let mut launch = start().await;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ixs = [
+        initialize_global_config_ix(&creator.pubkey(), &launch.treasury),
+        initialize_global_stats_ix(&creator.pubkey()),
+        instructions::initialize_bonding_curve(&creator.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None),
+        instructions::enable_reflections(&creator.pubkey(), &mint, 100),
+        instructions::finalize_launch(&mint, false),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &creator).await.unwrap();
+    }
+
+    let ix = instructions::swap(&trader.pubkey(), &mint, SwapDirection::Buy, BUY_LAMPORTS, 0);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::ReflectionVaultRequired),
+    );
+    let ix = instructions::swap_reflecting(&trader.pubkey(), &mint, SwapDirection::Buy, BUY_LAMPORTS, 0);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let tokens_out = INITIAL_TOKEN_SUPPLY - bonding_curve(&mut launch.context, &mint).await.real_token_reserves;
+    let vault = pda::reflection_vault_pda(&mint).0;
+    assert_eq!(token_balance(&mut launch.context, &vault).await, fundly::curve::fee_for_amount(tokens_out, 100).unwrap());
+
+    // Sells through swap reflect too
+    let reflected = token_balance(&mut launch.context, &vault).await;
+    let ix = instructions::swap_reflecting(&trader.pubkey(), &mint, SwapDirection::Sell, 1_000_000, 0);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert!(token_balance(&mut launch.context, &vault).await > reflected);
+
+    let jackpot_mint = launch_another(&mut launch).await;
+    let ix = instructions::enable_jackpot(&creator.pubkey(), &jackpot_mint, 2_000, 10);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let ix = instructions::swap(&trader.pubkey(), &jackpot_mint, SwapDirection::Buy, BUY_LAMPORTS, 0);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::JackpotRequired));
+    let ix = instructions::swap_with_jackpot(&trader.pubkey(), &jackpot_mint, BUY_LAMPORTS, 0);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let jackpot: fundly::Jackpot = fetch(&mut launch.context, &pda::jackpot_pda(&jackpot_mint).0).await;
+    assert_eq!(jackpot.pot().unwrap(), fee(BUY_LAMPORTS) / 5);
+}
+
+#[tokio::test]
+async fn every_trade_route_feeds_the_reflection_vault() {
+    let mut launch = start().await;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ixs = [
+        initialize_global_config_ix(&creator.pubkey(), &launch.treasury),
+        initialize_global_stats_ix(&creator.pubkey()),
+        instructions::initialize_bonding_curve(&creator.pubkey(), &mint, &launch.treasury, INITIAL_TOKEN_SUPPLY, None, None),
+        instructions::enable_reflections(&creator.pubkey(), &mint, 100),
+        instructions::finalize_launch(&mint, false),
+    ];
+    for ix in ixs {
+        send(&mut launch.context, ix, &creator).await.unwrap();
+    }
+    let reflecting = CurveExtras { reflections: true, ..Default::default() };
+    let vault = pda::reflection_vault_pda(&mint).0;
+
+    // Session-key buys
+    let session = Keypair::new();
+    airdrop(&mut launch.context, &session.pubkey(), 1_000_000_000);
+    let expires_at = now(&mut launch.context).await + 100;
+    let ix = instructions::authorize_trade_delegate(&trader.pubkey(), &session.pubkey(), BUY_LAMPORTS, expires_at, vec![mint], BUY_LAMPORTS);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let ix = instructions::delegated_buy(&session.pubkey(), &trader.pubkey(), &mint, CurveExtras::default(), BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &session).await,
+        program_error(ErrorCode::ReflectionVaultRequired),
+    );
+    let ix = instructions::delegated_buy(&session.pubkey(), &trader.pubkey(), &mint, reflecting, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &session).await.unwrap();
+    let reflected = token_balance(&mut launch.context, &vault).await;
+    assert!(reflected > 0);
+
+    // Baskets and curve-to-curve swaps, with the reflection curve on either side
+    let plain_mint = launch_another(&mut launch).await;
+    let legs = [(mint, 5_000, 1, reflecting), (plain_mint, 5_000, 1, CurveExtras::default())];
+    let ix = instructions::buy_basket(&trader.pubkey(), BUY_LAMPORTS, &legs);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert!(token_balance(&mut launch.context, &vault).await > reflected);
+    let reflected = token_balance(&mut launch.context, &vault).await;
+
+    let plain_tokens = get_associated_token_address(&trader.pubkey(), &plain_mint);
+    let balance = token_balance(&mut launch.context, &plain_tokens).await;
+    let ix = instructions::swap_between_curves(&trader.pubkey(), &plain_mint, CurveExtras::default(), &mint, reflecting, balance, 1);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert!(token_balance(&mut launch.context, &vault).await > reflected);
+}
+
+#[tokio::test]
+async fn jackpot_opens_a_draw_every_nth_buy_from_fees() {
+    let mut launch = launch().await;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+
+    let ix = instructions::enable_jackpot(&trader.pubkey(), &mint, 2_000, 10);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = instructions::enable_jackpot(&admin.pubkey(), &mint, 2_000, fundly::Jackpot::MIN_BUYS_PER_DRAW - 1);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InvalidJackpotConfig),
+    );
+    let ix = instructions::enable_jackpot(&admin.pubkey(), &mint, 2_000, 10);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::JackpotRequired));
+
+    let jackpot_address = pda::jackpot_pda(&mint).0;
+    let contribution = fee(BUY_LAMPORTS) / 5;
+    for _ in 0..9 {
+        let ix = instructions::buy_tokens_with_jackpot(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+        send(&mut launch.context, ix, &trader).await.unwrap();
+    }
+    let jackpot: fundly::Jackpot = fetch(&mut launch.context, &jackpot_address).await;
+    assert_eq!((jackpot.pot().unwrap(), jackpot.draws), (9 * contribution, 0));
+    // The fee share leaves the curve's withdrawable fees
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.accumulated_fees, 9 * (fee(BUY_LAMPORTS) - contribution));
+
+    // The tenth buy doesn't win the pot outright, it opens a draw among the recent buyers
+    let ix = instructions::buy_tokens_with_jackpot(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let jackpot: fundly::Jackpot = fetch(&mut launch.context, &jackpot_address).await;
+    assert!(jackpot.draw_pending);
+    assert_eq!((jackpot.draws, jackpot.pending_payout), (0, 0));
+    assert_eq!(jackpot.pot().unwrap(), 10 * contribution);
+}
+
+#[tokio::test]
+async fn every_buy_route_feeds_the_jackpot() {
+    let mut launch = launch().await;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let ix = instructions::enable_jackpot(&admin.pubkey(), &mint, 2_000, 10);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    let with_jackpot = CurveExtras { jackpot: true, ..Default::default() };
+    let jackpot_address = pda::jackpot_pda(&mint).0;
+    let contribution = fee(BUY_LAMPORTS) / 5;
+
+    // Session-key buys
+    let session = Keypair::new();
+    airdrop(&mut launch.context, &session.pubkey(), 1_000_000_000);
+    let expires_at = now(&mut launch.context).await + 100;
+    let ix = instructions::authorize_trade_delegate(&trader.pubkey(), &session.pubkey(), BUY_LAMPORTS, expires_at, vec![mint], BUY_LAMPORTS);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let ix = instructions::delegated_buy(&session.pubkey(), &trader.pubkey(), &mint, CurveExtras::default(), BUY_LAMPORTS, 0, None);
+    assert_error(send(&mut launch.context, ix, &session).await, program_error(ErrorCode::JackpotRequired));
+    let ix = instructions::delegated_buy(&session.pubkey(), &trader.pubkey(), &mint, with_jackpot, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &session).await.unwrap();
+
+    // Baskets
+    let other = launch_another(&mut launch).await;
+    let legs = [(mint, 5_000, 1, with_jackpot), (other, 5_000, 1, CurveExtras::default())];
+    let ix = instructions::buy_basket(&trader.pubkey(), 2 * BUY_LAMPORTS, &legs);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+
+    // DCA buys
+    let ix = instructions::create_dca_schedule(&trader.pubkey(), &mint, BUY_LAMPORTS, 3_600, 0, 0, 2 * BUY_LAMPORTS);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let ix = instructions::execute_dca_buy(&admin.pubkey(), &trader.pubkey(), &mint, with_jackpot);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+
+    let jackpot: fundly::Jackpot = fetch(&mut launch.context, &jackpot_address).await;
+    assert_eq!((jackpot.buy_count, jackpot.pot().unwrap()), (3, 3 * contribution));
+}
+
+#[tokio::test]
+async fn jackpot_draws_settle_with_switchboard_randomness() {
+    let mut launch = launch().await;
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let jackpot_address = pda::jackpot_pda(&mint).0;
+    let randomness = Pubkey::new_unique();
+    let kind = fundly::RandomnessConsumerKind::Jackpot;
+
+    let ix = instructions::enable_jackpot(&admin.pubkey(), &mint, 2_000, 10);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    launch.context.warp_to_slot(100).unwrap();
+    set_switchboard_randomness(&mut launch.context, &randomness, 99, 0, [0; 32]);
+
+    // No draw is due before the tenth buy
+    let ix = instructions::request_randomness(&trader.pubkey(), &jackpot_address, kind, &randomness);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::RandomnessNotNeeded));
+
+    for _ in 0..10 {
+        let ix = instructions::buy_tokens_with_jackpot(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+        send(&mut launch.context, ix, &trader).await.unwrap();
+    }
+    let jackpot: fundly::Jackpot = fetch(&mut launch.context, &jackpot_address).await;
+    assert!(jackpot.draw_pending);
+    assert_eq!((jackpot.draws, jackpot.pot().unwrap()), (0, 10 * (fee(BUY_LAMPORTS) / 5)));
+
+    // Buys while the draw is due still feed the pot but can't join the candidates
+    let late_buyer = Keypair::new();
+    airdrop(&mut launch.context, &late_buyer.pubkey(), 10 * BUY_LAMPORTS);
+    let ix = instructions::buy_tokens_with_jackpot(&late_buyer.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &late_buyer).await.unwrap();
+    let frozen: fundly::Jackpot = fetch(&mut launch.context, &jackpot_address).await;
+    assert_eq!(frozen.recent_buyers, jackpot.recent_buyers);
+    assert_eq!(frozen.pot().unwrap(), 11 * (fee(BUY_LAMPORTS) / 5));
+
+    // Only accounts from the configured queue count
+    set_switchboard_randomness_from(&mut launch.context, &randomness, &Pubkey::new_unique(), 99, 0, [0; 32]);
+    let ix = instructions::request_randomness(&trader.pubkey(), &jackpot_address, kind, &randomness);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::InvalidRandomnessAccount));
+
+    // Randomness committed before the previous slot may already be known
+    set_switchboard_randomness(&mut launch.context, &randomness, 98, 0, [0; 32]);
+    let ix = instructions::request_randomness(&trader.pubkey(), &jackpot_address, kind, &randomness);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::RandomnessAlreadyRevealed));
+    set_switchboard_randomness(&mut launch.context, &randomness, 99, 0, [0; 32]);
+    let ix = instructions::request_randomness(&trader.pubkey(), &jackpot_address, kind, &randomness);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+
+    // Nothing to deliver until the value is revealed
+    launch.context.warp_to_slot(102).unwrap();
+    let ix = instructions::fulfill_randomness(&jackpot_address, &randomness);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::RandomnessNotRevealed));
+    set_switchboard_randomness(&mut launch.context, &randomness, 99, 102, [7; 32]);
+
+    // Once revealed, the request can't be swapped for a fresh draw, even after it expires
+    launch.context.warp_to_slot(102 + fundly::RandomnessRequest::EXPIRY_SLOTS).unwrap();
+    let redraw = Pubkey::new_unique();
+    set_switchboard_randomness(&mut launch.context, &redraw, 101 + fundly::RandomnessRequest::EXPIRY_SLOTS, 0, [0; 32]);
+    let ix = instructions::request_randomness(&trader.pubkey(), &jackpot_address, kind, &redraw);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::RandomnessRequestPending));
+    let ix = instructions::replace_randomness_request(&trader.pubkey(), &jackpot_address, kind, &redraw, &randomness);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::RandomnessRequestPending));
+    let ix = instructions::fulfill_randomness(&jackpot_address, &randomness);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let request: fundly::RandomnessRequest =
+        fetch(&mut launch.context, &pda::randomness_request_pda(&jackpot_address).0).await;
+    assert_eq!(request.value, [7; 32]);
+
+    let jackpot: fundly::Jackpot = fetch(&mut launch.context, &jackpot_address).await;
+    assert!(!jackpot.draw_pending);
+    assert_eq!((jackpot.draws, jackpot.pending_winner), (1, trader.pubkey()));
+    let payout = jackpot.pending_payout;
+    assert_eq!(payout, 11 * (fee(BUY_LAMPORTS) / 5));
+
+    let ix = instructions::claim_jackpot(&mint, &admin.pubkey());
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::Unauthorized));
+    let before = launch.context.banks_client.get_balance(trader.pubkey()).await.unwrap();
+    let ix = instructions::claim_jackpot(&mint, &trader.pubkey());
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    assert_eq!(launch.context.banks_client.get_balance(trader.pubkey()).await.unwrap(), before + payout);
+    let ix = instructions::claim_jackpot(&mint, &trader.pubkey());
+    assert!(send(&mut launch.context, ix, &admin).await.is_err());
+}
+
+#[tokio::test]
+async fn per_wallet_cap_limits_net_sol_contributed() {
+    let mut launch = launch().await;
+    let creator = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+
+    let ix = instructions::set_max_sol_per_wallet(&trader.pubkey(), &mint, BUY_LAMPORTS);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = instructions::set_max_sol_per_wallet(&creator.pubkey(), &mint, 2 * BUY_LAMPORTS);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.max_sol_per_wallet, 2 * BUY_LAMPORTS);
+
+    // The cap is tracked on the buyer record, so capped curves need it
+    let record = pda::buyer_record_pda(&mint, &trader.pubkey()).0;
+    let ix = replace_account(instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None), &record, fundly::ID);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::BuyerRecordRequired),
+    );
+
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS + 1, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::WalletCapExceeded),
+    );
+    // The cap is fixed once trading started
+    let ix = instructions::set_max_sol_per_wallet(&creator.pubkey(), &mint, 0);
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        program_error(ErrorCode::WalletCapUnavailable),
+    );
+
+    // Selling with the record frees up room under the cap
+    let trader_tokens = get_associated_token_address(&trader.pubkey(), &mint);
+    let balance = token_balance(&mut launch.context, &trader_tokens).await;
+    let ix = instructions::sell_tokens_recorded(&trader.pubkey(), &mint, balance / 2, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let contributed = fetch::<fundly::BuyerRecord>(&mut launch.context, &record).await.net_sol_contributed;
+    assert!(contributed < BUY_LAMPORTS);
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, 2 * BUY_LAMPORTS - contributed, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    let record: fundly::BuyerRecord = fetch(&mut launch.context, &record).await;
+    assert_eq!(record.net_sol_contributed, 2 * BUY_LAMPORTS);
+}
+
+#[tokio::test]
+async fn buys_may_skip_the_buyer_record() {
+    let mut launch = launch().await;
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let record = pda::buyer_record_pda(&mint, &trader.pubkey()).0;
+
+    let ix = replace_account(instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None), &record, fundly::ID);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+    assert!(launch.context.banks_client.get_account(record).await.unwrap().is_none());
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.unique_buyers, 0);
+
+    // Passing the record later still counts the buyer once
+    buy(&mut launch, BUY_LAMPORTS).await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.unique_buyers, 1);
+}
+
+#[tokio::test]
+async fn compliance_gate_restricts_buys_on_flagged_launches() {
+    let verifier = Keypair::new();
+    let mut launch = launch_with(|config| config.kyc_verifier = verifier.pubkey()).await;
+    airdrop(&mut launch.context, &verifier.pubkey(), 1_000_000_000);
+    let admin = launch.admin.insecure_clone();
+    let trader = launch.trader.insecure_clone();
+    let mint = launch.mint;
+    let project = create_project(&mut launch.context, &admin.pubkey(), &mint);
+
+    let ix = set_compliance_flags_ix(&trader.pubkey(), &project, &mint, fundly::ComplianceGate::FLAG_MAX_BUY, 1);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+    let ix = set_compliance_flags_ix(&admin.pubkey(), &project, &mint, 1 << 7, 0);
+    assert_error(
+        send(&mut launch.context, ix, &admin).await,
+        program_error(ErrorCode::InvalidComplianceFlags),
+    );
+    let flags = fundly::ComplianceGate::FLAG_ATTESTATION | fundly::ComplianceGate::FLAG_MAX_BUY;
+    let ix = set_compliance_flags_ix(&admin.pubkey(), &project, &mint, flags, BUY_LAMPORTS);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    let state: fundly::ProjectState = fetch(&mut launch.context, &project).await;
+    assert_eq!(state.compliance_flags, flags);
+    assert_eq!(bonding_curve(&mut launch.context, &mint).await.compliance_gated, 1);
+
+    // The gate can't be skipped by leaving it out
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::ComplianceGateRequired),
+    );
+    let ix = instructions::buy_tokens_gated(&trader.pubkey(), &mint, None, BUY_LAMPORTS, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::KycAttestationRequired),
+    );
+
+    let expires_at = now(&mut launch.context).await + 100;
+    let ix = instructions::issue_kyc_attestation(&verifier.pubkey(), &trader.pubkey(), expires_at);
+    send(&mut launch.context, ix, &verifier).await.unwrap();
+    let ix = instructions::buy_tokens_gated(&trader.pubkey(), &mint, Some(&verifier.pubkey()), BUY_LAMPORTS + 1, 0, None);
+    assert_error(
+        send(&mut launch.context, ix, &trader).await,
+        program_error(ErrorCode::ComplianceLimitExceeded),
+    );
+    let ix = instructions::buy_tokens_gated(&trader.pubkey(), &mint, Some(&verifier.pubkey()), BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+
+    // Lifting the flags reopens the plain trade path
+    let ix = set_compliance_flags_ix(&admin.pubkey(), &project, &mint, 0, 0);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    let ix = instructions::buy_tokens(&trader.pubkey(), &mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &trader).await.unwrap();
+}
+
+#[tokio::test]
+async fn rage_quit_withdraws_pro_rata_until_milestones_release() {
+    let mut launch = launch().await;
+    let creator = launch.admin.insecure_clone();
+    let investor = launch.trader.insecure_clone();
+    let creator_tokens = get_associated_token_address(&creator.pubkey(), &launch.mint);
+    let ix = instructions::buy_tokens(&creator.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let allocation = token_balance(&mut launch.context, &creator_tokens).await;
+
+    let deadline = now(&mut launch.context).await + 1_000;
+    let ix = instructions::create_funding_round(&creator.pubkey(), &launch.mint, 2_000, 0, allocation, deadline);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let ix = instructions::enable_rage_quit(&creator.pubkey(), &launch.mint, 2, 100);
+    send(&mut launch.context, ix, &creator).await.unwrap();
+    let ix = instructions::invest_in_round(&investor.pubkey(), &launch.mint, BUY_LAMPORTS);
+    send(&mut launch.context, ix, &investor).await.unwrap();
+    // Investors must know the terms they enter on
+    let ix = instructions::enable_rage_quit(&creator.pubkey(), &launch.mint, 1, 100);
+    assert_error(
+        send(&mut launch.context, ix, &creator).await,
+        program_error(ErrorCode::FundingRoundHasInvestors),
+    );
+
+    graduate(&mut launch).await;
+    let mint = launch.mint;
+    let creator_lamports = lamports(&mut launch.context, &creator.pubkey()).await;
+    let ix = instructions::convert_funding_round(&mint, &creator.pubkey());
+    send(&mut launch.context, ix, &investor).await.unwrap();
+    // The raised SOL stays escrowed
+    assert_eq!(lamports(&mut launch.context, &creator.pubkey()).await, creator_lamports);
+    let converted_at = now(&mut launch.context).await;
+
+    let release = || instructions::release_round_milestone(&mint, &creator.pubkey());
+    assert_error(
+        send(&mut launch.context, release(), &investor).await,
+        program_error(ErrorCode::MilestoneNotReached),
+    );
+
+    let ix = instructions::claim_round_tokens(&investor.pubkey(), &mint);
+    send(&mut launch.context, ix, &investor).await.unwrap();
+    let investor_tokens = get_associated_token_address(&investor.pubkey(), &mint);
+    let burned = token_balance(&mut launch.context, &investor_tokens).await / 2;
+    let supply = mint_supply(&mut launch.context, &mint).await;
+    let round = pda::funding_round_pda(&mint).0;
+    let round_lamports = lamports(&mut launch.context, &round).await;
+    let ix = instructions::rage_quit(&investor.pubkey(), &mint, burned);
+    send(&mut launch.context, ix, &investor).await.unwrap();
+    let share = (BUY_LAMPORTS as u128 * burned as u128 / supply as u128) as u64;
+    assert_eq!(lamports(&mut launch.context, &round).await, round_lamports - share);
+    assert_eq!(mint_supply(&mut launch.context, &mint).await, supply - burned);
+
+    // Each milestone releases an equal part of what is left
+    set_time(&mut launch.context, converted_at + 100).await;
+    send(&mut launch.context, release(), &investor).await.unwrap();
+    let escrowed = BUY_LAMPORTS - share;
+    assert_eq!(lamports(&mut launch.context, &creator.pubkey()).await, creator_lamports + escrowed / 2);
+
+    set_time(&mut launch.context, converted_at + 200).await;
+    send(&mut launch.context, release(), &investor).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &creator.pubkey()).await, creator_lamports + escrowed);
+    let ix = instructions::rage_quit(&investor.pubkey(), &mint, burned);
+    assert_error(
+        send(&mut launch.context, ix, &investor).await,
+        program_error(ErrorCode::NothingEscrowed),
+    );
+}
+
+#[tokio::test]
+async fn snapshots_are_versioned_per_mint() {
+    let mut launch = launch().await;
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let admin = launch.admin.insecure_clone();
+    let trader_tokens = get_associated_token_address(&launch.trader.pubkey(), &launch.mint);
+    let holders = [
+        (launch.trader.pubkey(), token_balance(&mut launch.context, &trader_tokens).await),
+        (pda::bonding_curve_pda(&launch.mint).0, bonding_curve(&mut launch.context, &launch.mint).await.real_token_reserves),
+    ];
+    launch.context.warp_to_slot(100).unwrap();
+
+    let ix = create_snapshot_ix(&admin.pubkey(), &launch.mint, 0, &holders, 50);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    let snapshot: fundly::HolderSnapshot = fetch(&mut launch.context, &pda::holder_snapshot_pda(&launch.mint, 0).0).await;
+    assert_eq!(snapshot.epoch, 0);
+    assert_eq!(snapshot.holder_count, 2);
+    let curve_leaf = fundly::snapshot::leaf(&holders[1].0, holders[1].1);
+    assert!(snapshot.verify_balance(&holders[0].0, holders[0].1, &[curve_leaf]));
+    assert!(!snapshot.verify_balance(&holders[0].0, holders[0].1 + 1, &[curve_leaf]));
+
+    // Each snapshot must be taken after the previous one, and not in the future
+    let ix = create_snapshot_ix(&admin.pubkey(), &launch.mint, 1, &holders, 50);
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::InvalidSnapshot));
+    let ix = create_snapshot_ix(&admin.pubkey(), &launch.mint, 1, &holders, 1_000);
+    assert_error(send(&mut launch.context, ix, &admin).await, program_error(ErrorCode::InvalidSnapshot));
+    let ix = create_snapshot_ix(&admin.pubkey(), &launch.mint, 1, &holders, 90);
+    send(&mut launch.context, ix, &admin).await.unwrap();
+    let registry: fundly::SnapshotRegistry = fetch(&mut launch.context, &pda::snapshot_registry_pda(&launch.mint).0).await;
+    assert_eq!((registry.next_epoch, registry.latest_slot), (2, 90));
+
+    // Only the platform authority commits snapshots
+    let trader = launch.trader.insecure_clone();
+    let ix = create_snapshot_ix(&trader.pubkey(), &launch.mint, 2, &holders, 95);
+    assert_error(send(&mut launch.context, ix, &trader).await, program_error(ErrorCode::Unauthorized));
+}
+
+#[tokio::test]
+async fn sol_stream_pays_per_second_and_cancels() {
+    let mut launch = launch().await;
+    let payer = launch.admin.insecure_clone();
+    let payee = launch.trader.insecure_clone();
+    let start = now(&mut launch.context).await;
+    let terms = fundly::StreamTerms {
+        payee: payee.pubkey(),
+        rate_per_second: 1_000,
+        start_time: start,
+        cliff_duration: 100,
+        duration: 1_000,
+        cancelable: true,
+    };
+    send(&mut launch.context, instructions::create_sol_stream(&payer.pubkey(), 0, terms), &payer).await.unwrap();
+    let stream = pda::payment_stream_pda(&payer.pubkey(), 0).0;
+    let escrowed = lamports(&mut launch.context, &stream).await;
+
+    let withdraw = |amount| instructions::withdraw_from_stream(&payee.pubkey(), &payer.pubkey(), 0, None, amount);
+    set_time(&mut launch.context, start + 50).await;
+    assert_error(
+        send(&mut launch.context, withdraw(None), &payee).await,
+        program_error(ErrorCode::NothingToWithdraw),
+    );
+
+    set_time(&mut launch.context, start + 400).await;
+    assert_error(
+        send(&mut launch.context, withdraw(Some(400_001)), &payee).await,
+        program_error(ErrorCode::ClaimExceedsUnlocked),
+    );
+    send(&mut launch.context, withdraw(None), &payee).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &stream).await, escrowed - 400_000);
+
+    // The payee keeps what streamed before the cancel, the payer gets the rest back
+    set_time(&mut launch.context, start + 600).await;
+    let payee_before = lamports(&mut launch.context, &payee.pubkey()).await;
+    let ix = instructions::cancel_stream(&payer.pubkey(), &payee.pubkey(), 0, None);
+    send(&mut launch.context, ix, &payer).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &payee.pubkey()).await, payee_before + 200_000);
+    assert_eq!(lamports(&mut launch.context, &stream).await, 0);
+}
+
+#[tokio::test]
+async fn token_stream_without_cancel_runs_to_the_end() {
+    let mut launch = launch().await;
+    // Gives the payee a token account
+    buy(&mut launch, BUY_LAMPORTS).await;
+    let payer = launch.admin.insecure_clone();
+    let payee = launch.trader.insecure_clone();
+    let ix = instructions::buy_tokens(&payer.pubkey(), &launch.mint, BUY_LAMPORTS, 0, None);
+    send(&mut launch.context, ix, &payer).await.unwrap();
+    let payee_tokens = get_associated_token_address(&payee.pubkey(), &launch.mint);
+    let payee_balance = token_balance(&mut launch.context, &payee_tokens).await;
+
+    let start = now(&mut launch.context).await;
+    let terms = fundly::StreamTerms {
+        payee: payee.pubkey(),
+        rate_per_second: 10,
+        start_time: start,
+        cliff_duration: 0,
+        duration: 100,
+        cancelable: false,
+    };
+    let ix = instructions::create_token_stream(&payer.pubkey(), &launch.mint, 3, terms);
+    send(&mut launch.context, ix, &payer).await.unwrap();
+
+    set_time(&mut launch.context, start + 50).await;
+    let cancel = || instructions::cancel_stream(&payer.pubkey(), &payee.pubkey(), 3, Some(&launch.mint));
+    assert_error(
+        send(&mut launch.context, cancel(), &payer).await,
+        program_error(ErrorCode::StreamNotCancelable),
+    );
+    // Token streams must pass their token accounts
+    let ix = instructions::withdraw_from_stream(&payee.pubkey(), &payer.pubkey(), 3, None, None);
+    assert_error(
+        send(&mut launch.context, ix, &payee).await,
+        program_error(ErrorCode::InvalidStreamAccounts),
+    );
+
+    set_time(&mut launch.context, start + 100).await;
+    let ix = instructions::withdraw_from_stream(&payee.pubkey(), &payer.pubkey(), 3, Some(&launch.mint), None);
+    send(&mut launch.context, ix, &payee).await.unwrap();
+    assert_eq!(token_balance(&mut launch.context, &payee_tokens).await, payee_balance + 1_000);
+
+    // Once ended, the payer can close the emptied stream
+    send(&mut launch.context, cancel(), &payer).await.unwrap();
+    assert_eq!(lamports(&mut launch.context, &pda::payment_stream_pda(&payer.pubkey(), 3).0).await, 0);
+}
@@ -0,0 +1,448 @@
+//! CPI interface for the fundly program.
+//!
+//! Aggregators and routers can call `buy_tokens` / `sell_tokens` through this
+//! crate without depending on the full program (and its token-metadata stack).
+//! Account order and instruction encoding mirror the program exactly.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::Discriminator;
+
+declare_id!("5dtdAtkPad7cnAtBq8QLy6mfVbtb81pTrg5gCYxfUCgK");
+
+/// Event authority PDA the program emits events through (passed to every trade)
+pub fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID).0
+}
+
+/// Instruction arguments, serialized exactly as the program expects
+pub mod instruction {
+    use super::*;
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+    pub struct BuyTokens {
+        pub sol_amount: u64,
+        pub min_tokens_out: u64,
+        pub max_price_impact_bps: Option<u16>,
+    }
+
+    impl Discriminator for BuyTokens {
+        const DISCRIMINATOR: &'static [u8] = &[189, 21, 230, 133, 247, 2, 110, 42];
+    }
+
+    impl anchor_lang::InstructionData for BuyTokens {}
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+    pub struct SellTokens {
+        pub token_amount: u64,
+        pub min_sol_out: u64,
+        pub max_price_impact_bps: Option<u16>,
+    }
+
+    impl Discriminator for SellTokens {
+        const DISCRIMINATOR: &'static [u8] = &[114, 242, 25, 12, 62, 126, 92, 2];
+    }
+
+    impl anchor_lang::InstructionData for SellTokens {}
+}
+
+/// Account metas in program order, for building instructions off-chain
+pub mod accounts {
+    use super::*;
+
+    pub struct BuyTokens {
+        pub bonding_curve: Pubkey,
+        pub mint: Pubkey,
+        pub bonding_curve_sol_vault: Pubkey,
+        pub bonding_curve_token_account: Pubkey,
+        pub curve_oracle: Pubkey,
+        pub live_candle: Pubkey,
+        pub user_points: Pubkey,
+        pub trade_guard: Pubkey,
+        pub buyer_token_account: Pubkey,
+        pub buyer_record: Pubkey,
+        /// `None` when the buyer doesn't track a position
+        pub trader_position: Option<Pubkey>,
+        /// Competition the trade counts towards, with the trader's entry
+        pub competition: Option<Pubkey>,
+        pub competition_entry: Option<Pubkey>,
+        /// The curve's wSOL vault, only for curves holding their SOL side as wSOL
+        pub curve_wsol_vault: Option<Pubkey>,
+        /// Migration accounts, all passed to migrate the curve if this buy crosses the threshold
+        pub migration_sol_vault: Option<Pubkey>,
+        pub migration_token_account: Option<Pubkey>,
+        pub migration_authority: Option<Pubkey>,
+        pub creator_stats: Option<Pubkey>,
+        pub treasury: Option<Pubkey>,
+        /// The buyer's KYC attestation, only for KYC-gated curves
+        pub kyc_attestation: Option<Pubkey>,
+        /// The mint's compliance gate, only for compliance-gated curves
+        pub compliance_gate: Option<Pubkey>,
+        pub global_config: Pubkey,
+        pub global_stats: Pubkey,
+        pub buyer: Pubkey,
+        /// Pays rent for accounts created on the buyer's first trade (usually the buyer)
+        pub payer: Pubkey,
+        pub system_program: Pubkey,
+        pub token_program: Pubkey,
+        pub associated_token_program: Pubkey,
+        pub event_authority: Pubkey,
+        pub program: Pubkey,
+    }
+
+    impl ToAccountMetas for BuyTokens {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.bonding_curve, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new(self.bonding_curve_sol_vault, false),
+                AccountMeta::new(self.bonding_curve_token_account, false),
+                AccountMeta::new(self.curve_oracle, false),
+                AccountMeta::new(self.live_candle, false),
+                AccountMeta::new(self.user_points, false),
+                AccountMeta::new(self.trade_guard, false),
+                AccountMeta::new(self.buyer_token_account, false),
+                AccountMeta::new(self.buyer_record, false),
+                optional_meta(self.trader_position),
+                optional_meta(self.competition),
+                optional_meta(self.competition_entry),
+                optional_meta(self.curve_wsol_vault),
+                optional_meta(self.migration_sol_vault),
+                optional_meta(self.migration_token_account),
+                optional_readonly_meta(self.migration_authority),
+                optional_meta(self.creator_stats),
+                optional_meta(self.treasury),
+                optional_readonly_meta(self.kyc_attestation),
+                optional_readonly_meta(self.compliance_gate),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.global_stats, false),
+                AccountMeta::new(self.buyer, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.associated_token_program, false),
+                AccountMeta::new_readonly(self.event_authority, false),
+                AccountMeta::new_readonly(self.program, false),
+            ]
+        }
+    }
+
+    pub struct SellTokens {
+        pub bonding_curve: Pubkey,
+        pub mint: Pubkey,
+        pub bonding_curve_sol_vault: Pubkey,
+        pub bonding_curve_token_account: Pubkey,
+        pub curve_oracle: Pubkey,
+        pub live_candle: Pubkey,
+        pub user_points: Pubkey,
+        pub trade_guard: Pubkey,
+        pub seller_token_account: Pubkey,
+        /// `None` when the seller doesn't track a position
+        pub trader_position: Option<Pubkey>,
+        /// Competition the trade counts towards, with the trader's entry
+        pub competition: Option<Pubkey>,
+        pub competition_entry: Option<Pubkey>,
+        /// wSOL curves only: the curve's wSOL vault and the wSOL account receiving the payout
+        pub curve_wsol_vault: Option<Pubkey>,
+        pub seller_wsol_account: Option<Pubkey>,
+        /// The seller's buyer record, credited with the payout on capped curves
+        pub buyer_record: Option<Pubkey>,
+        pub global_config: Pubkey,
+        pub global_stats: Pubkey,
+        pub seller: Pubkey,
+        /// Pays rent for accounts created on the seller's first trade (usually the seller)
+        pub payer: Pubkey,
+        pub system_program: Pubkey,
+        pub token_program: Pubkey,
+        pub event_authority: Pubkey,
+        pub program: Pubkey,
+    }
+
+    impl ToAccountMetas for SellTokens {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.bonding_curve, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new(self.bonding_curve_sol_vault, false),
+                AccountMeta::new(self.bonding_curve_token_account, false),
+                AccountMeta::new(self.curve_oracle, false),
+                AccountMeta::new(self.live_candle, false),
+                AccountMeta::new(self.user_points, false),
+                AccountMeta::new(self.trade_guard, false),
+                AccountMeta::new(self.seller_token_account, false),
+                optional_meta(self.trader_position),
+                optional_meta(self.competition),
+                optional_meta(self.competition_entry),
+                optional_meta(self.curve_wsol_vault),
+                optional_meta(self.seller_wsol_account),
+                optional_meta(self.buyer_record),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.global_stats, false),
+                AccountMeta::new(self.seller, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.event_authority, false),
+                AccountMeta::new_readonly(self.program, false),
+            ]
+        }
+    }
+}
+
+// Anchor encodes an omitted optional account as the program id
+fn optional_meta(account: Option<Pubkey>) -> AccountMeta {
+    match account {
+        Some(key) => AccountMeta::new(key, false),
+        None => AccountMeta::new_readonly(ID, false),
+    }
+}
+
+fn optional_readonly_meta(account: Option<Pubkey>) -> AccountMeta {
+    AccountMeta::new_readonly(account.unwrap_or(ID), false)
+}
+
+/// CPI helpers for on-chain callers
+pub mod cpi {
+    use super::*;
+
+    pub mod accounts {
+        use super::*;
+
+        #[derive(Clone)]
+        pub struct BuyTokens<'info> {
+            pub bonding_curve: AccountInfo<'info>,
+            pub mint: AccountInfo<'info>,
+            pub bonding_curve_sol_vault: AccountInfo<'info>,
+            pub bonding_curve_token_account: AccountInfo<'info>,
+            pub curve_oracle: AccountInfo<'info>,
+            pub live_candle: AccountInfo<'info>,
+            pub user_points: AccountInfo<'info>,
+            pub trade_guard: AccountInfo<'info>,
+            pub buyer_token_account: AccountInfo<'info>,
+            pub buyer_record: AccountInfo<'info>,
+            pub trader_position: Option<AccountInfo<'info>>,
+            pub competition: Option<AccountInfo<'info>>,
+            pub competition_entry: Option<AccountInfo<'info>>,
+            pub curve_wsol_vault: Option<AccountInfo<'info>>,
+            pub migration_sol_vault: Option<AccountInfo<'info>>,
+            pub migration_token_account: Option<AccountInfo<'info>>,
+            pub migration_authority: Option<AccountInfo<'info>>,
+            pub creator_stats: Option<AccountInfo<'info>>,
+            pub treasury: Option<AccountInfo<'info>>,
+            pub kyc_attestation: Option<AccountInfo<'info>>,
+            pub compliance_gate: Option<AccountInfo<'info>>,
+            pub global_config: AccountInfo<'info>,
+            pub global_stats: AccountInfo<'info>,
+            pub buyer: AccountInfo<'info>,
+            pub payer: AccountInfo<'info>,
+            pub system_program: AccountInfo<'info>,
+            pub token_program: AccountInfo<'info>,
+            pub associated_token_program: AccountInfo<'info>,
+            pub event_authority: AccountInfo<'info>,
+            pub program: AccountInfo<'info>,
+        }
+
+        impl<'info> ToAccountMetas for BuyTokens<'info> {
+            fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+                crate::accounts::BuyTokens {
+                    bonding_curve: self.bonding_curve.key(),
+                    mint: self.mint.key(),
+                    bonding_curve_sol_vault: self.bonding_curve_sol_vault.key(),
+                    bonding_curve_token_account: self.bonding_curve_token_account.key(),
+                    curve_oracle: self.curve_oracle.key(),
+                    live_candle: self.live_candle.key(),
+                    user_points: self.user_points.key(),
+                    trade_guard: self.trade_guard.key(),
+                    buyer_token_account: self.buyer_token_account.key(),
+                    buyer_record: self.buyer_record.key(),
+                    trader_position: self.trader_position.as_ref().map(|info| info.key()),
+                    competition: self.competition.as_ref().map(|info| info.key()),
+                    competition_entry: self.competition_entry.as_ref().map(|info| info.key()),
+                    curve_wsol_vault: self.curve_wsol_vault.as_ref().map(|info| info.key()),
+                    migration_sol_vault: self.migration_sol_vault.as_ref().map(|info| info.key()),
+                    migration_token_account: self.migration_token_account.as_ref().map(|info| info.key()),
+                    migration_authority: self.migration_authority.as_ref().map(|info| info.key()),
+                    creator_stats: self.creator_stats.as_ref().map(|info| info.key()),
+                    treasury: self.treasury.as_ref().map(|info| info.key()),
+                    kyc_attestation: self.kyc_attestation.as_ref().map(|info| info.key()),
+                    compliance_gate: self.compliance_gate.as_ref().map(|info| info.key()),
+                    global_config: self.global_config.key(),
+                    global_stats: self.global_stats.key(),
+                    buyer: self.buyer.key(),
+                    payer: self.payer.key(),
+                    system_program: self.system_program.key(),
+                    token_program: self.token_program.key(),
+                    associated_token_program: self.associated_token_program.key(),
+                    event_authority: self.event_authority.key(),
+                    program: self.program.key(),
+                }
+                .to_account_metas(is_signer)
+            }
+        }
+
+        impl<'info> ToAccountInfos<'info> for BuyTokens<'info> {
+            fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+                let mut infos = vec![
+                    self.bonding_curve.clone(),
+                    self.mint.clone(),
+                    self.bonding_curve_sol_vault.clone(),
+                    self.bonding_curve_token_account.clone(),
+                    self.curve_oracle.clone(),
+                    self.live_candle.clone(),
+                    self.user_points.clone(),
+                    self.trade_guard.clone(),
+                    self.buyer_token_account.clone(),
+                    self.buyer_record.clone(),
+                ];
+                infos.extend(self.trader_position.clone());
+                infos.extend(self.competition.clone());
+                infos.extend(self.competition_entry.clone());
+                infos.extend(self.curve_wsol_vault.clone());
+                infos.extend(self.migration_sol_vault.clone());
+                infos.extend(self.migration_token_account.clone());
+                infos.extend(self.migration_authority.clone());
+                infos.extend(self.creator_stats.clone());
+                infos.extend(self.treasury.clone());
+                infos.extend(self.kyc_attestation.clone());
+                infos.extend(self.compliance_gate.clone());
+                infos.extend([
+                    self.global_config.clone(),
+                    self.global_stats.clone(),
+                    self.buyer.clone(),
+                    self.payer.clone(),
+                    self.system_program.clone(),
+                    self.token_program.clone(),
+                    self.associated_token_program.clone(),
+                    self.event_authority.clone(),
+                    self.program.clone(),
+                ]);
+                infos
+            }
+        }
+
+        #[derive(Clone)]
+        pub struct SellTokens<'info> {
+            pub bonding_curve: AccountInfo<'info>,
+            pub mint: AccountInfo<'info>,
+            pub bonding_curve_sol_vault: AccountInfo<'info>,
+            pub bonding_curve_token_account: AccountInfo<'info>,
+            pub curve_oracle: AccountInfo<'info>,
+            pub live_candle: AccountInfo<'info>,
+            pub user_points: AccountInfo<'info>,
+            pub trade_guard: AccountInfo<'info>,
+            pub seller_token_account: AccountInfo<'info>,
+            pub trader_position: Option<AccountInfo<'info>>,
+            pub competition: Option<AccountInfo<'info>>,
+            pub competition_entry: Option<AccountInfo<'info>>,
+            pub curve_wsol_vault: Option<AccountInfo<'info>>,
+            pub seller_wsol_account: Option<AccountInfo<'info>>,
+            pub buyer_record: Option<AccountInfo<'info>>,
+            pub global_config: AccountInfo<'info>,
+            pub global_stats: AccountInfo<'info>,
+            pub seller: AccountInfo<'info>,
+            pub payer: AccountInfo<'info>,
+            pub system_program: AccountInfo<'info>,
+            pub token_program: AccountInfo<'info>,
+            pub event_authority: AccountInfo<'info>,
+            pub program: AccountInfo<'info>,
+        }
+
+        impl<'info> ToAccountMetas for SellTokens<'info> {
+            fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+                crate::accounts::SellTokens {
+                    bonding_curve: self.bonding_curve.key(),
+                    mint: self.mint.key(),
+                    bonding_curve_sol_vault: self.bonding_curve_sol_vault.key(),
+                    bonding_curve_token_account: self.bonding_curve_token_account.key(),
+                    curve_oracle: self.curve_oracle.key(),
+                    live_candle: self.live_candle.key(),
+                    user_points: self.user_points.key(),
+                    trade_guard: self.trade_guard.key(),
+                    seller_token_account: self.seller_token_account.key(),
+                    trader_position: self.trader_position.as_ref().map(|info| info.key()),
+                    competition: self.competition.as_ref().map(|info| info.key()),
+                    competition_entry: self.competition_entry.as_ref().map(|info| info.key()),
+                    curve_wsol_vault: self.curve_wsol_vault.as_ref().map(|info| info.key()),
+                    seller_wsol_account: self.seller_wsol_account.as_ref().map(|info| info.key()),
+                    buyer_record: self.buyer_record.as_ref().map(|info| info.key()),
+                    global_config: self.global_config.key(),
+                    global_stats: self.global_stats.key(),
+                    seller: self.seller.key(),
+                    payer: self.payer.key(),
+                    system_program: self.system_program.key(),
+                    token_program: self.token_program.key(),
+                    event_authority: self.event_authority.key(),
+                    program: self.program.key(),
+                }
+                .to_account_metas(is_signer)
+            }
+        }
+
+        impl<'info> ToAccountInfos<'info> for SellTokens<'info> {
+            fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+                let mut infos = vec![
+                    self.bonding_curve.clone(),
+                    self.mint.clone(),
+                    self.bonding_curve_sol_vault.clone(),
+                    self.bonding_curve_token_account.clone(),
+                    self.curve_oracle.clone(),
+                    self.live_candle.clone(),
+                    self.user_points.clone(),
+                    self.trade_guard.clone(),
+                    self.seller_token_account.clone(),
+                ];
+                infos.extend(self.trader_position.clone());
+                infos.extend(self.competition.clone());
+                infos.extend(self.competition_entry.clone());
+                infos.extend(self.curve_wsol_vault.clone());
+                infos.extend(self.seller_wsol_account.clone());
+                infos.extend(self.buyer_record.clone());
+                infos.extend([
+                    self.global_config.clone(),
+                    self.global_stats.clone(),
+                    self.seller.clone(),
+                    self.payer.clone(),
+                    self.system_program.clone(),
+                    self.token_program.clone(),
+                    self.event_authority.clone(),
+                    self.program.clone(),
+                ]);
+                infos
+            }
+        }
+    }
+
+    pub fn buy_tokens<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, accounts::BuyTokens<'info>>,
+        sol_amount: u64,
+        min_tokens_out: u64,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        let data = crate::instruction::BuyTokens { sol_amount, min_tokens_out, max_price_impact_bps };
+        invoke(ctx, anchor_lang::InstructionData::data(&data))
+    }
+
+    pub fn sell_tokens<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, accounts::SellTokens<'info>>,
+        token_amount: u64,
+        min_sol_out: u64,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        let data = crate::instruction::SellTokens { token_amount, min_sol_out, max_price_impact_bps };
+        invoke(ctx, anchor_lang::InstructionData::data(&data))
+    }
+
+    fn invoke<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+        ctx: CpiContext<'_, '_, '_, 'info, T>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let instruction = Instruction {
+            program_id: ctx.program.key(),
+            accounts: ctx.to_account_metas(None),
+            data,
+        };
+        invoke_signed(&instruction, &ctx.to_account_infos(), ctx.signer_seeds)
+            .map_err(Into::into)
+    }
+}
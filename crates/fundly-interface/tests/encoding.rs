@@ -0,0 +1,44 @@
+//! Keeps the interface in lockstep with the program's generated encoding.
+
+use anchor_lang::{Discriminator, InstructionData};
+
+#[test]
+fn discriminators_match_program() {
+    assert_eq!(
+        fundly_interface::instruction::BuyTokens::DISCRIMINATOR,
+        fundly::instruction::BuyTokens::DISCRIMINATOR
+    );
+    assert_eq!(
+        fundly_interface::instruction::SellTokens::DISCRIMINATOR,
+        fundly::instruction::SellTokens::DISCRIMINATOR
+    );
+}
+
+#[test]
+fn instruction_data_matches_program() {
+    assert_eq!(
+        fundly_interface::instruction::BuyTokens {
+            sol_amount: 7,
+            min_tokens_out: 11,
+            max_price_impact_bps: Some(250),
+        }
+        .data(),
+        fundly::instruction::BuyTokens { sol_amount: 7, min_tokens_out: 11, max_price_impact_bps: Some(250) }
+            .data()
+    );
+    assert_eq!(
+        fundly_interface::instruction::SellTokens {
+            token_amount: 13,
+            min_sol_out: 17,
+            max_price_impact_bps: None,
+        }
+        .data(),
+        fundly::instruction::SellTokens { token_amount: 13, min_sol_out: 17, max_price_impact_bps: None }
+            .data()
+    );
+}
+
+#[test]
+fn program_ids_match() {
+    assert_eq!(fundly_interface::ID, fundly::ID);
+}
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+
+use crate::math::{div_rounded, mul_div, Rounding, SafeCast, SafeMath};
+use crate::ErrorCode;
+
+// Raw units per whole token (all mints use 6 decimals)
+pub const TOKEN_DECIMALS_FACTOR: u128 = 1_000_000;
+
+/// Reserves the constant product is computed over (virtual + real)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reserves {
+    pub sol: u128,
+    pub token: u128,
+}
+
+impl Reserves {
+    pub fn new(
+        virtual_sol: u64,
+        real_sol: u64,
+        virtual_token: u64,
+        real_token: u64,
+    ) -> Result<Self> {
+        Ok(Self {
+            sol: (virtual_sol as u128).safe_add(real_sol as u128)?,
+            token: (virtual_token as u128).safe_add(real_token as u128)?,
+        })
+    }
+
+    /// Constant product `k = sol * token`
+    pub fn k(&self) -> Result<u128> {
+        self.sol.safe_mul(self.token)
+    }
+}
+
+/// Side of a trade against the curve
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trade {
+    /// Lamports in, tokens out
+    Buy { sol_in: u64 },
+    /// Tokens in, lamports out
+    Sell { tokens_in: u64 },
+}
+
+/// Tokens received for `sol_in` lamports (fees already deducted)
+/// The post-trade token reserve is rounded up, so tokens out round down and `k` never decreases
+pub fn tokens_out_for_sol(reserves: Reserves, sol_in: u64) -> Result<u64> {
+    let k = reserves.k()?;
+    let sol_after = reserves.sol.safe_add(sol_in as u128)?;
+    let token_after = div_rounded(k, sol_after, Rounding::Up)?;
+    reserves.token.safe_sub(token_after)?.to_u64()
+}
+
+/// Lamports received for `tokens_in` tokens (before fees)
+/// The post-trade SOL reserve is rounded up, so SOL out rounds down and `k` never decreases
+pub fn sol_out_for_tokens(reserves: Reserves, tokens_in: u64) -> Result<u64> {
+    let k = reserves.k()?;
+    let token_after = reserves.token.safe_add(tokens_in as u128)?;
+    let sol_after = div_rounded(k, token_after, Rounding::Up)?;
+    reserves.sol.safe_sub(sol_after)?.to_u64()
+}
+
+/// Reserves after applying `trade`
+pub fn reserves_after_trade(reserves: Reserves, trade: Trade) -> Result<Reserves> {
+    match trade {
+        Trade::Buy { sol_in } => {
+            let tokens_out = tokens_out_for_sol(reserves, sol_in)?;
+            Ok(Reserves {
+                sol: reserves.sol.safe_add(sol_in as u128)?,
+                token: reserves.token.safe_sub(tokens_out as u128)?,
+            })
+        }
+        Trade::Sell { tokens_in } => {
+            let sol_out = sol_out_for_tokens(reserves, tokens_in)?;
+            Ok(Reserves {
+                sol: reserves.sol.safe_sub(sol_out as u128)?,
+                token: reserves.token.safe_add(tokens_in as u128)?,
+            })
+        }
+    }
+}
+
+/// Spot price in lamports per whole token
+pub fn spot_price(reserves: Reserves) -> Result<u64> {
+    require!(reserves.token > 0, ErrorCode::InvalidAmount);
+    reserves
+        .sol
+        .safe_mul(TOKEN_DECIMALS_FACTOR)?
+        .safe_div(reserves.token)?
+        .to_u64()
+}
+
+/// Spot price in lamports per whole token once `trade` has executed
+pub fn price_after_trade(reserves: Reserves, trade: Trade) -> Result<u64> {
+    spot_price(reserves_after_trade(reserves, trade)?)
+}
+
+/// Spot price once the curve holds `total_sol` (virtual + real) for a constant product `k`
+pub fn price_at_sol(k: u128, total_sol: u128) -> Result<u64> {
+    spot_price(Reserves {
+        sol: total_sol,
+        token: k.safe_div(total_sol)?,
+    })
+}
+
+/// Minimum tokens out when buying with `sol_after_fee` lamports at `quoted_price`
+/// (lamports per whole token), tolerating `slippage_bps` of adverse movement
+pub fn min_tokens_for_quote(sol_after_fee: u64, quoted_price: u64, slippage_bps: u16) -> Result<u64> {
+    require!(quoted_price > 0, ErrorCode::InvalidAmount);
+    require!(slippage_bps <= 10_000, ErrorCode::InvalidSlippage);
+    (sol_after_fee as u128)
+        .safe_mul(TOKEN_DECIMALS_FACTOR)?
+        .safe_div(quoted_price as u128)?
+        .safe_mul(10_000 - slippage_bps as u128)?
+        .safe_div(10_000)?
+        .to_u64()
+}
+
+/// Minimum lamports out (after the `fee_bps` trading fee) when selling `tokens_in` at
+/// `quoted_price` (lamports per whole token), tolerating `slippage_bps` of adverse movement
+pub fn min_sol_for_quote(
+    tokens_in: u64,
+    quoted_price: u64,
+    fee_bps: u16,
+    slippage_bps: u16,
+) -> Result<u64> {
+    require!(slippage_bps <= 10_000, ErrorCode::InvalidSlippage);
+    (tokens_in as u128)
+        .safe_mul(quoted_price as u128)?
+        .safe_div(TOKEN_DECIMALS_FACTOR)?
+        .safe_mul(10_000 - fee_bps as u128)?
+        .safe_div(10_000)?
+        .safe_mul(10_000 - slippage_bps as u128)?
+        .safe_div(10_000)?
+        .to_u64()
+}
+
+/// Price movement from `before` to `after` in basis points of `before`, in either direction
+pub fn price_impact_bps(before: u64, after: u64) -> Result<u64> {
+    require!(before > 0, ErrorCode::InvalidAmount);
+    (before.abs_diff(after) as u128)
+        .safe_mul(10_000)?
+        .safe_div(before as u128)?
+        .to_u64()
+}
+
+/// Platform fee of `fee_bps` on `amount`, rounded up
+pub fn fee_for_amount(amount: u64, fee_bps: u16) -> Result<u64> {
+    mul_div(amount as u128, fee_bps as u128, 10_000, Rounding::Up)?.to_u64()
+}
+
+/// `fee_bps` reduced by `discount_bps` of itself, rounded up
+pub fn discounted_fee_bps(fee_bps: u16, discount_bps: u16) -> Result<u16> {
+    let kept_bps = 10_000u128.safe_sub(discount_bps as u128)?;
+    u16::try_from(mul_div(fee_bps as u128, kept_bps, 10_000, Rounding::Up)?)
+        .map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+/// Price in lamports per whole token a funding round converts at: `migration_price` less
+/// `discount_bps`, capped at `price_cap` (0 = uncapped), and raised where needed so the
+/// `raised` lamports never convert into more than `allocation` tokens
+pub fn round_conversion_price(
+    migration_price: u64,
+    discount_bps: u16,
+    price_cap: u64,
+    raised: u64,
+    allocation: u64,
+) -> Result<u64> {
+    let kept_bps = 10_000u128.safe_sub(discount_bps as u128)?;
+    let mut price = mul_div(migration_price as u128, kept_bps, 10_000, Rounding::Down)?;
+    if price_cap > 0 {
+        price = price.min(price_cap as u128);
+    }
+    // Lowest price at which the allocation covers every deposit
+    let floor = mul_div(raised as u128, TOKEN_DECIMALS_FACTOR, allocation as u128, Rounding::Up)?;
+    price.max(floor).max(1).to_u64()
+}
+
+/// Tokens `deposit` lamports convert into at `price` (lamports per whole token), rounded down
+pub fn tokens_for_deposit(deposit: u64, price: u64) -> Result<u64> {
+    mul_div(deposit as u128, TOKEN_DECIMALS_FACTOR, price as u128, Rounding::Down)?.to_u64()
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+
+use crate::{BondingCurve, ErrorCode, MigrationStatus};
+
+// Size of the last Borsh-serialized layout. Every earlier Borsh layout only appended
+// fields to it, so a shorter account reads back correctly once zero-filled to this size
+const BORSH_LAYOUT_SIZE: usize = 300;
+
+/// `BondingCurve` as it was stored before the account became zero-copy
+#[derive(AnchorDeserialize)]
+struct BorshBondingCurve {
+    mint: Pubkey,
+    creator: Pubkey,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    real_sol_reserves: u64,
+    real_token_reserves: u64,
+    complete: bool,
+    migrated: bool,
+    raydium_pool: Pubkey,
+    bump: u8,
+    created_at: i64,
+    graduated_at: i64,
+    unique_buyers: u64,
+    total_fees_collected: u64,
+    graduation_sol_reserves: u64,
+    graduation_token_reserves: u64,
+    accumulated_fees: u64,
+    trade_count: u64,
+    holder_count: u64,
+    trending_score: u64,
+    trending_updated_at: i64,
+    dev_buy_sol: u64,
+    dev_buy_tokens: u64,
+    flag_reason: u8,
+    flagged_at: i64,
+    buy_fee_bps_override: Option<u16>,
+    sell_fee_bps_override: Option<u16>,
+    launch_deposit: u64,
+    trading_start_time: i64,
+    migration_status: MigrationStatus,
+    migration_sol_withdrawn: u64,
+    migration_tokens_withdrawn: u64,
+    vault_rent_reserve: u64,
+    wsol_vault: bool,
+}
+
+impl BorshBondingCurve {
+    fn upgrade(self) -> BondingCurve {
+        BondingCurve {
+            mint: self.mint,
+            creator: self.creator,
+            raydium_pool: self.raydium_pool,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            created_at: self.created_at,
+            graduated_at: self.graduated_at,
+            unique_buyers: self.unique_buyers,
+            total_fees_collected: self.total_fees_collected,
+            graduation_sol_reserves: self.graduation_sol_reserves,
+            graduation_token_reserves: self.graduation_token_reserves,
+            accumulated_fees: self.accumulated_fees,
+            trade_count: self.trade_count,
+            holder_count: self.holder_count,
+            trending_score: self.trending_score,
+            trending_updated_at: self.trending_updated_at,
+            dev_buy_sol: self.dev_buy_sol,
+            dev_buy_tokens: self.dev_buy_tokens,
+            flagged_at: self.flagged_at,
+            launch_deposit: self.launch_deposit,
+            trading_start_time: self.trading_start_time,
+            migration_sol_withdrawn: self.migration_sol_withdrawn,
+            migration_tokens_withdrawn: self.migration_tokens_withdrawn,
+            vault_rent_reserve: self.vault_rent_reserve,
+            buy_fee_bps_override: self
+                .buy_fee_bps_override
+                .unwrap_or(BondingCurve::NO_FEE_OVERRIDE),
+            sell_fee_bps_override: self
+                .sell_fee_bps_override
+                .unwrap_or(BondingCurve::NO_FEE_OVERRIDE),
+            complete: self.complete as u8,
+            migrated: self.migrated as u8,
+            bump: self.bump,
+            flag_reason: self.flag_reason,
+            migration_status: self.migration_status as u8,
+            wsol_vault: self.wsol_vault as u8,
+            ..BondingCurve::zeroed()
+        }
+    }
+}
+
+/// Decode a `BondingCurve` account written with an earlier layout into the current one
+///
+/// Only the Borsh layout predates the zero-copy account, whose size never changes, so any
+/// account no longer than it is read as Borsh. Those curves also predate `finalize_launch`
+/// and are marked finalized, since their supply was never gated on it
+pub fn upgrade(data: &[u8]) -> Result<BondingCurve> {
+    let len = data.len();
+    let curve = if len <= BORSH_LAYOUT_SIZE {
+        let mut padded = data.to_vec();
+        padded.resize(BORSH_LAYOUT_SIZE, 0);
+        let legacy = BorshBondingCurve::deserialize(&mut &padded[8..])
+            .map_err(|_| error!(ErrorCode::UnknownCurveLayout))?;
+        BondingCurve {
+            launch_finalized: 1,
+            ..legacy.upgrade()
+        }
+    } else {
+        return err!(ErrorCode::UnknownCurveLayout);
+    };
+    Ok(curve)
+}
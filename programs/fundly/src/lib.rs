@@ -1,13 +1,42 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token::{Mint, Token, TokenAccount, MintTo, Transfer, Burn, mint_to, transfer, burn};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{Mint, Token, TokenAccount, MintTo, Transfer, Burn, CloseAccount, FreezeAccount, SyncNative, mint_to, transfer, burn, close_account, freeze_account, sync_native};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::associated_token::{create_idempotent, get_associated_token_address, AssociatedToken, Create};
 use anchor_spl::metadata::{
-    create_metadata_accounts_v3,
-    mpl_token_metadata::types::DataV2,
-    CreateMetadataAccountsV3, Metadata,
+    create_master_edition_v3, create_metadata_accounts_v3,
+    mpl_token_metadata::{self, types::DataV2},
+    CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
 };
 
+pub mod curve;
+pub mod legacy_curve;
+pub mod math;
+pub mod oracle;
+pub mod randomness;
+pub mod snapshot;
+pub mod vault;
+
+use curve::{Reserves, Trade, TOKEN_DECIMALS_FACTOR};
+use math::{mul_div, Rounding, SafeCast, SafeMath};
+use randomness::{RandomnessConsumer, SwitchboardRandomness};
+use vault::{drain_lamports, move_lamports};
+
+// Log the remaining compute units at `label`; compiled out unless built with `cu-log`
+macro_rules! cu_checkpoint {
+    ($label:literal) => {
+        #[cfg(feature = "cu-log")]
+        {
+            msg!($label);
+            anchor_lang::solana_program::log::sol_log_compute_units();
+        }
+    };
+}
+
 declare_id!("5dtdAtkPad7cnAtBq8QLy6mfVbtb81pTrg5gCYxfUCgK");
 
 #[program]
@@ -19,69 +48,192 @@ pub mod fundly {
         name: String,
         symbol: String,
         total_supply: u64,
-        category: String,
+        category: u8,
     ) -> Result<()> {
+        validate_text(&name, ProjectState::MAX_NAME, is_printable_ascii, ErrorCode::InvalidName)?;
+        validate_text(&symbol, ProjectState::MAX_SYMBOL, is_symbol_char, ErrorCode::InvalidSymbol)?;
+        require!(
+            category < ctx.accounts.category_registry.count,
+            ErrorCode::InvalidCategory
+        );
+
         let state = &mut ctx.accounts.project_state;
         state.owner = ctx.accounts.owner.key();
         state.mint = Pubkey::default();
-        state.name = name;
-        state.symbol = symbol;
+        (state.name, state.name_len) = fixed_bytes(&name);
+        (state.symbol, state.symbol_len) = fixed_bytes(&symbol);
         state.created_at = Clock::get()?.unix_timestamp;
         state.total_supply = total_supply;
         state.category = category;
+        state.boosted_until = 0;
+        state.compliance_flags = 0;
+        state.mint_source = MintSource::Unminted;
+
+        emit!(ProjectCreated {
+            project: state.key(),
+            owner: state.owner,
+            name,
+            symbol,
+            total_supply,
+            category,
+            timestamp: state.created_at,
+        });
+
         Ok(())
     }
 
+    /// Create the project's mint from a client-generated keypair, which signs the transaction
+    /// Keypairs ground for a vanity address can pass `vanity_prefix` to have the program
+    /// check the mint's base58 address starts with it
     pub fn create_mint(
-        ctx: Context<CreateMint>,
+        mut ctx: Context<CreateMint>,
         name: String,
         symbol: String,
         uri: String,
+        vanity_prefix: Option<String>,
     ) -> Result<()> {
-        // Record the mint on the state account
-        let state = &mut ctx.accounts.project_state;
-        state.mint = ctx.accounts.mint.key();
+        if let Some(prefix) = vanity_prefix {
+            require!(
+                !prefix.is_empty()
+                    && prefix.len() <= ProjectState::MAX_VANITY_PREFIX
+                    && ctx.accounts.mint.key().to_string().starts_with(&prefix),
+                ErrorCode::VanityPrefixMismatch
+            );
+        }
 
-        // Create metadata account
-        let data_v2 = DataV2 {
+        let accounts = &mut ctx.accounts;
+        mint_project_supply(
+            ProjectMint {
+                project_state: &mut accounts.project_state,
+                global_config: &accounts.global_config,
+                mint: &accounts.mint,
+                metadata: &accounts.metadata,
+                owner_token_account: &accounts.owner_token_account,
+                owner: &accounts.owner,
+                system_program: &accounts.system_program,
+                token_program: &accounts.token_program,
+                token_metadata_program: &accounts.token_metadata_program,
+                rent: &accounts.rent,
+            },
             name,
             symbol,
             uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        };
+            MintSource::Keypair,
+        )
+    }
 
-        let cpi_context = CpiContext::new(
-            ctx.accounts.token_metadata_program.to_account_info(),
-            CreateMetadataAccountsV3 {
-                metadata: ctx.accounts.metadata.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                mint_authority: ctx.accounts.owner.to_account_info(),
-                payer: ctx.accounts.owner.to_account_info(),
-                update_authority: ctx.accounts.owner.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
+    /// Create the project's mint at the address derived from the project
+    /// (["project_mint", project_state]), so integrators can compute it before it exists
+    pub fn create_project_mint(
+        mut ctx: Context<CreateProjectMint>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let accounts = &mut ctx.accounts;
+        mint_project_supply(
+            ProjectMint {
+                project_state: &mut accounts.project_state,
+                global_config: &accounts.global_config,
+                mint: &accounts.mint,
+                metadata: &accounts.metadata,
+                owner_token_account: &accounts.owner_token_account,
+                owner: &accounts.owner,
+                system_program: &accounts.system_program,
+                token_program: &accounts.token_program,
+                token_metadata_program: &accounts.token_metadata_program,
+                rent: &accounts.rent,
             },
+            name,
+            symbol,
+            uri,
+            MintSource::ProjectPda,
+        )
+    }
+
+    /// Pay the treasury to promote a project for `duration` seconds
+    /// Boosting an already-boosted project extends the current boost
+    pub fn boost_project(
+        ctx: Context<BoostProject>,
+        duration: i64,
+    ) -> Result<()> {
+        let price_per_hour = ctx.accounts.global_config.boost_lamports_per_hour;
+        require!(price_per_hour > 0, ErrorCode::BoostingDisabled);
+        require!(
+            duration > 0 && duration <= ProjectState::MAX_BOOST_DURATION,
+            ErrorCode::InvalidBoostDuration
         );
 
-        create_metadata_accounts_v3(cpi_context, data_v2, true, true, None)?;
+        // Charge per second, rounded up so short boosts are never free
+        let numerator = (price_per_hour as u128).safe_mul(duration as u128)?;
+        let mut cost = numerator.safe_div(3_600)?;
+        if numerator % 3_600 != 0 {
+            cost = cost.safe_add(1)?;
+        }
+        let cost = cost.to_u64()?;
 
-        // Mint the initial supply (999,999,999.999999 tokens with 6 decimals)
-        // The total_supply is already in raw token units (includes decimals)
-        let amount_with_decimals = state.total_supply;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
 
-        let mint_cpi_context = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.owner_token_account.to_account_info(),
-                authority: ctx.accounts.owner.to_account_info(),
-            },
-        );
+        let current_time = Clock::get()?.unix_timestamp;
+        let state = &mut ctx.accounts.project_state;
+        let boost_start = state.boosted_until.max(current_time);
+        state.boosted_until = boost_start.safe_add(duration)?;
+
+        emit!(ProjectBoosted {
+            project: state.key(),
+            owner: state.owner,
+            mint: state.mint,
+            duration,
+            cost,
+            boosted_until: state.boosted_until,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Anchor an investor update on-chain as the project's next sequence-numbered
+    /// `ProjectUpdate`: `content_hash` commits to the content published at `uri`
+    pub fn post_update(
+        ctx: Context<PostUpdate>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        require!(content_hash != [0; 32], ErrorCode::InvalidContentHash);
+        validate_text(&uri, ProjectUpdate::MAX_URI, is_uri_char, ErrorCode::InvalidUri)?;
+
+        let feed = &mut ctx.accounts.update_feed;
+        let sequence = feed.update_count;
+        feed.project = ctx.accounts.project_state.key();
+        feed.update_count = sequence.safe_add(1)?;
+        feed.bump = ctx.bumps.update_feed;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let update = &mut ctx.accounts.project_update;
+        update.project = feed.project;
+        update.sequence = sequence;
+        update.content_hash = content_hash;
+        (update.uri, update.uri_len) = fixed_bytes(&uri);
+        update.posted_at = current_time;
+        update.bump = ctx.bumps.project_update;
 
-        mint_to(mint_cpi_context, amount_with_decimals)?;
+        emit!(ProjectUpdatePosted {
+            project: update.project,
+            owner: ctx.accounts.owner.key(),
+            sequence,
+            content_hash,
+            uri,
+            timestamp: current_time,
+        });
 
         Ok(())
     }
@@ -98,7 +250,10 @@ pub mod fundly {
     ) -> Result<()> {
         require!(total_amount > 0, ErrorCode::InvalidAmount);
         require!(vesting_duration > 0, ErrorCode::InvalidVestingDuration);
-        require!(cliff_duration < vesting_duration, ErrorCode::InvalidCliffDuration);
+        require!(
+            cliff_duration >= 0 && cliff_duration < vesting_duration,
+            ErrorCode::InvalidCliffDuration
+        );
 
         let vesting_schedule = &mut ctx.accounts.vesting_schedule;
         vesting_schedule.beneficiary = ctx.accounts.creator.key();
@@ -106,18 +261,40 @@ pub mod fundly {
         vesting_schedule.total_amount = total_amount;
         vesting_schedule.claimed_amount = 0;
         vesting_schedule.start_time = start_time;
-        vesting_schedule.cliff_time = start_time.checked_add(cliff_duration).unwrap();
-        vesting_schedule.end_time = start_time.checked_add(vesting_duration).unwrap();
+        vesting_schedule.cliff_time = start_time.safe_add(cliff_duration)?;
+        vesting_schedule.end_time = start_time.safe_add(vesting_duration)?;
         vesting_schedule.release_interval = release_interval;
         vesting_schedule.last_claim_time = start_time;
         vesting_schedule.bump = ctx.bumps.vesting_schedule;
+        vesting_schedule.claim_delegate = Pubkey::default();
+
+        Ok(())
+    }
+
+    /// Let `delegate` (e.g. an automation bot) trigger claims on the beneficiary's behalf,
+    /// or revoke it with `None`. Claimed tokens still only go to the beneficiary's accounts
+    pub fn set_claim_delegate(
+        ctx: Context<SetClaimDelegate>,
+        delegate: Option<Pubkey>,
+    ) -> Result<()> {
+        let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+        vesting_schedule.claim_delegate = delegate.unwrap_or_default();
+
+        emit!(ClaimDelegateUpdated {
+            vesting_schedule: vesting_schedule.key(),
+            beneficiary: vesting_schedule.beneficiary,
+            mint: vesting_schedule.mint,
+            delegate,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    /// Claim vested tokens that have unlocked
+    /// Claim vested tokens that have unlocked. Signed by the beneficiary or their claim delegate
     pub fn claim_vested_tokens(
         ctx: Context<ClaimVestedTokens>,
+        amount: Option<u64>,
     ) -> Result<()> {
         let vesting_schedule = &ctx.accounts.vesting_schedule;
         let current_time = Clock::get()?.unix_timestamp;
@@ -126,14 +303,19 @@ pub mod fundly {
         require!(current_time >= vesting_schedule.cliff_time, ErrorCode::CliffNotReached);
 
         // Calculate how many tokens are unlocked
-        let unlocked_amount = calculate_unlocked_amount(vesting_schedule, current_time)?;
+        let unlocked_amount = vesting_schedule.unlocked_amount(current_time)?;
         let claimable_amount = unlocked_amount
             .checked_sub(vesting_schedule.claimed_amount)
             .ok_or(ErrorCode::NoTokensToCllaim)?;
 
         require!(claimable_amount > 0, ErrorCode::NoTokensToCllaim);
 
-        // Transfer tokens from vesting vault to creator
+        // Claim everything unlocked unless a partial amount is requested
+        let claim_amount = amount.unwrap_or(claimable_amount);
+        require!(claim_amount > 0, ErrorCode::InvalidAmount);
+        require!(claim_amount <= claimable_amount, ErrorCode::ClaimExceedsUnlocked);
+
+        // Transfer tokens from vesting vault to the beneficiary's chosen account
         let mint_key = vesting_schedule.mint;
         let beneficiary_key = vesting_schedule.beneficiary;
         let bump = vesting_schedule.bump;
@@ -148,24 +330,24 @@ pub mod fundly {
 
         let cpi_accounts = Transfer {
             from: ctx.accounts.vesting_vault.to_account_info(),
-            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
             authority: ctx.accounts.vesting_schedule.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        transfer(cpi_ctx, claimable_amount)?;
+        transfer(cpi_ctx, claim_amount)?;
 
         // Update claimed amount and last claim time
         let vesting_schedule = &mut ctx.accounts.vesting_schedule;
         vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
-            .checked_add(claimable_amount)
-            .unwrap();
+            .safe_add(claim_amount)?;
         vesting_schedule.last_claim_time = current_time;
 
-        emit!(VestingClaimEvent {
+        emit_cpi!(VestingClaimEvent {
             beneficiary: vesting_schedule.beneficiary,
             mint: vesting_schedule.mint,
-            amount_claimed: claimable_amount,
+            claimer: ctx.accounts.claimer.key(),
+            amount_claimed: claim_amount,
             total_claimed: vesting_schedule.claimed_amount,
             timestamp: current_time,
         });
@@ -185,7 +367,7 @@ pub mod fundly {
             return Ok(());
         }
 
-        let unlocked_amount = calculate_unlocked_amount(vesting_schedule, current_time)?;
+        let unlocked_amount = vesting_schedule.unlocked_amount(current_time)?;
         let claimable_amount = unlocked_amount.saturating_sub(vesting_schedule.claimed_amount);
 
         msg!("Unlocked: {}, Claimed: {}, Claimable: {}", 
@@ -197,1514 +379,17680 @@ pub mod fundly {
         Ok(())
     }
 
-    /// Initialize the global configuration for bonding curve parameters
-    pub fn initialize_global_config(
-        ctx: Context<InitializeGlobalConfig>,
-        treasury: Pubkey,
-        virtual_sol_reserves: u64,
-        virtual_token_reserves: u64,
-        initial_token_supply: u64,
-        fee_basis_points: u16,
-        migration_threshold_sol: u64,
-        raydium_amm_program: Pubkey,
+    /// Lock `total_amount` lamports for `beneficiary`, released on the same cliff-then-linear
+    /// schedule as token vesting. The lamports are held by the schedule PDA itself
+    pub fn initialize_sol_vesting(
+        ctx: Context<InitializeSolVesting>,
+        total_amount: u64,
+        start_time: i64,
+        cliff_duration: i64,
+        vesting_duration: i64,
+        release_interval: i64,
     ) -> Result<()> {
-        let global_config = &mut ctx.accounts.global_config;
-        global_config.authority = ctx.accounts.authority.key();
-        global_config.treasury = treasury;
-        global_config.virtual_sol_reserves = virtual_sol_reserves;
-        global_config.virtual_token_reserves = virtual_token_reserves;
-        global_config.initial_token_supply = initial_token_supply;
-        global_config.fee_basis_points = fee_basis_points;
-        global_config.migration_threshold_sol = migration_threshold_sol;
-        global_config.raydium_amm_program = raydium_amm_program;
-        Ok(())
-    }
+        require!(total_amount > 0, ErrorCode::InvalidAmount);
+        require!(vesting_duration > 0, ErrorCode::InvalidVestingDuration);
+        require!(
+            cliff_duration >= 0 && cliff_duration < vesting_duration,
+            ErrorCode::InvalidCliffDuration
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.sol_vesting_schedule.to_account_info(),
+                },
+            ),
+            total_amount,
+        )?;
+
+        let schedule = &mut ctx.accounts.sol_vesting_schedule;
+        schedule.funder = ctx.accounts.funder.key();
+        schedule.beneficiary = ctx.accounts.beneficiary.key();
+        schedule.total_amount = total_amount;
+        schedule.claimed_amount = 0;
+        schedule.start_time = start_time;
+        schedule.cliff_time = start_time.safe_add(cliff_duration)?;
+        schedule.end_time = start_time.safe_add(vesting_duration)?;
+        schedule.release_interval = release_interval;
+        schedule.last_claim_time = start_time;
+        schedule.bump = ctx.bumps.sol_vesting_schedule;
+
+        emit!(SolVestingCreated {
+            sol_vesting_schedule: schedule.key(),
+            funder: schedule.funder,
+            beneficiary: schedule.beneficiary,
+            total_amount,
+            cliff_time: schedule.cliff_time,
+            end_time: schedule.end_time,
+        });
 
-    /// Update the global configuration (admin only)
-    pub fn update_global_config(
-        ctx: Context<UpdateGlobalConfig>,
-        treasury: Option<Pubkey>,
-        virtual_sol_reserves: Option<u64>,
-        virtual_token_reserves: Option<u64>,
-        initial_token_supply: Option<u64>,
-        fee_basis_points: Option<u16>,
-        migration_threshold_sol: Option<u64>,
-        raydium_amm_program: Option<Pubkey>,
-    ) -> Result<()> {
-        let global_config = &mut ctx.accounts.global_config;
-        
-        // Only update fields that are provided
-        if let Some(val) = treasury {
-            global_config.treasury = val;
-        }
-        if let Some(val) = virtual_sol_reserves {
-            global_config.virtual_sol_reserves = val;
-        }
-        if let Some(val) = virtual_token_reserves {
-            global_config.virtual_token_reserves = val;
-        }
-        if let Some(val) = initial_token_supply {
-            global_config.initial_token_supply = val;
-        }
-        if let Some(val) = fee_basis_points {
-            global_config.fee_basis_points = val;
-        }
-        if let Some(val) = migration_threshold_sol {
-            global_config.migration_threshold_sol = val;
-        }
-        if let Some(val) = raydium_amm_program {
-            global_config.raydium_amm_program = val;
-        }
-        
         Ok(())
     }
 
-    /// Close the global configuration and recover rent (admin only)
-    /// This is a workaround for accounts with incompatible structure
-    pub fn close_global_config(
-        ctx: Context<CloseGlobalConfig>,
+    /// Claim unlocked lamports from a SOL vesting schedule into the beneficiary's wallet.
+    /// `amount` claims part of them (default: everything claimable)
+    pub fn claim_vested_sol(
+        ctx: Context<ClaimVestedSol>,
+        amount: Option<u64>,
     ) -> Result<()> {
-        // Transfer all lamports from global_config to authority
-        let dest_starting_lamports = ctx.accounts.authority.lamports();
-        **ctx.accounts.authority.lamports.borrow_mut() = dest_starting_lamports
-            .checked_add(ctx.accounts.global_config.lamports())
-            .unwrap();
-        **ctx.accounts.global_config.lamports.borrow_mut() = 0;
+        let current_time = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.sol_vesting_schedule;
+        require!(current_time >= schedule.cliff_time, ErrorCode::CliffNotReached);
+
+        let claimable_amount = schedule
+            .unlocked_amount(current_time)?
+            .saturating_sub(schedule.claimed_amount);
+        require!(claimable_amount > 0, ErrorCode::NoTokensToCllaim);
+
+        let claim_amount = amount.unwrap_or(claimable_amount);
+        require!(claim_amount > 0, ErrorCode::InvalidAmount);
+        require!(claim_amount <= claimable_amount, ErrorCode::ClaimExceedsUnlocked);
+
+        move_lamports(
+            &schedule.to_account_info(),
+            &ctx.accounts.beneficiary.to_account_info(),
+            claim_amount,
+        )?;
+        schedule.claimed_amount = schedule.claimed_amount.safe_add(claim_amount)?;
+        schedule.last_claim_time = current_time;
+
+        emit!(SolVestingClaimEvent {
+            sol_vesting_schedule: schedule.key(),
+            beneficiary: schedule.beneficiary,
+            amount_claimed: claim_amount,
+            total_claimed: schedule.claimed_amount,
+            timestamp: current_time,
+        });
 
         Ok(())
     }
 
-    /// Initialize a bonding curve for a token
-    pub fn initialize_bonding_curve(
-        ctx: Context<InitializeBondingCurve>,
-        token_supply: u64,
+    /// Lock `amount` of any SPL token until `unlock_time`. `index` lets one owner hold several
+    /// locks of the same mint (e.g. LP, team and partner allocations)
+    pub fn lock_tokens(
+        ctx: Context<LockTokens>,
+        index: u64,
+        amount: u64,
+        unlock_time: i64,
     ) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        let global_config = &ctx.accounts.global_config;
-        
-        bonding_curve.mint = ctx.accounts.mint.key();
-        bonding_curve.creator = ctx.accounts.creator.key();
-        bonding_curve.virtual_sol_reserves = global_config.virtual_sol_reserves;
-        bonding_curve.virtual_token_reserves = global_config.virtual_token_reserves;
-        bonding_curve.real_sol_reserves = 0;
-        bonding_curve.real_token_reserves = token_supply;
-        bonding_curve.complete = false;
-        bonding_curve.migrated = false;
-        bonding_curve.raydium_pool = Pubkey::default();
-        bonding_curve.bump = ctx.bumps.bonding_curve;
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(unlock_time > current_time, ErrorCode::InvalidLockDuration);
 
-        // Move the full token supply from the creator's account into the bonding curve ATA
-        // This replicates pump.fun behavior where all tokens are sold from the curve
         let cpi_accounts = Transfer {
-            from: ctx
-                .accounts
-                .creator_token_account
-                .to_account_info(),
-            to: ctx
-                .accounts
-                .bonding_curve_token_account
-                .to_account_info(),
-            authority: ctx.accounts.creator.to_account_info(),
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            to: ctx.accounts.lock_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        transfer(cpi_ctx, token_supply)?;
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, amount)?;
+
+        let token_lock = &mut ctx.accounts.token_lock;
+        token_lock.owner = ctx.accounts.owner.key();
+        token_lock.mint = ctx.accounts.mint.key();
+        token_lock.index = index;
+        token_lock.amount = amount;
+        token_lock.locked_at = current_time;
+        token_lock.unlock_time = unlock_time;
+        token_lock.bump = ctx.bumps.token_lock;
+
+        emit!(TokensLocked {
+            token_lock: token_lock.key(),
+            owner: token_lock.owner,
+            mint: token_lock.mint,
+            index,
+            amount,
+            unlock_time,
+            timestamp: current_time,
+        });
 
         Ok(())
     }
 
-    /// Buy tokens from the bonding curve
-    pub fn buy_tokens(
-        ctx: Context<BuyTokens>,
-        sol_amount: u64,
-        min_tokens_out: u64,
+    /// Push a lock's unlock time further out. Locks can never be shortened
+    pub fn extend_lock(
+        ctx: Context<ExtendLock>,
+        new_unlock_time: i64,
     ) -> Result<()> {
-        require!(!ctx.accounts.bonding_curve.complete, ErrorCode::BondingCurveComplete);
-        require!(!ctx.accounts.bonding_curve.migrated, ErrorCode::AlreadyMigrated);
-        require!(sol_amount > 0, ErrorCode::InvalidAmount);
-
-        // Calculate fee
-        let fee = (sol_amount as u128)
-            .checked_mul(ctx.accounts.global_config.fee_basis_points as u128)
-            .unwrap()
-            .checked_div(10_000)
-            .unwrap() as u64;
-        let sol_after_fee = sol_amount.checked_sub(fee).unwrap();
-
-        // Calculate tokens out using constant product formula
-        let virtual_sol = ctx.accounts.bonding_curve.virtual_sol_reserves;
-        let virtual_token = ctx.accounts.bonding_curve.virtual_token_reserves;
-        let real_sol = ctx.accounts.bonding_curve.real_sol_reserves;
-        let real_token = ctx.accounts.bonding_curve.real_token_reserves;
-
-        let total_sol_before = (virtual_sol as u128).checked_add(real_sol as u128).unwrap();
-        let total_token_before = (virtual_token as u128).checked_add(real_token as u128).unwrap();
-        let k = total_sol_before.checked_mul(total_token_before).unwrap();
-
-        // New SOL amount after adding user's SOL
-        let total_sol_after = total_sol_before.checked_add(sol_after_fee as u128).unwrap();
-        
-        // Calculate new token reserves to maintain k
-        let total_token_after = k.checked_div(total_sol_after).unwrap();
-        let tokens_out = total_token_before.checked_sub(total_token_after).unwrap() as u64;
-
-        require!(tokens_out >= min_tokens_out, ErrorCode::SlippageExceeded);
-        require!(tokens_out <= real_token, ErrorCode::InsufficientTokens);
-
-        // Transfer SOL (after fee) from buyer to bonding curve vault
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.bonding_curve_sol_vault.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(cpi_context, sol_after_fee)?;
+        let token_lock = &mut ctx.accounts.token_lock;
+        require!(new_unlock_time > token_lock.unlock_time, ErrorCode::InvalidLockDuration);
 
-        // Transfer fee directly to treasury
-        let fee_cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.treasury.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(fee_cpi_context, fee)?;
+        let previous_unlock_time = token_lock.unlock_time;
+        token_lock.unlock_time = new_unlock_time;
 
-        // Transfer tokens from bonding curve to buyer
-        let mint_key = ctx.accounts.bonding_curve.mint;
-        let bump = ctx.accounts.bonding_curve.bump;
-        
-        let seeds = &[
-            b"bonding_curve",
+        emit!(LockExtended {
+            token_lock: token_lock.key(),
+            owner: token_lock.owner,
+            mint: token_lock.mint,
+            index: token_lock.index,
+            previous_unlock_time,
+            unlock_time: new_unlock_time,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw everything from an expired lock and close it, returning rent to the owner
+    pub fn withdraw_after_unlock(
+        ctx: Context<WithdrawAfterUnlock>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let token_lock = &ctx.accounts.token_lock;
+        require!(current_time >= token_lock.unlock_time, ErrorCode::TokensStillLocked);
+
+        let mint_key = token_lock.mint;
+        let owner_key = token_lock.owner;
+        let index_bytes = token_lock.index.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            b"token_lock",
             mint_key.as_ref(),
-            &[bump],
+            owner_key.as_ref(),
+            &index_bytes,
+            &[token_lock.bump],
         ];
-        let signer = &[&seeds[..]];
+        let signer = &[seeds];
 
+        // Includes anything sent to the vault directly after locking
+        let amount = ctx.accounts.lock_vault.amount;
         let cpi_accounts = Transfer {
-            from: ctx.accounts.bonding_curve_token_account.to_account_info(),
-            to: ctx.accounts.buyer_token_account.to_account_info(),
-            authority: ctx.accounts.bonding_curve.to_account_info(),
+            from: ctx.accounts.lock_vault.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.token_lock.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        transfer(cpi_ctx, tokens_out)?;
-
-        // Update reserves
-        ctx.accounts.bonding_curve.real_sol_reserves = ctx.accounts.bonding_curve.real_sol_reserves.checked_add(sol_after_fee).unwrap();
-        ctx.accounts.bonding_curve.real_token_reserves = ctx.accounts.bonding_curve.real_token_reserves.checked_sub(tokens_out).unwrap();
-
-        // Check if bonding curve is complete (all tokens sold)
-        if ctx.accounts.bonding_curve.real_token_reserves == 0 {
-            ctx.accounts.bonding_curve.complete = true;
-        }
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        transfer(cpi_ctx, amount)?;
 
-        // Check if migration threshold has been reached
-        let migration_threshold = ctx.accounts.global_config.migration_threshold_sol;
-        if !ctx.accounts.bonding_curve.migrated 
-            && ctx.accounts.bonding_curve.real_sol_reserves >= migration_threshold {
-            // Emit event that threshold is reached - migration should be triggered
-            emit!(MigrationThresholdReached {
-                mint: ctx.accounts.bonding_curve.mint,
-                sol_reserves: ctx.accounts.bonding_curve.real_sol_reserves,
-                token_reserves: ctx.accounts.bonding_curve.real_token_reserves,
-                timestamp: Clock::get()?.unix_timestamp,
-            });
-        }
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.lock_vault.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.token_lock.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        close_account(cpi_ctx)?;
 
-        emit!(BuyEvent {
-            buyer: ctx.accounts.buyer.key(),
-            mint: ctx.accounts.bonding_curve.mint,
-            sol_amount,
-            tokens_out,
-            fee,
+        emit!(LockWithdrawn {
+            token_lock: ctx.accounts.token_lock.key(),
+            owner: owner_key,
+            mint: mint_key,
+            index: ctx.accounts.token_lock.index,
+            amount,
+            timestamp: current_time,
         });
 
         Ok(())
     }
 
-    /// Migrate bonding curve liquidity to Raydium when threshold is reached
-    /// This creates a Raydium pool and adds liquidity with all SOL and remaining tokens
-    /// 
-    /// Migration Fee Economics:
-    /// - Collects 6 SOL migration fee to treasury
-    /// - Backend uses treasury funds to pay Raydium pool creation (~0.5 SOL)
-    /// - Net platform revenue: ~5.5 SOL per migration
-    /// - Remaining SOL (threshold - 6) goes into liquidity pool
-    pub fn migrate_to_raydium(
-        ctx: Context<MigrateToRaydium>,
+    /// Open an OTC deal selling `token_amount` of `mint` to `buyer` for `sol_amount` lamports.
+    /// Both legs are escrowed by the deal PDA until `settle_otc_deal` swaps them
+    pub fn create_otc_deal(
+        ctx: Context<CreateOtcDeal>,
+        id: u64,
+        buyer: Pubkey,
+        token_amount: u64,
+        sol_amount: u64,
     ) -> Result<()> {
-        let bonding_curve = &ctx.accounts.bonding_curve;
-        let global_config = &ctx.accounts.global_config;
+        require!(token_amount > 0 && sol_amount > 0, ErrorCode::InvalidAmount);
+        require!(buyer != ctx.accounts.seller.key(), ErrorCode::InvalidOtcCounterparty);
 
-        // Verify migration conditions
-        require!(!bonding_curve.migrated, ErrorCode::AlreadyMigrated);
-        require!(
-            bonding_curve.real_sol_reserves >= global_config.migration_threshold_sol,
-            ErrorCode::ThresholdNotReached
-        );
+        let current_time = Clock::get()?.unix_timestamp;
+        let otc_deal = &mut ctx.accounts.otc_deal;
+        otc_deal.id = id;
+        otc_deal.seller = ctx.accounts.seller.key();
+        otc_deal.buyer = buyer;
+        otc_deal.mint = ctx.accounts.mint.key();
+        otc_deal.token_amount = token_amount;
+        otc_deal.sol_amount = sol_amount;
+        otc_deal.tokens_deposited = false;
+        otc_deal.sol_deposited = false;
+        otc_deal.created_at = current_time;
+        otc_deal.bump = ctx.bumps.otc_deal;
 
-        let total_sol = bonding_curve.real_sol_reserves;
-        let tokens_to_migrate = bonding_curve.real_token_reserves;
+        emit!(OtcDealCreated {
+            otc_deal: otc_deal.key(),
+            seller: otc_deal.seller,
+            buyer,
+            mint: otc_deal.mint,
+            token_amount,
+            sol_amount,
+            timestamp: current_time,
+        });
 
-        require!(total_sol > 0, ErrorCode::InsufficientSOL);
-        require!(tokens_to_migrate > 0, ErrorCode::InsufficientTokens);
+        Ok(())
+    }
 
-        // Migration fee: 6 SOL goes to treasury
-        let migration_fee = 6_000_000_000u64; // 6 SOL in lamports
-        require!(total_sol > migration_fee, ErrorCode::InsufficientSOLForMigration);
-        
-        let sol_to_migrate = total_sol.checked_sub(migration_fee).unwrap();
+    /// Seller funds the token leg of an OTC deal
+    pub fn deposit_otc_tokens(
+        ctx: Context<DepositOtcTokens>,
+    ) -> Result<()> {
+        let otc_deal = &ctx.accounts.otc_deal;
+        require!(!otc_deal.tokens_deposited, ErrorCode::OtcLegAlreadyFunded);
 
-        msg!("Starting migration with {} total SOL", total_sol);
-        msg!("Migration fee: {} SOL (6 SOL)", migration_fee);
-        msg!("SOL to pool: {} lamports", sol_to_migrate);
-        msg!("Tokens to pool: {} tokens", tokens_to_migrate);
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.seller_token_account.to_account_info(),
+            to: ctx.accounts.escrow_vault.to_account_info(),
+            authority: ctx.accounts.seller.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, otc_deal.token_amount)?;
 
-        // Verify vault has enough balance
-        let sol_vault_balance = ctx.accounts.bonding_curve_sol_vault.lamports();
-        require!(sol_vault_balance >= total_sol, ErrorCode::InsufficientSOL);
+        let otc_deal = &mut ctx.accounts.otc_deal;
+        otc_deal.tokens_deposited = true;
 
-        // Transfer migration fee to treasury
-        **ctx.accounts.bonding_curve_sol_vault.try_borrow_mut_lamports()? -= migration_fee;
-        **ctx.accounts.treasury.try_borrow_mut_lamports()? += migration_fee;
-        msg!("Transferred {} SOL migration fee to treasury", migration_fee / 1_000_000_000);
+        emit!(OtcDealFunded {
+            otc_deal: otc_deal.key(),
+            depositor: otc_deal.seller,
+            token_amount: otc_deal.token_amount,
+            sol_amount: 0,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Transfer remaining SOL to migration vault (for liquidity pool)
-        **ctx.accounts.bonding_curve_sol_vault.try_borrow_mut_lamports()? -= sol_to_migrate;
-        **ctx.accounts.migration_sol_vault.try_borrow_mut_lamports()? += sol_to_migrate;
+        Ok(())
+    }
 
-        msg!("Transferred {} lamports to migration vault for pool", sol_to_migrate);
+    /// Buyer funds the SOL leg of an OTC deal
+    pub fn deposit_otc_sol(
+        ctx: Context<DepositOtcSol>,
+    ) -> Result<()> {
+        let otc_deal = &ctx.accounts.otc_deal;
+        require!(!otc_deal.sol_deposited, ErrorCode::OtcLegAlreadyFunded);
 
-        // Transfer tokens from bonding curve token account to migration token account
-        let mint_key = ctx.accounts.mint.key();
-        let seeds = &[
-            b"bonding_curve",
-            mint_key.as_ref(),
-            &[bonding_curve.bump],
-        ];
-        let signer = &[&seeds[..]];
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.otc_deal.to_account_info(),
+                },
+            ),
+            otc_deal.sol_amount,
+        )?;
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.bonding_curve_token_account.to_account_info(),
-            to: ctx.accounts.migration_token_account.to_account_info(),
-            authority: ctx.accounts.bonding_curve.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        
-        transfer(cpi_ctx, tokens_to_migrate)?;
+        let otc_deal = &mut ctx.accounts.otc_deal;
+        otc_deal.sol_deposited = true;
+
+        emit!(OtcDealFunded {
+            otc_deal: otc_deal.key(),
+            depositor: otc_deal.buyer,
+            token_amount: 0,
+            sol_amount: otc_deal.sol_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        msg!("Transferred {} tokens to migration vault", tokens_to_migrate);
+        Ok(())
+    }
 
-        // Update bonding curve state
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-        bonding_curve.migrated = true;
-        bonding_curve.raydium_pool = ctx.accounts.migration_sol_vault.key(); // Store migration vault for now
-        bonding_curve.real_sol_reserves = 0;
-        bonding_curve.real_token_reserves = 0;
+    /// Swap both legs of a fully funded OTC deal in one step and close it (permissionless)
+    pub fn settle_otc_deal(
+        ctx: Context<SettleOtcDeal>,
+    ) -> Result<()> {
+        let otc_deal = &ctx.accounts.otc_deal;
+        require!(otc_deal.tokens_deposited && otc_deal.sol_deposited, ErrorCode::OtcDealNotFunded);
 
-        msg!("Migration state updated - bonding curve is now locked");
+        otc_deal_release(
+            otc_deal,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.buyer_token_account.to_account_info(),
+            &ctx.accounts.seller,
+            &ctx.accounts.token_program,
+        )?;
+        move_lamports(
+            &ctx.accounts.otc_deal.to_account_info(),
+            &ctx.accounts.seller,
+            otc_deal.sol_amount,
+        )?;
 
-        // Emit migration complete event
-        emit!(MigrationComplete {
-            mint: bonding_curve.mint,
-            raydium_pool: ctx.accounts.migration_sol_vault.key(),
-            sol_migrated: sol_to_migrate,
-            tokens_migrated: tokens_to_migrate,
-            migration_fee,
+        emit!(OtcDealSettled {
+            otc_deal: otc_deal.key(),
+            seller: otc_deal.seller,
+            buyer: otc_deal.buyer,
+            mint: otc_deal.mint,
+            token_amount: otc_deal.token_amount,
+            sol_amount: otc_deal.sol_amount,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Migration complete!");
-        msg!("  - Migration fee collected: {} SOL", migration_fee / 1_000_000_000);
-        msg!("  - SOL for pool: {} lamports", sol_to_migrate);
-        msg!("  - Tokens for pool: {}", tokens_to_migrate);
-        msg!("Use the create-raydium-pool script to finalize DEX listing.");
-
         Ok(())
     }
 
-    /// Sell tokens back to the bonding curve
-    pub fn sell_tokens(
-        ctx: Context<SellTokens>,
-        token_amount: u64,
-        min_sol_out: u64,
+    /// Either party cancels an OTC deal that isn't fully funded, refunding any deposited leg
+    pub fn cancel_otc_deal(
+        ctx: Context<CancelOtcDeal>,
     ) -> Result<()> {
-        require!(!ctx.accounts.bonding_curve.complete, ErrorCode::BondingCurveComplete);
-        require!(!ctx.accounts.bonding_curve.migrated, ErrorCode::AlreadyMigrated);
-        require!(token_amount > 0, ErrorCode::InvalidAmount);
-
-        // Calculate SOL out using constant product formula
-        let virtual_sol = ctx.accounts.bonding_curve.virtual_sol_reserves;
-        let virtual_token = ctx.accounts.bonding_curve.virtual_token_reserves;
-        let real_sol = ctx.accounts.bonding_curve.real_sol_reserves;
-        let real_token = ctx.accounts.bonding_curve.real_token_reserves;
-
-        let total_sol_before = (virtual_sol as u128).checked_add(real_sol as u128).unwrap();
-        let total_token_before = (virtual_token as u128).checked_add(real_token as u128).unwrap();
-        let k = total_sol_before.checked_mul(total_token_before).unwrap();
+        let otc_deal = &ctx.accounts.otc_deal;
+        require!(
+            !(otc_deal.tokens_deposited && otc_deal.sol_deposited),
+            ErrorCode::OtcDealFullyFunded
+        );
 
-        // New token amount after adding seller's tokens
-        let total_token_after = total_token_before.checked_add(token_amount as u128).unwrap();
-        
-        // Calculate new SOL reserves to maintain k
-        let total_sol_after = k.checked_div(total_token_after).unwrap();
-        let sol_out_before_fee = total_sol_before.checked_sub(total_sol_after).unwrap() as u64;
-
-        // Calculate fee
-        let fee = (sol_out_before_fee as u128)
-            .checked_mul(ctx.accounts.global_config.fee_basis_points as u128)
-            .unwrap()
-            .checked_div(10_000)
-            .unwrap() as u64;
-        let sol_out = sol_out_before_fee.checked_sub(fee).unwrap();
-
-        require!(sol_out >= min_sol_out, ErrorCode::SlippageExceeded);
-        // Check that we have enough real SOL to cover the full amount (before fees are taken)
-        require!(sol_out_before_fee <= real_sol, ErrorCode::InsufficientSOL);
-
-        // Transfer tokens from seller to bonding curve
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.seller_token_account.to_account_info(),
-            to: ctx.accounts.bonding_curve_token_account.to_account_info(),
-            authority: ctx.accounts.seller.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        transfer(cpi_ctx, token_amount)?;
-
-        // Transfer SOL from bonding curve vault to seller (after fee)
-        **ctx.accounts.bonding_curve_sol_vault.to_account_info().try_borrow_mut_lamports()? -= sol_out;
-        **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += sol_out;
-
-        // Transfer fee from bonding curve vault to treasury
-        **ctx.accounts.bonding_curve_sol_vault.to_account_info().try_borrow_mut_lamports()? -= fee;
-        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += fee;
-
-        // Update reserves
-        // Subtract the full amount calculated by the constant product (before fee)
-        ctx.accounts.bonding_curve.real_sol_reserves = ctx.accounts.bonding_curve.real_sol_reserves
-            .checked_sub(sol_out_before_fee)
-            .ok_or(ErrorCode::InsufficientSOL)?;
-        ctx.accounts.bonding_curve.real_token_reserves = ctx.accounts.bonding_curve.real_token_reserves
-            .checked_add(token_amount)
-            .ok_or(ErrorCode::InvalidAmount)?;
+        otc_deal_release(
+            otc_deal,
+            &ctx.accounts.escrow_vault,
+            &ctx.accounts.seller_token_account.to_account_info(),
+            &ctx.accounts.seller,
+            &ctx.accounts.token_program,
+        )?;
+        if otc_deal.sol_deposited {
+            move_lamports(
+                &ctx.accounts.otc_deal.to_account_info(),
+                &ctx.accounts.buyer,
+                otc_deal.sol_amount,
+            )?;
+        }
 
-        emit!(SellEvent {
-            seller: ctx.accounts.seller.key(),
-            mint: ctx.accounts.bonding_curve.mint,
-            token_amount,
-            sol_out,
-            fee,
+        emit!(OtcDealCancelled {
+            otc_deal: otc_deal.key(),
+            cancelled_by: ctx.accounts.party.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Withdraw accumulated platform fees from a bonding curve vault
-    /// Only the global authority can call this function
-    pub fn withdraw_platform_fees(
-        ctx: Context<WithdrawPlatformFees>,
+    /// Open a convertible funding round for the creator's curve. Investors' SOL is escrowed
+    /// until the curve graduates, then converts into `token_allocation` tokens escrowed here at
+    /// the migration price less `discount_bps`, capped at `price_cap` lamports per whole token
+    /// (0 = uncapped). If the curve hasn't graduated by `deadline`, investors are refunded
+    pub fn create_funding_round(
+        ctx: Context<CreateFundingRound>,
+        discount_bps: u16,
+        price_cap: u64,
+        token_allocation: u64,
+        deadline: i64,
     ) -> Result<()> {
-        // Verify the caller is the platform authority
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ctx.accounts.creator.key() == bonding_curve.creator,
             ErrorCode::Unauthorized
         );
+        require!(!bonding_curve.is_migrated(), ErrorCode::AlreadyMigrated);
 
-        // Calculate accumulated fees
-        // Fees = vault balance - real_sol_reserves - rent_exempt_minimum
-        let vault_balance = ctx.accounts.bonding_curve_sol_vault.lamports();
-        let real_sol_reserves = ctx.accounts.bonding_curve.real_sol_reserves;
-        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
-
-        // Ensure we have enough balance to cover reserves + rent
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(token_allocation > 0, ErrorCode::InvalidAmount);
         require!(
-            vault_balance >= real_sol_reserves + rent_exempt_minimum,
-            ErrorCode::InsufficientFees
+            discount_bps < 10_000 && deadline > current_time,
+            ErrorCode::InvalidFundingRoundTerms
         );
 
-        let accumulated_fees = vault_balance
-            .checked_sub(real_sol_reserves)
-            .unwrap()
-            .checked_sub(rent_exempt_minimum)
-            .unwrap();
-
-        require!(accumulated_fees > 0, ErrorCode::NoFeesToWithdraw);
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.creator_token_account.to_account_info(),
+            to: ctx.accounts.round_vault.to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, token_allocation)?;
 
-        // Transfer accumulated fees to treasury
-        **ctx.accounts.bonding_curve_sol_vault.to_account_info().try_borrow_mut_lamports()? -= accumulated_fees;
-        **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += accumulated_fees;
+        let funding_round = &mut ctx.accounts.funding_round;
+        funding_round.mint = ctx.accounts.mint.key();
+        funding_round.creator = ctx.accounts.creator.key();
+        funding_round.discount_bps = discount_bps;
+        funding_round.price_cap = price_cap;
+        funding_round.token_allocation = token_allocation;
+        funding_round.deadline = deadline;
+        funding_round.total_raised = 0;
+        funding_round.investor_count = 0;
+        funding_round.conversion_price = 0;
+        funding_round.tokens_owed = 0;
+        funding_round.converted_at = 0;
+        funding_round.milestone_count = 0;
+        funding_round.milestones_released = 0;
+        funding_round.milestone_interval = 0;
+        funding_round.escrowed_sol = 0;
+        funding_round.bump = ctx.bumps.funding_round;
 
-        emit!(FeeWithdrawalEvent {
-            mint: ctx.accounts.bonding_curve.mint,
-            authority: ctx.accounts.authority.key(),
-            treasury: ctx.accounts.treasury.key(),
-            amount: accumulated_fees,
-            timestamp: Clock::get()?.unix_timestamp,
+        emit!(FundingRoundCreated {
+            mint: funding_round.mint,
+            creator: funding_round.creator,
+            discount_bps,
+            price_cap,
+            token_allocation,
+            deadline,
+            timestamp: current_time,
         });
 
         Ok(())
     }
 
-    /// Withdraw funds from migration vault to create Raydium pool
-    /// This allows the platform to use migration vault funds for pool creation
-    pub fn withdraw_migration_funds(
-        ctx: Context<WithdrawMigrationFunds>,
-        sol_amount: u64,
-        token_amount: u64,
+    /// Deposit `amount` lamports into a funding round before its deadline and before the
+    /// curve graduates
+    pub fn invest_in_round(
+        ctx: Context<InvestInRound>,
+        amount: u64,
     ) -> Result<()> {
-        // Verify the caller is the platform authority
-        require!(
-            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
-            ErrorCode::Unauthorized
-        );
-
-        // Verify the bonding curve is migrated
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        let current_time = Clock::get()?.unix_timestamp;
         require!(
-            ctx.accounts.bonding_curve.migrated,
-            ErrorCode::NotMigrated
+            current_time < ctx.accounts.funding_round.deadline
+                && !ctx.accounts.bonding_curve.load()?.is_migrated(),
+            ErrorCode::FundingRoundClosed
         );
 
-        msg!("Withdrawing {} SOL and {} tokens from migration vault", sol_amount, token_amount);
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.investor.to_account_info(),
+                    to: ctx.accounts.funding_round.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
 
-        // Withdraw SOL using System Program
-        if sol_amount > 0 {
-            let vault_balance = ctx.accounts.migration_sol_vault.lamports();
-            require!(vault_balance >= sol_amount, ErrorCode::InsufficientSOL);
+        let funding_round = &mut ctx.accounts.funding_round;
+        let position = &mut ctx.accounts.round_position;
+        if position.deposited == 0 {
+            position.mint = funding_round.mint;
+            position.investor = ctx.accounts.investor.key();
+            position.bump = ctx.bumps.round_position;
+            funding_round.investor_count = funding_round.investor_count.safe_add(1)?;
+        }
+        position.deposited = position.deposited.safe_add(amount)?;
+        funding_round.total_raised = funding_round.total_raised.safe_add(amount)?;
 
-            // Use System Program to transfer SOL from PDA to recipient
-            let mint_key = ctx.accounts.mint.key();
-            let vault_bump = ctx.bumps.migration_sol_vault;
-            let vault_seeds: &[&[u8]] = &[
-                b"migration_vault",
-                mint_key.as_ref(),
-                &[vault_bump],
-            ];
-            let vault_signer = &[vault_seeds];
+        emit!(RoundInvestment {
+            mint: funding_round.mint,
+            investor: position.investor,
+            amount,
+            deposited: position.deposited,
+            total_raised: funding_round.total_raised,
+            timestamp: current_time,
+        });
 
-            system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: ctx.accounts.migration_sol_vault.to_account_info(),
-                        to: ctx.accounts.recipient.to_account_info(),
-                    },
-                    vault_signer,
-                ),
-                sol_amount,
-            )?;
+        Ok(())
+    }
 
-            msg!("Transferred {} lamports from migration vault", sol_amount);
-        }
+    /// Fix a funding round's conversion price once its curve has graduated, pay the raised SOL
+    /// to the creator and return unneeded allocation tokens to them (permissionless)
+    pub fn convert_funding_round(
+        ctx: Context<ConvertFundingRound>,
+    ) -> Result<()> {
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        let funding_round = &ctx.accounts.funding_round;
+        require!(!funding_round.is_converted(), ErrorCode::FundingRoundAlreadyConverted);
+        require!(funding_round.graduated_in_time(&bonding_curve), ErrorCode::FundingRoundNotGraduated);
 
-        // Withdraw tokens
-        if token_amount > 0 {
-            let authority_bump = ctx.bumps.migration_authority;
-            let seeds: &[&[u8]] = &[
-                b"migration_authority",
-                &[authority_bump],
-            ];
-            let signer = &[seeds];
+        // The price the curve's liquidity was seeded into the pool at
+        let migration_price = curve::spot_price(Reserves {
+            sol: bonding_curve.graduation_sol_reserves as u128,
+            token: bonding_curve.graduation_token_reserves as u128,
+        })?;
+        let conversion_price = curve::round_conversion_price(
+            migration_price,
+            funding_round.discount_bps,
+            funding_round.price_cap,
+            funding_round.total_raised,
+            funding_round.token_allocation,
+        )?;
+        let tokens_owed = curve::tokens_for_deposit(funding_round.total_raised, conversion_price)?;
 
+        let mint_key = funding_round.mint;
+        let seeds: &[&[u8]] = &[
+            b"funding_round",
+            mint_key.as_ref(),
+            &[funding_round.bump],
+        ];
+        let signer = &[seeds];
+        let surplus = ctx.accounts.round_vault.amount.safe_sub(tokens_owed)?;
+        if surplus > 0 {
             let cpi_accounts = Transfer {
-                from: ctx.accounts.migration_token_account.to_account_info(),
-                to: ctx.accounts.recipient_token_account.to_account_info(),
-                authority: ctx.accounts.migration_authority.to_account_info(),
+                from: ctx.accounts.round_vault.to_account_info(),
+                to: ctx.accounts.creator_token_account.to_account_info(),
+                authority: ctx.accounts.funding_round.to_account_info(),
             };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-            
-            transfer(cpi_ctx, token_amount)?;
+            transfer(
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+                surplus,
+            )?;
+        }
+        let current_time = Clock::get()?.unix_timestamp;
+        // With rage-quit enabled the SOL stays escrowed and is released in milestones
+        if !funding_round.rage_quit_enabled() {
+            move_lamports(
+                &ctx.accounts.funding_round.to_account_info(),
+                &ctx.accounts.creator,
+                funding_round.total_raised,
+            )?;
+        }
 
-            msg!("Transferred {} tokens from migration vault", token_amount);
+        let funding_round = &mut ctx.accounts.funding_round;
+        funding_round.conversion_price = conversion_price;
+        funding_round.tokens_owed = tokens_owed;
+        funding_round.converted_at = current_time;
+        if funding_round.rage_quit_enabled() {
+            funding_round.escrowed_sol = funding_round.total_raised;
         }
 
-        emit!(MigrationFundsWithdrawn {
-            mint: ctx.accounts.bonding_curve.mint,
-            authority: ctx.accounts.authority.key(),
-            recipient: ctx.accounts.recipient.key(),
-            sol_amount,
-            token_amount,
-            timestamp: Clock::get()?.unix_timestamp,
+        emit!(FundingRoundConverted {
+            mint: funding_round.mint,
+            migration_price,
+            conversion_price,
+            total_raised: funding_round.total_raised,
+            tokens_owed,
+            timestamp: current_time,
         });
 
-        msg!("Migration funds withdrawn successfully");
+        Ok(())
+    }
+
+    /// Claim the tokens an investor's deposit converted into, closing their position
+    pub fn claim_round_tokens(
+        ctx: Context<ClaimRoundTokens>,
+    ) -> Result<()> {
+        let funding_round = &ctx.accounts.funding_round;
+        require!(funding_round.is_converted(), ErrorCode::FundingRoundNotConverted);
+
+        let position = &ctx.accounts.round_position;
+        let tokens = curve::tokens_for_deposit(position.deposited, funding_round.conversion_price)?;
+
+        let mint_key = funding_round.mint;
+        let seeds: &[&[u8]] = &[
+            b"funding_round",
+            mint_key.as_ref(),
+            &[funding_round.bump],
+        ];
+        let signer = &[seeds];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to: ctx.accounts.investor_token_account.to_account_info(),
+            authority: ctx.accounts.funding_round.to_account_info(),
+        };
+        transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+            tokens,
+        )?;
+
+        emit!(RoundTokensClaimed {
+            mint: mint_key,
+            investor: position.investor,
+            deposited: position.deposited,
+            tokens,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         Ok(())
     }
 
-    /// Create Raydium pool and burn LP tokens to permanently lock liquidity
-    /// This ensures liquidity cannot be rug-pulled, similar to pump.fun
-    /// 
-    /// IMPORTANT: This is a two-step process:
-    /// 1. Use Raydium SDK/UI to create the pool with migration vault funds
-    /// 2. Call this instruction to burn the received LP tokens
-    /// 
-    /// After this instruction, liquidity is PERMANENTLY LOCKED.
-    pub fn burn_raydium_lp_tokens(
-        ctx: Context<BurnRaydiumLpTokens>,
-        lp_amount: u64,
+    /// Refund an investor's deposit once a round's curve missed its deadline, closing their
+    /// position
+    pub fn refund_round_deposit(
+        ctx: Context<RefundRoundDeposit>,
     ) -> Result<()> {
-        // Verify the caller is the platform authority
+        let current_time = Clock::get()?.unix_timestamp;
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
-            ErrorCode::Unauthorized
+            ctx.accounts.funding_round.has_failed(&*ctx.accounts.bonding_curve.load()?, current_time),
+            ErrorCode::FundingRoundNotFailed
         );
 
-        // Verify the bonding curve is migrated
+        let deposited = ctx.accounts.round_position.deposited;
+        move_lamports(
+            &ctx.accounts.funding_round.to_account_info(),
+            &ctx.accounts.investor.to_account_info(),
+            deposited,
+        )?;
+        let funding_round = &mut ctx.accounts.funding_round;
+        funding_round.total_raised = funding_round.total_raised.safe_sub(deposited)?;
+
+        emit!(RoundDepositRefunded {
+            mint: funding_round.mint,
+            investor: ctx.accounts.investor.key(),
+            amount: deposited,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Return a failed round's token allocation to the creator
+    pub fn reclaim_round_tokens(
+        ctx: Context<ReclaimRoundTokens>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let funding_round = &ctx.accounts.funding_round;
         require!(
-            ctx.accounts.bonding_curve.migrated,
-            ErrorCode::NotMigrated
+            funding_round.has_failed(&*ctx.accounts.bonding_curve.load()?, current_time),
+            ErrorCode::FundingRoundNotFailed
         );
 
-        msg!("Burning {} LP tokens to permanently lock liquidity", lp_amount);
-
-        // Burn the LP tokens using migration authority
-        let authority_bump = ctx.bumps.migration_authority;
+        let mint_key = funding_round.mint;
         let seeds: &[&[u8]] = &[
-            b"migration_authority",
-            &[authority_bump],
+            b"funding_round",
+            mint_key.as_ref(),
+            &[funding_round.bump],
         ];
         let signer = &[seeds];
-
-        let burn_accounts = Burn {
-            mint: ctx.accounts.lp_mint.to_account_info(),
-            from: ctx.accounts.lp_token_account.to_account_info(),
-            authority: ctx.accounts.migration_authority.to_account_info(),
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.round_vault.to_account_info(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: ctx.accounts.funding_round.to_account_info(),
         };
-        let burn_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            burn_accounts,
-            signer,
-        );
-        
-        burn(burn_ctx, lp_amount)?;
+        transfer(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+            ctx.accounts.round_vault.amount,
+        )?;
 
-        msg!("Successfully burned {} LP tokens", lp_amount);
+        Ok(())
+    }
 
-        // Create LP burn info account to track the burn
-        let lp_burn_info = &mut ctx.accounts.lp_burn_info;
-        lp_burn_info.mint = ctx.accounts.bonding_curve.mint;
-        lp_burn_info.lp_mint = ctx.accounts.lp_mint.key();
-        lp_burn_info.raydium_pool = ctx.accounts.raydium_pool.key();
-        lp_burn_info.lp_burned_amount = lp_amount;
-        lp_burn_info.burn_timestamp = Clock::get()?.unix_timestamp;
-        lp_burn_info.bump = ctx.bumps.lp_burn_info;
+    /// Opt a funding round into rage-quit before anyone invests: the raised SOL stays escrowed
+    /// after conversion and is released to the creator in `milestone_count` equal tranches, one
+    /// every `milestone_interval` seconds. Until the last release, any holder can burn tokens
+    /// for their pro-rata share of what is still escrowed (`rage_quit`)
+    pub fn enable_rage_quit(
+        ctx: Context<EnableRageQuit>,
+        milestone_count: u8,
+        milestone_interval: i64,
+    ) -> Result<()> {
+        let funding_round = &mut ctx.accounts.funding_round;
+        require!(funding_round.investor_count == 0, ErrorCode::FundingRoundHasInvestors);
+        require!(
+            milestone_count > 0 && milestone_interval > 0,
+            ErrorCode::InvalidFundingRoundTerms
+        );
 
-        emit!(LpTokensBurnedEvent {
-            mint: ctx.accounts.bonding_curve.mint,
-            raydium_pool: ctx.accounts.raydium_pool.key(),
-            lp_mint: ctx.accounts.lp_mint.key(),
-            lp_amount_burned: lp_amount,
+        funding_round.milestone_count = milestone_count;
+        funding_round.milestone_interval = milestone_interval;
+
+        emit!(RageQuitEnabled {
+            mint: funding_round.mint,
+            milestone_count,
+            milestone_interval,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Liquidity is now PERMANENTLY LOCKED! 🔒");
-        msg!("Pool address: {}", ctx.accounts.raydium_pool.key());
-        msg!("LP tokens burned: {}", lp_amount);
-
         Ok(())
     }
 
-    /// Complete Raydium pool creation with automatic LP burning
-    /// This is a comprehensive instruction that handles the entire process
-    /// 
-    /// NOTE: This requires integration with Raydium's CPMM program
-    /// For now, use the two-step process:
-    /// 1. Create pool manually with Raydium SDK
-    /// 2. Call burn_raydium_lp_tokens to lock liquidity
-    pub fn create_and_lock_raydium_pool(
-        ctx: Context<CreateAndLockRaydiumPool>,
+    /// Release the next milestone of a rage-quit round's escrowed SOL to the creator
+    /// (permissionless). Each release pays an equal part of what is left, so rage-quits before
+    /// a milestone shrink it rather than later ones
+    pub fn release_round_milestone(
+        ctx: Context<ReleaseRoundMilestone>,
     ) -> Result<()> {
-        // Verify the caller is the platform authority
+        let current_time = Clock::get()?.unix_timestamp;
+        let funding_round = &ctx.accounts.funding_round;
+        require!(funding_round.rage_quit_enabled(), ErrorCode::RageQuitDisabled);
+        require!(funding_round.is_converted(), ErrorCode::FundingRoundNotConverted);
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
-            ErrorCode::Unauthorized
+            funding_round.milestones_released < funding_round.milestone_count,
+            ErrorCode::NothingEscrowed
         );
-
-        // Verify the bonding curve is migrated
         require!(
-            ctx.accounts.bonding_curve.migrated,
-            ErrorCode::NotMigrated
+            current_time >= funding_round.next_milestone_time()?,
+            ErrorCode::MilestoneNotReached
         );
 
-        // NOTE: Pool creation check would be done by checking if lp_burn_info account exists
-        // For now, this is a placeholder instruction
+        let remaining_milestones = funding_round
+            .milestone_count
+            .safe_sub(funding_round.milestones_released)?;
+        let amount = funding_round.escrowed_sol.safe_div(remaining_milestones as u64)?;
+        move_lamports(
+            &ctx.accounts.funding_round.to_account_info(),
+            &ctx.accounts.creator,
+            amount,
+        )?;
 
-        msg!("Creating Raydium pool with automatic LP burning...");
+        let funding_round = &mut ctx.accounts.funding_round;
+        funding_round.escrowed_sol = funding_round.escrowed_sol.safe_sub(amount)?;
+        funding_round.milestones_released = funding_round.milestones_released.safe_add(1)?;
 
-        // TODO: Implement Raydium CPMM pool creation via CPI
-        // This requires:
-        // 1. CPI to Raydium's initialize_pool instruction
-        // 2. Add liquidity from migration vaults
-        // 3. Receive LP tokens
-        // 4. Immediately burn LP tokens
-        
-        msg!("⚠️  This instruction is not yet fully implemented.");
-        msg!("Please use the two-step manual process:");
-        msg!("1. Create Raydium pool using their SDK/UI");
-        msg!("2. Call burn_raydium_lp_tokens to lock liquidity");
+        emit!(RoundMilestoneReleased {
+            mint: funding_round.mint,
+            milestone: funding_round.milestones_released,
+            amount,
+            escrowed_sol: funding_round.escrowed_sol,
+            timestamp: current_time,
+        });
 
-        Err(ErrorCode::NotImplemented.into())
+        Ok(())
     }
-}
 
-#[derive(Accounts)]
-#[instruction(name: String, symbol: String, total_supply: u64, category: String)]
-pub struct InitializeProject<'info> {
-    #[account(
-        init,
-        payer = owner,
-        seeds = [b"project", owner.key().as_ref(), symbol.as_bytes()],
-        bump,
-        space = ProjectState::MAX_SIZE,
-    )]
-    pub project_state: Account<'info, ProjectState>,
+    /// Burn `amount` tokens of a rage-quit round's mint for the same share of its escrowed SOL
+    /// as of the mint's supply
+    pub fn rage_quit(
+        ctx: Context<RageQuit>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        let funding_round = &ctx.accounts.funding_round;
+        require!(funding_round.rage_quit_enabled(), ErrorCode::RageQuitDisabled);
+        require!(funding_round.is_converted(), ErrorCode::FundingRoundNotConverted);
+        require!(funding_round.escrowed_sol > 0, ErrorCode::NothingEscrowed);
 
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        let lamports = funding_round.rage_quit_share(amount, ctx.accounts.mint.supply)?;
+        require!(lamports > 0, ErrorCode::InvalidAmount);
 
-#[derive(Accounts)]
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        move_lamports(
+            &ctx.accounts.funding_round.to_account_info(),
+            &ctx.accounts.holder.to_account_info(),
+            lamports,
+        )?;
+
+        let funding_round = &mut ctx.accounts.funding_round;
+        funding_round.escrowed_sol = funding_round.escrowed_sol.safe_sub(lamports)?;
+
+        emit!(RageQuitEvent {
+            mint: funding_round.mint,
+            holder: ctx.accounts.holder.key(),
+            tokens_burned: amount,
+            lamports,
+            escrowed_sol: funding_round.escrowed_sol,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Stream lamports to a contributor on `terms`. The full amount is escrowed up front in the
+    /// stream PDA
+    pub fn create_sol_stream(
+        ctx: Context<CreateSolStream>,
+        id: u64,
+        terms: StreamTerms,
+    ) -> Result<()> {
+        let stream = &mut ctx.accounts.payment_stream;
+        stream.open(id, ctx.accounts.payer.key(), Pubkey::default(), terms, ctx.bumps.payment_stream)?;
+        let total_amount = stream.total_amount()?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.payment_stream.to_account_info(),
+                },
+            ),
+            total_amount,
+        )?;
+
+        let stream = &ctx.accounts.payment_stream;
+        emit!(PaymentStreamCreated {
+            payment_stream: stream.key(),
+            payer: stream.payer,
+            payee: stream.payee,
+            mint: stream.mint,
+            rate_per_second: stream.rate_per_second,
+            start_time: stream.start_time,
+            cliff_time: stream.cliff_time,
+            end_time: stream.end_time,
+            cancelable: stream.cancelable,
+        });
+
+        Ok(())
+    }
+
+    /// `create_sol_stream` for an SPL token, escrowed in the stream's vault
+    pub fn create_token_stream(
+        ctx: Context<CreateTokenStream>,
+        id: u64,
+        terms: StreamTerms,
+    ) -> Result<()> {
+        let stream = &mut ctx.accounts.payment_stream;
+        stream.open(id, ctx.accounts.payer.key(), ctx.accounts.mint.key(), terms, ctx.bumps.payment_stream)?;
+        let total_amount = stream.total_amount()?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer_token_account.to_account_info(),
+            to: ctx.accounts.stream_vault.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, total_amount)?;
+
+        let stream = &ctx.accounts.payment_stream;
+        emit!(PaymentStreamCreated {
+            payment_stream: stream.key(),
+            payer: stream.payer,
+            payee: stream.payee,
+            mint: stream.mint,
+            rate_per_second: stream.rate_per_second,
+            start_time: stream.start_time,
+            cliff_time: stream.cliff_time,
+            end_time: stream.end_time,
+            cancelable: stream.cancelable,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw streamed funds to the payee. `amount` withdraws part of them (default: all).
+    /// Token streams need the vault, the payee's token account and the token program
+    pub fn withdraw_from_stream(
+        ctx: Context<WithdrawFromStream>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let stream = &ctx.accounts.payment_stream;
+        let available = stream.streamed_amount(current_time)?.safe_sub(stream.withdrawn)?;
+        require!(available > 0, ErrorCode::NothingToWithdraw);
+        let amount = amount.unwrap_or(available);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= available, ErrorCode::ClaimExceedsUnlocked);
+
+        pay_from_stream(
+            stream,
+            ctx.accounts.stream_vault.as_ref(),
+            ctx.accounts.payee_token_account.as_ref().map(|account| account.to_account_info()),
+            &ctx.accounts.payee,
+            ctx.accounts.token_program.as_ref(),
+            amount,
+        )?;
+
+        let stream = &mut ctx.accounts.payment_stream;
+        stream.withdrawn = stream.withdrawn.safe_add(amount)?;
+
+        emit!(StreamWithdrawn {
+            payment_stream: stream.key(),
+            payee: stream.payee,
+            amount,
+            withdrawn: stream.withdrawn,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Stop a stream (payer only): the payee gets what has streamed so far, the payer the rest,
+    /// and the stream is closed. Streams created non-cancelable can only be closed once ended
+    pub fn cancel_stream(
+        ctx: Context<CancelStream>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let stream = &ctx.accounts.payment_stream;
+        require!(
+            stream.cancelable || current_time >= stream.end_time,
+            ErrorCode::StreamNotCancelable
+        );
+
+        let streamed = stream.streamed_amount(current_time)?;
+        let payee_amount = streamed.safe_sub(stream.withdrawn)?;
+        let refund = stream.total_amount()?.safe_sub(streamed)?;
+        pay_from_stream(
+            stream,
+            ctx.accounts.stream_vault.as_ref(),
+            ctx.accounts.payee_token_account.as_ref().map(|account| account.to_account_info()),
+            &ctx.accounts.payee,
+            ctx.accounts.token_program.as_ref(),
+            payee_amount,
+        )?;
+
+        // SOL refunds leave with the stream's rent when it closes
+        if !stream.is_sol() {
+            pay_from_stream(
+                stream,
+                ctx.accounts.stream_vault.as_ref(),
+                ctx.accounts.payer_token_account.as_ref().map(|account| account.to_account_info()),
+                &ctx.accounts.payer,
+                ctx.accounts.token_program.as_ref(),
+                refund,
+            )?;
+            close_stream_vault(
+                stream,
+                ctx.accounts.stream_vault.as_ref(),
+                &ctx.accounts.payer,
+                ctx.accounts.token_program.as_ref(),
+            )?;
+        }
+
+        emit!(PaymentStreamCanceled {
+            payment_stream: stream.key(),
+            payer: stream.payer,
+            payee: stream.payee,
+            payee_amount,
+            refund,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Initialize the global configuration for bonding curve parameters
+    pub fn initialize_global_config(
+        ctx: Context<InitializeGlobalConfig>,
+        params: GlobalConfigParams,
+    ) -> Result<()> {
+        let GlobalConfigParams {
+            treasury,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            initial_token_supply,
+            buy_fee_bps,
+            migration_threshold_sol,
+            raydium_amm_program,
+            migration_keeper_reward,
+            lp_fee_creator_share_bps,
+            config_update_delay,
+            boost_lamports_per_hour,
+            points_per_sol_bought,
+            points_per_sol_sold,
+            round_trip_slot_window,
+            fee_tiers,
+            sell_fee_bps,
+            platform_token_mint,
+            holder_discount_tiers,
+            fee_share_bps,
+            launch_fee_lamports,
+            launch_deposit_lamports,
+            max_launches_per_window,
+            launch_window_seconds,
+            migration_operator,
+            insurance_fee_bps,
+            buyback_bps,
+            kyc_verifier,
+            jupiter_program,
+            token_decimals,
+            randomness_queue,
+            randomness_authority,
+            sol_usd_price_account,
+            max_price_age_seconds,
+            max_price_confidence_bps,
+            launch_fee_usd,
+        } = params;
+        let global_config = &mut ctx.accounts.global_config;
+        global_config.authority = ctx.accounts.authority.key();
+        global_config.treasury = treasury;
+        global_config.virtual_sol_reserves = virtual_sol_reserves;
+        global_config.virtual_token_reserves = virtual_token_reserves;
+        global_config.initial_token_supply = initial_token_supply;
+        global_config.buy_fee_bps = buy_fee_bps;
+        global_config.migration_threshold_sol = migration_threshold_sol;
+        global_config.raydium_amm_program = raydium_amm_program;
+        global_config.migration_keeper_reward = migration_keeper_reward;
+        global_config.lp_fee_creator_share_bps = lp_fee_creator_share_bps;
+        global_config.secondary_payouts = [Pubkey::default(); GlobalConfig::MAX_SECONDARY_PAYOUTS];
+        global_config.version = GlobalConfig::CURRENT_VERSION;
+        global_config.config_update_delay = config_update_delay;
+        global_config.pending_authority = Pubkey::default();
+        global_config.boost_lamports_per_hour = boost_lamports_per_hour;
+        global_config.points_per_sol_bought = points_per_sol_bought;
+        global_config.points_per_sol_sold = points_per_sol_sold;
+        global_config.points_epoch = 0;
+        global_config.round_trip_slot_window = round_trip_slot_window;
+        global_config.fee_tiers = fee_tiers;
+        global_config.sell_fee_bps = sell_fee_bps;
+        global_config.platform_token_mint = platform_token_mint;
+        global_config.holder_discount_tiers = holder_discount_tiers;
+        global_config.fee_share_bps = fee_share_bps;
+        global_config.launch_fee_lamports = launch_fee_lamports;
+        global_config.launch_deposit_lamports = launch_deposit_lamports;
+        global_config.max_launches_per_window = max_launches_per_window;
+        global_config.launch_window_seconds = launch_window_seconds;
+        global_config.migration_operator = migration_operator;
+        global_config.treasury_splits = [TreasurySplit::default(); GlobalConfig::MAX_TREASURY_SPLITS];
+        global_config.insurance_fee_bps = insurance_fee_bps;
+        global_config.buyback_bps = buyback_bps;
+        global_config.kyc_verifier = kyc_verifier;
+        global_config.jupiter_program = jupiter_program;
+        global_config.token_decimals = token_decimals;
+        global_config.randomness_queue = randomness_queue;
+        global_config.randomness_authority = randomness_authority;
+        global_config.sol_usd_price_account = sol_usd_price_account;
+        global_config.max_price_age_seconds = max_price_age_seconds;
+        global_config.max_price_confidence_bps = max_price_confidence_bps;
+        global_config.launch_fee_usd = launch_fee_usd;
+        global_config.validate()?;
+        Ok(())
+    }
+
+    /// Update the global configuration (admin only)
+    pub fn update_global_config(
+        ctx: Context<UpdateGlobalConfig>,
+        update: ConfigUpdate,
+    ) -> Result<()> {
+        let ConfigUpdate {
+            treasury,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            initial_token_supply,
+            buy_fee_bps,
+            migration_threshold_sol,
+            raydium_amm_program,
+            migration_keeper_reward,
+            lp_fee_creator_share_bps,
+            config_update_delay,
+            boost_lamports_per_hour,
+            points_per_sol_bought,
+            points_per_sol_sold,
+            round_trip_slot_window,
+            fee_tiers,
+            sell_fee_bps,
+            platform_token_mint,
+            holder_discount_tiers,
+            fee_share_bps,
+            launch_fee_lamports,
+            launch_deposit_lamports,
+            max_launches_per_window,
+            launch_window_seconds,
+            migration_operator,
+            insurance_fee_bps,
+            buyback_bps,
+            kyc_verifier,
+            jupiter_program,
+            token_decimals,
+            randomness_queue,
+            randomness_authority,
+            sol_usd_price_account,
+            max_price_age_seconds,
+            max_price_confidence_bps,
+            launch_fee_usd,
+        } = update;
+        let global_config = &mut ctx.accounts.global_config;
+
+        // With a timelock configured, economic parameters and the addresses funds are paid
+        // to or routed through must go through queue_config_update
+        if global_config.config_update_delay > 0 {
+            require!(
+                treasury.is_none()
+                    && virtual_sol_reserves.is_none()
+                    && virtual_token_reserves.is_none()
+                    && initial_token_supply.is_none()
+                    && buy_fee_bps.is_none()
+                    && sell_fee_bps.is_none()
+                    && migration_threshold_sol.is_none()
+                    && raydium_amm_program.is_none()
+                    && migration_keeper_reward.is_none()
+                    && lp_fee_creator_share_bps.is_none()
+                    && fee_tiers.is_none()
+                    && launch_fee_lamports.is_none()
+                    && launch_deposit_lamports.is_none(),
+                ErrorCode::TimelockRequired
+            );
+        }
+        
+        // Only update fields that are provided
+        if let Some(val) = treasury {
+            global_config.treasury = val;
+        }
+        if let Some(val) = virtual_sol_reserves {
+            global_config.virtual_sol_reserves = val;
+        }
+        if let Some(val) = virtual_token_reserves {
+            global_config.virtual_token_reserves = val;
+        }
+        if let Some(val) = initial_token_supply {
+            global_config.initial_token_supply = val;
+        }
+        if let Some(val) = buy_fee_bps {
+            global_config.buy_fee_bps = val;
+        }
+        if let Some(val) = migration_threshold_sol {
+            global_config.migration_threshold_sol = val;
+        }
+        if let Some(val) = raydium_amm_program {
+            global_config.raydium_amm_program = val;
+        }
+        if let Some(val) = migration_keeper_reward {
+            global_config.migration_keeper_reward = val;
+        }
+        if let Some(val) = lp_fee_creator_share_bps {
+            global_config.lp_fee_creator_share_bps = val;
+        }
+        if let Some(val) = boost_lamports_per_hour {
+            global_config.boost_lamports_per_hour = val;
+        }
+        if let Some(val) = points_per_sol_bought {
+            global_config.points_per_sol_bought = val;
+        }
+        if let Some(val) = points_per_sol_sold {
+            global_config.points_per_sol_sold = val;
+        }
+        if let Some(val) = round_trip_slot_window {
+            global_config.round_trip_slot_window = val;
+        }
+        if let Some(val) = fee_tiers {
+            global_config.fee_tiers = val;
+        }
+        if let Some(val) = sell_fee_bps {
+            global_config.sell_fee_bps = val;
+        }
+        if let Some(val) = platform_token_mint {
+            global_config.platform_token_mint = val;
+        }
+        if let Some(val) = holder_discount_tiers {
+            global_config.holder_discount_tiers = val;
+        }
+        if let Some(val) = fee_share_bps {
+            global_config.fee_share_bps = val;
+        }
+        if let Some(val) = launch_fee_lamports {
+            global_config.launch_fee_lamports = val;
+        }
+        if let Some(val) = launch_deposit_lamports {
+            global_config.launch_deposit_lamports = val;
+        }
+        if let Some(val) = max_launches_per_window {
+            global_config.max_launches_per_window = val;
+        }
+        if let Some(val) = launch_window_seconds {
+            global_config.launch_window_seconds = val;
+        }
+        if let Some(val) = migration_operator {
+            global_config.migration_operator = val;
+        }
+        if let Some(val) = insurance_fee_bps {
+            global_config.insurance_fee_bps = val;
+        }
+        if let Some(val) = buyback_bps {
+            global_config.buyback_bps = val;
+        }
+        if let Some(val) = kyc_verifier {
+            global_config.kyc_verifier = val;
+        }
+        if let Some(val) = jupiter_program {
+            global_config.jupiter_program = val;
+        }
+        if let Some(val) = token_decimals {
+            global_config.token_decimals = val;
+        }
+        if let Some(val) = randomness_queue {
+            global_config.randomness_queue = val;
+        }
+        if let Some(val) = randomness_authority {
+            global_config.randomness_authority = val;
+        }
+        if let Some(val) = sol_usd_price_account {
+            global_config.sol_usd_price_account = val;
+        }
+        if let Some(val) = max_price_age_seconds {
+            global_config.max_price_age_seconds = val;
+        }
+        if let Some(val) = max_price_confidence_bps {
+            global_config.max_price_confidence_bps = val;
+        }
+        if let Some(val) = launch_fee_usd {
+            global_config.launch_fee_usd = val;
+        }
+        if let Some(val) = config_update_delay {
+            // The delay can be raised instantly but only lowered through the timelock
+            require!(val >= global_config.config_update_delay, ErrorCode::TimelockRequired);
+            global_config.config_update_delay = val;
+        }
+
+        // Validate the resulting configuration as a whole
+        global_config.validate()?;
+        
+        Ok(())
+    }
+
+    /// Queue a timelocked update of economic parameters or payout addresses (admin only)
+    /// The update can be executed once `config_update_delay` seconds have passed,
+    /// giving traders and creators time to react
+    pub fn queue_config_update(
+        ctx: Context<QueueConfigUpdate>,
+        update: TimelockedConfigUpdate,
+    ) -> Result<()> {
+        let TimelockedConfigUpdate {
+            treasury,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            initial_token_supply,
+            buy_fee_bps,
+            migration_threshold_sol,
+            raydium_amm_program,
+            migration_keeper_reward,
+            lp_fee_creator_share_bps,
+            config_update_delay,
+            fee_tiers,
+            sell_fee_bps,
+            launch_fee_lamports,
+            launch_deposit_lamports,
+        } = update;
+        let current_time = Clock::get()?.unix_timestamp;
+        let eta = current_time
+            .safe_add(ctx.accounts.global_config.config_update_delay)?;
+
+        let pending = &mut ctx.accounts.pending_config_update;
+        pending.treasury = treasury;
+        pending.virtual_sol_reserves = virtual_sol_reserves;
+        pending.virtual_token_reserves = virtual_token_reserves;
+        pending.initial_token_supply = initial_token_supply;
+        pending.buy_fee_bps = buy_fee_bps;
+        pending.migration_threshold_sol = migration_threshold_sol;
+        pending.raydium_amm_program = raydium_amm_program;
+        pending.migration_keeper_reward = migration_keeper_reward;
+        pending.lp_fee_creator_share_bps = lp_fee_creator_share_bps;
+        pending.config_update_delay = config_update_delay;
+        pending.fee_tiers = fee_tiers;
+        pending.sell_fee_bps = sell_fee_bps;
+        pending.launch_fee_lamports = launch_fee_lamports;
+        pending.launch_deposit_lamports = launch_deposit_lamports;
+        pending.queued_at = current_time;
+        pending.eta = eta;
+        pending.bump = ctx.bumps.pending_config_update;
+
+        emit!(ConfigUpdateQueued {
+            authority: ctx.accounts.authority.key(),
+            buy_fee_bps,
+            migration_threshold_sol,
+            eta,
+            timestamp: current_time,
+        });
+
+        msg!("Config update queued, executable at {}", eta);
+
+        Ok(())
+    }
+
+    /// Apply a queued config update once its timelock has expired (admin only)
+    pub fn execute_config_update(
+        ctx: Context<ExecuteConfigUpdate>,
+    ) -> Result<()> {
+        let pending = &ctx.accounts.pending_config_update;
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(current_time >= pending.eta, ErrorCode::TimelockNotExpired);
+
+        let global_config = &mut ctx.accounts.global_config;
+        if let Some(val) = pending.treasury {
+            global_config.treasury = val;
+        }
+        if let Some(val) = pending.virtual_sol_reserves {
+            global_config.virtual_sol_reserves = val;
+        }
+        if let Some(val) = pending.virtual_token_reserves {
+            global_config.virtual_token_reserves = val;
+        }
+        if let Some(val) = pending.initial_token_supply {
+            global_config.initial_token_supply = val;
+        }
+        if let Some(val) = pending.buy_fee_bps {
+            global_config.buy_fee_bps = val;
+        }
+        if let Some(val) = pending.migration_threshold_sol {
+            global_config.migration_threshold_sol = val;
+        }
+        if let Some(val) = pending.raydium_amm_program {
+            global_config.raydium_amm_program = val;
+        }
+        if let Some(val) = pending.migration_keeper_reward {
+            global_config.migration_keeper_reward = val;
+        }
+        if let Some(val) = pending.lp_fee_creator_share_bps {
+            global_config.lp_fee_creator_share_bps = val;
+        }
+        if let Some(val) = pending.config_update_delay {
+            global_config.config_update_delay = val;
+        }
+        if let Some(val) = pending.fee_tiers {
+            global_config.fee_tiers = val;
+        }
+        if let Some(val) = pending.sell_fee_bps {
+            global_config.sell_fee_bps = val;
+        }
+        if let Some(val) = pending.launch_fee_lamports {
+            global_config.launch_fee_lamports = val;
+        }
+        if let Some(val) = pending.launch_deposit_lamports {
+            global_config.launch_deposit_lamports = val;
+        }
+
+        global_config.validate()?;
+
+        emit!(ConfigUpdateExecuted {
+            authority: ctx.accounts.authority.key(),
+            buy_fee_bps: global_config.buy_fee_bps,
+            migration_threshold_sol: global_config.migration_threshold_sol,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a queued config update (admin only)
+    pub fn cancel_config_update(
+        ctx: Context<CancelConfigUpdate>,
+    ) -> Result<()> {
+        emit!(ConfigUpdateCancelled {
+            authority: ctx.accounts.authority.key(),
+            eta: ctx.accounts.pending_config_update.eta,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the list of secondary payout addresses allowed to receive platform fees (admin only)
+    /// Pass an empty list to clear it; the primary treasury is always allowed
+    pub fn set_secondary_payouts(
+        ctx: Context<UpdateGlobalConfig>,
+        payouts: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            payouts.len() <= GlobalConfig::MAX_SECONDARY_PAYOUTS,
+            ErrorCode::TooManyPayoutAddresses
+        );
+        require!(
+            payouts.iter().all(|payout| *payout != Pubkey::default()),
+            ErrorCode::InvalidTreasury
+        );
+
+        let global_config = &mut ctx.accounts.global_config;
+        global_config.secondary_payouts = [Pubkey::default(); GlobalConfig::MAX_SECONDARY_PAYOUTS];
+        for (slot, payout) in global_config.secondary_payouts.iter_mut().zip(payouts.iter()) {
+            *slot = *payout;
+        }
+
+        emit!(SecondaryPayoutsUpdated {
+            authority: ctx.accounts.authority.key(),
+            payouts,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Replace the recipients that share withdrawn platform fees (admin only)
+    /// Weights must total 10,000 bps; pass an empty list to pay everything to the treasury
+    pub fn set_treasury_split(
+        ctx: Context<UpdateGlobalConfig>,
+        splits: Vec<TreasurySplit>,
+    ) -> Result<()> {
+        require!(
+            splits.len() <= GlobalConfig::MAX_TREASURY_SPLITS,
+            ErrorCode::InvalidTreasurySplit
+        );
+        require!(
+            splits.iter().all(|split| split.weight_bps > 0),
+            ErrorCode::InvalidTreasurySplit
+        );
+
+        let global_config = &mut ctx.accounts.global_config;
+        global_config.treasury_splits = [TreasurySplit::default(); GlobalConfig::MAX_TREASURY_SPLITS];
+        for (slot, split) in global_config.treasury_splits.iter_mut().zip(splits.iter()) {
+            *slot = *split;
+        }
+        global_config.validate()?;
+
+        emit!(TreasurySplitUpdated {
+            authority: ctx.accounts.authority.key(),
+            splits,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close the current points epoch so its totals can be snapshotted for an airdrop (admin only)
+    /// Each wallet's points for the closed epoch are preserved in `UserPoints::snapshot_points`
+    pub fn close_points_epoch(
+        ctx: Context<UpdateGlobalConfig>,
+    ) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        let closed_epoch = global_config.points_epoch;
+        global_config.points_epoch = closed_epoch.safe_add(1)?;
+
+        emit!(PointsEpochClosed {
+            epoch: closed_epoch,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Commit the Merkle root of `mint`'s holder balances at `slot` as the next snapshot epoch
+    /// (admin only). Leaves and proofs follow `snapshot::leaf` and `snapshot::verify`
+    pub fn create_snapshot(
+        ctx: Context<CreateSnapshot>,
+        merkle_root: [u8; 32],
+        slot: u64,
+        holder_count: u64,
+        total_balance: u64,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.snapshot_registry;
+        require!(merkle_root != [0; 32], ErrorCode::InvalidSnapshot);
+        require!(
+            slot <= Clock::get()?.slot && (registry.next_epoch == 0 || slot > registry.latest_slot),
+            ErrorCode::InvalidSnapshot
+        );
+
+        let epoch = registry.next_epoch;
+        registry.mint = ctx.accounts.mint.key();
+        registry.next_epoch = epoch.safe_add(1)?;
+        registry.latest_slot = slot;
+        registry.bump = ctx.bumps.snapshot_registry;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let snapshot = &mut ctx.accounts.holder_snapshot;
+        snapshot.mint = registry.mint;
+        snapshot.epoch = epoch;
+        snapshot.merkle_root = merkle_root;
+        snapshot.slot = slot;
+        snapshot.holder_count = holder_count;
+        snapshot.total_balance = total_balance;
+        snapshot.created_at = current_time;
+        snapshot.bump = ctx.bumps.holder_snapshot;
+
+        emit!(SnapshotCreated {
+            mint: snapshot.mint,
+            epoch,
+            merkle_root,
+            slot,
+            holder_count,
+            total_balance,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Nominate a new platform authority (admin only)
+    /// The nominee takes over once it calls `accept_authority`, so a PDA such as
+    /// a multisig vault can be installed without ever holding a private key
+    pub fn propose_authority(
+        ctx: Context<UpdateGlobalConfig>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        global_config.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            authority: ctx.accounts.authority.key(),
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending authority nomination (nominee only)
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>,
+    ) -> Result<()> {
+        let global_config = &mut ctx.accounts.global_config;
+        require!(
+            global_config.pending_authority != Pubkey::default(),
+            ErrorCode::NoPendingAuthority
+        );
+        require!(
+            ctx.accounts.new_authority.key() == global_config.pending_authority,
+            ErrorCode::Unauthorized
+        );
+
+        let old_authority = global_config.authority;
+        global_config.authority = global_config.pending_authority;
+        global_config.pending_authority = Pubkey::default();
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: global_config.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Simulate proposed global configuration values without applying them (admin only)
+    /// Returns example quotes via return data so parameter changes can be sanity-checked
+    pub fn simulate_global_config(
+        ctx: Context<SimulateGlobalConfig>,
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+        initial_token_supply: u64,
+        buy_fee_bps: u16,
+        migration_threshold_sol: u64,
+    ) -> Result<ConfigSimulation> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(virtual_sol_reserves > 0, ErrorCode::InvalidAmount);
+        require!(virtual_token_reserves > 0, ErrorCode::InvalidAmount);
+
+        // Same constant product the curve is initialized with
+        let total_sol_start = virtual_sol_reserves as u128;
+        let total_token_start = (virtual_token_reserves as u128)
+            .safe_add(initial_token_supply as u128)?;
+        let k = total_sol_start.safe_mul(total_token_start)?;
+
+        let half_threshold = (migration_threshold_sol as u128).safe_div(2)?;
+        let price_at_start = curve::price_at_sol(k, total_sol_start)?;
+        let price_at_half = curve::price_at_sol(k, total_sol_start.safe_add(half_threshold)?)?;
+        let price_at_graduation = curve::price_at_sol(
+            k,
+            total_sol_start.safe_add(migration_threshold_sol as u128)?,
+        )?;
+
+        // Market cap in lamports = price per whole token * whole tokens in supply
+        let graduation_market_cap = (price_at_graduation as u128)
+            .safe_mul(initial_token_supply as u128)?
+            .safe_div(TOKEN_DECIMALS_FACTOR)?
+            .to_u64()?;
+
+        let fee_on_one_sol = 1_000_000_000u128 // 1 SOL in lamports
+            .safe_mul(buy_fee_bps as u128)?
+            .safe_div(10_000)?
+            .to_u64()?;
+
+        let simulation = ConfigSimulation {
+            price_at_start,
+            price_at_half,
+            price_at_graduation,
+            graduation_market_cap,
+            fee_on_one_sol,
+        };
+
+        msg!("Price at 0%: {} lamports/token", simulation.price_at_start);
+        msg!("Price at 50%: {} lamports/token", simulation.price_at_half);
+        msg!("Price at 100%: {} lamports/token", simulation.price_at_graduation);
+        msg!("Graduation market cap: {} lamports", simulation.graduation_market_cap);
+        msg!("Fee on 1 SOL trade: {} lamports", simulation.fee_on_one_sol);
+
+        Ok(simulation)
+    }
+
+    /// Close the global configuration and recover rent (admin only)
+    /// The account is read without full deserialization so configs written with an
+    /// older layout can still be closed by their authority. A timelocked config must
+    /// first lower `config_update_delay` to zero through the timelock
+    pub fn close_global_config(
+        ctx: Context<CloseGlobalConfig>,
+    ) -> Result<()> {
+        let config_info = ctx.accounts.global_config.to_account_info();
+        let config_authority = read_global_config_authority(&config_info)?;
+        require!(
+            config_authority == ctx.accounts.authority.key(),
+            ErrorCode::Unauthorized
+        );
+        // Closing and re-initializing a timelocked config would skip the delay
+        require!(
+            read_global_config_update_delay(&config_info)? == 0,
+            ErrorCode::TimelockRequired
+        );
+
+        // Transfer all lamports from global_config to authority
+        let config_lamports = drain_lamports(&config_info, &ctx.accounts.authority.to_account_info())?;
+
+        // Hand the account back to the system program so it is fully closed
+        config_info.assign(&system_program::ID);
+        config_info.resize(0)?;
+
+        emit!(GlobalConfigClosed {
+            authority: config_authority,
+            lamports_recovered: config_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Upgrade an existing global configuration to the current version in place (admin only)
+    /// New fields are always appended to `GlobalConfig`, so existing data stays valid:
+    /// the account is reallocated, the new tail is zero-filled, and any fields whose
+    /// default isn't zero are populated by `GlobalConfig::apply_upgrade_defaults`
+    pub fn upgrade_global_config(
+        ctx: Context<UpgradeGlobalConfig>,
+    ) -> Result<()> {
+        let config_info = ctx.accounts.global_config.to_account_info();
+        let config_authority = read_global_config_authority(&config_info)?;
+        require!(
+            config_authority == ctx.accounts.authority.key(),
+            ErrorCode::Unauthorized
+        );
+
+        let old_len = config_info.data_len();
+        let new_len = GlobalConfig::MAX_SIZE;
+        require!(old_len <= new_len, ErrorCode::InvalidGlobalConfig);
+
+        // Top up rent for the larger account
+        let required_lamports = Rent::get()?.minimum_balance(new_len);
+        let current_lamports = config_info.lamports();
+        if required_lamports > current_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: config_info.clone(),
+                    },
+                ),
+                required_lamports.safe_sub(current_lamports)?,
+            )?;
+        }
+
+        if old_len < new_len {
+            config_info.resize(new_len)?;
+            let mut data = config_info.try_borrow_mut_data()?;
+            data[old_len..].fill(0);
+        }
+
+        // Configs written before versioning existed read back as version 0
+        let mut global_config = {
+            let data = config_info.try_borrow_data()?;
+            GlobalConfig::try_deserialize(&mut &data[..])?
+        };
+        let old_version = global_config.version;
+        require!(
+            old_version < GlobalConfig::CURRENT_VERSION,
+            ErrorCode::ConfigAlreadyCurrent
+        );
+
+        global_config.apply_upgrade_defaults(old_version);
+        global_config.version = GlobalConfig::CURRENT_VERSION;
+
+        {
+            let mut data = config_info.try_borrow_mut_data()?;
+            global_config.try_serialize(&mut &mut data[..])?;
+        }
+
+        emit!(GlobalConfigUpgraded {
+            authority: config_authority,
+            old_version,
+            new_version: global_config.version,
+            old_len: old_len as u32,
+            new_len: new_len as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Global config upgraded from v{} to v{}", old_version, global_config.version);
+
+        Ok(())
+    }
+
+    /// Original name of `upgrade_global_config`, kept so existing clients keep working
+    pub fn migrate_global_config(
+        ctx: Context<UpgradeGlobalConfig>,
+    ) -> Result<()> {
+        upgrade_global_config(ctx)
+    }
+
+    /// Rewrite a bonding curve created by an earlier program version in the current layout
+    /// (permissionless). The old layout is recognised by the account's size; the payer covers
+    /// the extra rent
+    pub fn upgrade_bonding_curve(
+        ctx: Context<UpgradeBondingCurve>,
+    ) -> Result<()> {
+        let curve_info = ctx.accounts.bonding_curve.to_account_info();
+        let old_len = curve_info.data_len();
+        let new_len = BondingCurve::MAX_SIZE;
+        require!(old_len != new_len, ErrorCode::CurveAlreadyCurrent);
+
+        let curve = {
+            let data = curve_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && &data[..8] == BondingCurve::DISCRIMINATOR,
+                ErrorCode::UnknownCurveLayout
+            );
+            legacy_curve::upgrade(&data)?
+        };
+        require_keys_eq!(curve.mint, ctx.accounts.mint.key(), ErrorCode::InvalidMint);
+
+        // Top up rent for the larger account
+        let required_lamports = Rent::get()?.minimum_balance(new_len);
+        let current_lamports = curve_info.lamports();
+        if required_lamports > current_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: curve_info.clone(),
+                    },
+                ),
+                required_lamports.safe_sub(current_lamports)?,
+            )?;
+        }
+
+        curve_info.resize(new_len)?;
+        {
+            let mut data = curve_info.try_borrow_mut_data()?;
+            data[8..].copy_from_slice(bytemuck::bytes_of(&curve));
+        }
+
+        emit!(BondingCurveUpgraded {
+            mint: curve.mint,
+            old_len: old_len as u32,
+            new_len: new_len as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the platform-wide statistics account (authority only, once)
+    pub fn initialize_global_stats(
+        ctx: Context<InitializeGlobalStats>,
+    ) -> Result<()> {
+        let global_stats = &mut ctx.accounts.global_stats;
+        global_stats.bump = ctx.bumps.global_stats;
+        global_stats.initialized_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    /// Set reputation flags (rug / abandoned) on a creator (admin only)
+    pub fn set_creator_flags(
+        ctx: Context<SetCreatorFlags>,
+        flags: u8,
+    ) -> Result<()> {
+        require!(flags & !CreatorStats::ALL_FLAGS == 0, ErrorCode::InvalidCreatorFlags);
+
+        let creator_stats = &mut ctx.accounts.creator_stats;
+        let old_flags = creator_stats.flags;
+        creator_stats.flags = flags;
+
+        emit!(CreatorFlagsUpdated {
+            creator: creator_stats.creator,
+            old_flags,
+            new_flags: flags,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set platform compliance restrictions on a launched project (admin only), enforced on
+    /// its curve's buys through the mint's `ComplianceGate`. `max_buy_lamports` is the cap for
+    /// `ComplianceGate::FLAG_MAX_BUY`; flags of 0 lift every restriction
+    pub fn set_compliance_flags(
+        ctx: Context<SetComplianceFlags>,
+        flags: u16,
+        max_buy_lamports: u64,
+    ) -> Result<()> {
+        require!(flags & !ComplianceGate::ALL_FLAGS == 0, ErrorCode::InvalidComplianceFlags);
+        require!(
+            flags & ComplianceGate::FLAG_MAX_BUY == 0 || max_buy_lamports > 0,
+            ErrorCode::InvalidComplianceFlags
+        );
+        require!(
+            flags & ComplianceGate::FLAG_ATTESTATION == 0
+                || ctx.accounts.global_config.kyc_verifier != Pubkey::default(),
+            ErrorCode::KycVerifierNotSet
+        );
+
+        let project = &mut ctx.accounts.project_state;
+        let old_flags = project.compliance_flags;
+        project.compliance_flags = flags;
+
+        let gate = &mut ctx.accounts.compliance_gate;
+        gate.mint = project.mint;
+        gate.project = project.key();
+        gate.flags = flags;
+        gate.max_buy_lamports = max_buy_lamports;
+        gate.bump = ctx.bumps.compliance_gate;
+
+        ctx.accounts.bonding_curve.load_mut()?.compliance_gated = (flags != 0) as u8;
+
+        emit!(ComplianceFlagsUpdated {
+            project: gate.project,
+            mint: gate.mint,
+            old_flags,
+            new_flags: flags,
+            max_buy_lamports,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Divert `fee_share_bps` of the curve's buy fees into a jackpot drawn every
+    /// `buys_per_draw` buys among the recent buyers, settled with verifiable randomness so
+    /// no buyer can time their buy to win (admin only, can't be undone). Needs the randomness
+    /// source configured, and isn't available on wSOL curves. Every route that buys from the
+    /// curve must then pass its jackpot
+    pub fn enable_jackpot(
+        ctx: Context<EnableJackpot>,
+        fee_share_bps: u16,
+        buys_per_draw: u64,
+    ) -> Result<()> {
+        require!(
+            fee_share_bps > 0
+                && fee_share_bps <= Jackpot::MAX_FEE_SHARE_BPS
+                && buys_per_draw >= Jackpot::MIN_BUYS_PER_DRAW,
+            ErrorCode::InvalidJackpotConfig
+        );
+        require!(
+            ctx.accounts.global_config.randomness_queue != Pubkey::default(),
+            ErrorCode::RandomnessSourceNotSet
+        );
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            !bonding_curve.has_jackpot() && !bonding_curve.has_wsol_vault() && !bonding_curve.is_migrated(),
+            ErrorCode::JackpotUnavailable
+        );
+        bonding_curve.jackpot = 1;
+
+        let jackpot = &mut ctx.accounts.jackpot;
+        jackpot.mint = ctx.accounts.mint.key();
+        jackpot.fee_share_bps = fee_share_bps;
+        jackpot.buys_per_draw = buys_per_draw;
+        jackpot.buy_count = 0;
+        jackpot.total_contributed = 0;
+        jackpot.total_paid = 0;
+        jackpot.draws = 0;
+        jackpot.last_winner = Pubkey::default();
+        jackpot.last_payout = 0;
+        jackpot.last_draw_at = 0;
+        jackpot.draw_pending = false;
+        jackpot.recent_buyers = [Pubkey::default(); Jackpot::RECENT_BUYERS];
+        jackpot.recent_cursor = 0;
+        jackpot.pending_winner = Pubkey::default();
+        jackpot.pending_payout = 0;
+        jackpot.bump = ctx.bumps.jackpot;
+
+        emit!(JackpotEnabled {
+            mint: jackpot.mint,
+            fee_share_bps,
+            buys_per_draw,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay a jackpot draw's winner (permissionless)
+    pub fn claim_jackpot(
+        ctx: Context<ClaimJackpot>,
+    ) -> Result<()> {
+        let jackpot = &mut ctx.accounts.jackpot;
+        let amount = jackpot.pending_payout;
+        require!(amount > 0, ErrorCode::NoJackpotToClaim);
+
+        move_lamports(&jackpot.to_account_info(), &ctx.accounts.winner, amount)?;
+        jackpot.pending_winner = Pubkey::default();
+        jackpot.pending_payout = 0;
+
+        emit!(JackpotClaimed {
+            mint: jackpot.mint,
+            winner: ctx.accounts.winner.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Open a draw for `consumer`, bound to a Switchboard randomness account committed in the
+    /// previous slot so its value is still unknown (permissionless). The account must come
+    /// from the configured randomness queue and authority. The consumer must be waiting on a
+    /// draw; a request left unfulfilled for `RandomnessRequest::EXPIRY_SLOTS` can be replaced,
+    /// unless its value was revealed, as it must then be fulfilled with that value
+    pub fn request_randomness(
+        ctx: Context<RequestRandomness>,
+        kind: RandomnessConsumerKind,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let request = &mut ctx.accounts.randomness_request;
+        require!(!request.blocks_new_request(clock.slot)?, ErrorCode::RandomnessRequestPending);
+        if request.is_pending() {
+            // Replacing a request whose value is out would let the requester redraw until
+            // the winner suits them
+            let previous = ctx.accounts.previous_randomness_account.as_ref()
+                .ok_or(ErrorCode::RandomnessRequestPending)?;
+            let previous = SwitchboardRandomness::load(previous)?;
+            require!(
+                previous.seed_slot != request.seed_slot || previous.revealed_value().is_none(),
+                ErrorCode::RandomnessRequestPending
+            );
+        }
+        let consumer = &ctx.accounts.consumer;
+        let awaiting = match kind {
+            RandomnessConsumerKind::Jackpot => consumer_awaiting_randomness::<Jackpot>(consumer)?,
+        };
+        require!(awaiting, ErrorCode::RandomnessNotNeeded);
+        let randomness = SwitchboardRandomness::load(&ctx.accounts.randomness_account)?;
+        let global_config = &ctx.accounts.global_config;
+        require!(
+            randomness.is_from(&global_config.randomness_queue, &global_config.randomness_authority),
+            ErrorCode::InvalidRandomnessAccount
+        );
+        require!(randomness.is_fresh_commit(clock.slot), ErrorCode::RandomnessAlreadyRevealed);
+
+        request.consumer = consumer.key();
+        request.kind = kind;
+        request.randomness_account = ctx.accounts.randomness_account.key();
+        request.seed_slot = randomness.seed_slot;
+        request.requested_slot = clock.slot;
+        request.requested_at = clock.unix_timestamp;
+        request.fulfilled_at = 0;
+        request.value = [0; 32];
+        request.bump = ctx.bumps.randomness_request;
+
+        emit!(RandomnessRequested {
+            consumer: request.consumer,
+            kind,
+            randomness_account: request.randomness_account,
+            seed_slot: request.seed_slot,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Read the value revealed for an open request and hand it to the consumer (permissionless)
+    pub fn fulfill_randomness(
+        ctx: Context<FulfillRandomness>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let request = &mut ctx.accounts.randomness_request;
+        require!(request.is_pending(), ErrorCode::NoRandomnessRequest);
+        let randomness = SwitchboardRandomness::load(&ctx.accounts.randomness_account)?;
+        require!(randomness.seed_slot == request.seed_slot, ErrorCode::InvalidRandomnessAccount);
+        let value = randomness
+            .revealed_value()
+            .ok_or(ErrorCode::RandomnessNotRevealed)?;
+        request.value = value;
+        request.fulfilled_at = clock.unix_timestamp;
+
+        let consumer = &ctx.accounts.consumer;
+        match request.kind {
+            RandomnessConsumerKind::Jackpot => {
+                deliver_randomness::<Jackpot>(consumer, &value, clock.unix_timestamp)?
+            }
+        }
+
+        emit!(RandomnessFulfilled {
+            consumer: request.consumer,
+            kind: request.kind,
+            value,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Flag a curve as compromised or fraudulent (admin only)
+    /// Buys are rejected while flagged, but sells stay open so holders can exit;
+    /// a `reason` of 0 clears the flag
+    pub fn flag_curve(
+        ctx: Context<FlagCurve>,
+        reason: u8,
+    ) -> Result<()> {
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        let old_reason = bonding_curve.flag_reason;
+        let timestamp = Clock::get()?.unix_timestamp;
+        bonding_curve.flag_reason = reason;
+        if reason != 0 {
+            bonding_curve.flagged_at = timestamp;
+        }
+
+        emit!(CurveFlagged {
+            mint: bonding_curve.mint,
+            old_reason,
+            reason,
+            authority: ctx.accounts.authority.key(),
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Override the buy and/or sell fee for a single curve (admin only)
+    /// `None` falls back to the global fees for that side. Fees are economic parameters, so
+    /// overrides are unavailable once a config timelock is set
+    pub fn set_curve_fees(
+        ctx: Context<SetCurveFees>,
+        buy_fee_bps: Option<u16>,
+        sell_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        require!(ctx.accounts.global_config.config_update_delay == 0, ErrorCode::TimelockRequired);
+        for fee in [buy_fee_bps, sell_fee_bps].into_iter().flatten() {
+            require!(fee <= GlobalConfig::MAX_FEE_BASIS_POINTS, ErrorCode::FeeTooHigh);
+        }
+
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        bonding_curve.buy_fee_bps_override = buy_fee_bps.unwrap_or(BondingCurve::NO_FEE_OVERRIDE);
+        bonding_curve.sell_fee_bps_override = sell_fee_bps.unwrap_or(BondingCurve::NO_FEE_OVERRIDE);
+
+        emit!(CurveFeesUpdated {
+            mint: bonding_curve.mint,
+            buy_fee_bps,
+            sell_fee_bps,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Initialize a bonding curve for a token
+    /// An optional `initial_buy_sol` executes a creator "dev buy" atomically, before
+    /// anyone else can trade
+    /// An optional `trading_start_time` keeps the curve closed to trading until an
+    /// announced time (at most `MAX_TRADING_START_DELAY` ahead)
+    pub fn initialize_bonding_curve(
+        ctx: Context<InitializeBondingCurve>,
+        token_supply: u64,
+        initial_buy_sol: Option<u64>,
+        trading_start_time: Option<i64>,
+    ) -> Result<()> {
+        let global_config = &ctx.accounts.global_config;
+        let created_at = Clock::get()?.unix_timestamp;
+
+        // Every curve starts from the same full supply, so its price tracks the virtual reserves
+        require!(
+            token_supply == global_config.initial_token_supply
+                && ctx.accounts.mint.supply == token_supply,
+            ErrorCode::NonstandardTokenSupply
+        );
+        require!(
+            ctx.accounts.mint.decimals == global_config.token_decimals,
+            ErrorCode::NonstandardTokenDecimals
+        );
+
+        // Built locally and stored once at the end, after the CPIs the curve signs for
+        let mut bonding_curve = BondingCurve {
+            mint: ctx.accounts.mint.key(),
+            creator: ctx.accounts.creator.key(),
+            raydium_pool: Pubkey::default(),
+            virtual_sol_reserves: global_config.virtual_sol_reserves,
+            virtual_token_reserves: global_config.virtual_token_reserves,
+            real_sol_reserves: 0,
+            real_token_reserves: token_supply,
+            created_at,
+            graduated_at: 0,
+            unique_buyers: 0,
+            total_fees_collected: 0,
+            graduation_sol_reserves: 0,
+            graduation_token_reserves: 0,
+            accumulated_fees: 0,
+            trade_count: 0,
+            holder_count: 0,
+            trending_score: 0,
+            trending_updated_at: created_at,
+            dev_buy_sol: 0,
+            dev_buy_tokens: 0,
+            flagged_at: 0,
+            launch_deposit: global_config.launch_deposit_lamports,
+            trading_start_time: 0,
+            migration_sol_withdrawn: 0,
+            migration_tokens_withdrawn: 0,
+            vault_rent_reserve: Rent::get()?.minimum_balance(0),
+            max_sol_per_wallet: 0,
+            creator_royalties: 0,
+            creator_allocation: 0,
+            allocation_release_delay: 0,
+            lbp_start_time: 0,
+            lbp_duration: 0,
+            reserved_u64: [0; 2],
+            buy_fee_bps_override: BondingCurve::NO_FEE_OVERRIDE,
+            sell_fee_bps_override: BondingCurve::NO_FEE_OVERRIDE,
+            creator_royalty_bps: 0,
+            reflection_bps: 0,
+            lbp_weights_bps: [0; 4],
+            reserved_u16: [0; 2],
+            complete: 0,
+            migrated: 0,
+            bump: ctx.bumps.bonding_curve,
+            flag_reason: 0,
+            migration_status: MigrationStatus::NotStarted as u8,
+            wsol_vault: 0,
+            kyc_required: 0,
+            compliance_gated: 0,
+            inline_sol: 0,
+            launch_finalized: 0,
+            jackpot: 0,
+            padding: [0; 1],
+        };
+        bonding_curve.trading_start_time = match trading_start_time {
+            Some(start) => {
+                require!(
+                    start >= created_at
+                        && start <= created_at
+                            .safe_add(BondingCurve::MAX_TRADING_START_DELAY)?,
+                    ErrorCode::InvalidTradingStartTime
+                );
+                start
+            }
+            None => 0,
+        };
+
+        let global_stats = &mut ctx.accounts.global_stats;
+        global_stats.total_curves_created = global_stats.total_curves_created.safe_add(1)?;
+        global_stats.active_curves = global_stats.active_curves.safe_add(1)?;
+
+        let creator_stats = &mut ctx.accounts.creator_stats;
+        creator_stats.ensure_initialized(ctx.accounts.creator.key(), ctx.bumps.creator_stats);
+        creator_stats.record_launch(
+            bonding_curve.created_at,
+            global_config.max_launches_per_window,
+            global_config.launch_window_seconds,
+        )?;
+
+        let initial_price = curve_spot_price(&bonding_curve)?;
+        ctx.accounts.curve_oracle.initialize(
+            ctx.accounts.mint.key(),
+            ctx.bumps.curve_oracle,
+            bonding_curve.created_at,
+            initial_price,
+        );
+
+        // Move the full token supply from the creator's account into the bonding curve ATA
+        // This replicates pump.fun behavior where all tokens are sold from the curve
+        let cpi_accounts = Transfer {
+            from: ctx
+                .accounts
+                .creator_token_account
+                .to_account_info(),
+            to: ctx
+                .accounts
+                .bonding_curve_token_account
+                .to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        transfer(cpi_ctx, token_supply)?;
+
+        // Anti-spam: a launch fee to the treasury plus a deposit refunded at graduation
+        let launch_fee = ctx.accounts.global_config.launch_fee_lamports_at(
+            ctx.accounts.sol_usd_price.as_deref(),
+            Clock::get()?.unix_timestamp,
+        )?;
+        if launch_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                launch_fee,
+            )?;
+        }
+        let launch_deposit = bonding_curve.launch_deposit;
+        if launch_deposit > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.bonding_curve.to_account_info(),
+                    },
+                ),
+                launch_deposit,
+            )?;
+        }
+
+        if let Some(sol_amount) = initial_buy_sol.filter(|amount| *amount > 0) {
+            execute_dev_buy(ctx.accounts, &mut bonding_curve, sol_amount)?;
+        }
+
+        *ctx.accounts.bonding_curve.load_init()? = bonding_curve;
+
+        Ok(())
+    }
+
+    /// Open a launched curve to buys once it can't dilute buyers (permissionless): the curve's
+    /// token account must hold its reserves, which with the dev buy and any escrowed creator
+    /// allocation make up the whole minted supply, the mint authority must be revoked or held by the curve, and the freeze
+    /// authority cleared. The result is cached on the curve
+    pub fn finalize_launch(
+        ctx: Context<FinalizeLaunch>,
+    ) -> Result<()> {
+        let mint = &ctx.accounts.mint;
+        let curve_key = ctx.accounts.bonding_curve.key();
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(!bonding_curve.is_launch_finalized(), ErrorCode::LaunchAlreadyFinalized);
+        // Every token is in the curve's reserves, with the dev buyer, or in the creator
+        // allocation escrow. Tokens a buyback burned before finalizing left the reserves
+        // and the supply together, so they drop out of both sides
+        let escrowed = if bonding_curve.creator_allocation > 0 {
+            ctx.accounts
+                .creator_allocation_escrow
+                .as_ref()
+                .ok_or(ErrorCode::CreatorAllocationEscrowRequired)?
+                .amount
+        } else {
+            0
+        };
+        require!(
+            ctx.accounts.bonding_curve_token_account.amount >= bonding_curve.real_token_reserves
+                && escrowed >= bonding_curve.creator_allocation
+                && bonding_curve
+                    .real_token_reserves
+                    .safe_add(bonding_curve.dev_buy_tokens)?
+                    .safe_add(bonding_curve.creator_allocation)?
+                    == mint.supply,
+            ErrorCode::LaunchSupplyNotDeposited
+        );
+        let mint_authority_revoked = match mint.mint_authority {
+            COption::None => true,
+            COption::Some(authority) => authority == curve_key,
+        };
+        require!(mint_authority_revoked, ErrorCode::MintAuthorityNotRevoked);
+        require!(mint.freeze_authority.is_none(), ErrorCode::FreezeAuthorityNotRevoked);
+        bonding_curve.launch_finalized = 1;
+        let current_time = Clock::get()?.unix_timestamp;
+        if bonding_curve.has_liquidity_bootstrapping() {
+            // The window opens with trading
+            bonding_curve.lbp_start_time = current_time.max(bonding_curve.trading_start_time);
+        }
+
+        emit!(LaunchFinalized {
+            mint: bonding_curve.mint,
+            supply: mint.supply,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Create the price oracle for a curve launched before oracles existed (permissionless)
+    pub fn initialize_curve_oracle(
+        ctx: Context<InitializeCurveOracle>,
+    ) -> Result<()> {
+        let price = curve_spot_price(&*ctx.accounts.bonding_curve.load()?)?;
+        ctx.accounts.curve_oracle.initialize(
+            ctx.accounts.mint.key(),
+            ctx.bumps.curve_oracle,
+            Clock::get()?.unix_timestamp,
+            price,
+        );
+
+        Ok(())
+    }
+
+    /// Time-weighted average price (lamports per whole token) over at least the last
+    /// `window_seconds`, returned via return data for other programs to consume
+    pub fn get_twap(
+        ctx: Context<GetTwap>,
+        window_seconds: i64,
+    ) -> Result<u64> {
+        let current_time = Clock::get()?.unix_timestamp;
+        ctx.accounts.curve_oracle.twap(current_time, window_seconds)
+    }
+
+    /// Snapshot of a curve's reserves, price, fees and graduation progress, returned via
+    /// return data so frontends can render a curve from one simulated call
+    pub fn get_curve_state(
+        ctx: Context<GetCurveState>,
+    ) -> Result<CurveState> {
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        let global_config = &ctx.accounts.global_config;
+
+        Ok(CurveState {
+            mint: bonding_curve.mint,
+            creator: bonding_curve.creator,
+            virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: bonding_curve.virtual_token_reserves,
+            real_sol_reserves: bonding_curve.real_sol_reserves,
+            real_token_reserves: bonding_curve.real_token_reserves,
+            price_lamports_per_token: curve_spot_price(&bonding_curve)?,
+            lbp_weight_bps: bonding_curve.lbp_weight_bps(Clock::get()?.unix_timestamp)?,
+            migration_threshold_sol: global_config.migration_threshold_sol,
+            graduation_progress_bps: global_config.graduation_progress_bps(&bonding_curve)?,
+            buy_fee_bps: global_config.buy_fee_bps_for(&bonding_curve)?,
+            sell_fee_bps: global_config.sell_fee_bps_for(&bonding_curve),
+            trade_count: bonding_curve.trade_count,
+            unique_buyers: bonding_curve.unique_buyers,
+            holder_count: bonding_curve.holder_count,
+            created_at: bonding_curve.created_at,
+            trading_start_time: bonding_curve.trading_start_time,
+            complete: bonding_curve.is_complete(),
+            migrated: bonding_curve.is_migrated(),
+            migration_status: bonding_curve.migration_stage(),
+        })
+    }
+
+    /// A curve's price, market cap and raised SOL in micro-dollars at the SOL/USD oracle
+    /// price, returned via return data for dashboards and analytics
+    pub fn get_curve_usd_value(
+        ctx: Context<GetCurveUsdValue>,
+    ) -> Result<CurveUsdValue> {
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        let now = Clock::get()?.unix_timestamp;
+        let sol_usd = oracle::sol_usd(&ctx.accounts.sol_usd_price, &ctx.accounts.global_config, now)?;
+        let price_lamports = curve_spot_price(&bonding_curve)?;
+        let market_cap_lamports = mul_div(
+            price_lamports as u128,
+            ctx.accounts.mint.supply as u128,
+            TOKEN_DECIMALS_FACTOR,
+            Rounding::Down,
+        )?
+        .to_u64()?;
+
+        Ok(CurveUsdValue {
+            mint: bonding_curve.mint,
+            sol_usd,
+            price_per_token: oracle::usd_for_lamports(price_lamports, sol_usd)?,
+            market_cap: oracle::usd_for_lamports(market_cap_lamports, sol_usd)?,
+            real_sol_reserves: oracle::usd_for_lamports(bonding_curve.real_sol_reserves, sol_usd)?,
+        })
+    }
+
+    /// Persist a finished OHLCV bucket into its own `Candle` account (permissionless crank)
+    /// Finalizes either the last completed bucket or a current bucket whose time has run out
+    pub fn finalize_candle(
+        ctx: Context<FinalizeCandle>,
+        bucket_start: i64,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let live_candle = &mut ctx.accounts.live_candle;
+        let data = live_candle.take_finished(bucket_start, current_time)?;
+
+        let candle = &mut ctx.accounts.candle;
+        candle.mint = live_candle.mint;
+        candle.data = data;
+        candle.finalized_at = current_time;
+        candle.bump = ctx.bumps.candle;
+
+        emit!(CandleFinalized {
+            mint: candle.mint,
+            bucket_start,
+            open: data.open,
+            high: data.high,
+            low: data.low,
+            close: data.close,
+            volume_sol: data.volume_sol,
+            volume_tokens: data.volume_tokens,
+            trade_count: data.trade_count,
+        });
+
+        Ok(())
+    }
+
+    /// Crown `bonding_curve` king of the hill if its decayed trending score beats the
+    /// current king's (permissionless crank)
+    pub fn refresh_king_of_the_hill(
+        ctx: Context<RefreshKingOfTheHill>,
+    ) -> Result<()> {
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        require!(!bonding_curve.is_migrated(), ErrorCode::AlreadyMigrated);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let candidate_score = bonding_curve.trending_score_at(current_time)?;
+
+        let king = &mut ctx.accounts.king_of_the_hill;
+        king.bump = ctx.bumps.king_of_the_hill;
+
+        if king.mint == bonding_curve.mint {
+            // Refresh the reigning king's score
+            king.score = candidate_score;
+            king.updated_at = current_time;
+            return Ok(());
+        }
+
+        let king_score = king.score_at(current_time)?;
+        require!(candidate_score > king_score, ErrorCode::ScoreTooLow);
+
+        let previous_mint = king.mint;
+        king.mint = bonding_curve.mint;
+        king.score = candidate_score;
+        king.crowned_at = current_time;
+        king.updated_at = current_time;
+
+        emit!(KingOfTheHillCrowned {
+            mint: king.mint,
+            previous_mint,
+            score: candidate_score,
+            previous_score: king_score,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Create a trading competition funded with `prize_pool` lamports (admin only)
+    /// `mint` scopes the competition to one curve; `Pubkey::default()` makes it platform-wide
+    pub fn create_competition(
+        ctx: Context<CreateCompetition>,
+        competition_id: u64,
+        mint: Pubkey,
+        start_time: i64,
+        end_time: i64,
+        prize_pool: u64,
+    ) -> Result<()> {
+        require!(end_time > start_time, ErrorCode::InvalidCompetitionWindow);
+        require!(prize_pool > 0, ErrorCode::InvalidAmount);
+
+        // Prize pool is held by the competition account itself
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.competition.to_account_info(),
+                },
+            ),
+            prize_pool,
+        )?;
+
+        let competition = &mut ctx.accounts.competition;
+        competition.id = competition_id;
+        competition.authority = ctx.accounts.authority.key();
+        competition.mint = mint;
+        competition.start_time = start_time;
+        competition.end_time = end_time;
+        competition.prize_pool = prize_pool;
+        competition.bump = ctx.bumps.competition;
+
+        emit!(CompetitionCreated {
+            competition: competition.key(),
+            id: competition_id,
+            mint,
+            start_time,
+            end_time,
+            prize_pool,
+        });
+
+        Ok(())
+    }
+
+    /// Enter a competition; volume traded afterwards counts once the entry is passed to trades
+    pub fn register_for_competition(
+        ctx: Context<RegisterForCompetition>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let competition = &mut ctx.accounts.competition;
+        require!(current_time < competition.end_time, ErrorCode::CompetitionEnded);
+        competition.participant_count = competition.participant_count.safe_add(1)?;
+
+        let entry = &mut ctx.accounts.competition_entry;
+        entry.competition = competition.key();
+        entry.trader = ctx.accounts.trader.key();
+        entry.bump = ctx.bumps.competition_entry;
+
+        Ok(())
+    }
+
+    /// Freeze the leaderboard and split the prize pool among the winners (permissionless)
+    pub fn finalize_competition(
+        ctx: Context<FinalizeCompetition>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let competition = &mut ctx.accounts.competition;
+        require!(current_time >= competition.end_time, ErrorCode::CompetitionNotEnded);
+        require!(!competition.finalized, ErrorCode::CompetitionFinalized);
+
+        competition.compute_prizes()?;
+        competition.finalized = true;
+
+        emit!(CompetitionFinalized {
+            competition: competition.key(),
+            winners: competition.leaders,
+            volumes: competition.leader_volumes,
+            prizes: competition.prizes,
+        });
+
+        Ok(())
+    }
+
+    /// Claim a finalized competition prize
+    pub fn claim_prize(
+        ctx: Context<ClaimPrize>,
+    ) -> Result<()> {
+        let competition = &ctx.accounts.competition;
+        require!(competition.finalized, ErrorCode::CompetitionNotFinalized);
+
+        let entry = &mut ctx.accounts.competition_entry;
+        require!(!entry.claimed, ErrorCode::PrizeAlreadyClaimed);
+
+        let trader = ctx.accounts.trader.key();
+        let rank = competition
+            .leaders
+            .iter()
+            .position(|leader| *leader == trader)
+            .ok_or(ErrorCode::NoPrize)?;
+        let prize = competition.prizes[rank];
+        require!(prize > 0, ErrorCode::NoPrize);
+
+        entry.claimed = true;
+        move_lamports(
+            &ctx.accounts.competition.to_account_info(),
+            &ctx.accounts.trader.to_account_info(),
+            prize,
+        )?;
+
+        emit!(PrizeClaimed {
+            competition: ctx.accounts.competition.key(),
+            trader,
+            rank: rank as u8,
+            prize,
+        });
+
+        Ok(())
+    }
+
+    /// Set up achievement badges for a curve (creator only)
+    /// The first `early_buyer_count` buyers, and buyers of the token once it graduates,
+    /// can each claim one soulbound badge NFT per kind
+    pub fn create_badge_collection(
+        ctx: Context<CreateBadgeCollection>,
+        early_buyer_count: u16,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        require!(
+            early_buyer_count as usize <= BadgeCollection::MAX_HOLDERS,
+            ErrorCode::InvalidBadgeCount
+        );
+        require!(name.len() <= BadgeCollection::MAX_NAME, ErrorCode::StringTooLong);
+        require!(symbol.len() <= BadgeCollection::MAX_SYMBOL, ErrorCode::StringTooLong);
+        require!(uri.len() <= BadgeCollection::MAX_URI, ErrorCode::StringTooLong);
+
+        let collection = &mut ctx.accounts.badge_collection;
+        collection.mint = ctx.accounts.mint.key();
+        collection.creator = ctx.accounts.creator.key();
+        collection.early_buyer_count = early_buyer_count;
+        collection.name = name;
+        collection.symbol = symbol;
+        collection.uri = uri;
+        collection.early_buyer_claims = [0; BadgeCollection::BITMAP_BYTES];
+        collection.graduate_claims = [0; BadgeCollection::BITMAP_BYTES];
+        collection.bump = ctx.bumps.badge_collection;
+
+        Ok(())
+    }
+
+    /// Mint a soulbound badge NFT to an eligible buyer
+    /// The badge's token account is frozen before the master edition takes over the
+    /// freeze authority, so it can never be transferred
+    pub fn claim_badge(
+        ctx: Context<ClaimBadge>,
+        kind: BadgeKind,
+    ) -> Result<()> {
+        let buyer_index = ctx.accounts.buyer_record.buyer_index;
+        match kind {
+            BadgeKind::EarlyBuyer => require!(
+                buyer_index < ctx.accounts.badge_collection.early_buyer_count as u64,
+                ErrorCode::NotEligibleForBadge
+            ),
+            BadgeKind::Graduate => require!(
+                ctx.accounts.bonding_curve.load()?.is_migrated(),
+                ErrorCode::NotEligibleForBadge
+            ),
+        }
+        ctx.accounts.badge_collection.mark_claimed(kind, buyer_index)?;
+
+        let authority_seeds: &[&[u8]] = &[b"badge_authority", &[ctx.bumps.badge_authority]];
+        let signer = &[authority_seeds];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.badge_mint.to_account_info(),
+                    to: ctx.accounts.badge_token_account.to_account_info(),
+                    authority: ctx.accounts.badge_authority.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.badge_token_account.to_account_info(),
+                mint: ctx.accounts.badge_mint.to_account_info(),
+                authority: ctx.accounts.badge_authority.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        let collection = &ctx.accounts.badge_collection;
+        let data_v2 = DataV2 {
+            name: collection.name.clone(),
+            symbol: collection.symbol.clone(),
+            uri: collection.uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint: ctx.accounts.badge_mint.to_account_info(),
+                    mint_authority: ctx.accounts.badge_authority.to_account_info(),
+                    payer: ctx.accounts.buyer.to_account_info(),
+                    update_authority: ctx.accounts.badge_authority.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer,
+            ),
+            data_v2,
+            false,
+            true,
+            None,
+        )?;
+
+        // Max supply 0: no prints, and mint/freeze authority move to the edition
+        create_master_edition_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMasterEditionV3 {
+                    edition: ctx.accounts.master_edition.to_account_info(),
+                    mint: ctx.accounts.badge_mint.to_account_info(),
+                    update_authority: ctx.accounts.badge_authority.to_account_info(),
+                    mint_authority: ctx.accounts.badge_authority.to_account_info(),
+                    payer: ctx.accounts.buyer.to_account_info(),
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer,
+            ),
+            Some(0),
+        )?;
+
+        emit!(BadgeClaimed {
+            mint: ctx.accounts.mint.key(),
+            buyer: ctx.accounts.buyer.key(),
+            badge_mint: ctx.accounts.badge_mint.key(),
+            kind,
+            buyer_index,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Start tracking the caller's position (net tokens, SOL in/out) on a curve
+    /// Once open, pass the position to buy/sell/swap and it is updated on every trade
+    pub fn open_trader_position(
+        ctx: Context<OpenTraderPosition>,
+    ) -> Result<()> {
+        let position = &mut ctx.accounts.trader_position;
+        position.mint = ctx.accounts.mint.key();
+        position.owner = ctx.accounts.owner.key();
+        position.opened_at = Clock::get()?.unix_timestamp;
+        position.bump = ctx.bumps.trader_position;
+
+        Ok(())
+    }
+
+    /// Stop tracking a position and return its rent to the owner
+    pub fn close_trader_position(
+        _ctx: Context<CloseTraderPosition>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Buy tokens from the bonding curve
+    /// `max_price_impact_bps` optionally caps how far the trade may move the spot price
+    /// Pass the buyer's platform token account as the first remaining account for a fee discount
+    /// (the program ID when there's none), followed by the treasury split's recipients in slot
+    /// order when the buy migrates the curve
+    pub fn buy_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
+        sol_amount: u64,
+        min_tokens_out: u64,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        let fee_discount_bps = holder_fee_discount_bps(
+            &ctx.accounts.global_config,
+            &ctx.accounts.buyer.key(),
+            ctx.remaining_accounts.first(),
+        )?;
+        let trade = CurveTrade {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            mint: ctx.accounts.mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            trader_token_account: &mut ctx.accounts.buyer_token_account,
+            curve_oracle: &mut ctx.accounts.curve_oracle,
+            live_candle: &mut ctx.accounts.live_candle,
+            live_candle_bump: ctx.bumps.live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.trade_guard,
+            trade_guard_bump: ctx.bumps.trade_guard,
+            trader: &ctx.accounts.buyer,
+            escrow: None,
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+            trader_wsol_account: None,
+            fee_discount_bps,
+            trader_position: ctx.accounts.trader_position.as_mut(),
+            kyc_attestation: ctx.accounts.kyc_attestation.as_ref(),
+            compliance_gate: ctx.accounts.compliance_gate.as_ref(),
+            reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+            jackpot: ctx.accounts.jackpot.as_mut(),
+            competition: ctx.accounts.competition.as_mut()
+                .zip(ctx.accounts.competition_entry.as_mut()),
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        let events = execute_curve_buy(
+            trade,
+            ctx.accounts.buyer_record.as_mut(),
+            ctx.bumps.buyer_record.unwrap_or_default(),
+            ctx.accounts.payer.key(),
+            sol_amount,
+            min_tokens_out,
+            max_price_impact_bps,
+        )?;
+        let threshold_crossed = events.threshold_reached.is_some();
+        if let Some(threshold_reached) = events.threshold_reached {
+            emit_cpi!(threshold_reached);
+        }
+        emit_cpi!(events.buy);
+
+        // Migrate right away when the buyer brought the accounts, closing the window where a
+        // graduated curve still trades at curve prices. The creator isn't among the buy's
+        // accounts, so the launch deposit is refunded when the curve is closed instead
+        let has_tokens = ctx.accounts.bonding_curve.load()?.real_token_reserves > 0;
+        if threshold_crossed && has_tokens {
+            let treasury_split = ctx.remaining_accounts.get(1..).unwrap_or_default();
+            if let Some(migration) = ctx.accounts.curve_migration(treasury_split) {
+                let migration = migrate_curve(migration)?;
+                emit_cpi!(migration);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Buy with slippage given in basis points from `quoted_price` (lamports per whole
+    /// token) instead of an absolute minimum; the trading fee is accounted for on-chain
+    pub fn buy_tokens_bps<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
+        sol_amount: u64,
+        quoted_price: u64,
+        slippage_bps: u16,
+    ) -> Result<()> {
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let min_tokens_out = min_tokens_at_price(
+            &ctx.accounts.global_config,
+            &bonding_curve,
+            sol_amount,
+            quoted_price,
+            slippage_bps,
+        )?;
+        buy_tokens(ctx, sol_amount, min_tokens_out, None)
+    }
+
+    /// Buy in a transaction sponsored by a relayer: `payer` pays the transaction and rent
+    /// fees and receives `relayer_tip` lamports from the buyer, who only funds the trade
+    pub fn buy_tokens_relayed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
+        sol_amount: u64,
+        min_tokens_out: u64,
+        max_price_impact_bps: Option<u16>,
+        relayer_tip: u64,
+    ) -> Result<()> {
+        let tip = RelayerTip::new(
+            &ctx.accounts.buyer,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            relayer_tip,
+        )?;
+        let mint = ctx.accounts.mint.key();
+        buy_tokens(ctx, sol_amount, min_tokens_out, max_price_impact_bps)?;
+        tip.pay(mint)
+    }
+
+    /// Buy with any token Jupiter can swap to SOL, in one transaction: the route's output is
+    /// received as wSOL, unwrapped, and spent on the curve buy, so a failed leg reverts both
+    /// At least `min_sol_received` lamports must come out of the route
+    ///
+    /// Remaining accounts (in order):
+    /// 0. Jupiter program (must match `global_config.jupiter_program`)
+    /// 1. Buyer's wSOL associated token account, the route's destination. It must exist, and
+    ///    is closed afterwards to unwrap, together with any wSOL it already held
+    /// 2.. The route's accounts, forwarded to Jupiter as given
+    pub fn buy_tokens_via_jupiter<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
+        route_data: Vec<u8>,
+        min_sol_received: u64,
+        min_tokens_out: u64,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        let remaining = ctx.remaining_accounts;
+        require!(remaining.len() >= 2, ErrorCode::InvalidJupiterRoute);
+        let jupiter_program = &remaining[0];
+        let buyer_wsol_account = &remaining[1];
+        let buyer_key = ctx.accounts.buyer.key();
+        require!(
+            ctx.accounts.global_config.jupiter_program != Pubkey::default()
+                && jupiter_program.key() == ctx.accounts.global_config.jupiter_program,
+            ErrorCode::InvalidJupiterRoute
+        );
+        require_keys_eq!(
+            buyer_wsol_account.key(),
+            get_associated_token_address(&buyer_key, &native_mint::ID),
+            ErrorCode::InvalidWsolAccount
+        );
+
+        let wsol_before = Account::<TokenAccount>::try_from(buyer_wsol_account)?.amount;
+        let route_ix = Instruction {
+            program_id: jupiter_program.key(),
+            accounts: remaining[2..]
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: route_data,
+        };
+        let mut account_infos = vec![ctx.accounts.buyer.to_account_info()];
+        account_infos.extend_from_slice(&remaining[1..]);
+        invoke(&route_ix, &account_infos)?;
+
+        let sol_received = Account::<TokenAccount>::try_from(buyer_wsol_account)?
+            .amount
+            .safe_sub(wsol_before)?;
+        require!(
+            sol_received > 0 && sol_received >= min_sol_received,
+            ErrorCode::SlippageExceeded
+        );
+
+        // Unwrap into the buyer's wallet, which the buy then pays from
+        close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: buyer_wsol_account.clone(),
+                destination: ctx.accounts.buyer.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ))?;
+
+        emit_cpi!(JupiterBuyRouted {
+            buyer: buyer_key,
+            mint: ctx.accounts.mint.key(),
+            jupiter_program: jupiter_program.key(),
+            sol_received,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        // The route's accounts must not be read as a holder-discount account
+        ctx.remaining_accounts = &[];
+        buy_tokens(ctx, sol_received, min_tokens_out, max_price_impact_bps)
+    }
+
+    /// Migrate bonding curve liquidity to Raydium when threshold is reached
+    /// This creates a Raydium pool and adds liquidity with all SOL and remaining tokens
+    /// 
+    /// Permissionless: anyone can crank this once the threshold is hit, so graduation
+    /// never waits on the backend. The caller is paid `migration_keeper_reward` out of
+    /// the migration fee for doing so.
+    /// 
+    /// Migration Fee Economics:
+    /// - Collects 6 SOL migration fee (minus keeper reward) to treasury
+    /// - Backend uses treasury funds to pay Raydium pool creation (~0.5 SOL)
+    /// - Net platform revenue: ~5.5 SOL per migration
+    /// - Remaining SOL (threshold - 6) goes into liquidity pool
+    /// 
+    /// Pass the treasury split's recipients as remaining accounts in slot order when one
+    /// is configured
+    pub fn migrate_to_raydium<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateToRaydium<'info>>,
+    ) -> Result<()> {
+        let creator_stats_bump = ctx.bumps.creator_stats;
+        let migration = migrate_curve(CurveMigration {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_deref(),
+            migration_sol_vault: &ctx.accounts.migration_sol_vault,
+            migration_token_account: &ctx.accounts.migration_token_account,
+            creator_stats: &mut ctx.accounts.creator_stats,
+            creator_stats_bump,
+            treasury: &ctx.accounts.treasury,
+            treasury_split: ctx.remaining_accounts,
+            keeper: ctx.accounts.payer.as_ref(),
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            token_program: &ctx.accounts.token_program,
+        })?;
+
+        refund_launch_deposit(&ctx.accounts.bonding_curve, &ctx.accounts.creator)?;
+
+        msg!("Use the create-raydium-pool script to finalize DEX listing.");
+        emit_cpi!(migration);
+
+        Ok(())
+    }
+
+    /// Sell tokens back to the bonding curve
+    /// `max_price_impact_bps` optionally caps how far the trade may move the spot price
+    /// Pass the seller's platform token account as the first remaining account for a fee discount
+    pub fn sell_tokens(
+        ctx: Context<SellTokens>,
+        token_amount: u64,
+        min_sol_out: u64,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        let fee_discount_bps = holder_fee_discount_bps(
+            &ctx.accounts.global_config,
+            &ctx.accounts.seller.key(),
+            ctx.remaining_accounts.first(),
+        )?;
+        let trade = CurveTrade {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            mint: ctx.accounts.mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            trader_token_account: &mut ctx.accounts.seller_token_account,
+            curve_oracle: &mut ctx.accounts.curve_oracle,
+            live_candle: &mut ctx.accounts.live_candle,
+            live_candle_bump: ctx.bumps.live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.trade_guard,
+            trade_guard_bump: ctx.bumps.trade_guard,
+            trader: &ctx.accounts.seller,
+            escrow: None,
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+            trader_wsol_account: ctx.accounts.seller_wsol_account.as_ref(),
+            fee_discount_bps,
+            trader_position: ctx.accounts.trader_position.as_mut(),
+            kyc_attestation: None,
+            compliance_gate: None,
+            reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+            jackpot: None,
+            competition: ctx.accounts.competition.as_mut()
+                .zip(ctx.accounts.competition_entry.as_mut()),
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        let sell_event = execute_curve_sell(
+            trade,
+            ctx.accounts.buyer_record.as_mut(),
+            token_amount,
+            min_sol_out,
+            max_price_impact_bps,
+        )?;
+        emit_cpi!(sell_event);
+
+        Ok(())
+    }
+
+    /// Sell with slippage given in basis points from `quoted_price` (lamports per whole
+    /// token) instead of an absolute minimum; the trading fee is accounted for on-chain
+    pub fn sell_tokens_bps(
+        ctx: Context<SellTokens>,
+        token_amount: u64,
+        quoted_price: u64,
+        slippage_bps: u16,
+    ) -> Result<()> {
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let min_sol_out = min_sol_at_price(
+            &ctx.accounts.global_config,
+            &bonding_curve,
+            token_amount,
+            quoted_price,
+            slippage_bps,
+        )?;
+        sell_tokens(ctx, token_amount, min_sol_out, None)
+    }
+
+    /// Sell the seller's entire token balance (read on-chain) back to the curve
+    /// Slippage is given in basis points from `quoted_price`, as in `sell_tokens_bps`
+    pub fn sell_all_tokens(
+        ctx: Context<SellTokens>,
+        quoted_price: u64,
+        slippage_bps: u16,
+    ) -> Result<()> {
+        let token_amount = ctx.accounts.seller_token_account.amount;
+        require!(token_amount > 0, ErrorCode::InvalidAmount);
+        sell_tokens_bps(ctx, token_amount, quoted_price, slippage_bps)
+    }
+
+    /// Sell in a transaction sponsored by a relayer: `payer` pays the transaction and rent
+    /// fees and receives `relayer_tip` lamports out of the seller's proceeds
+    pub fn sell_tokens_relayed(
+        ctx: Context<SellTokens>,
+        token_amount: u64,
+        min_sol_out: u64,
+        max_price_impact_bps: Option<u16>,
+        relayer_tip: u64,
+    ) -> Result<()> {
+        let tip = RelayerTip::new(
+            &ctx.accounts.seller,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            relayer_tip,
+        )?;
+        let mint = ctx.accounts.mint.key();
+        sell_tokens(ctx, token_amount, min_sol_out, max_price_impact_bps)?;
+        tip.pay(mint)
+    }
+
+    /// Authorize `session_key` to trade on the owner's behalf until `expires_at`, spending at
+    /// most `max_sol_per_trade` per trade on `allowed_mints` (empty = any mint). `deposit`
+    /// lamports are added to the delegate's budget for buys. Authorizing an existing session
+    /// key again replaces its limits
+    /// For delegated sells, approve the delegate account as SPL delegate of the token account
+    pub fn authorize_trade_delegate(
+        ctx: Context<AuthorizeTradeDelegate>,
+        session_key: Pubkey,
+        max_sol_per_trade: u64,
+        expires_at: i64,
+        allowed_mints: Vec<Pubkey>,
+        deposit: u64,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            max_sol_per_trade > 0
+                && expires_at > current_time
+                && allowed_mints.len() <= TradeDelegate::MAX_MINTS
+                && session_key != ctx.accounts.owner.key(),
+            ErrorCode::InvalidTradeDelegate
+        );
+
+        if deposit > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.trade_delegate.to_account_info(),
+                    },
+                ),
+                deposit,
+            )?;
+        }
+
+        let delegate = &mut ctx.accounts.trade_delegate;
+        delegate.owner = ctx.accounts.owner.key();
+        delegate.session_key = session_key;
+        delegate.max_sol_per_trade = max_sol_per_trade;
+        delegate.expires_at = expires_at;
+        delegate.mint_count = allowed_mints.len() as u8;
+        delegate.allowed_mints = [Pubkey::default(); TradeDelegate::MAX_MINTS];
+        delegate.allowed_mints[..allowed_mints.len()].copy_from_slice(&allowed_mints);
+        delegate.bump = ctx.bumps.trade_delegate;
+
+        emit!(TradeDelegateAuthorized {
+            owner: delegate.owner,
+            session_key,
+            max_sol_per_trade,
+            expires_at,
+            allowed_mints,
+            deposit,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a session key, returning its unspent budget and rent to the owner
+    pub fn revoke_trade_delegate(
+        ctx: Context<RevokeTradeDelegate>,
+    ) -> Result<()> {
+        emit!(TradeDelegateRevoked {
+            owner: ctx.accounts.owner.key(),
+            session_key: ctx.accounts.trade_delegate.session_key,
+            refunded: ctx.accounts.trade_delegate.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Buy for `owner` with an authorized session key, paid from the delegate's budget
+    /// The tokens go to the owner
+    pub fn delegated_buy(
+        ctx: Context<DelegatedBuy>,
+        sol_amount: u64,
+        min_tokens_out: u64,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        let delegate = &ctx.accounts.trade_delegate;
+        delegate.check_trade(&ctx.accounts.mint.key(), sol_amount, Clock::get()?.unix_timestamp)?;
+        // The deposit is spendable down to the account's rent-exempt minimum
+        let rent = Rent::get()?.minimum_balance(TradeDelegate::MAX_SIZE);
+        require!(
+            sol_amount <= delegate.to_account_info().lamports().saturating_sub(rent),
+            ErrorCode::TradeDelegateBudgetExceeded
+        );
+
+        let owner_key = ctx.accounts.owner.key();
+        let session_key = ctx.accounts.session_key.key();
+        let bump = [delegate.bump];
+        let signer_seeds: &[&[u8]] = &[b"trade_delegate", owner_key.as_ref(), session_key.as_ref(), &bump];
+        let escrow = TradeEscrow { account: delegate.to_account_info(), signer_seeds };
+
+        let trade = CurveTrade {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            mint: ctx.accounts.mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            trader_token_account: &mut ctx.accounts.owner_token_account,
+            curve_oracle: &mut ctx.accounts.curve_oracle,
+            live_candle: &mut ctx.accounts.live_candle,
+            live_candle_bump: ctx.bumps.live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.trade_guard,
+            trade_guard_bump: ctx.bumps.trade_guard,
+            trader: &ctx.accounts.owner,
+            escrow: Some(escrow),
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+            trader_wsol_account: None,
+            fee_discount_bps: 0,
+            trader_position: None,
+            kyc_attestation: ctx.accounts.kyc_attestation.as_ref(),
+            compliance_gate: ctx.accounts.compliance_gate.as_ref(),
+            reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+            jackpot: ctx.accounts.jackpot.as_mut(),
+            competition: None,
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        let events = execute_curve_buy(
+            trade,
+            ctx.accounts.buyer_record.as_mut(),
+            ctx.bumps.buyer_record.unwrap_or_default(),
+            ctx.accounts.session_key.key(),
+            sol_amount,
+            min_tokens_out,
+            max_price_impact_bps,
+        )?;
+        if let Some(threshold_reached) = events.threshold_reached {
+            emit_cpi!(threshold_reached);
+        }
+        emit_cpi!(events.buy);
+
+        Ok(())
+    }
+
+    /// Sell `owner`'s tokens with an authorized session key; the proceeds go to the owner
+    /// The delegate account must be approved as SPL delegate of the owner's token account
+    pub fn delegated_sell(
+        ctx: Context<DelegatedSell>,
+        token_amount: u64,
+        min_sol_out: u64,
+        max_price_impact_bps: Option<u16>,
+    ) -> Result<()> {
+        let owner_key = ctx.accounts.owner.key();
+        let session_key = ctx.accounts.session_key.key();
+        let bump = [ctx.accounts.trade_delegate.bump];
+        let signer_seeds: &[&[u8]] = &[b"trade_delegate", owner_key.as_ref(), session_key.as_ref(), &bump];
+        let escrow = TradeEscrow { account: ctx.accounts.trade_delegate.to_account_info(), signer_seeds };
+
+        let trade = CurveTrade {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            mint: ctx.accounts.mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            trader_token_account: &mut ctx.accounts.owner_token_account,
+            curve_oracle: &mut ctx.accounts.curve_oracle,
+            live_candle: &mut ctx.accounts.live_candle,
+            live_candle_bump: ctx.bumps.live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.trade_guard,
+            trade_guard_bump: ctx.bumps.trade_guard,
+            trader: &ctx.accounts.owner,
+            escrow: Some(escrow),
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+            trader_wsol_account: ctx.accounts.owner_wsol_account.as_ref(),
+            fee_discount_bps: 0,
+            trader_position: None,
+            kyc_attestation: None,
+            compliance_gate: None,
+            reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+            jackpot: None,
+            competition: None,
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        let sell_event = execute_curve_sell(trade, None, token_amount, min_sol_out, max_price_impact_bps)?;
+        // The payout is only known once the sale is quoted
+        ctx.accounts.trade_delegate.check_trade(
+            &ctx.accounts.mint.key(),
+            sell_event.sol_out,
+            Clock::get()?.unix_timestamp,
+        )?;
+        emit_cpi!(sell_event);
+
+        Ok(())
+    }
+
+    /// Place a limit order on the curve, filled by `fill_limit_order` once the spot price
+    /// crosses `price` (lamports per whole token): at or below it for buys, at or above it
+    /// for sells. The order escrows `amount` lamports (buys) or tokens (sells) until it is
+    /// filled, cancelled or expires at `expires_at`
+    pub fn place_limit_order(
+        ctx: Context<PlaceLimitOrder>,
+        order_id: u64,
+        side: SwapDirection,
+        price: u64,
+        amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            price > 0 && amount > 0 && expires_at > current_time,
+            ErrorCode::InvalidLimitOrder
+        );
+        require!(!ctx.accounts.bonding_curve.load()?.is_migrated(), ErrorCode::AlreadyMigrated);
+
+        match side {
+            SwapDirection::Buy => system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.limit_order.to_account_info(),
+                    },
+                ),
+                amount,
+            )?,
+            SwapDirection::Sell => {
+                let owner_token_account = ctx.accounts.owner_token_account.as_ref()
+                    .ok_or(ErrorCode::LimitOrderTokenAccountRequired)?;
+                let cpi_accounts = Transfer {
+                    from: owner_token_account.to_account_info(),
+                    to: ctx.accounts.escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                };
+                transfer(
+                    CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+                    amount,
+                )?;
+            }
+        }
+
+        let order = &mut ctx.accounts.limit_order;
+        order.owner = ctx.accounts.owner.key();
+        order.mint = ctx.accounts.mint.key();
+        order.order_id = order_id;
+        order.side = side;
+        order.price = price;
+        order.amount = amount;
+        order.expires_at = expires_at;
+        order.bump = ctx.bumps.limit_order;
+
+        emit!(LimitOrderPlaced {
+            owner: order.owner,
+            mint: order.mint,
+            order_id,
+            side,
+            price,
+            amount,
+            expires_at,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Fill a limit order whose price has been crossed. Permissionless, so keepers can crank
+    /// it; the keeper pays for any accounts the trade needs created
+    /// The whole order executes against the curve at an average price no worse than its limit,
+    /// fees aside; the tokens or proceeds go to the owner, who also gets the order's rent back
+    pub fn fill_limit_order(
+        ctx: Context<FillLimitOrder>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let order = &ctx.accounts.limit_order;
+        require!(current_time < order.expires_at, ErrorCode::LimitOrderExpired);
+        let (side, price, amount, order_id) = (order.side, order.price, order.amount, order.order_id);
+
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let spot_price = curve_spot_price(&bonding_curve)?;
+        let min_out = match side {
+            SwapDirection::Buy => {
+                require!(spot_price <= price, ErrorCode::LimitPriceNotReached);
+                min_tokens_at_price(&ctx.accounts.global_config, &bonding_curve, amount, price, 0)?
+            }
+            SwapDirection::Sell => {
+                require!(spot_price >= price, ErrorCode::LimitPriceNotReached);
+                min_sol_at_price(&ctx.accounts.global_config, &bonding_curve, amount, price, 0)?
+            }
+        };
+
+        let owner_key = ctx.accounts.owner.key();
+        let order_id_bytes = order_id.to_le_bytes();
+        let bump = [order.bump];
+        let signer_seeds: &[&[u8]] = &[b"limit_order", owner_key.as_ref(), &order_id_bytes, &bump];
+        let escrow = TradeEscrow { account: order.to_account_info(), signer_seeds };
+
+        // Bought tokens go straight to the owner; sold tokens come out of the escrow
+        let trader_token_account = match side {
+            SwapDirection::Buy => &mut ctx.accounts.owner_token_account,
+            SwapDirection::Sell => &mut ctx.accounts.escrow_token_account,
+        };
+        let trade = CurveTrade {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            mint: ctx.accounts.mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            trader_token_account,
+            curve_oracle: &mut ctx.accounts.curve_oracle,
+            live_candle: &mut ctx.accounts.live_candle,
+            live_candle_bump: ctx.bumps.live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.trade_guard,
+            trade_guard_bump: ctx.bumps.trade_guard,
+            trader: &ctx.accounts.owner,
+            escrow: Some(escrow),
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+            trader_wsol_account: ctx.accounts.owner_wsol_account.as_ref(),
+            fee_discount_bps: 0,
+            trader_position: None,
+            kyc_attestation: ctx.accounts.kyc_attestation.as_ref(),
+            compliance_gate: ctx.accounts.compliance_gate.as_ref(),
+            reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+            jackpot: ctx.accounts.jackpot.as_mut(),
+            competition: None,
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        match side {
+            SwapDirection::Buy => {
+                let events = execute_curve_buy(
+                    trade,
+                    ctx.accounts.buyer_record.as_mut(),
+                    ctx.bumps.buyer_record.unwrap_or_default(),
+                    ctx.accounts.keeper.key(),
+                    amount,
+                    min_out,
+                    None,
+                )?;
+                if let Some(threshold_reached) = events.threshold_reached {
+                    emit_cpi!(threshold_reached);
+                }
+                emit_cpi!(events.buy);
+            }
+            SwapDirection::Sell => {
+                let sell_event = execute_curve_sell(trade, None, amount, min_out, None)?;
+                emit_cpi!(sell_event);
+            }
+        }
+
+        // Tokens sent to the escrow from outside would block closing it, so they're returned
+        ctx.accounts.escrow_token_account.reload()?;
+        let stray_tokens = ctx.accounts.escrow_token_account.amount;
+        if stray_tokens > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.limit_order.to_account_info(),
+            };
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    &[signer_seeds],
+                ),
+                stray_tokens,
+            )?;
+        }
+        // The escrow's rent goes back with the order's
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.limit_order.to_account_info(),
+            },
+            &[signer_seeds],
+        ))?;
+
+        emit_cpi!(LimitOrderFilled {
+            owner: owner_key,
+            mint: ctx.accounts.mint.key(),
+            order_id,
+            side,
+            price,
+            amount,
+            spot_price,
+            keeper: ctx.accounts.keeper.key(),
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a limit order, refunding its escrow and rent to the owner
+    /// The owner can cancel at any time; once expired, anyone can
+    pub fn cancel_limit_order(
+        ctx: Context<CancelLimitOrder>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let order = &ctx.accounts.limit_order;
+        let expired = current_time >= order.expires_at;
+        require!(
+            expired || ctx.accounts.authority.key() == order.owner,
+            ErrorCode::Unauthorized
+        );
+
+        let owner_key = ctx.accounts.owner.key();
+        let order_id_bytes = order.order_id.to_le_bytes();
+        let bump = [order.bump];
+        let signer_seeds: &[&[u8]] = &[b"limit_order", owner_key.as_ref(), &order_id_bytes, &bump];
+
+        // Escrowed lamports are refunded when the order closes; tokens are moved back first
+        let escrowed_tokens = ctx.accounts.escrow_token_account.amount;
+        if escrowed_tokens > 0 {
+            let owner_token_account = ctx.accounts.owner_token_account.as_ref()
+                .ok_or(ErrorCode::LimitOrderTokenAccountRequired)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: owner_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            };
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    &[signer_seeds],
+                ),
+                escrowed_tokens,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[signer_seeds],
+        ))?;
+
+        emit!(LimitOrderCancelled {
+            owner: owner_key,
+            mint: order.mint,
+            order_id: order.order_id,
+            side: order.side,
+            amount: order.amount,
+            expired,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Rotate from one curve into another in one transaction: sell `token_amount` tokens of
+    /// `sell_mint` and spend all the proceeds buying `buy_mint`
+    /// Slippage is checked once, on the tokens finally received. When the sold curve holds
+    /// its SOL side as wSOL, the proceeds are paid to the trader's wSOL account and the buy is
+    /// paid from their lamports
+    pub fn swap_between_curves(
+        ctx: Context<SwapBetweenCurves>,
+        token_amount: u64,
+        min_tokens_out: u64,
+    ) -> Result<()> {
+        let sell = CurveTrade {
+            bonding_curve: &ctx.accounts.sell_bonding_curve,
+            mint: ctx.accounts.sell_mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.sell_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.sell_curve_token_account,
+            trader_token_account: &mut ctx.accounts.seller_token_account,
+            curve_oracle: &mut ctx.accounts.sell_curve_oracle,
+            live_candle: &mut ctx.accounts.sell_live_candle,
+            live_candle_bump: ctx.bumps.sell_live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.sell_trade_guard,
+            trade_guard_bump: ctx.bumps.sell_trade_guard,
+            trader: &ctx.accounts.trader,
+            escrow: None,
+            curve_wsol_vault: ctx.accounts.sell_curve_wsol_vault.as_deref(),
+            trader_wsol_account: ctx.accounts.trader_wsol_account.as_deref(),
+            fee_discount_bps: 0,
+            trader_position: None,
+            kyc_attestation: None,
+            compliance_gate: None,
+            reflection_vault: ctx.accounts.sell_reflection_vault.as_deref(),
+            jackpot: None,
+            competition: None,
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+        let sell_event = execute_curve_sell(sell, None, token_amount, 0, None)?;
+        let sol_routed = sell_event.sol_out;
+        emit_cpi!(sell_event);
+
+        let buy = CurveTrade {
+            bonding_curve: &ctx.accounts.buy_bonding_curve,
+            mint: ctx.accounts.buy_mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.buy_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.buy_curve_token_account,
+            trader_token_account: &mut ctx.accounts.buyer_token_account,
+            curve_oracle: &mut ctx.accounts.buy_curve_oracle,
+            live_candle: &mut ctx.accounts.buy_live_candle,
+            live_candle_bump: ctx.bumps.buy_live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.buy_trade_guard,
+            trade_guard_bump: ctx.bumps.buy_trade_guard,
+            trader: &ctx.accounts.trader,
+            escrow: None,
+            curve_wsol_vault: ctx.accounts.buy_curve_wsol_vault.as_deref(),
+            trader_wsol_account: None,
+            fee_discount_bps: 0,
+            trader_position: None,
+            kyc_attestation: ctx.accounts.kyc_attestation.as_ref(),
+            compliance_gate: ctx.accounts.compliance_gate.as_ref(),
+            reflection_vault: ctx.accounts.buy_reflection_vault.as_deref(),
+            jackpot: ctx.accounts.buy_jackpot.as_deref_mut(),
+            competition: None,
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+        let events = execute_curve_buy(
+            buy,
+            ctx.accounts.buyer_record.as_deref_mut(),
+            ctx.bumps.buyer_record.unwrap_or_default(),
+            ctx.accounts.trader.key(),
+            sol_routed,
+            min_tokens_out,
+            None,
+        )?;
+        let tokens_out = events.buy.tokens_out;
+        if let Some(threshold_reached) = events.threshold_reached {
+            emit_cpi!(threshold_reached);
+        }
+        emit_cpi!(events.buy);
+
+        emit_cpi!(CurvesSwapped {
+            trader: ctx.accounts.trader.key(),
+            sell_mint: ctx.accounts.sell_mint.key(),
+            buy_mint: ctx.accounts.buy_mint.key(),
+            token_amount,
+            sol_routed,
+            tokens_out,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Buy several curves in one transaction, splitting `sol_amount` between them by
+    /// `weights` (bps summing to 10_000, the last curve taking any rounding dust)
+    /// Remaining accounts are BASKET_LEG_ACCOUNTS per curve in `weights` order: (bonding_curve,
+    /// mint, sol_vault, curve_token_account, curve_oracle, live_candle, trade_guard,
+    /// buyer_token_account, buyer_record, curve_wsol_vault, reflection_vault, jackpot), the
+    /// buyer's accounts created if missing and the program ID standing in for an optional
+    /// account that isn't passed
+    /// Each curve gets its own `min_tokens_out`; compliance-gated curves must be bought
+    /// individually
+    pub fn buy_basket<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyBasket<'info>>,
+        sol_amount: u64,
+        weights: Vec<u16>,
+        min_tokens_out: Vec<u64>,
+    ) -> Result<()> {
+        let legs = weights.len();
+        require!(
+            legs > 0 && legs <= MAX_BASKET_CURVES && min_tokens_out.len() == legs,
+            ErrorCode::InvalidBasket
+        );
+        require!(
+            weights.iter().all(|&weight| weight > 0)
+                && weights.iter().map(|&weight| weight as u64).sum::<u64>() == 10_000,
+            ErrorCode::InvalidBasket
+        );
+        require!(
+            ctx.remaining_accounts.len() == legs * BASKET_LEG_ACCOUNTS,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let buyer = ctx.accounts.buyer.to_account_info();
+        let buyer_key = buyer.key();
+        let mut sol_left = sol_amount;
+        for (index, group) in ctx.remaining_accounts.chunks(BASKET_LEG_ACCOUNTS).enumerate() {
+            let [
+                curve_info,
+                mint_info,
+                vault_info,
+                curve_token_info,
+                oracle_info,
+                candle_info,
+                guard_info,
+                buyer_token_info,
+                record_info,
+                wsol_vault_info,
+                reflection_info,
+                jackpot_info,
+            ] = group else {
+                return err!(ErrorCode::InvalidRemainingAccounts);
+            };
+            let leg_sol = if index + 1 == legs {
+                sol_left
+            } else {
+                mul_div(sol_amount as u128, weights[index] as u128, 10_000, Rounding::Down)?.to_u64()?
+            };
+            sol_left = sol_left.safe_sub(leg_sol)?;
+
+            // Loading checks the owner and discriminator; the rest must be the curve's own accounts
+            let bonding_curve: AccountLoader<'info, BondingCurve> = AccountLoader::try_from(curve_info)?;
+            let (curve_mint, curve_bump) = {
+                let curve = bonding_curve.load()?;
+                (curve.mint, curve.bump)
+            };
+            let mint = mint_info.key();
+            require_keys_eq!(curve_mint, mint, ErrorCode::InvalidRemainingAccounts);
+            let curve_address = Pubkey::create_program_address(
+                &[b"bonding_curve", mint.as_ref(), &[curve_bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::InvalidRemainingAccounts)?;
+            require_keys_eq!(curve_address, curve_info.key(), ErrorCode::InvalidRemainingAccounts);
+            let (vault_address, _) = Pubkey::find_program_address(&[b"sol_vault", mint.as_ref()], ctx.program_id);
+            require_keys_eq!(vault_address, vault_info.key(), ErrorCode::InvalidRemainingAccounts);
+            require_keys_eq!(
+                curve_token_info.key(),
+                get_associated_token_address(&curve_address, &mint),
+                ErrorCode::InvalidRemainingAccounts
+            );
+            let curve_token_account: Account<'info, TokenAccount> = Account::try_from(curve_token_info)?;
+            let mut curve_oracle: Account<'info, CurveOracle> = Account::try_from(oracle_info)?;
+            let oracle_address = Pubkey::create_program_address(
+                &[b"curve_oracle", mint.as_ref(), &[curve_oracle.bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::InvalidRemainingAccounts)?;
+            require_keys_eq!(oracle_address, oracle_info.key(), ErrorCode::InvalidRemainingAccounts);
+            let curve_wsol_vault: Option<Account<'info, TokenAccount>> =
+                match optional_remaining_account(wsol_vault_info, ctx.program_id) {
+                    Some(info) => {
+                        require_keys_eq!(
+                            info.key(),
+                            get_associated_token_address(&curve_address, &native_mint::ID),
+                            ErrorCode::InvalidRemainingAccounts
+                        );
+                        Some(Account::try_from(info)?)
+                    }
+                    None => None,
+                };
+            let reflection_vault: Option<Account<'info, TokenAccount>> =
+                match optional_remaining_account(reflection_info, ctx.program_id) {
+                    Some(info) => {
+                        let (vault_address, _) =
+                            Pubkey::find_program_address(&[b"reflection_vault", mint.as_ref()], ctx.program_id);
+                        require_keys_eq!(vault_address, info.key(), ErrorCode::InvalidRemainingAccounts);
+                        Some(Account::try_from(info)?)
+                    }
+                    None => None,
+                };
+            let mut jackpot: Option<Account<'info, Jackpot>> =
+                match optional_remaining_account(jackpot_info, ctx.program_id) {
+                    Some(info) => {
+                        let jackpot: Account<'info, Jackpot> = Account::try_from(info)?;
+                        let jackpot_address = Pubkey::create_program_address(
+                            &[b"jackpot", mint.as_ref(), &[jackpot.bump]],
+                            ctx.program_id,
+                        )
+                        .map_err(|_| ErrorCode::InvalidRemainingAccounts)?;
+                        require_keys_eq!(jackpot_address, info.key(), ErrorCode::InvalidRemainingAccounts);
+                        Some(jackpot)
+                    }
+                    None => None,
+                };
+
+            // The buyer's accounts for this curve, created on first use like buy_tokens does
+            create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: buyer.clone(),
+                    associated_token: buyer_token_info.clone(),
+                    authority: buyer.clone(),
+                    mint: mint_info.clone(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+            let mut buyer_token_account: Account<'info, TokenAccount> = Account::try_from(buyer_token_info)?;
+            let (mut live_candle, live_candle_bump) = load_or_init_pda::<LiveCandle>(
+                candle_info,
+                &[b"live_candle", mint.as_ref()],
+                LiveCandle::MAX_SIZE,
+                &buyer,
+                &ctx.accounts.system_program,
+            )?;
+            let (mut trade_guard, trade_guard_bump) = load_or_init_pda::<TradeGuard>(
+                guard_info,
+                &[b"trade_guard", mint.as_ref(), buyer_key.as_ref()],
+                TradeGuard::MAX_SIZE,
+                &buyer,
+                &ctx.accounts.system_program,
+            )?;
+            let (mut buyer_record, buyer_record_bump) =
+                match optional_remaining_account(record_info, ctx.program_id) {
+                    Some(info) => {
+                        let (record, bump) = load_or_init_pda::<BuyerRecord>(
+                            info,
+                            &[b"buyer_record", mint.as_ref(), buyer_key.as_ref()],
+                            BuyerRecord::MAX_SIZE,
+                            &buyer,
+                            &ctx.accounts.system_program,
+                        )?;
+                        (Some(record), bump)
+                    }
+                    None => (None, 0),
+                };
+
+            let trade = CurveTrade {
+                bonding_curve: &bonding_curve,
+                mint,
+                bonding_curve_sol_vault: vault_info,
+                bonding_curve_token_account: &curve_token_account,
+                trader_token_account: &mut buyer_token_account,
+                curve_oracle: &mut curve_oracle,
+                live_candle: &mut live_candle,
+                live_candle_bump,
+                user_points: &mut ctx.accounts.user_points,
+                user_points_bump: ctx.bumps.user_points,
+                trade_guard: &mut trade_guard,
+                trade_guard_bump,
+                trader: &buyer,
+                escrow: None,
+                curve_wsol_vault: curve_wsol_vault.as_ref(),
+                trader_wsol_account: None,
+                fee_discount_bps: 0,
+                trader_position: None,
+                kyc_attestation: ctx.accounts.kyc_attestation.as_ref(),
+                compliance_gate: None,
+                reflection_vault: reflection_vault.as_ref(),
+                jackpot: jackpot.as_mut(),
+                competition: None,
+                global_config: &ctx.accounts.global_config,
+                global_stats: &mut ctx.accounts.global_stats,
+                system_program: &ctx.accounts.system_program,
+                token_program: &ctx.accounts.token_program,
+            };
+            let events = execute_curve_buy(
+                trade,
+                buyer_record.as_mut(),
+                buyer_record_bump,
+                buyer_key,
+                leg_sol,
+                min_tokens_out[index],
+                None,
+            )?;
+            if let Some(threshold_reached) = events.threshold_reached {
+                emit_cpi!(threshold_reached);
+            }
+            emit_cpi!(events.buy);
+
+            // Accounts loaded from remaining accounts aren't written back automatically
+            curve_oracle.exit(ctx.program_id)?;
+            live_candle.exit(ctx.program_id)?;
+            trade_guard.exit(ctx.program_id)?;
+            if let Some(buyer_record) = &buyer_record {
+                buyer_record.exit(ctx.program_id)?;
+            }
+            if let Some(jackpot) = &jackpot {
+                jackpot.exit(ctx.program_id)?;
+            }
+        }
+
+        emit_cpi!(BasketBought {
+            buyer: buyer_key,
+            curves: legs as u8,
+            sol_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pre-authorize a sell of `token_amount` tokens that keepers execute once the curve price
+    /// crosses `trigger`: falling to a stop-loss or rising to a take-profit. The tokens and
+    /// `keeper_tip` lamports are escrowed in the order; the sale must pay within
+    /// `slippage_bps` of the trigger price, after fees
+    pub fn place_conditional_order(
+        ctx: Context<PlaceConditionalOrder>,
+        order_id: u64,
+        trigger: OrderTrigger,
+        token_amount: u64,
+        slippage_bps: u16,
+        keeper_tip: u64,
+    ) -> Result<()> {
+        require!(
+            trigger.price() > 0 && token_amount > 0 && slippage_bps <= 10_000,
+            ErrorCode::InvalidConditionalOrder
+        );
+        require!(!ctx.accounts.bonding_curve.load()?.is_migrated(), ErrorCode::AlreadyMigrated);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            to: ctx.accounts.escrow_token_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            token_amount,
+        )?;
+        if keeper_tip > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.conditional_order.to_account_info(),
+                    },
+                ),
+                keeper_tip,
+            )?;
+        }
+
+        let order = &mut ctx.accounts.conditional_order;
+        order.owner = ctx.accounts.owner.key();
+        order.mint = ctx.accounts.mint.key();
+        order.order_id = order_id;
+        order.trigger = trigger;
+        order.token_amount = token_amount;
+        order.slippage_bps = slippage_bps;
+        order.keeper_tip = keeper_tip;
+        order.bump = ctx.bumps.conditional_order;
+
+        emit!(ConditionalOrderPlaced {
+            owner: order.owner,
+            mint: order.mint,
+            order_id,
+            trigger,
+            token_amount,
+            slippage_bps,
+            keeper_tip,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a triggered conditional order, selling its tokens for the owner and paying the
+    /// keeper its tip. Permissionless; the keeper pays for any accounts the trade creates
+    pub fn execute_conditional_order(
+        ctx: Context<ExecuteConditionalOrder>,
+    ) -> Result<()> {
+        let order = &ctx.accounts.conditional_order;
+        let (trigger, token_amount, keeper_tip, order_id) =
+            (order.trigger, order.token_amount, order.keeper_tip, order.order_id);
+
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let spot_price = curve_spot_price(&bonding_curve)?;
+        require!(trigger.is_triggered(spot_price), ErrorCode::OrderNotTriggered);
+        let min_sol_out = min_sol_at_price(
+            &ctx.accounts.global_config,
+            &bonding_curve,
+            token_amount,
+            trigger.price(),
+            order.slippage_bps,
+        )?;
+
+        let owner_key = ctx.accounts.owner.key();
+        let order_id_bytes = order_id.to_le_bytes();
+        let bump = [order.bump];
+        let signer_seeds: &[&[u8]] = &[b"conditional_order", owner_key.as_ref(), &order_id_bytes, &bump];
+        let escrow = TradeEscrow { account: order.to_account_info(), signer_seeds };
+
+        let trade = CurveTrade {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            mint: ctx.accounts.mint.key(),
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            trader_token_account: &mut ctx.accounts.escrow_token_account,
+            curve_oracle: &mut ctx.accounts.curve_oracle,
+            live_candle: &mut ctx.accounts.live_candle,
+            live_candle_bump: ctx.bumps.live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.trade_guard,
+            trade_guard_bump: ctx.bumps.trade_guard,
+            trader: &ctx.accounts.owner,
+            escrow: Some(escrow),
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+            trader_wsol_account: ctx.accounts.owner_wsol_account.as_ref(),
+            fee_discount_bps: 0,
+            trader_position: None,
+            kyc_attestation: None,
+            compliance_gate: None,
+            reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+            jackpot: None,
+            competition: None,
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+        let sell_event = execute_curve_sell(trade, None, token_amount, min_sol_out, None)?;
+        emit_cpi!(sell_event);
+
+        if keeper_tip > 0 {
+            move_lamports(
+                &ctx.accounts.conditional_order.to_account_info(),
+                &ctx.accounts.keeper.to_account_info(),
+                keeper_tip,
+            )?;
+        }
+
+        // Tokens sent to the escrow from outside would block closing it, so they're returned
+        ctx.accounts.escrow_token_account.reload()?;
+        let stray_tokens = ctx.accounts.escrow_token_account.amount;
+        if stray_tokens > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.conditional_order.to_account_info(),
+            };
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    &[signer_seeds],
+                ),
+                stray_tokens,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.conditional_order.to_account_info(),
+            },
+            &[signer_seeds],
+        ))?;
+
+        emit_cpi!(ConditionalOrderExecuted {
+            owner: owner_key,
+            mint: ctx.accounts.mint.key(),
+            order_id,
+            trigger,
+            spot_price,
+            token_amount,
+            keeper: ctx.accounts.keeper.key(),
+            keeper_tip,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a conditional order, returning its tokens, tip and rent to the owner
+    pub fn cancel_conditional_order(
+        ctx: Context<CancelConditionalOrder>,
+    ) -> Result<()> {
+        let order = &ctx.accounts.conditional_order;
+        let owner_key = ctx.accounts.owner.key();
+        let order_id_bytes = order.order_id.to_le_bytes();
+        let bump = [order.bump];
+        let signer_seeds: &[&[u8]] = &[b"conditional_order", owner_key.as_ref(), &order_id_bytes, &bump];
+
+        let escrowed_tokens = ctx.accounts.escrow_token_account.amount;
+        if escrowed_tokens > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: order.to_account_info(),
+            };
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    &[signer_seeds],
+                ),
+                escrowed_tokens,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: order.to_account_info(),
+            },
+            &[signer_seeds],
+        ))?;
+
+        emit!(ConditionalOrderCancelled {
+            owner: owner_key,
+            mint: order.mint,
+            order_id: order.order_id,
+            token_amount: escrowed_tokens,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Schedule recurring buys of `amount_per_buy` lamports every `interval` seconds, paid out of
+    /// `deposit` lamports escrowed in the schedule. Keepers execute each buy for `keeper_tip`
+    /// lamports; `max_price` (lamports per whole token, 0 = none) caps each buy's average price
+    /// The first buy is due straight away
+    pub fn create_dca_schedule(
+        ctx: Context<CreateDcaSchedule>,
+        amount_per_buy: u64,
+        interval: i64,
+        max_price: u64,
+        keeper_tip: u64,
+        deposit: u64,
+    ) -> Result<()> {
+        require!(
+            amount_per_buy > 0
+                && interval > 0
+                && deposit >= amount_per_buy.safe_add(keeper_tip)?,
+            ErrorCode::InvalidDcaSchedule
+        );
+        require!(!ctx.accounts.bonding_curve.load()?.is_complete(), ErrorCode::BondingCurveComplete);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.dca_schedule.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.dca_schedule;
+        schedule.owner = ctx.accounts.owner.key();
+        schedule.mint = ctx.accounts.mint.key();
+        schedule.amount_per_buy = amount_per_buy;
+        schedule.interval = interval;
+        schedule.max_price = max_price;
+        schedule.keeper_tip = keeper_tip;
+        schedule.next_buy_at = current_time;
+        schedule.buys_executed = 0;
+        schedule.bump = ctx.bumps.dca_schedule;
+
+        emit!(DcaScheduleCreated {
+            owner: schedule.owner,
+            mint: schedule.mint,
+            amount_per_buy,
+            interval,
+            max_price,
+            keeper_tip,
+            deposit,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a due DCA buy for the schedule's owner and tip the keeper. Permissionless
+    /// The schedule closes, refunding what is left to the owner, once its deposit can't fund
+    /// another buy or the curve has completed
+    pub fn execute_dca_buy(
+        ctx: Context<ExecuteDcaBuy>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        if bonding_curve.is_complete() {
+            return close_dca_schedule(&ctx.accounts.dca_schedule, &ctx.accounts.owner, current_time);
+        }
+
+        let schedule = &ctx.accounts.dca_schedule;
+        require!(current_time >= schedule.next_buy_at, ErrorCode::DcaBuyNotDue);
+        let (sol_amount, keeper_tip) = (schedule.amount_per_buy, schedule.keeper_tip);
+        let min_tokens_out = match schedule.max_price {
+            0 => 0,
+            max_price => min_tokens_at_price(&ctx.accounts.global_config, &bonding_curve, sol_amount, max_price, 0)?,
+        };
+
+        let owner_key = ctx.accounts.owner.key();
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [schedule.bump];
+        let signer_seeds: &[&[u8]] = &[b"dca_schedule", owner_key.as_ref(), mint_key.as_ref(), &bump];
+        let escrow = TradeEscrow { account: schedule.to_account_info(), signer_seeds };
+
+        let trade = CurveTrade {
+            bonding_curve: &ctx.accounts.bonding_curve,
+            mint: mint_key,
+            bonding_curve_sol_vault: &ctx.accounts.bonding_curve_sol_vault,
+            bonding_curve_token_account: &ctx.accounts.bonding_curve_token_account,
+            trader_token_account: &mut ctx.accounts.owner_token_account,
+            curve_oracle: &mut ctx.accounts.curve_oracle,
+            live_candle: &mut ctx.accounts.live_candle,
+            live_candle_bump: ctx.bumps.live_candle,
+            user_points: &mut ctx.accounts.user_points,
+            user_points_bump: ctx.bumps.user_points,
+            trade_guard: &mut ctx.accounts.trade_guard,
+            trade_guard_bump: ctx.bumps.trade_guard,
+            trader: &ctx.accounts.owner,
+            escrow: Some(escrow),
+            curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+            trader_wsol_account: None,
+            fee_discount_bps: 0,
+            trader_position: None,
+            kyc_attestation: ctx.accounts.kyc_attestation.as_ref(),
+            compliance_gate: ctx.accounts.compliance_gate.as_ref(),
+            reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+            jackpot: ctx.accounts.jackpot.as_mut(),
+            competition: None,
+            global_config: &ctx.accounts.global_config,
+            global_stats: &mut ctx.accounts.global_stats,
+            system_program: &ctx.accounts.system_program,
+            token_program: &ctx.accounts.token_program,
+        };
+
+        let events = execute_curve_buy(
+            trade,
+            ctx.accounts.buyer_record.as_mut(),
+            ctx.bumps.buyer_record.unwrap_or_default(),
+            ctx.accounts.keeper.key(),
+            sol_amount,
+            min_tokens_out,
+            None,
+        )?;
+        if let Some(threshold_reached) = events.threshold_reached {
+            emit_cpi!(threshold_reached);
+        }
+        emit_cpi!(events.buy);
+
+        if keeper_tip > 0 {
+            move_lamports(
+                &ctx.accounts.dca_schedule.to_account_info(),
+                &ctx.accounts.keeper.to_account_info(),
+                keeper_tip,
+            )?;
+        }
+
+        // Later rounds are spaced from this one, so a late keeper doesn't cause a burst of buys
+        let schedule = &mut ctx.accounts.dca_schedule;
+        schedule.buys_executed = schedule.buys_executed.safe_add(1)?;
+        schedule.next_buy_at = current_time.safe_add(schedule.interval)?;
+
+        emit_cpi!(DcaBuyExecuted {
+            owner: owner_key,
+            mint: mint_key,
+            sol_amount,
+            keeper: ctx.accounts.keeper.key(),
+            keeper_tip,
+            buys_executed: schedule.buys_executed,
+            next_buy_at: schedule.next_buy_at,
+            timestamp: current_time,
+        });
+
+        let rent = Rent::get()?.minimum_balance(DcaSchedule::MAX_SIZE);
+        let budget = schedule.to_account_info().lamports().saturating_sub(rent);
+        if budget < sol_amount.safe_add(keeper_tip)? {
+            close_dca_schedule(&ctx.accounts.dca_schedule, &ctx.accounts.owner, current_time)?;
+        }
+
+        Ok(())
+    }
+
+    /// Cancel a DCA schedule, refunding its unspent deposit and rent to the owner
+    pub fn cancel_dca_schedule(
+        ctx: Context<CancelDcaSchedule>,
+    ) -> Result<()> {
+        emit!(DcaScheduleClosed {
+            owner: ctx.accounts.owner.key(),
+            mint: ctx.accounts.dca_schedule.mint,
+            buys_executed: ctx.accounts.dca_schedule.buys_executed,
+            refunded: ctx.accounts.dca_schedule.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Single swap entrypoint that stays stable across the curve's lifetime
+    /// Before migration the trade executes against the bonding curve; after migration
+    /// it is routed to the recorded Raydium CPMM pool via CPI, read from the graduation
+    /// archive instead once the curve has been closed
+    /// 
+    /// Post-migration remaining accounts (in order):
+    /// 0. Raydium CPMM program
+    /// 1. Pool authority
+    /// 2. AMM config
+    /// 3. Pool state (must match `bonding_curve.raydium_pool`)
+    /// 4. User input token account
+    /// 5. User output token account
+    /// 6. Input vault
+    /// 7. Output vault
+    /// 8. Input token program
+    /// 9. Output token program
+    /// 10. Input mint
+    /// 11. Output mint
+    /// 12. Observation state
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
+        direction: SwapDirection,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        let (migrated, pool_recorded, raydium_pool) = match (
+            ctx.accounts.bonding_curve.as_ref(),
+            ctx.accounts.graduation_archive.as_ref(),
+        ) {
+            (Some(bonding_curve), _) => {
+                let bonding_curve = bonding_curve.load()?;
+                (
+                    bonding_curve.is_migrated(),
+                    matches!(
+                        bonding_curve.migration_stage(),
+                        MigrationStatus::PoolCreated | MigrationStatus::LpBurned
+                    ),
+                    bonding_curve.raydium_pool,
+                )
+            }
+            // Only the archive is left once the curve has been closed
+            (None, Some(archive)) => (true, true, archive.raydium_pool),
+            (None, None) => return err!(ErrorCode::CurveAccountsRequired),
+        };
+        if !migrated {
+            let (Some(bonding_curve), Some(sol_vault), Some(curve_token_account)) = (
+                ctx.accounts.bonding_curve.as_ref(),
+                ctx.accounts.bonding_curve_sol_vault.as_ref(),
+                ctx.accounts.bonding_curve_token_account.as_ref(),
+            ) else {
+                return err!(ErrorCode::CurveAccountsRequired);
+            };
+            let fee_discount_bps = holder_fee_discount_bps(
+                &ctx.accounts.global_config,
+                &ctx.accounts.user.key(),
+                ctx.accounts.platform_token_account.as_deref(),
+            )?;
+            let trade = CurveTrade {
+                bonding_curve,
+                mint: ctx.accounts.mint.key(),
+                bonding_curve_sol_vault: sol_vault,
+                bonding_curve_token_account: curve_token_account,
+                trader_token_account: &mut ctx.accounts.user_token_account,
+                curve_oracle: &mut ctx.accounts.curve_oracle,
+                live_candle: &mut ctx.accounts.live_candle,
+                live_candle_bump: ctx.bumps.live_candle,
+                user_points: &mut ctx.accounts.user_points,
+                user_points_bump: ctx.bumps.user_points,
+                trade_guard: &mut ctx.accounts.trade_guard,
+                trade_guard_bump: ctx.bumps.trade_guard,
+                trader: &ctx.accounts.user,
+                escrow: None,
+                curve_wsol_vault: ctx.accounts.curve_wsol_vault.as_ref(),
+                trader_wsol_account: ctx.accounts.user_wsol_account.as_ref(),
+                fee_discount_bps,
+                trader_position: ctx.accounts.trader_position.as_mut(),
+                kyc_attestation: ctx.accounts.kyc_attestation.as_ref(),
+                compliance_gate: ctx.accounts.compliance_gate.as_ref(),
+                reflection_vault: ctx.accounts.reflection_vault.as_ref(),
+                jackpot: ctx.accounts.jackpot.as_mut(),
+                competition: ctx.accounts.competition.as_mut()
+                    .zip(ctx.accounts.competition_entry.as_mut()),
+                global_config: &ctx.accounts.global_config,
+                global_stats: &mut ctx.accounts.global_stats,
+                system_program: &ctx.accounts.system_program,
+                token_program: &ctx.accounts.token_program,
+            };
+
+            match direction {
+                SwapDirection::Buy => {
+                    let events = execute_curve_buy(
+                        trade,
+                        ctx.accounts.buyer_record.as_mut(),
+                        ctx.bumps.buyer_record.unwrap_or_default(),
+                        ctx.accounts.user.key(),
+                        amount_in,
+                        min_amount_out,
+                        None,
+                    )?;
+                    if let Some(threshold_reached) = events.threshold_reached {
+                        emit_cpi!(threshold_reached);
+                    }
+                    emit_cpi!(events.buy);
+                }
+                SwapDirection::Sell => {
+                    let sell_event = execute_curve_sell(
+                        trade,
+                        ctx.accounts.buyer_record.as_mut(),
+                        amount_in,
+                        min_amount_out,
+                        None,
+                    )?;
+                    emit_cpi!(sell_event);
+                }
+            }
+            return Ok(());
+        }
+
+        require!(amount_in > 0, ErrorCode::InvalidAmount);
+        // Until `record_raydium_pool` runs, `raydium_pool` still holds the migration vault
+        require!(pool_recorded, ErrorCode::RaydiumPoolNotRecorded);
+
+        let remaining = ctx.remaining_accounts;
+        require!(remaining.len() >= 13, ErrorCode::InvalidRaydiumAccounts);
+
+        // Only route to the configured Raydium program and the pool recorded for this curve
+        let raydium_program = &remaining[0];
+        require!(
+            raydium_program.key() == ctx.accounts.global_config.raydium_amm_program,
+            ErrorCode::InvalidRaydiumAccounts
+        );
+        require!(
+            remaining[3].key() == raydium_pool && remaining[3].owner == &raydium_program.key(),
+            ErrorCode::InvalidRaydiumAccounts
+        );
+
+        // The curve's token must be on the correct side of the swap
+        let mint_key = ctx.accounts.mint.key();
+        let token_side = match direction {
+            SwapDirection::Buy => &remaining[11],
+            SwapDirection::Sell => &remaining[10],
+        };
+        require!(token_side.key() == mint_key, ErrorCode::InvalidRaydiumAccounts);
+
+        let accounts = vec![
+            AccountMeta::new_readonly(ctx.accounts.user.key(), true),
+            AccountMeta::new_readonly(remaining[1].key(), false),
+            AccountMeta::new_readonly(remaining[2].key(), false),
+            AccountMeta::new(remaining[3].key(), false),
+            AccountMeta::new(remaining[4].key(), false),
+            AccountMeta::new(remaining[5].key(), false),
+            AccountMeta::new(remaining[6].key(), false),
+            AccountMeta::new(remaining[7].key(), false),
+            AccountMeta::new_readonly(remaining[8].key(), false),
+            AccountMeta::new_readonly(remaining[9].key(), false),
+            AccountMeta::new_readonly(remaining[10].key(), false),
+            AccountMeta::new_readonly(remaining[11].key(), false),
+            AccountMeta::new(remaining[12].key(), false),
+        ];
+
+        let mut data = RAYDIUM_CPMM_SWAP_BASE_INPUT.to_vec();
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&min_amount_out.to_le_bytes());
+
+        let swap_ix = Instruction {
+            program_id: raydium_program.key(),
+            accounts,
+            data,
+        };
+
+        let mut account_infos = vec![ctx.accounts.user.to_account_info()];
+        account_infos.extend_from_slice(&remaining[1..13]);
+        invoke(&swap_ix, &account_infos)?;
+
+        msg!("Routed swap of {} through Raydium pool {}", amount_in, remaining[3].key());
+
+        emit_cpi!(RaydiumSwapRouted {
+            user: ctx.accounts.user.key(),
+            mint: mint_key,
+            raydium_pool: remaining[3].key(),
+            is_buy: direction == SwapDirection::Buy,
+            amount_in,
+            min_amount_out,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw accumulated platform fees from a bonding curve vault
+    /// Only the global authority can call this function
+    /// With a treasury split configured, remaining accounts are its recipients in slot order
+    pub fn withdraw_platform_fees(
+        ctx: Context<WithdrawPlatformFees>,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Withdraw exactly the fees recorded by trades, never inferred from the balance
+        // Copied out so the curve isn't borrowed while it signs the wSOL transfer
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let accumulated_fees = bonding_curve.accumulated_fees;
+        require!(accumulated_fees > 0, ErrorCode::NoFeesToWithdraw);
+
+        let real_sol_reserves = bonding_curve.real_sol_reserves;
+        let (fee_share, insurance) = if bonding_curve.has_wsol_vault() {
+            // wSOL fees go to the treasury's wSOL account in full, since the fee share
+            // vault, insurance fund and treasury split hold lamports
+            let accounts = &ctx.accounts;
+            let (Some(vault), Some(treasury_wsol), Some(token_program)) = (
+                accounts.curve_wsol_vault.as_ref(),
+                accounts.treasury_wsol_account.as_ref(),
+                accounts.token_program.as_ref(),
+            ) else {
+                return err!(ErrorCode::WsolVaultRequired);
+            };
+            require!(
+                vault.amount >= real_sol_reserves.safe_add(accumulated_fees)?,
+                ErrorCode::InsufficientFees
+            );
+            let mint_key = accounts.mint.key();
+            let seeds = &[
+                b"bonding_curve",
+                mint_key.as_ref(),
+                &[bonding_curve.bump],
+            ];
+            transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: vault.to_account_info(),
+                        to: treasury_wsol.to_account_info(),
+                        authority: accounts.bonding_curve.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                accumulated_fees,
+            )?;
+            (0, 0)
+        } else {
+            let sol_holder = bonding_curve.sol_holder(
+                ctx.accounts.bonding_curve.to_account_info(),
+                &ctx.accounts.bonding_curve_sol_vault,
+            );
+
+            // Ensure the vault still covers reserves + rent after the withdrawal
+            let required_balance = real_sol_reserves
+                .safe_add(bonding_curve.sol_floor_lamports()?)?
+                .safe_add(accumulated_fees)?;
+            require!(sol_holder.lamports() >= required_balance, ErrorCode::InsufficientFees);
+
+            // Route the stakers' and insurance fund's shares, the rest to treasury
+            let fee_share = route_fee_share(
+                &ctx.accounts.global_config,
+                ctx.accounts.fee_share_vault.as_mut(),
+                &sol_holder,
+                accumulated_fees,
+            )?;
+            let insurance = route_insurance(
+                &ctx.accounts.global_config,
+                ctx.accounts.insurance_fund.as_mut(),
+                &sol_holder,
+                accumulated_fees,
+            )?;
+            pay_treasury(
+                &ctx.accounts.global_config,
+                &sol_holder,
+                &ctx.accounts.treasury,
+                ctx.remaining_accounts,
+                accumulated_fees.safe_sub(fee_share)?.safe_sub(insurance)?,
+            )?;
+            (fee_share, insurance)
+        };
+        ctx.accounts.bonding_curve.load_mut()?.accumulated_fees = 0;
+
+        emit!(FeeWithdrawalEvent {
+            mint: bonding_curve.mint,
+            authority: ctx.accounts.authority.key(),
+            treasury: ctx.accounts.treasury.key(),
+            amount: accumulated_fees,
+            fee_share,
+            insurance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw accumulated platform fees from many bonding curves in one transaction
+    /// Remaining accounts are the treasury split's recipients (if one is configured),
+    /// followed by (bonding_curve, sol_vault) pairs, all writable
+    /// Curves with nothing to withdraw are skipped rather than failing the batch
+    pub fn withdraw_platform_fees_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawPlatformFeesBatch<'info>>,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let split_count = ctx.accounts.global_config.active_treasury_splits().len();
+        require!(
+            ctx.remaining_accounts.len() > split_count,
+            ErrorCode::InvalidRemainingAccounts
+        );
+        let (split_recipients, remaining) = ctx.remaining_accounts.split_at(split_count);
+        require!(remaining.len() % 2 == 0, ErrorCode::InvalidRemainingAccounts);
+
+        let mut total_withdrawn: u64 = 0;
+        let mut total_fee_share: u64 = 0;
+        let mut total_insurance: u64 = 0;
+        let mut curves_swept: u32 = 0;
+
+        for pair in remaining.chunks(2) {
+            let curve_info = &pair[0];
+            let vault_info = &pair[1];
+
+            // Loading checks the owner and discriminator
+            let curve_loader: AccountLoader<'info, BondingCurve> = AccountLoader::try_from(curve_info)?;
+            let mut bonding_curve = curve_loader.load_mut()?;
+
+            let curve_address = Pubkey::create_program_address(
+                &[b"bonding_curve", bonding_curve.mint.as_ref(), &[bonding_curve.bump]],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::InvalidRemainingAccounts)?;
+            require!(curve_address == curve_info.key(), ErrorCode::InvalidRemainingAccounts);
+
+            let (vault_address, _) = Pubkey::find_program_address(
+                &[b"sol_vault", bonding_curve.mint.as_ref()],
+                ctx.program_id,
+            );
+            require!(vault_address == vault_info.key(), ErrorCode::InvalidRemainingAccounts);
+
+            // wSOL curves need their token accounts, so they're withdrawn individually
+            let accumulated_fees = bonding_curve.accumulated_fees;
+            if accumulated_fees == 0 || bonding_curve.has_wsol_vault() {
+                continue;
+            }
+
+            // Ensure the vault still covers reserves + rent after the withdrawal
+            let sol_holder = bonding_curve.sol_holder(curve_info.clone(), vault_info);
+            let required_balance = bonding_curve.real_sol_reserves
+                .safe_add(bonding_curve.sol_floor_lamports()?)?
+                .safe_add(accumulated_fees)?;
+            if sol_holder.lamports() < required_balance {
+                msg!("Skipping {}: vault balance below reserves + fees", bonding_curve.mint);
+                continue;
+            }
+
+            let fee_share = route_fee_share(
+                &ctx.accounts.global_config,
+                ctx.accounts.fee_share_vault.as_mut(),
+                &sol_holder,
+                accumulated_fees,
+            )?;
+            let insurance = route_insurance(
+                &ctx.accounts.global_config,
+                ctx.accounts.insurance_fund.as_mut(),
+                &sol_holder,
+                accumulated_fees,
+            )?;
+            pay_treasury(
+                &ctx.accounts.global_config,
+                &sol_holder,
+                &ctx.accounts.treasury,
+                split_recipients,
+                accumulated_fees.safe_sub(fee_share)?.safe_sub(insurance)?,
+            )?;
+
+            // Zero-copy: the write goes straight to the account data
+            bonding_curve.accumulated_fees = 0;
+
+            total_withdrawn = total_withdrawn.safe_add(accumulated_fees)?;
+            total_fee_share = total_fee_share.safe_add(fee_share)?;
+            total_insurance = total_insurance.safe_add(insurance)?;
+            curves_swept = curves_swept.safe_add(1)?;
+        }
+
+        require!(total_withdrawn > 0, ErrorCode::NoFeesToWithdraw);
+
+        emit!(BatchFeeWithdrawalEvent {
+            authority: ctx.accounts.authority.key(),
+            treasury: ctx.accounts.treasury.key(),
+            curves_swept,
+            amount: total_withdrawn,
+            fee_share: total_fee_share,
+            insurance: total_insurance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Withdrew {} lamports of fees from {} curves", total_withdrawn, curves_swept);
+
+        Ok(())
+    }
+
+    /// Spend `buyback_bps` of a curve's accumulated fees buying its token back, and burn it (admin only)
+    /// Before migration the curve fills the buy itself: the fees become reserves, no fee is charged.
+    /// After migration the buy is routed through the curve's Raydium pool. The 13 swap accounts
+    /// are then passed as remaining accounts, in the same order as `swap`. The curve's wSOL
+    /// account is the input and its token account the output
+    pub fn buyback_and_burn<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuybackAndBurn<'info>>,
+        min_tokens_out: u64,
+    ) -> Result<()> {
+        let global_config = &ctx.accounts.global_config;
+        require!(global_config.buyback_bps > 0, ErrorCode::BuybackDisabled);
+
+        // Copied out so the curve isn't borrowed while it signs CPIs
+        let mut bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let sol_spent = (bonding_curve.accumulated_fees as u128)
+            .safe_mul(global_config.buyback_bps as u128)?
+            .safe_div(10_000)?
+            .to_u64()?;
+        require!(sol_spent > 0, ErrorCode::NoFeesToWithdraw);
+
+        let mint_key = ctx.accounts.mint.key();
+        let bump = [bonding_curve.bump];
+        let seeds: &[&[u8]] = &[b"bonding_curve", mint_key.as_ref(), &bump];
+        let via_raydium = bonding_curve.is_migrated();
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut threshold_reached = None;
+
+        let tokens_burned = if !via_raydium {
+            // Gated like any other curve buy
+            require!(!bonding_curve.is_complete(), ErrorCode::BondingCurveComplete);
+            require!(bonding_curve.flag_reason == 0, ErrorCode::CurveFlagged);
+            require!(
+                bonding_curve.is_launch_finalized() || bonding_curve.unique_buyers > 0,
+                ErrorCode::LaunchNotFinalized
+            );
+            require!(
+                current_time >= bonding_curve.trading_start_time,
+                ErrorCode::TradingNotStarted
+            );
+            let reserves = curve_reserves(&bonding_curve)?;
+            let tokens_out = curve::tokens_out_for_sol(reserves, sol_spent)?;
+            require!(tokens_out >= min_tokens_out, ErrorCode::SlippageExceeded);
+            require!(tokens_out <= bonding_curve.real_token_reserves, ErrorCode::InsufficientTokens);
+
+            // The lamports already sit in the curve's vault; they only move from fees to reserves
+            bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves.safe_add(sol_spent)?;
+            bonding_curve.real_token_reserves = bonding_curve.real_token_reserves.safe_sub(tokens_out)?;
+            if bonding_curve.real_token_reserves == 0 {
+                bonding_curve.complete = 1;
+            }
+            // The added reserves can push the curve over the migration threshold
+            if bonding_curve.real_sol_reserves >= global_config.migration_threshold_sol {
+                threshold_reached = Some(MigrationThresholdReached {
+                    mint: mint_key,
+                    sol_reserves: bonding_curve.real_sol_reserves,
+                    token_reserves: bonding_curve.real_token_reserves,
+                    timestamp: current_time,
+                });
+            }
+            tokens_out
+        } else {
+            let accounts = &ctx.accounts;
+            let curve_wsol_account = accounts.curve_wsol_account.as_ref()
+                .ok_or(ErrorCode::InvalidRaydiumAccounts)?;
+
+            let remaining = ctx.remaining_accounts;
+            require!(remaining.len() >= 13, ErrorCode::InvalidRaydiumAccounts);
+            require!(
+                remaining[0].key() == global_config.raydium_amm_program
+                    && remaining[3].key() == bonding_curve.raydium_pool
+                    && remaining[4].key() == curve_wsol_account.key()
+                    && remaining[5].key() == accounts.bonding_curve_token_account.key()
+                    && remaining[10].key() == native_mint::ID
+                    && remaining[11].key() == mint_key,
+                ErrorCode::InvalidRaydiumAccounts
+            );
+
+            // Wrap the fees into the curve's wSOL account so the curve can pay the pool
+            let sol_holder = bonding_curve.sol_holder(
+                accounts.bonding_curve.to_account_info(),
+                &accounts.bonding_curve_sol_vault,
+            );
+            require!(
+                sol_holder.lamports()
+                    >= bonding_curve.accumulated_fees.safe_add(bonding_curve.sol_floor_lamports()?)?,
+                ErrorCode::InsufficientFees
+            );
+            move_lamports(&sol_holder, &curve_wsol_account.to_account_info(), sol_spent)?;
+            sync_native(CpiContext::new(
+                accounts.token_program.to_account_info(),
+                SyncNative { account: curve_wsol_account.to_account_info() },
+            ))?;
+
+            let balance_before = accounts.bonding_curve_token_account.amount;
+            let swap_accounts = vec![
+                AccountMeta::new_readonly(accounts.bonding_curve.key(), true),
+                AccountMeta::new_readonly(remaining[1].key(), false),
+                AccountMeta::new_readonly(remaining[2].key(), false),
+                AccountMeta::new(remaining[3].key(), false),
+                AccountMeta::new(remaining[4].key(), false),
+                AccountMeta::new(remaining[5].key(), false),
+                AccountMeta::new(remaining[6].key(), false),
+                AccountMeta::new(remaining[7].key(), false),
+                AccountMeta::new_readonly(remaining[8].key(), false),
+                AccountMeta::new_readonly(remaining[9].key(), false),
+                AccountMeta::new_readonly(remaining[10].key(), false),
+                AccountMeta::new_readonly(remaining[11].key(), false),
+                AccountMeta::new(remaining[12].key(), false),
+            ];
+            let mut data = RAYDIUM_CPMM_SWAP_BASE_INPUT.to_vec();
+            data.extend_from_slice(&sol_spent.to_le_bytes());
+            data.extend_from_slice(&min_tokens_out.to_le_bytes());
+            let swap_ix = Instruction {
+                program_id: remaining[0].key(),
+                accounts: swap_accounts,
+                data,
+            };
+            let mut account_infos = vec![accounts.bonding_curve.to_account_info()];
+            account_infos.extend_from_slice(&remaining[1..13]);
+            invoke_signed(&swap_ix, &account_infos, &[seeds])?;
+
+            // Return the emptied wSOL account's rent to the authority that created it
+            close_account(CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: curve_wsol_account.to_account_info(),
+                    destination: accounts.authority.to_account_info(),
+                    authority: accounts.bonding_curve.to_account_info(),
+                },
+                &[seeds],
+            ))?;
+
+            ctx.accounts.bonding_curve_token_account.reload()?;
+            let bought = ctx.accounts.bonding_curve_token_account.amount.safe_sub(balance_before)?;
+            require!(bought >= min_tokens_out, ErrorCode::SlippageExceeded);
+            bought
+        };
+
+        burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                &[seeds],
+            ),
+            tokens_burned,
+        )?;
+
+        bonding_curve.accumulated_fees = bonding_curve.accumulated_fees.safe_sub(sol_spent)?;
+        *ctx.accounts.bonding_curve.load_mut()? = bonding_curve;
+
+        emit!(BuybackBurned {
+            mint: mint_key,
+            authority: ctx.accounts.authority.key(),
+            sol_spent,
+            tokens_burned,
+            via_raydium,
+            accumulated_fees: bonding_curve.accumulated_fees,
+            timestamp: current_time,
+        });
+        if let Some(threshold_reached) = threshold_reached {
+            emit!(threshold_reached);
+        }
+
+        Ok(())
+    }
+
+    /// Create the vault that shares trading fees with platform-token stakers (authority only, once)
+    pub fn initialize_fee_share_vault(
+        ctx: Context<InitializeFeeShareVault>,
+    ) -> Result<()> {
+        let fee_share_vault = &mut ctx.accounts.fee_share_vault;
+        fee_share_vault.stake_mint = ctx.accounts.stake_mint.key();
+        fee_share_vault.bump = ctx.bumps.fee_share_vault;
+
+        Ok(())
+    }
+
+    /// Stake platform tokens to earn a share of trading fees
+    /// Fees already earned on the existing stake are paid out first
+    pub fn stake_platform_token(
+        ctx: Context<StakePlatformToken>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let fee_share_stake = &mut ctx.accounts.fee_share_stake;
+        // Created on first use
+        if fee_share_stake.owner == Pubkey::default() {
+            fee_share_stake.owner = ctx.accounts.owner.key();
+            fee_share_stake.bump = ctx.bumps.fee_share_stake;
+        }
+        settle_fee_share(
+            &ctx.accounts.fee_share_vault,
+            fee_share_stake,
+            &ctx.accounts.owner.to_account_info(),
+        )?;
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let fee_share_vault = &mut ctx.accounts.fee_share_vault;
+        fee_share_stake.amount = fee_share_stake.amount.safe_add(amount)?;
+        fee_share_vault.total_staked = fee_share_vault.total_staked.safe_add(amount)?;
+        fee_share_stake.reward_debt = fee_share_vault.accrued_for(fee_share_stake.amount)?;
+        // Fees that arrived while nothing was staked go to the first stakers
+        fee_share_vault.distribute(0)?;
+
+        emit!(PlatformTokenStaked {
+            owner: fee_share_stake.owner,
+            amount,
+            staked: fee_share_stake.amount,
+            total_staked: fee_share_vault.total_staked,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw staked platform tokens, paying out any fees earned on them
+    pub fn unstake(
+        ctx: Context<Unstake>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount <= ctx.accounts.fee_share_stake.amount, ErrorCode::InsufficientStake);
+
+        settle_fee_share(
+            &ctx.accounts.fee_share_vault,
+            &mut ctx.accounts.fee_share_stake,
+            &ctx.accounts.owner.to_account_info(),
+        )?;
+
+        let bump = ctx.accounts.fee_share_vault.bump;
+        let seeds = &[b"fee_share_vault".as_ref(), &[bump]];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.fee_share_vault.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        let fee_share_vault = &mut ctx.accounts.fee_share_vault;
+        let fee_share_stake = &mut ctx.accounts.fee_share_stake;
+        fee_share_stake.amount = fee_share_stake.amount.safe_sub(amount)?;
+        fee_share_vault.total_staked = fee_share_vault.total_staked.safe_sub(amount)?;
+        fee_share_stake.reward_debt = fee_share_vault.accrued_for(fee_share_stake.amount)?;
+
+        emit!(PlatformTokenUnstaked {
+            owner: fee_share_stake.owner,
+            amount,
+            staked: fee_share_stake.amount,
+            total_staked: fee_share_vault.total_staked,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim the trading fees earned by a platform-token stake
+    pub fn claim_fee_share(
+        ctx: Context<ClaimFeeShare>,
+    ) -> Result<()> {
+        let amount = settle_fee_share(
+            &ctx.accounts.fee_share_vault,
+            &mut ctx.accounts.fee_share_stake,
+            &ctx.accounts.owner.to_account_info(),
+        )?;
+        require!(amount > 0, ErrorCode::NoFeeShare);
+
+        Ok(())
+    }
+
+    /// Create the insurance fund that receives `insurance_fee_bps` of withdrawn trading fees
+    /// (authority only, once). Payouts must wait `payout_delay` seconds after being proposed
+    pub fn initialize_insurance_fund(
+        ctx: Context<InitializeInsuranceFund>,
+        payout_delay: i64,
+    ) -> Result<()> {
+        require!(payout_delay >= 0, ErrorCode::InvalidTimelockDelay);
+
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.payout_delay = payout_delay;
+        insurance_fund.bump = ctx.bumps.insurance_fund;
+
+        Ok(())
+    }
+
+    /// Propose compensating `recipient` from the insurance fund (admin only)
+    /// The amount is reserved immediately and can be paid once the payout delay has passed
+    pub fn propose_payout(
+        ctx: Context<ProposePayout>,
+        recipient: Pubkey,
+        amount: u64,
+        reason: String,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        validate_text(&reason, InsurancePayout::MAX_REASON, is_printable_ascii, ErrorCode::InvalidPayoutReason)?;
+
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        let available = insurance_fund.available_lamports(&insurance_fund.to_account_info())?;
+        require!(amount <= available, ErrorCode::InsufficientInsuranceFunds);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let payout = &mut ctx.accounts.payout;
+        payout.id = insurance_fund.next_payout_id;
+        payout.recipient = recipient;
+        payout.amount = amount;
+        (payout.reason, payout.reason_len) = fixed_bytes(&reason);
+        payout.proposed_at = current_time;
+        payout.executable_at = current_time.safe_add(insurance_fund.payout_delay)?;
+        payout.executed_at = 0;
+        payout.bump = ctx.bumps.payout;
+
+        insurance_fund.pending_amount = insurance_fund.pending_amount.safe_add(amount)?;
+        insurance_fund.next_payout_id = insurance_fund.next_payout_id.safe_add(1)?;
+
+        emit!(InsurancePayoutProposed {
+            id: payout.id,
+            recipient,
+            amount,
+            reason,
+            executable_at: payout.executable_at,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Pay a proposed insurance payout once its delay has passed (permissionless crank)
+    /// The payout account is kept as a record of the compensation
+    pub fn execute_payout(
+        ctx: Context<ExecutePayout>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let payout = &mut ctx.accounts.payout;
+        require!(payout.executed_at == 0, ErrorCode::PayoutAlreadyExecuted);
+        require!(current_time >= payout.executable_at, ErrorCode::PayoutNotReady);
+
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        move_lamports(
+            &insurance_fund.to_account_info(),
+            &ctx.accounts.recipient,
+            payout.amount,
+        )?;
+        insurance_fund.pending_amount = insurance_fund.pending_amount.safe_sub(payout.amount)?;
+        insurance_fund.total_paid_out = insurance_fund.total_paid_out.safe_add(payout.amount)?;
+        payout.executed_at = current_time;
+
+        emit!(InsurancePayoutExecuted {
+            id: payout.id,
+            recipient: payout.recipient,
+            amount: payout.amount,
+            total_paid_out: insurance_fund.total_paid_out,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a payout that hasn't been executed, releasing its reserved amount (admin only)
+    pub fn cancel_payout(
+        ctx: Context<CancelPayout>,
+    ) -> Result<()> {
+        let payout = &ctx.accounts.payout;
+        require!(payout.executed_at == 0, ErrorCode::PayoutAlreadyExecuted);
+
+        let insurance_fund = &mut ctx.accounts.insurance_fund;
+        insurance_fund.pending_amount = insurance_fund.pending_amount.safe_sub(payout.amount)?;
+
+        emit!(InsurancePayoutCancelled {
+            id: payout.id,
+            recipient: payout.recipient,
+            amount: payout.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw funds from migration vault to create Raydium pool
+    /// This allows the platform to use migration vault funds for pool creation
+    /// Funds only go to the configured migration operator, only before the pool is recorded,
+    /// and never more than the curve migrated; leftovers are returned or settled afterwards
+    pub fn withdraw_migration_funds(
+        ctx: Context<WithdrawMigrationFunds>,
+        sol_amount: u64,
+        token_amount: u64,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Verify the bonding curve is migrated
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            bonding_curve.is_migrated(),
+            ErrorCode::NotMigrated
+        );
+
+        require!(
+            bonding_curve.migration_stage() == MigrationStatus::FundsMoved,
+            ErrorCode::MigrationWithdrawalClosed
+        );
+
+        // Never more than what migration moved into the vaults
+        let sol_migrated = bonding_curve.graduation_sol_reserves
+            .saturating_sub(MIGRATION_FEE_LAMPORTS);
+        bonding_curve.migration_sol_withdrawn = bonding_curve.migration_sol_withdrawn
+            .safe_add(sol_amount)?;
+        bonding_curve.migration_tokens_withdrawn = bonding_curve.migration_tokens_withdrawn
+            .safe_add(token_amount)?;
+        require!(
+            bonding_curve.migration_sol_withdrawn <= sol_migrated
+                && bonding_curve.migration_tokens_withdrawn <= bonding_curve.graduation_token_reserves,
+            ErrorCode::MigrationWithdrawalCapExceeded
+        );
+
+        msg!("Withdrawing {} SOL and {} tokens from migration vault", sol_amount, token_amount);
+
+        // Withdraw SOL using System Program
+        if sol_amount > 0 {
+            let vault_balance = ctx.accounts.migration_sol_vault.lamports();
+            require!(vault_balance >= sol_amount, ErrorCode::InsufficientSOL);
+
+            // Use System Program to transfer SOL from PDA to recipient
+            let mint_key = ctx.accounts.mint.key();
+            let vault_bump = ctx.bumps.migration_sol_vault;
+            let vault_seeds: &[&[u8]] = &[
+                b"migration_vault",
+                mint_key.as_ref(),
+                &[vault_bump],
+            ];
+            let vault_signer = &[vault_seeds];
+
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.migration_sol_vault.to_account_info(),
+                        to: ctx.accounts.recipient.to_account_info(),
+                    },
+                    vault_signer,
+                ),
+                sol_amount,
+            )?;
+
+            msg!("Transferred {} lamports from migration vault", sol_amount);
+        }
+
+        // Withdraw tokens
+        if token_amount > 0 {
+            let authority_bump = ctx.bumps.migration_authority;
+            let seeds: &[&[u8]] = &[
+                b"migration_authority",
+                &[authority_bump],
+            ];
+            let signer = &[seeds];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.migration_token_account.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.migration_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            
+            transfer(cpi_ctx, token_amount)?;
+
+            msg!("Transferred {} tokens from migration vault", token_amount);
+        }
+
+        emit_cpi!(MigrationFundsWithdrawn {
+            mint: bonding_curve.mint,
+            authority: ctx.accounts.authority.key(),
+            recipient: ctx.accounts.recipient.key(),
+            sol_amount,
+            token_amount,
+            total_sol_withdrawn: bonding_curve.migration_sol_withdrawn,
+            total_tokens_withdrawn: bonding_curve.migration_tokens_withdrawn,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Migration funds withdrawn successfully");
+
+        Ok(())
+    }
+
+    /// Return unused migration funds from the operator to the migration vaults
+    pub fn return_migration_funds(
+        ctx: Context<ReturnMigrationFunds>,
+        sol_amount: u64,
+        token_amount: u64,
+    ) -> Result<()> {
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(bonding_curve.is_migrated(), ErrorCode::NotMigrated);
+        bonding_curve.migration_sol_withdrawn = bonding_curve.migration_sol_withdrawn
+            .checked_sub(sol_amount)
+            .ok_or(ErrorCode::InvalidAmount)?;
+        bonding_curve.migration_tokens_withdrawn = bonding_curve.migration_tokens_withdrawn
+            .checked_sub(token_amount)
+            .ok_or(ErrorCode::InvalidAmount)?;
+
+        if sol_amount > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.operator.to_account_info(),
+                        to: ctx.accounts.migration_sol_vault.to_account_info(),
+                    },
+                ),
+                sol_amount,
+            )?;
+        }
+        if token_amount > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.operator_token_account.to_account_info(),
+                        to: ctx.accounts.migration_token_account.to_account_info(),
+                        authority: ctx.accounts.operator.to_account_info(),
+                    },
+                ),
+                token_amount,
+            )?;
+        }
+
+        emit_cpi!(MigrationFundsReturned {
+            mint: bonding_curve.mint,
+            operator: ctx.accounts.operator.key(),
+            sol_amount,
+            token_amount,
+            total_sol_withdrawn: bonding_curve.migration_sol_withdrawn,
+            total_tokens_withdrawn: bonding_curve.migration_tokens_withdrawn,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle what's left in the migration vaults once the pool exists (admin only)
+    /// Remaining tokens are burned and remaining SOL goes to the treasury
+    pub fn settle_migration_remainder(
+        ctx: Context<SettleMigrationRemainder>,
+    ) -> Result<()> {
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        require!(
+            matches!(
+                bonding_curve.migration_stage(),
+                MigrationStatus::PoolCreated | MigrationStatus::LpBurned
+            ),
+            ErrorCode::InvalidMigrationTransition
+        );
+
+        let tokens_burned = ctx.accounts.migration_token_account.amount;
+        if tokens_burned > 0 {
+            let authority_seeds: &[&[u8]] = &[
+                b"migration_authority",
+                &[ctx.bumps.migration_authority],
+            ];
+            burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.migration_token_account.to_account_info(),
+                        authority: ctx.accounts.migration_authority.to_account_info(),
+                    },
+                    &[authority_seeds],
+                ),
+                tokens_burned,
+            )?;
+        }
+
+        let sol_to_treasury = ctx.accounts.migration_sol_vault.lamports();
+        if sol_to_treasury > 0 {
+            let mint_key = ctx.accounts.mint.key();
+            let vault_seeds: &[&[u8]] = &[
+                b"migration_vault",
+                mint_key.as_ref(),
+                &[ctx.bumps.migration_sol_vault],
+            ];
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.migration_sol_vault.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                    &[vault_seeds],
+                ),
+                sol_to_treasury,
+            )?;
+        }
+
+        emit_cpi!(MigrationRemainderSettled {
+            mint: bonding_curve.mint,
+            sol_to_treasury,
+            tokens_burned,
+            total_sol_withdrawn: bonding_curve.migration_sol_withdrawn,
+            total_tokens_withdrawn: bonding_curve.migration_tokens_withdrawn,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Advance a migrated curve to `status` once the off-chain step has succeeded (admin only)
+    /// Steps go FundsMoved -> PoolCreated -> LpBurned; repeating the current step is a no-op
+    pub fn advance_migration_status(
+        ctx: Context<AdvanceMigrationStatus>,
+        status: MigrationStatus,
+    ) -> Result<()> {
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(bonding_curve.is_migrated(), ErrorCode::NotMigrated);
+        advance_migration(&mut bonding_curve, status)
+    }
+
+    /// Record the real Raydium pool once it has been created from the migration vaults (admin only)
+    /// Replaces the migration vault placeholder so routed swaps target the pool, and advances
+    /// the migration to PoolCreated; the pool can be corrected until its LP is burned
+    pub fn record_raydium_pool(
+        ctx: Context<RecordRaydiumPool>,
+    ) -> Result<()> {
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(bonding_curve.is_migrated(), ErrorCode::NotMigrated);
+
+        let previous_pool = bonding_curve.raydium_pool;
+        bonding_curve.raydium_pool = ctx.accounts.raydium_pool.key();
+        advance_migration(&mut bonding_curve, MigrationStatus::PoolCreated)?;
+
+        emit!(RaydiumPoolRecorded {
+            mint: bonding_curve.mint,
+            raydium_pool: bonding_curve.raydium_pool,
+            previous_pool,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create Raydium pool and burn LP tokens to permanently lock liquidity
+    /// This ensures liquidity cannot be rug-pulled, similar to pump.fun
+    /// 
+    /// IMPORTANT: This is a two-step process:
+    /// 1. Use Raydium SDK/UI to create the pool with migration vault funds
+    /// 2. Call this instruction to burn the received LP tokens
+    /// 
+    /// After this instruction, liquidity is PERMANENTLY LOCKED.
+    pub fn burn_raydium_lp_tokens(
+        ctx: Context<BurnRaydiumLpTokens>,
+        lp_amount: u64,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Verify the bonding curve is migrated
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            bonding_curve.is_migrated(),
+            ErrorCode::NotMigrated
+        );
+
+        // The pool must be recorded as created before its LP can be burned
+        require!(
+            bonding_curve.migration_stage() == MigrationStatus::PoolCreated,
+            ErrorCode::InvalidMigrationTransition
+        );
+
+        msg!("Burning {} LP tokens to permanently lock liquidity", lp_amount);
+
+        // Burn the LP tokens using migration authority
+        let authority_bump = ctx.bumps.migration_authority;
+        let seeds: &[&[u8]] = &[
+            b"migration_authority",
+            &[authority_bump],
+        ];
+        let signer = &[seeds];
+
+        let burn_accounts = Burn {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            from: ctx.accounts.lp_token_account.to_account_info(),
+            authority: ctx.accounts.migration_authority.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            burn_accounts,
+            signer,
+        );
+        
+        burn(burn_ctx, lp_amount)?;
+
+        msg!("Successfully burned {} LP tokens", lp_amount);
+
+        // Create LP burn info account to track the burn
+        let lp_burn_info = &mut ctx.accounts.lp_burn_info;
+        lp_burn_info.mint = bonding_curve.mint;
+        lp_burn_info.lp_mint = ctx.accounts.lp_mint.key();
+        lp_burn_info.raydium_pool = ctx.accounts.raydium_pool.key();
+        lp_burn_info.lp_burned_amount = lp_amount;
+        lp_burn_info.burn_timestamp = Clock::get()?.unix_timestamp;
+        lp_burn_info.bump = ctx.bumps.lp_burn_info;
+
+        advance_migration(&mut bonding_curve, MigrationStatus::LpBurned)?;
+
+        emit!(LpTokensBurnedEvent {
+            mint: bonding_curve.mint,
+            raydium_pool: ctx.accounts.raydium_pool.key(),
+            lp_mint: ctx.accounts.lp_mint.key(),
+            lp_amount_burned: lp_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Liquidity is now PERMANENTLY LOCKED! 🔒");
+        msg!("Pool address: {}", ctx.accounts.raydium_pool.key());
+        msg!("LP tokens burned: {}", lp_amount);
+
+        Ok(())
+    }
+
+    /// Lock Raydium LP tokens in a program escrow instead of burning them
+    /// Liquidity stays locked until `unlock_time`, after which it can be recovered
+    /// with `withdraw_locked_lp` (e.g., to claim accumulated LP fees)
+    pub fn lock_lp_tokens(
+        ctx: Context<LockLpTokens>,
+        lp_amount: u64,
+        duration: i64,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Verify the bonding curve is migrated
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        require!(
+            bonding_curve.is_migrated(),
+            ErrorCode::NotMigrated
+        );
+        require!(lp_amount > 0, ErrorCode::InvalidAmount);
+        require!(duration > 0, ErrorCode::InvalidLockDuration);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let unlock_time = current_time
+            .safe_add(duration)?;
+
+        msg!("Locking {} LP tokens until {}", lp_amount, unlock_time);
+
+        // Move the LP tokens from the migration authority into the lock escrow
+        let authority_bump = ctx.bumps.migration_authority;
+        let seeds: &[&[u8]] = &[
+            b"migration_authority",
+            &[authority_bump],
+        ];
+        let signer = &[seeds];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.lp_token_account.to_account_info(),
+            to: ctx.accounts.lp_lock_vault.to_account_info(),
+            authority: ctx.accounts.migration_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        transfer(cpi_ctx, lp_amount)?;
+
+        let lp_lock = &mut ctx.accounts.lp_lock;
+        lp_lock.mint = bonding_curve.mint;
+        lp_lock.lp_mint = ctx.accounts.lp_mint.key();
+        lp_lock.raydium_pool = ctx.accounts.raydium_pool.key();
+        lp_lock.lp_amount = lp_amount;
+        lp_lock.locked_at = current_time;
+        lp_lock.unlock_time = unlock_time;
+        lp_lock.withdrawn = false;
+        lp_lock.bump = ctx.bumps.lp_lock;
+        // Fee growth is measured against the liquidity backing each LP token right now
+        lp_lock.liquidity_per_lp = pool_liquidity_per_lp(
+            &ctx.accounts.token_0_vault,
+            &ctx.accounts.token_1_vault,
+            &ctx.accounts.lp_mint,
+        )?;
+
+        emit!(LpTokensLockedEvent {
+            mint: lp_lock.mint,
+            raydium_pool: lp_lock.raydium_pool,
+            lp_mint: lp_lock.lp_mint,
+            lp_amount,
+            unlock_time,
+            timestamp: current_time,
+        });
+
+        msg!("Liquidity is locked until {} 🔒", unlock_time);
+
+        Ok(())
+    }
+
+    /// Withdraw LP tokens from the lock escrow once the lock has expired
+    pub fn withdraw_locked_lp(
+        ctx: Context<WithdrawLockedLp>,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        let lp_lock = &ctx.accounts.lp_lock;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(!lp_lock.withdrawn, ErrorCode::LpAlreadyWithdrawn);
+        require!(current_time >= lp_lock.unlock_time, ErrorCode::LpStillLocked);
+
+        let lp_amount = ctx.accounts.lp_lock_vault.amount;
+        require!(lp_amount > 0, ErrorCode::InvalidAmount);
+
+        let mint_key = lp_lock.mint;
+        let seeds: &[&[u8]] = &[
+            b"lp_lock",
+            mint_key.as_ref(),
+            &[lp_lock.bump],
+        ];
+        let signer = &[seeds];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.lp_lock_vault.to_account_info(),
+            to: ctx.accounts.recipient_lp_token_account.to_account_info(),
+            authority: ctx.accounts.lp_lock.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        transfer(cpi_ctx, lp_amount)?;
+
+        let lp_lock = &mut ctx.accounts.lp_lock;
+        lp_lock.withdrawn = true;
+
+        emit!(LockedLpWithdrawnEvent {
+            mint: lp_lock.mint,
+            lp_mint: lp_lock.lp_mint,
+            recipient: ctx.accounts.recipient_lp_token_account.key(),
+            lp_amount,
+            timestamp: current_time,
+        });
+
+        msg!("Withdrew {} unlocked LP tokens", lp_amount);
+
+        Ok(())
+    }
+
+    /// Harvest accumulated Raydium LP trading fees from locked liquidity
+    /// Withdraws `lp_amount` of locked LP through Raydium CPMM and splits the received
+    /// tokens between the project creator and the platform treasury
+    /// 
+    /// Only LP representing fee growth since the lock or the last harvest can be
+    /// withdrawn, so the liquidity that was originally locked is never touched.
+    pub fn claim_lp_fees(
+        ctx: Context<ClaimLpFees>,
+        lp_amount: u64,
+        minimum_token_0_amount: u64,
+        minimum_token_1_amount: u64,
+    ) -> Result<()> {
+        // Either the creator or the platform authority can harvest
+        let caller = ctx.accounts.caller.key();
+        require!(
+            caller == ctx.accounts.bonding_curve.load()?.creator
+                || caller == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+        require!(!ctx.accounts.lp_lock.withdrawn, ErrorCode::LpAlreadyWithdrawn);
+        require!(
+            ctx.accounts.raydium_program.key() == ctx.accounts.global_config.raydium_amm_program,
+            ErrorCode::InvalidRaydiumAccounts
+        );
+        require!(
+            ctx.accounts.pool_state.key() == ctx.accounts.lp_lock.raydium_pool,
+            ErrorCode::InvalidRaydiumAccounts
+        );
+
+        let creator_share_bps = ctx.accounts.global_config.lp_fee_creator_share_bps;
+        require!(creator_share_bps <= 10_000, ErrorCode::InvalidFeeShare);
+
+        let liquidity_per_lp = pool_liquidity_per_lp(
+            &ctx.accounts.token_0_vault,
+            &ctx.accounts.token_1_vault,
+            &ctx.accounts.lp_mint,
+        )?;
+
+        let lp_lock = &mut ctx.accounts.lp_lock;
+        // LP that can be withdrawn while leaving the principal fully backed
+        require!(liquidity_per_lp > lp_lock.liquidity_per_lp, ErrorCode::NoLpFeesToClaim);
+        let principal = (lp_lock.lp_amount as u128)
+            .safe_mul(lp_lock.liquidity_per_lp)?;
+        let harvestable_lp = (lp_lock.lp_amount as u128)
+            .safe_mul(liquidity_per_lp.safe_sub(lp_lock.liquidity_per_lp)?)?
+            .safe_div(liquidity_per_lp)?
+            .to_u64()?;
+        require!(lp_amount > 0 && lp_amount <= harvestable_lp, ErrorCode::NoLpFeesToClaim);
+
+        let remaining_lp = lp_lock.lp_amount.safe_sub(lp_amount)?;
+        require!(remaining_lp > 0, ErrorCode::NoLpFeesToClaim);
+
+        // Remaining LP keeps backing the same principal
+        lp_lock.lp_amount = remaining_lp;
+        lp_lock.liquidity_per_lp = principal.safe_div(remaining_lp as u128)?;
+
+        let token_0_before = ctx.accounts.lp_fee_token_0_account.amount;
+        let token_1_before = ctx.accounts.lp_fee_token_1_account.amount;
+
+        // Withdraw the fee LP from Raydium into the lock's fee accounts
+        let accounts = vec![
+            AccountMeta::new_readonly(ctx.accounts.lp_lock.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), false),
+            AccountMeta::new(ctx.accounts.pool_state.key(), false),
+            AccountMeta::new(ctx.accounts.lp_lock_vault.key(), false),
+            AccountMeta::new(ctx.accounts.lp_fee_token_0_account.key(), false),
+            AccountMeta::new(ctx.accounts.lp_fee_token_1_account.key(), false),
+            AccountMeta::new(ctx.accounts.token_0_vault.key(), false),
+            AccountMeta::new(ctx.accounts.token_1_vault.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program_2022.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.vault_0_mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.vault_1_mint.key(), false),
+            AccountMeta::new(ctx.accounts.lp_mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.memo_program.key(), false),
+        ];
+
+        let mut data = RAYDIUM_CPMM_WITHDRAW.to_vec();
+        data.extend_from_slice(&lp_amount.to_le_bytes());
+        data.extend_from_slice(&minimum_token_0_amount.to_le_bytes());
+        data.extend_from_slice(&minimum_token_1_amount.to_le_bytes());
+
+        let withdraw_ix = Instruction {
+            program_id: ctx.accounts.raydium_program.key(),
+            accounts,
+            data,
+        };
+
+        let mint_key = ctx.accounts.mint.key();
+        let lock_seeds: &[&[u8]] = &[
+            b"lp_lock",
+            mint_key.as_ref(),
+            &[ctx.accounts.lp_lock.bump],
+        ];
+
+        invoke_signed(
+            &withdraw_ix,
+            &[
+                ctx.accounts.lp_lock.to_account_info(),
+                ctx.accounts.pool_authority.to_account_info(),
+                ctx.accounts.pool_state.to_account_info(),
+                ctx.accounts.lp_lock_vault.to_account_info(),
+                ctx.accounts.lp_fee_token_0_account.to_account_info(),
+                ctx.accounts.lp_fee_token_1_account.to_account_info(),
+                ctx.accounts.token_0_vault.to_account_info(),
+                ctx.accounts.token_1_vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.token_program_2022.to_account_info(),
+                ctx.accounts.vault_0_mint.to_account_info(),
+                ctx.accounts.vault_1_mint.to_account_info(),
+                ctx.accounts.lp_mint.to_account_info(),
+                ctx.accounts.memo_program.to_account_info(),
+            ],
+            &[lock_seeds],
+        )?;
+
+        ctx.accounts.lp_fee_token_0_account.reload()?;
+        ctx.accounts.lp_fee_token_1_account.reload()?;
+        let token_0_received = ctx.accounts.lp_fee_token_0_account.amount
+            .safe_sub(token_0_before)?;
+        let token_1_received = ctx.accounts.lp_fee_token_1_account.amount
+            .safe_sub(token_1_before)?;
+
+        // Split each side between creator and treasury
+        let token_0_creator = (token_0_received as u128)
+            .safe_mul(creator_share_bps as u128)?
+            .safe_div(10_000)?
+            .to_u64()?;
+        let token_1_creator = (token_1_received as u128)
+            .safe_mul(creator_share_bps as u128)?
+            .safe_div(10_000)?
+            .to_u64()?;
+        let token_0_treasury = token_0_received.safe_sub(token_0_creator)?;
+        let token_1_treasury = token_1_received.safe_sub(token_1_creator)?;
+
+        let payouts = [
+            (&ctx.accounts.lp_fee_token_0_account, &ctx.accounts.creator_token_0_account, token_0_creator),
+            (&ctx.accounts.lp_fee_token_0_account, &ctx.accounts.treasury_token_0_account, token_0_treasury),
+            (&ctx.accounts.lp_fee_token_1_account, &ctx.accounts.creator_token_1_account, token_1_creator),
+            (&ctx.accounts.lp_fee_token_1_account, &ctx.accounts.treasury_token_1_account, token_1_treasury),
+        ];
+        let signer = &[lock_seeds];
+        for (from, to, amount) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.lp_lock.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer,
+            );
+            transfer(cpi_ctx, amount)?;
+        }
+
+        emit!(LpFeesClaimedEvent {
+            mint: mint_key,
+            raydium_pool: ctx.accounts.pool_state.key(),
+            lp_amount_withdrawn: lp_amount,
+            token_0_mint: ctx.accounts.vault_0_mint.key(),
+            token_0_to_creator: token_0_creator,
+            token_0_to_treasury: token_0_treasury,
+            token_1_mint: ctx.accounts.vault_1_mint.key(),
+            token_1_to_creator: token_1_creator,
+            token_1_to_treasury: token_1_treasury,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Harvested LP fees from {} LP tokens", lp_amount);
+
+        Ok(())
+    }
+
+    /// Complete Raydium pool creation with automatic LP burning
+    /// This is a comprehensive instruction that handles the entire process
+    /// 
+    /// NOTE: This requires integration with Raydium's CPMM program
+    /// For now, use the two-step process:
+    /// 1. Create pool manually with Raydium SDK
+    /// 2. Call burn_raydium_lp_tokens to lock liquidity
+    pub fn create_and_lock_raydium_pool(
+        ctx: Context<CreateAndLockRaydiumPool>,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Verify the bonding curve is migrated
+        require!(
+            ctx.accounts.bonding_curve.load()?.is_migrated(),
+            ErrorCode::NotMigrated
+        );
+
+        // NOTE: Pool creation check would be done by checking if lp_burn_info account exists
+        // For now, this is a placeholder instruction
+
+        msg!("Creating Raydium pool with automatic LP burning...");
+
+        // TODO: Implement Raydium CPMM pool creation via CPI
+        // This requires:
+        // 1. CPI to Raydium's initialize_pool instruction
+        // 2. Add liquidity from migration vaults
+        // 3. Receive LP tokens
+        // 4. Immediately burn LP tokens
+        
+        msg!("⚠️  This instruction is not yet fully implemented.");
+        msg!("Please use the two-step manual process:");
+        msg!("1. Create Raydium pool using their SDK/UI");
+        msg!("2. Call burn_raydium_lp_tokens to lock liquidity");
+
+        Err(ErrorCode::NotImplemented.into())
+    }
+
+    /// Write the permanent graduation record for a migrated curve
+    /// The archive is never modified after creation, so once it exists the
+    /// per-buyer records that fed its statistics can be closed for rent
+    pub fn finalize_migration(
+        ctx: Context<FinalizeMigration>,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Verify the bonding curve is migrated
+        let bonding_curve = ctx.accounts.bonding_curve.load()?;
+        require!(
+            bonding_curve.is_migrated(),
+            ErrorCode::NotMigrated
+        );
+
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let archive = &mut ctx.accounts.graduation_archive;
+        archive.mint = bonding_curve.mint;
+        archive.creator = bonding_curve.creator;
+        archive.virtual_sol_reserves = bonding_curve.virtual_sol_reserves;
+        archive.virtual_token_reserves = bonding_curve.virtual_token_reserves;
+        archive.final_sol_reserves = bonding_curve.graduation_sol_reserves;
+        archive.final_token_reserves = bonding_curve.graduation_token_reserves;
+        archive.unique_buyers = bonding_curve.unique_buyers;
+        archive.created_at = bonding_curve.created_at;
+        archive.graduated_at = bonding_curve.graduated_at;
+        archive.duration = bonding_curve.graduated_at
+            .safe_sub(bonding_curve.created_at)?;
+        archive.total_fees_paid = bonding_curve.total_fees_collected;
+        archive.raydium_pool = bonding_curve.raydium_pool;
+        archive.archived_at = current_time;
+        archive.bump = ctx.bumps.graduation_archive;
+
+        emit_cpi!(GraduationArchived {
+            mint: archive.mint,
+            raydium_pool: archive.raydium_pool,
+            unique_buyers: archive.unique_buyers,
+            duration: archive.duration,
+            total_fees_paid: archive.total_fees_paid,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Close a buyer record once the curve's graduation archive exists
+    /// Rent is returned to whoever paid for the record, which is the relayer or
+    /// keeper rather than the buyer when the first buy was sponsored
+    pub fn close_buyer_record(
+        ctx: Context<CloseBuyerRecord>,
+    ) -> Result<()> {
+        emit!(BuyerRecordClosed {
+            mint: ctx.accounts.mint.key(),
+            buyer: ctx.accounts.buyer.key(),
+            rent_payer: ctx.accounts.rent_payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Close a migrated curve's bonding curve account, SOL vault and token account
+    /// All recovered rent (and any SOL dust left in the vault) goes to the treasury
+    /// The graduation archive must be written first so the curve's history is preserved
+    pub fn close_bonding_curve(
+        ctx: Context<CloseBondingCurve>,
+    ) -> Result<()> {
+        // Verify the caller is the platform authority
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.global_config.authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Verify the bonding curve is migrated
+        // The curve signs the CPIs below, so its data is only borrowed for this check
+        let bump = {
+            let bonding_curve = ctx.accounts.bonding_curve.load()?;
+            require!(
+                bonding_curve.is_migrated(),
+                ErrorCode::NotMigrated
+            );
+            // The vault is drained to the treasury, so royalties are settled first
+            require!(bonding_curve.creator_royalties == 0, ErrorCode::RoyaltiesUnclaimed);
+            // The escrow's release timelock is read from the curve
+            require!(bonding_curve.creator_allocation == 0, ErrorCode::CreatorAllocationUnclaimed);
+            bonding_curve.bump
+        };
+
+        // Token side must be fully drained before the ATA can be closed
+        require!(
+            ctx.accounts.bonding_curve_token_account.amount == 0,
+            ErrorCode::CurveNotDrained
+        );
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Close the curve's token account
+        let ata_rent = ctx.accounts.bonding_curve_token_account.to_account_info().lamports();
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+            destination: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.bonding_curve.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        close_account(cpi_ctx)?;
+
+        // Close the SOL vault by moving all of its lamports to the treasury
+        let vault_lamports = drain_lamports(
+            &ctx.accounts.bonding_curve_sol_vault,
+            &ctx.accounts.treasury,
+        )?;
+
+        // Return the launch deposit before the rest of the account goes to the treasury
+        refund_launch_deposit(&ctx.accounts.bonding_curve, &ctx.accounts.creator)?;
+
+        // The bonding curve account itself is closed via the `close` constraint
+        let curve_rent = ctx.accounts.bonding_curve.to_account_info().lamports();
+
+        emit!(BondingCurveClosed {
+            mint: mint_key,
+            treasury: ctx.accounts.treasury.key(),
+            lamports_recovered: ata_rent
+                .safe_add(vault_lamports)?
+                .safe_add(curve_rent)?,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Closed bonding curve accounts for {}", mint_key);
+
+        Ok(())
+    }
+
+    /// Cancel a mistaken launch before anyone but the creator has traded (creator only)
+    /// Unsold tokens, any escrowed creator allocation and the dev buy's SOL go back to the
+    /// creator, the dev buy's fee to the treasury, and the curve, vault, token accounts and
+    /// oracle are closed
+    pub fn cancel_bonding_curve(
+        ctx: Context<CancelBondingCurve>,
+    ) -> Result<()> {
+        // The curve signs the CPIs below, so its data is only borrowed for these checks
+        let (bump, creator_allocation) = {
+            let bonding_curve = ctx.accounts.bonding_curve.load()?;
+            require!(!bonding_curve.is_migrated(), ErrorCode::AlreadyMigrated);
+            // The only trade allowed is the creator's launch dev buy
+            let dev_buy_trades = if bonding_curve.dev_buy_sol > 0 { 1 } else { 0 };
+            require!(
+                bonding_curve.trade_count == dev_buy_trades,
+                ErrorCode::CurveHasTraded
+            );
+            (bonding_curve.bump, bonding_curve.creator_allocation)
+        };
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Return unsold tokens, then close the curve's token account
+        let mut tokens_returned = ctx.accounts.bonding_curve_token_account.amount;
+        if tokens_returned > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                        to: ctx.accounts.creator_token_account.to_account_info(),
+                        authority: ctx.accounts.bonding_curve.to_account_info(),
+                    },
+                    signer,
+                ),
+                tokens_returned,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        // The escrow is owned by the curve, so it can't outlive it
+        if creator_allocation > 0 {
+            let escrow = ctx
+                .accounts
+                .creator_allocation_escrow
+                .as_ref()
+                .ok_or(ErrorCode::CreatorAllocationEscrowRequired)?;
+            tokens_returned = tokens_returned.safe_add(escrow.amount)?;
+            release_creator_allocation(
+                escrow,
+                &ctx.accounts.creator_token_account,
+                &ctx.accounts.bonding_curve,
+                &ctx.accounts.creator.to_account_info(),
+                &ctx.accounts.token_program,
+                signer,
+            )?;
+            ctx.accounts.bonding_curve.load_mut()?.creator_allocation = 0;
+        }
+
+        unwrap_wsol_vault(
+            &ctx.accounts.bonding_curve,
+            ctx.accounts.curve_wsol_vault.as_deref(),
+            &ctx.accounts.bonding_curve_sol_vault,
+            &ctx.accounts.token_program.to_account_info(),
+        )?;
+
+        // Fees from the dev buy belong to the platform; everything else in the vault is the creator's
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let fees = bonding_curve.accumulated_fees;
+        let curve_info = ctx.accounts.bonding_curve.to_account_info();
+        let sol_holder = bonding_curve.sol_holder(curve_info.clone(), &ctx.accounts.bonding_curve_sol_vault);
+        move_lamports(&sol_holder, &ctx.accounts.treasury, fees)?;
+        let mut sol_returned = drain_lamports(
+            &ctx.accounts.bonding_curve_sol_vault,
+            &ctx.accounts.creator.to_account_info(),
+        )?;
+        if bonding_curve.has_inline_sol() {
+            let spare = curve_info.lamports().safe_sub(bonding_curve.sol_floor_lamports()?)?;
+            move_lamports(&curve_info, &ctx.accounts.creator.to_account_info(), spare)?;
+            sol_returned = sol_returned.safe_add(spare)?;
+        }
+        ctx.accounts.bonding_curve.load_mut()?.accumulated_fees = 0;
+
+        refund_launch_deposit(&ctx.accounts.bonding_curve, &ctx.accounts.creator.to_account_info())?;
+
+        // Curves launched before stats were tracked were never counted as active
+        let global_stats = &mut ctx.accounts.global_stats;
+        global_stats.active_curves = global_stats.active_curves.saturating_sub(1);
+
+        // The bonding curve and oracle accounts are closed via their `close` constraints
+        emit!(BondingCurveCancelled {
+            mint: mint_key,
+            creator: ctx.accounts.creator.key(),
+            tokens_returned,
+            sol_returned,
+            fees_to_treasury: fees,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Hold the curve's SOL side as wSOL in a token account owned by the curve (creator only)
+    /// Only possible before the first trade. Buys still pay lamports; sells, fee withdrawals
+    /// and migration move wSOL. Every route that trades the curve must then pass its wSOL
+    /// vault, and sells a wSOL account for the payout. Batch fee withdrawals only support
+    /// lamport vaults
+    pub fn enable_wsol_vault(
+        ctx: Context<EnableWsolVault>,
+    ) -> Result<()> {
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            !bonding_curve.has_wsol_vault()
+                && bonding_curve.trade_count == 0
+                && bonding_curve.real_sol_reserves == 0
+                && bonding_curve.accumulated_fees == 0
+                && bonding_curve.creator_royalty_bps == 0
+                && !bonding_curve.has_inline_sol()
+                && !bonding_curve.has_jackpot(),
+            ErrorCode::WsolVaultUnavailable
+        );
+        bonding_curve.wsol_vault = 1;
+
+        emit!(WsolVaultEnabled {
+            mint: bonding_curve.mint,
+            wsol_vault: ctx.accounts.curve_wsol_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Hold the curve's SOL side in the bonding curve account's own lamports, above its rent
+    /// and launch deposit, instead of the separate sol_vault (creator only)
+    /// Clients then only need to watch one account per market. Only possible before the
+    /// first trade, and not together with a wSOL vault
+    pub fn enable_inline_sol(
+        ctx: Context<EnableInlineSol>,
+    ) -> Result<()> {
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            !bonding_curve.has_inline_sol()
+                && !bonding_curve.has_wsol_vault()
+                && bonding_curve.trade_count == 0
+                && bonding_curve.real_sol_reserves == 0
+                && bonding_curve.accumulated_fees == 0
+                && bonding_curve.creator_royalties == 0,
+            ErrorCode::InlineSolUnavailable
+        );
+        bonding_curve.inline_sol = 1;
+
+        emit!(InlineSolEnabled {
+            mint: bonding_curve.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Only let wallets holding an unexpired `KycAttestation` from the platform's
+    /// `kyc_verifier` buy on the curve (creator only). Only possible before the first trade,
+    /// and can't be undone. Sells stay open to every holder
+    pub fn enable_kyc_gating(
+        ctx: Context<EnableKycGating>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.global_config.kyc_verifier != Pubkey::default(),
+            ErrorCode::KycVerifierNotSet
+        );
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            !bonding_curve.kyc_required() && bonding_curve.trade_count == 0,
+            ErrorCode::KycGatingUnavailable
+        );
+        bonding_curve.kyc_required = 1;
+
+        emit!(KycGatingEnabled {
+            mint: bonding_curve.mint,
+            verifier: ctx.accounts.global_config.kyc_verifier,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Attest that `wallet` passed verification, until `expires_at` (verifier only). Issuing
+    /// again renews the attestation
+    pub fn issue_kyc_attestation(
+        ctx: Context<IssueKycAttestation>,
+        wallet: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(expires_at > current_time, ErrorCode::InvalidKycAttestation);
+
+        let attestation = &mut ctx.accounts.kyc_attestation;
+        attestation.wallet = wallet;
+        attestation.verifier = ctx.accounts.verifier.key();
+        attestation.issued_at = current_time;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.kyc_attestation;
+
+        emit!(KycAttestationIssued {
+            wallet,
+            verifier: attestation.verifier,
+            expires_at,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Price the curve as a liquidity bootstrapping launch (creator only): its virtual SOL
+    /// reserves are scaled by `weights_bps` (10,000 = 1x) at each quarter of a `duration`
+    /// second window opened by finalize_launch, easing down to the plain curve as it ends, so
+    /// the earliest buyers pay the most. Weights must not increase. Only possible before the
+    /// launch is finalized and before any SOL is in the curve
+    pub fn enable_liquidity_bootstrapping(
+        ctx: Context<EnableLiquidityBootstrapping>,
+        weights_bps: [u16; 4],
+        duration: i64,
+    ) -> Result<()> {
+        require!(
+            duration > 0
+                && duration <= BondingCurve::MAX_LBP_DURATION
+                && weights_bps[0] <= BondingCurve::MAX_LBP_WEIGHT_BPS
+                && weights_bps.iter().all(|weight| *weight as u64 >= BondingCurve::LBP_BASE_WEIGHT_BPS)
+                && weights_bps.windows(2).all(|pair| pair[0] >= pair[1]),
+            ErrorCode::InvalidLbpSchedule
+        );
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            !bonding_curve.has_liquidity_bootstrapping()
+                && !bonding_curve.is_launch_finalized()
+                && bonding_curve.real_sol_reserves == 0,
+            ErrorCode::LbpUnavailable
+        );
+        bonding_curve.lbp_weights_bps = weights_bps;
+        bonding_curve.lbp_duration = duration;
+
+        emit!(LiquidityBootstrappingEnabled {
+            mint: bonding_curve.mint,
+            weights_bps,
+            duration,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cap the net SOL any single wallet may put into the curve (creator only, 0 = uncapped)
+    /// Contributions are tracked on each wallet's buyer record, so the cap can only be set
+    /// before the first trade
+    pub fn set_max_sol_per_wallet(
+        ctx: Context<SetMaxSolPerWallet>,
+        max_sol_per_wallet: u64,
+    ) -> Result<()> {
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(bonding_curve.trade_count == 0, ErrorCode::WalletCapUnavailable);
+        bonding_curve.max_sol_per_wallet = max_sol_per_wallet;
+
+        emit!(WalletCapSet {
+            mint: bonding_curve.mint,
+            max_sol_per_wallet,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the creator's royalty on every buy and sell of the curve (creator only)
+    /// Charged on top of the platform fee and held in the SOL vault until claimed. Only
+    /// possible before the first trade, and not on wSOL curves
+    pub fn set_creator_royalty(
+        ctx: Context<SetCreatorRoyalty>,
+        royalty_bps: u16,
+    ) -> Result<()> {
+        require!(
+            royalty_bps <= BondingCurve::MAX_CREATOR_ROYALTY_BPS,
+            ErrorCode::InvalidCreatorRoyalty
+        );
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            bonding_curve.trade_count == 0 && !bonding_curve.has_wsol_vault(),
+            ErrorCode::CreatorRoyaltyUnavailable
+        );
+        bonding_curve.creator_royalty_bps = royalty_bps;
+
+        emit!(CreatorRoyaltySet {
+            mint: bonding_curve.mint,
+            royalty_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay the curve's accrued royalties out of the SOL vault to its creator
+    /// Permissionless so royalties can be settled before the curve's accounts are closed
+    pub fn claim_creator_royalties(
+        ctx: Context<ClaimCreatorRoyalties>,
+    ) -> Result<()> {
+        let bonding_curve = *ctx.accounts.bonding_curve.load()?;
+        let amount = bonding_curve.creator_royalties;
+        require!(amount > 0, ErrorCode::NoRoyaltiesToClaim);
+
+        let sol_holder = bonding_curve.sol_holder(
+            ctx.accounts.bonding_curve.to_account_info(),
+            &ctx.accounts.bonding_curve_sol_vault,
+        );
+        move_lamports(&sol_holder, &ctx.accounts.creator.to_account_info(), amount)?;
+        ctx.accounts.bonding_curve.load_mut()?.creator_royalties = 0;
+
+        emit!(CreatorRoyaltiesClaimed {
+            mint: ctx.accounts.mint.key(),
+            creator: ctx.accounts.creator.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Reserve `allocation_bps` of the supply for the creator, taken out of the curve's
+    /// reserves into an escrow the curve owns (creator only). The tokens can't be claimed
+    /// until `release_delay` seconds after the curve migrates, so the team can't dump on
+    /// buyers before graduation. Only possible before the launch is finalized
+    pub fn escrow_creator_allocation(
+        ctx: Context<EscrowCreatorAllocation>,
+        allocation_bps: u16,
+        release_delay: i64,
+    ) -> Result<()> {
+        require!(
+            allocation_bps > 0
+                && allocation_bps <= BondingCurve::MAX_CREATOR_ALLOCATION_BPS
+                && (0..=BondingCurve::MAX_ALLOCATION_RELEASE_DELAY).contains(&release_delay),
+            ErrorCode::InvalidCreatorAllocation
+        );
+
+        let mint_key = ctx.accounts.mint.key();
+        let (bump, amount) = {
+            let bonding_curve = ctx.accounts.bonding_curve.load()?;
+            require!(
+                !bonding_curve.is_launch_finalized()
+                    && bonding_curve.unique_buyers == 0
+                    && bonding_curve.creator_allocation == 0,
+                ErrorCode::CreatorAllocationUnavailable
+            );
+            let amount = mul_div(
+                ctx.accounts.mint.supply as u128,
+                allocation_bps as u128,
+                10_000,
+                Rounding::Down,
+            )?
+            .to_u64()?;
+            require!(
+                amount > 0 && amount < bonding_curve.real_token_reserves,
+                ErrorCode::InvalidCreatorAllocation
+            );
+            (bonding_curve.bump, amount)
+        };
+
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bonding_curve_token_account.to_account_info(),
+                    to: ctx.accounts.creator_allocation_escrow.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        bonding_curve.real_token_reserves = bonding_curve.real_token_reserves.safe_sub(amount)?;
+        bonding_curve.creator_allocation = amount;
+        bonding_curve.allocation_release_delay = release_delay;
+
+        emit!(CreatorAllocationEscrowed {
+            mint: mint_key,
+            creator: ctx.accounts.creator.key(),
+            amount,
+            release_delay,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay the escrowed creator allocation to the creator once the curve has migrated and
+    /// its release delay has passed, closing the escrow (permissionless)
+    pub fn claim_creator_allocation(
+        ctx: Context<ClaimCreatorAllocation>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mint_key = ctx.accounts.mint.key();
+        let (bump, amount) = {
+            let bonding_curve = ctx.accounts.bonding_curve.load()?;
+            require!(bonding_curve.creator_allocation > 0, ErrorCode::NoCreatorAllocation);
+            require!(bonding_curve.is_migrated(), ErrorCode::NotMigrated);
+            require!(
+                current_time >= bonding_curve
+                    .graduated_at
+                    .safe_add(bonding_curve.allocation_release_delay)?,
+                ErrorCode::CreatorAllocationLocked
+            );
+            (bonding_curve.bump, ctx.accounts.creator_allocation_escrow.amount)
+        };
+
+        let seeds = &[
+            b"bonding_curve",
+            mint_key.as_ref(),
+            &[bump],
+        ];
+        release_creator_allocation(
+            &ctx.accounts.creator_allocation_escrow,
+            &ctx.accounts.creator_token_account,
+            &ctx.accounts.bonding_curve,
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+        )?;
+        ctx.accounts.bonding_curve.load_mut()?.creator_allocation = 0;
+
+        emit!(CreatorAllocationReleased {
+            mint: mint_key,
+            creator: ctx.accounts.creator.key(),
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Send `reflection_bps` of the tokens in every buy and sell to a reflection vault whose
+    /// balance is shared among holders pro-rata to their balances in the mint's holder
+    /// snapshots (creator only). Only possible before the launch is finalized, and can't be
+    /// undone. Every route that trades the curve must then pass its reflection vault
+    pub fn enable_reflections(
+        ctx: Context<EnableReflections>,
+        reflection_bps: u16,
+    ) -> Result<()> {
+        require!(
+            reflection_bps > 0 && reflection_bps <= BondingCurve::MAX_REFLECTION_BPS,
+            ErrorCode::InvalidReflectionBps
+        );
+        let mut bonding_curve = ctx.accounts.bonding_curve.load_mut()?;
+        require!(
+            !bonding_curve.has_reflections() && !bonding_curve.is_launch_finalized(),
+            ErrorCode::ReflectionsUnavailable
+        );
+        bonding_curve.reflection_bps = reflection_bps;
+
+        let pool = &mut ctx.accounts.reflection_pool;
+        pool.mint = ctx.accounts.mint.key();
+        pool.acc_tokens_per_share = 0;
+        pool.total_distributed = 0;
+        pool.total_claimed = 0;
+        pool.next_epoch = 0;
+        pool.bump = ctx.bumps.reflection_pool;
+
+        emit!(ReflectionsEnabled {
+            mint: pool.mint,
+            reflection_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Share the reflections accrued since the last distribution among the holders in
+    /// snapshot `epoch`, pro-rata to their snapshotted balances (permissionless). Epochs are
+    /// distributed in order; skipped ones get nothing
+    pub fn distribute_reflections(
+        ctx: Context<DistributeReflections>,
+        epoch: u64,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.reflection_pool;
+        let snapshot = &ctx.accounts.holder_snapshot;
+        require!(
+            epoch >= pool.next_epoch && snapshot.total_balance > 0,
+            ErrorCode::InvalidReflectionEpoch
+        );
+
+        let undistributed = ctx.accounts.reflection_vault.amount.safe_sub(pool.outstanding()?)?;
+        let tokens_per_share = (undistributed as u128)
+            .safe_mul(ReflectionPool::ACC_PRECISION)?
+            .safe_div(snapshot.total_balance as u128)?;
+        // Rounding dust stays in the vault for the next distribution
+        let amount = (snapshot.total_balance as u128)
+            .safe_mul(tokens_per_share)?
+            .safe_div(ReflectionPool::ACC_PRECISION)?
+            .to_u64()?;
+        require!(amount > 0, ErrorCode::NoReflectionsToDistribute);
+
+        pool.acc_tokens_per_share = pool.acc_tokens_per_share.safe_add(tokens_per_share)?;
+        pool.total_distributed = pool.total_distributed.safe_add(amount)?;
+        pool.next_epoch = epoch.safe_add(1)?;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let distribution = &mut ctx.accounts.reflection_distribution;
+        distribution.mint = pool.mint;
+        distribution.epoch = epoch;
+        distribution.tokens_per_share = tokens_per_share;
+        distribution.amount = amount;
+        distribution.distributed_at = current_time;
+        distribution.bump = ctx.bumps.reflection_distribution;
+
+        emit!(ReflectionsDistributed {
+            mint: pool.mint,
+            epoch,
+            amount,
+            total_balance: snapshot.total_balance,
+            acc_tokens_per_share: pool.acc_tokens_per_share,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Claim the holder's share of the reflections distributed against snapshot `epoch`,
+    /// proving their `balance` with a Merkle `proof` (holder only, once per epoch)
+    pub fn claim_reflections(
+        ctx: Context<ClaimReflections>,
+        epoch: u64,
+        balance: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let holder = ctx.accounts.holder.key();
+        require!(
+            ctx.accounts.holder_snapshot.verify_balance(&holder, balance, &proof),
+            ErrorCode::InvalidSnapshotProof
+        );
+        let distribution = &mut ctx.accounts.reflection_distribution;
+        let amount = distribution.share_of(balance)?;
+        require!(amount > 0, ErrorCode::NoReflectionsToClaim);
+        // The snapshot's total isn't checked against its leaves, so an epoch's claims are
+        // capped at what was distributed and can't reach other epochs' tokens
+        distribution.claimed = distribution.claimed.safe_add(amount)?;
+        require!(
+            distribution.claimed <= distribution.amount,
+            ErrorCode::ReflectionsOverclaimed
+        );
+
+        let pool = &ctx.accounts.reflection_pool;
+        let seeds = &[
+            b"reflection_pool",
+            pool.mint.as_ref(),
+            &[pool.bump],
+        ];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reflection_vault.to_account_info(),
+                    to: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            amount,
+        )?;
+
+        let pool = &mut ctx.accounts.reflection_pool;
+        pool.total_claimed = pool.total_claimed.safe_add(amount)?;
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let claim = &mut ctx.accounts.reflection_claim;
+        claim.holder = holder;
+        claim.epoch = epoch;
+        claim.amount = amount;
+        claim.claimed_at = current_time;
+        claim.bump = ctx.bumps.reflection_claim;
+
+        emit!(ReflectionsClaimed {
+            mint: pool.mint,
+            holder,
+            epoch,
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw a wallet's attestation, returning its rent to the verifier (verifier only)
+    pub fn revoke_kyc_attestation(
+        ctx: Context<RevokeKycAttestation>,
+    ) -> Result<()> {
+        emit!(KycAttestationRevoked {
+            wallet: ctx.accounts.kyc_attestation.wallet,
+            verifier: ctx.accounts.verifier.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the project category registry (authority only, once)
+    pub fn initialize_category_registry(
+        ctx: Context<InitializeCategoryRegistry>,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.category_registry;
+        registry.count = 0;
+        registry.bump = ctx.bumps.category_registry;
+        Ok(())
+    }
+
+    /// Register a new project category (authority only)
+    /// Projects reference categories by index, so existing entries are never renamed or removed
+    pub fn add_category(
+        ctx: Context<AddCategory>,
+        name: String,
+    ) -> Result<()> {
+        validate_text(&name, CategoryRegistry::MAX_NAME, is_printable_ascii, ErrorCode::InvalidCategoryName)?;
+
+        let registry = &mut ctx.accounts.category_registry;
+        require!(
+            (registry.count as usize) < CategoryRegistry::MAX_CATEGORIES,
+            ErrorCode::CategoryRegistryFull
+        );
+        require!(registry.find(&name).is_none(), ErrorCode::DuplicateCategory);
+
+        let index = registry.count;
+        (registry.names[index as usize], registry.name_lens[index as usize]) = fixed_bytes(&name);
+        registry.count = registry.count.safe_add(1)?;
+
+        emit!(CategoryAdded {
+            index,
+            name,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, total_supply: u64, category: u8)]
+pub struct InitializeProject<'info> {
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"project", owner.key().as_ref(), symbol.as_bytes()],
+        bump,
+        space = ProjectState::MAX_SIZE,
+    )]
+    pub project_state: Account<'info, ProjectState>,
+
+    #[account(
+        seeds = [b"category_registry"],
+        bump = category_registry.bump,
+    )]
+    pub category_registry: Box<Account<'info, CategoryRegistry>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BoostProject<'info> {
+    #[account(mut, has_one = owner @ ErrorCode::Unauthorized)]
+    pub project_state: Account<'info, ProjectState>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PostUpdate<'info> {
+    #[account(has_one = owner @ ErrorCode::Unauthorized)]
+    pub project_state: Account<'info, ProjectState>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"update_feed", project_state.key().as_ref()],
+        bump,
+        space = ProjectUpdateFeed::MAX_SIZE,
+    )]
+    pub update_feed: Account<'info, ProjectUpdateFeed>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"project_update", project_state.key().as_ref(), &update_feed.update_count.to_le_bytes()],
+        bump,
+        space = ProjectUpdate::MAX_SIZE,
+    )]
+    pub project_update: Account<'info, ProjectUpdate>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
 pub struct CreateMint<'info> {
     #[account(mut, has_one = owner @ ErrorCode::Unauthorized)]
     pub project_state: Account<'info, ProjectState>,
 
-    #[account(
-        init,
-        payer = owner,
-        mint::decimals = 6,
-        mint::authority = owner,
-        mint::freeze_authority = owner
-    )]
-    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = global_config.token_decimals,
+        mint::authority = owner,
+        mint::freeze_authority = owner
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: This account is initialized by the Metaplex Token Metadata program
+    #[account(
+        mut,
+        address = metadata_pda(&mint.key()) @ ErrorCode::InvalidMetadataAccount,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProjectMint<'info> {
+    #[account(mut, has_one = owner @ ErrorCode::Unauthorized)]
+    pub project_state: Account<'info, ProjectState>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"project_mint", project_state.key().as_ref()],
+        bump,
+        mint::decimals = global_config.token_decimals,
+        mint::authority = owner,
+        mint::freeze_authority = owner
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: This account is initialized by the Metaplex Token Metadata program
+    #[account(
+        mut,
+        address = metadata_pda(&mint.key()) @ ErrorCode::InvalidMetadataAccount,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"global_config"],
+        bump,
+        space = GlobalConfig::MAX_SIZE,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSnapshot<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"snapshot_registry", mint.key().as_ref()],
+        bump,
+        space = SnapshotRegistry::MAX_SIZE,
+    )]
+    pub snapshot_registry: Account<'info, SnapshotRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"holder_snapshot", mint.key().as_ref(), &snapshot_registry.next_epoch.to_le_bytes()],
+        bump,
+        space = HolderSnapshot::MAX_SIZE,
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Nominated authority (may be a PDA signing via CPI)
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueueConfigUpdate<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Only one update can be pending at a time (new account)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"pending_config_update"],
+        bump,
+        space = PendingConfigUpdate::MAX_SIZE,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_config_update"],
+        bump = pending_config_update.bump,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigUpdate<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_config_update"],
+        bump = pending_config_update.bump,
+    )]
+    pub pending_config_update: Account<'info, PendingConfigUpdate>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SimulateGlobalConfig<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseGlobalConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+    )]
+    /// CHECK: We're closing this account without deserializing it - authority is read from raw data
+    pub global_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeGlobalConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+    )]
+    /// CHECK: May still use an older layout - authority is read from raw data
+    pub global_config: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeBondingCurve<'info> {
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: May still use an older layout - decoded by `legacy_curve::upgrade`
+    pub bonding_curve: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Pays rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalStats<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"global_stats"],
+        bump,
+        space = GlobalStats::MAX_SIZE,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorFlags<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_stats", creator_stats.creator.as_ref()],
+        bump = creator_stats.bump,
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableJackpot<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"jackpot", mint.key().as_ref()],
+        bump,
+        space = Jackpot::MAX_SIZE,
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimJackpot<'info> {
+    #[account(
+        mut,
+        seeds = [b"jackpot", jackpot.mint.as_ref()],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(
+        mut,
+        address = jackpot.pending_winner @ ErrorCode::Unauthorized,
+    )]
+    /// CHECK: Receives the payout; checked against the draw's winner
+    pub winner: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRandomness<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"randomness_request", consumer.key().as_ref()],
+        bump,
+        space = RandomnessRequest::MAX_SIZE,
+    )]
+    pub randomness_request: Account<'info, RandomnessRequest>,
+
+    /// CHECK: Loaded as the requested kind of consumer, which checks its owner
+    pub consumer: UncheckedAccount<'info>,
+
+    /// CHECK: Parsed as a Switchboard randomness account, which checks its owner
+    pub randomness_account: UncheckedAccount<'info>,
+
+    /// The randomness account of the expired request being replaced, required to replace one
+    #[account(
+        address = randomness_request.randomness_account @ ErrorCode::InvalidRandomnessAccount,
+    )]
+    /// CHECK: Parsed as a Switchboard randomness account, which checks its owner
+    pub previous_randomness_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillRandomness<'info> {
+    #[account(
+        mut,
+        seeds = [b"randomness_request", consumer.key().as_ref()],
+        bump = randomness_request.bump,
+    )]
+    pub randomness_request: Account<'info, RandomnessRequest>,
+
+    #[account(mut)]
+    /// CHECK: Loaded as the request's kind of consumer, which checks its owner
+    pub consumer: UncheckedAccount<'info>,
+
+    #[account(
+        address = randomness_request.randomness_account @ ErrorCode::InvalidRandomnessAccount,
+    )]
+    /// CHECK: Parsed as a Switchboard randomness account, which checks its owner
+    pub randomness_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetComplianceFlags<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        constraint = project_state.mint == mint.key() @ ErrorCode::InvalidMint,
+    )]
+    pub project_state: Account<'info, ProjectState>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"compliance_gate", mint.key().as_ref()],
+        bump,
+        space = ComplianceGate::MAX_SIZE,
+    )]
+    pub compliance_gate: Account<'info, ComplianceGate>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FlagCurve<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceMigrationStatus<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordRaydiumPool<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(
+        constraint = raydium_pool.owner == &global_config.raydium_amm_program
+            && raydium_pool_trades_mint(&raydium_pool, &bonding_curve.load()?.mint) @ ErrorCode::InvalidRaydiumPool,
+    )]
+    /// CHECK: Pool state, validated as a configured Raydium program pool trading the curve's mint
+    pub raydium_pool: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCurveFees<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.load()?.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVesting<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"vesting", mint.key().as_ref(), creator.key().as_ref()],
+        bump,
+        space = VestingSchedule::MAX_SIZE,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimVestedTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"vesting", mint.key().as_ref(), beneficiary.key().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = beneficiary @ ErrorCode::Unauthorized,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+
+    /// Any existing token account the beneficiary owns, e.g. their ATA or a cold wallet's
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = beneficiary,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Must match the vesting schedule's beneficiary (has_one); doesn't need to sign
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// The beneficiary or the schedule's claim delegate
+    #[account(
+        constraint = vesting_schedule.can_claim(&claimer.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub claimer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [b"vesting", mint.key().as_ref(), beneficiary.key().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = beneficiary @ ErrorCode::Unauthorized,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub beneficiary: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetClaimableAmount<'info> {
+    #[account(
+        seeds = [b"vesting", mint.key().as_ref(), vesting_schedule.beneficiary.as_ref()],
+        bump = vesting_schedule.bump,
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSolVesting<'info> {
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"sol_vesting", funder.key().as_ref(), beneficiary.key().as_ref()],
+        bump,
+        space = SolVestingSchedule::MAX_SIZE,
+    )]
+    pub sol_vesting_schedule: Account<'info, SolVestingSchedule>,
+
+    /// CHECK: Only recorded as the schedule's beneficiary
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVestedSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"sol_vesting", sol_vesting_schedule.funder.as_ref(), beneficiary.key().as_ref()],
+        bump = sol_vesting_schedule.bump,
+        has_one = beneficiary @ ErrorCode::Unauthorized,
+    )]
+    pub sol_vesting_schedule: Account<'info, SolVestingSchedule>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct LockTokens<'info> {
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"token_lock", mint.key().as_ref(), owner.key().as_ref(), &index.to_le_bytes()],
+        bump,
+        space = TokenLock::MAX_SIZE,
+    )]
+    pub token_lock: Account<'info, TokenLock>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Escrow holding the locked tokens (owned by the token_lock PDA)
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = token_lock,
+    )]
+    pub lock_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    #[account(
+        mut,
+        seeds = [b"token_lock", token_lock.mint.as_ref(), owner.key().as_ref(), &token_lock.index.to_le_bytes()],
+        bump = token_lock.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub token_lock: Account<'info, TokenLock>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAfterUnlock<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"token_lock", mint.key().as_ref(), owner.key().as_ref(), &token_lock.index.to_le_bytes()],
+        bump = token_lock.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub token_lock: Account<'info, TokenLock>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_lock,
+    )]
+    pub lock_vault: Account<'info, TokenAccount>,
+
+    /// Any token account the owner holds for this mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateOtcDeal<'info> {
+    #[account(
+        init,
+        payer = seller,
+        seeds = [b"otc_deal", seller.key().as_ref(), &id.to_le_bytes()],
+        bump,
+        space = OtcDeal::MAX_SIZE,
+    )]
+    pub otc_deal: Account<'info, OtcDeal>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Escrow for the token leg (owned by the otc_deal PDA)
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = otc_deal,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct DepositOtcTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"otc_deal", seller.key().as_ref(), &otc_deal.id.to_le_bytes()],
+        bump = otc_deal.bump,
+        has_one = seller @ ErrorCode::Unauthorized,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub otc_deal: Account<'info, OtcDeal>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = otc_deal,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DepositOtcSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"otc_deal", otc_deal.seller.as_ref(), &otc_deal.id.to_le_bytes()],
+        bump = otc_deal.bump,
+        has_one = buyer @ ErrorCode::Unauthorized,
+    )]
+    pub otc_deal: Account<'info, OtcDeal>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleOtcDeal<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"otc_deal", seller.key().as_ref(), &otc_deal.id.to_le_bytes()],
+        bump = otc_deal.bump,
+        has_one = seller @ ErrorCode::InvalidOtcCounterparty,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub otc_deal: Account<'info, OtcDeal>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = otc_deal,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    /// Any token account the buyer holds for the mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = otc_deal.buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the SOL leg and the escrow rent; must match the deal's seller (has_one)
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOtcDeal<'info> {
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"otc_deal", seller.key().as_ref(), &otc_deal.id.to_le_bytes()],
+        bump = otc_deal.bump,
+        has_one = seller @ ErrorCode::InvalidOtcCounterparty,
+        has_one = buyer @ ErrorCode::InvalidOtcCounterparty,
+        has_one = mint @ ErrorCode::InvalidMint,
+        constraint = party.key() == otc_deal.seller || party.key() == otc_deal.buyer @ ErrorCode::Unauthorized,
+    )]
+    pub otc_deal: Account<'info, OtcDeal>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = otc_deal,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+
+    /// Refund destination for a deposited token leg
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the escrow rent; must match the deal's seller (has_one)
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Refund destination for a deposited SOL leg; must match the deal's buyer (has_one)
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// The seller or the buyer
+    pub party: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateSolStream<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"payment_stream", payer.key().as_ref(), &id.to_le_bytes()],
+        bump,
+        space = PaymentStream::MAX_SIZE,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateTokenStream<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"payment_stream", payer.key().as_ref(), &id.to_le_bytes()],
+        bump,
+        space = PaymentStream::MAX_SIZE,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Escrow for the streamed tokens (owned by the payment_stream PDA)
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = payment_stream,
+    )]
+    pub stream_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = payer,
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"payment_stream", payment_stream.payer.as_ref(), &payment_stream.id.to_le_bytes()],
+        bump = payment_stream.bump,
+        has_one = payee @ ErrorCode::Unauthorized,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    /// Token streams only: the stream's escrow
+    #[account(
+        mut,
+        address = get_associated_token_address(&payment_stream.key(), &payment_stream.mint) @ ErrorCode::InvalidStreamAccounts,
+    )]
+    pub stream_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Token streams only: any token account the payee holds for the mint
+    #[account(
+        mut,
+        token::mint = payment_stream.mint,
+        token::authority = payee,
+    )]
+    pub payee_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payee: Signer<'info>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"payment_stream", payer.key().as_ref(), &payment_stream.id.to_le_bytes()],
+        bump = payment_stream.bump,
+        has_one = payer @ ErrorCode::Unauthorized,
+        has_one = payee @ ErrorCode::InvalidStreamAccounts,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    /// Token streams only: the stream's escrow, closed to the payer
+    #[account(
+        mut,
+        address = get_associated_token_address(&payment_stream.key(), &payment_stream.mint) @ ErrorCode::InvalidStreamAccounts,
+    )]
+    pub stream_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Token streams only: any token account the payee holds for the mint
+    #[account(
+        mut,
+        token::mint = payment_stream.mint,
+        token::authority = payee,
+    )]
+    pub payee_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token streams only: receives the unstreamed tokens
+    #[account(
+        mut,
+        token::mint = payment_stream.mint,
+        token::authority = payer,
+    )]
+    pub payer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Receives streamed SOL; must match the stream's payee (has_one)
+    #[account(mut)]
+    pub payee: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateFundingRound<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"funding_round", mint.key().as_ref()],
+        bump,
+        space = FundingRound::MAX_SIZE,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Escrow for the token allocation (owned by the funding_round PDA)
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = funding_round,
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct InvestInRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"funding_round", funding_round.mint.as_ref()],
+        bump = funding_round.bump,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        seeds = [b"round_position", funding_round.mint.as_ref(), investor.key().as_ref()],
+        bump,
+        space = RoundPosition::MAX_SIZE,
+    )]
+    pub round_position: Account<'info, RoundPosition>,
+
+    #[account(
+        seeds = [b"bonding_curve", funding_round.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertFundingRound<'info> {
+    #[account(
+        mut,
+        seeds = [b"funding_round", mint.key().as_ref()],
+        bump = funding_round.bump,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = funding_round,
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    /// Receives the allocation tokens the round's deposits don't need
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the raised SOL; must match the round's creator (has_one)
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRoundTokens<'info> {
+    #[account(
+        seeds = [b"funding_round", mint.key().as_ref()],
+        bump = funding_round.bump,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    #[account(
+        mut,
+        close = investor,
+        seeds = [b"round_position", mint.key().as_ref(), investor.key().as_ref()],
+        bump = round_position.bump,
+        has_one = investor @ ErrorCode::Unauthorized,
+    )]
+    pub round_position: Account<'info, RoundPosition>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = funding_round,
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = investor,
+        associated_token::mint = mint,
+        associated_token::authority = investor,
+    )]
+    pub investor_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct RefundRoundDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"funding_round", funding_round.mint.as_ref()],
+        bump = funding_round.bump,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    #[account(
+        mut,
+        close = investor,
+        seeds = [b"round_position", funding_round.mint.as_ref(), investor.key().as_ref()],
+        bump = round_position.bump,
+        has_one = investor @ ErrorCode::Unauthorized,
+    )]
+    pub round_position: Account<'info, RoundPosition>,
+
+    #[account(
+        seeds = [b"bonding_curve", funding_round.mint.as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub investor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimRoundTokens<'info> {
+    #[account(
+        seeds = [b"funding_round", mint.key().as_ref()],
+        bump = funding_round.bump,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = funding_round,
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EnableRageQuit<'info> {
+    #[account(
+        mut,
+        seeds = [b"funding_round", funding_round.mint.as_ref()],
+        bump = funding_round.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseRoundMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [b"funding_round", funding_round.mint.as_ref()],
+        bump = funding_round.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    /// CHECK: Receives the released SOL; must match the round's creator (has_one)
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RageQuit<'info> {
+    #[account(
+        mut,
+        seeds = [b"funding_round", mint.key().as_ref()],
+        bump = funding_round.bump,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub funding_round: Account<'info, FundingRound>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder,
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCurveOracle<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump,
+        space = CurveOracle::MAX_SIZE,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(bucket_start: i64)]
+pub struct FinalizeCandle<'info> {
+    #[account(
+        mut,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump = live_candle.bump,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Finalized candle for the bucket (new account)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"candle", mint.key().as_ref(), &bucket_start.to_le_bytes()],
+        bump,
+        space = Candle::MAX_SIZE,
+    )]
+    pub candle: Account<'info, Candle>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshKingOfTheHill<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"king_of_the_hill"],
+        bump,
+        space = KingOfTheHill::MAX_SIZE,
+    )]
+    pub king_of_the_hill: Account<'info, KingOfTheHill>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(competition_id: u64)]
+pub struct CreateCompetition<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"competition".as_ref(), competition_id.to_le_bytes().as_ref()],
+        bump,
+        space = Competition::MAX_SIZE,
+    )]
+    pub competition: Account<'info, Competition>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent and funds the prize pool (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterForCompetition<'info> {
+    #[account(
+        mut,
+        seeds = [b"competition", &competition.id.to_le_bytes()],
+        bump = competition.bump,
+    )]
+    pub competition: Account<'info, Competition>,
+
+    #[account(
+        init,
+        payer = trader,
+        seeds = [b"competition_entry", competition.key().as_ref(), trader.key().as_ref()],
+        bump,
+        space = CompetitionEntry::MAX_SIZE,
+    )]
+    pub competition_entry: Account<'info, CompetitionEntry>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeCompetition<'info> {
+    #[account(
+        mut,
+        seeds = [b"competition", &competition.id.to_le_bytes()],
+        bump = competition.bump,
+    )]
+    pub competition: Account<'info, Competition>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPrize<'info> {
+    #[account(
+        mut,
+        seeds = [b"competition", &competition.id.to_le_bytes()],
+        bump = competition.bump,
+    )]
+    pub competition: Account<'info, Competition>,
+
+    #[account(
+        mut,
+        seeds = [b"competition_entry", competition.key().as_ref(), trader.key().as_ref()],
+        bump = competition_entry.bump,
+    )]
+    pub competition_entry: Account<'info, CompetitionEntry>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateBadgeCollection<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"badge_collection", mint.key().as_ref()],
+        bump,
+        space = BadgeCollection::MAX_SIZE,
+    )]
+    pub badge_collection: Account<'info, BadgeCollection>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(kind: BadgeKind)]
+pub struct ClaimBadge<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"buyer_record", mint.key().as_ref(), buyer.key().as_ref()],
+        bump = buyer_record.bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"badge_collection", mint.key().as_ref()],
+        bump = badge_collection.bump,
+    )]
+    pub badge_collection: Account<'info, BadgeCollection>,
+
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [b"badge_mint", mint.key().as_ref(), buyer.key().as_ref(), &[kind as u8]],
+        bump,
+        mint::decimals = 0,
+        mint::authority = badge_authority,
+        mint::freeze_authority = badge_authority,
+    )]
+    pub badge_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = badge_mint,
+        associated_token::authority = buyer,
+    )]
+    pub badge_token_account: Account<'info, TokenAccount>,
+
+    /// Mint, freeze and update authority of every badge (a PDA)
+    #[account(
+        seeds = [b"badge_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for badge mints
+    pub badge_authority: AccountInfo<'info>,
+
+    /// CHECK: This account is initialized by the Metaplex Token Metadata program
+    #[account(
+        mut,
+        address = metadata_pda(&badge_mint.key()) @ ErrorCode::InvalidMetadataAccount,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: This account is initialized by the Metaplex Token Metadata program
+    #[account(
+        mut,
+        address = master_edition_pda(&badge_mint.key()) @ ErrorCode::InvalidMetadataAccount,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct OpenTraderPosition<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"trader_position", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = TraderPosition::MAX_SIZE,
+    )]
+    pub trader_position: Account<'info, TraderPosition>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTraderPosition<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"trader_position", mint.key().as_ref(), owner.key().as_ref()],
+        bump = trader_position.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+        has_one = mint @ ErrorCode::InvalidMint,
+    )]
+    pub trader_position: Account<'info, TraderPosition>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    #[account(
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct GetCurveState<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct GetCurveUsdValue<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Checked against global config and parsed by `oracle::sol_usd`
+    pub sol_usd_price: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBondingCurve<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump,
+        space = BondingCurve::MAX_SIZE,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+        space = 0,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump,
+        space = CurveOracle::MAX_SIZE,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// A graduated (and possibly closed) curve must never be re-initialized
+    #[account(
+        seeds = [b"graduation_archive", mint.key().as_ref()],
+        bump,
+        constraint = graduation_archive.data_is_empty() @ ErrorCode::CurveAlreadyGraduated,
+    )]
+    /// CHECK: Only checked to be uninitialized
+    pub graduation_archive: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// Launch history and reputation of the creator
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [b"creator_stats", creator.key().as_ref()],
+        bump,
+        space = CreatorStats::MAX_SIZE,
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    /// SOL/USD price the launch fee is converted at; required while it's priced in USD
+    /// CHECK: Checked against global config and parsed by `oracle::sol_usd`
+    pub sol_usd_price: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the trader
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"user_points", buyer.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the trader's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"trade_guard", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    /// Per-wallet record used to count unique buyers (optional; curves
+    /// with a per-wallet cap require it)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"buyer_record", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        space = BuyerRecord::MAX_SIZE,
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// Optional position tracking for the trader (see `open_trader_position`)
+    #[account(
+        mut,
+        seeds = [b"trader_position", mint.key().as_ref(), buyer.key().as_ref()],
+        bump = trader_position.bump,
+    )]
+    pub trader_position: Option<Account<'info, TraderPosition>>,
+
+    /// Optional competition the trade counts towards (pass with `competition_entry`)
+    #[account(mut)]
+    pub competition: Option<Account<'info, Competition>>,
+
+    /// The trader's entry in `competition`
+    #[account(mut)]
+    pub competition_entry: Option<Account<'info, CompetitionEntry>>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Optional migration accounts: when all are passed, a buy that crosses the threshold
+    /// migrates the curve in the same transaction and the buyer earns the keeper reward
+    #[account(
+        mut,
+        seeds = [b"migration_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for migration
+    pub migration_sol_vault: Option<UncheckedAccount<'info>>,
+
+    /// Must already exist (created by the keeper or the buyer ahead of time)
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_stats", bonding_curve.load()?.creator.as_ref()],
+        bump = creator_stats.bump,
+    )]
+    pub creator_stats: Option<Account<'info, CreatorStats>>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury address validated against global config
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// The buyer's attestation, required on KYC-gated curves
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// The mint's compliance gate, required on compliance-gated curves
+    #[account(
+        seeds = [b"compliance_gate", mint.key().as_ref()],
+        bump = compliance_gate.bump,
+    )]
+    pub compliance_gate: Option<Account<'info, ComplianceGate>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's jackpot, required on curves with one
+    #[account(
+        mut,
+        seeds = [b"jackpot", mint.key().as_ref()],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Pays rent for accounts created on the buyer's first trade: the buyer itself, or a
+    /// relayer sponsoring the transaction
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> BuyTokens<'info> {
+    // Migration accounts for an in-transaction migration, if the buyer supplied all of them
+    fn curve_migration<'a>(
+        &'a mut self,
+        treasury_split: &'a [AccountInfo<'info>],
+    ) -> Option<CurveMigration<'a, 'info>> {
+        let creator_stats = self.creator_stats.as_mut()?;
+        let creator_stats_bump = creator_stats.bump;
+        Some(CurveMigration {
+            bonding_curve: &self.bonding_curve,
+            bonding_curve_sol_vault: &self.bonding_curve_sol_vault,
+            bonding_curve_token_account: &self.bonding_curve_token_account,
+            curve_wsol_vault: self.curve_wsol_vault.as_ref().map(|vault| vault.as_ref()),
+            migration_sol_vault: self.migration_sol_vault.as_deref()?,
+            migration_token_account: self.migration_token_account.as_ref()?,
+            creator_stats,
+            creator_stats_bump,
+            treasury: self.treasury.as_deref()?,
+            treasury_split,
+            keeper: self.buyer.as_ref(),
+            global_config: &self.global_config,
+            global_stats: &mut self.global_stats,
+            token_program: &self.token_program,
+        })
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the trader
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"user_points", seller.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the trader's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"trade_guard", mint.key().as_ref(), seller.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    /// Optional position tracking for the trader (see `open_trader_position`)
+    #[account(
+        mut,
+        seeds = [b"trader_position", mint.key().as_ref(), seller.key().as_ref()],
+        bump = trader_position.bump,
+    )]
+    pub trader_position: Option<Account<'info, TraderPosition>>,
+
+    /// Optional competition the trade counts towards (pass with `competition_entry`)
+    #[account(mut)]
+    pub competition: Option<Account<'info, Competition>>,
+
+    /// The trader's entry in `competition`
+    #[account(mut)]
+    pub competition_entry: Option<Account<'info, CompetitionEntry>>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Receives the payout from a wSOL curve
+    #[account(
+        mut,
+        constraint = seller_wsol_account.mint == native_mint::ID @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub seller_wsol_account: Option<Account<'info, TokenAccount>>,
+
+    /// The seller's buyer record, credited with the payout on curves with a per-wallet cap
+    #[account(
+        mut,
+        seeds = [b"buyer_record", mint.key().as_ref(), seller.key().as_ref()],
+        bump = buyer_record.bump,
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Pays rent for accounts created on the seller's first trade: the seller itself, or a
+    /// relayer sponsoring the transaction
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct AuthorizeTradeDelegate<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"trade_delegate", owner.key().as_ref(), session_key.as_ref()],
+        bump,
+        space = TradeDelegate::MAX_SIZE,
+    )]
+    pub trade_delegate: Account<'info, TradeDelegate>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeTradeDelegate<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"trade_delegate", owner.key().as_ref(), trade_delegate.session_key.as_ref()],
+        bump = trade_delegate.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub trade_delegate: Account<'info, TradeDelegate>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DelegatedBuy<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the owner
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        seeds = [b"user_points", owner.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the owner's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        seeds = [b"trade_guard", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    /// Per-wallet record used to count unique buyers (optional; curves
+    /// with a per-wallet cap require it)
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        seeds = [b"buyer_record", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = BuyerRecord::MAX_SIZE,
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// Pays for the buy out of its deposited budget
+    #[account(
+        mut,
+        seeds = [b"trade_delegate", owner.key().as_ref(), session_key.key().as_ref()],
+        bump = trade_delegate.bump,
+    )]
+    pub trade_delegate: Account<'info, TradeDelegate>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The owner's attestation, required on KYC-gated curves
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// The mint's compliance gate, required on compliance-gated curves
+    #[account(
+        seeds = [b"compliance_gate", mint.key().as_ref()],
+        bump = compliance_gate.bump,
+    )]
+    pub compliance_gate: Option<Account<'info, ComplianceGate>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's jackpot, required on curves with one
+    #[account(
+        mut,
+        seeds = [b"jackpot", mint.key().as_ref()],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: The wallet traded for, bound to the session key by the delegate's seeds
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub session_key: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DelegatedSell<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the owner
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        seeds = [b"user_points", owner.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the owner's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = session_key,
+        seeds = [b"trade_guard", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    /// Moves the owner's tokens as their SPL delegate
+    #[account(
+        mut,
+        seeds = [b"trade_delegate", owner.key().as_ref(), session_key.key().as_ref()],
+        bump = trade_delegate.bump,
+    )]
+    pub trade_delegate: Account<'info, TradeDelegate>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Receives the owner's payout from a wSOL curve
+    #[account(
+        mut,
+        constraint = owner_wsol_account.mint == native_mint::ID
+            && owner_wsol_account.owner == owner.key() @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub owner_wsol_account: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: The wallet traded for, bound to the session key by the delegate's seeds;
+    /// receives the proceeds
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub session_key: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceLimitOrder<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Holds the escrowed lamports of a buy order
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"limit_order", owner.key().as_ref(), &order_id.to_le_bytes()],
+        bump,
+        space = LimitOrder::MAX_SIZE,
+    )]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    /// Holds the escrowed tokens of a sell order
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = limit_order,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// The tokens to sell, required for sell orders
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FillLimitOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the owner
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"user_points", owner.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the owner's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"trade_guard", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    /// Receives the tokens of a buy order
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    /// Per-wallet record used to count unique buyers (optional; curves
+    /// with a per-wallet cap require it)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"buyer_record", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = BuyerRecord::MAX_SIZE,
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"limit_order", owner.key().as_ref(), &limit_order.order_id.to_le_bytes()],
+        bump = limit_order.bump,
+        has_one = owner @ ErrorCode::InvalidLimitOrder,
+        has_one = mint @ ErrorCode::InvalidLimitOrder,
+    )]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = limit_order,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// The owner's attestation, required for buys on KYC-gated curves
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// The mint's compliance gate, required for buys on compliance-gated curves
+    #[account(
+        seeds = [b"compliance_gate", mint.key().as_ref()],
+        bump = compliance_gate.bump,
+    )]
+    pub compliance_gate: Option<Account<'info, ComplianceGate>>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Receives the owner's payout from a wSOL curve
+    #[account(
+        mut,
+        constraint = owner_wsol_account.mint == native_mint::ID
+            && owner_wsol_account.owner == owner.key() @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub owner_wsol_account: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's jackpot, required on curves with one
+    #[account(
+        mut,
+        seeds = [b"jackpot", mint.key().as_ref()],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: The order's owner, bound by its seeds; receives the proceeds and rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"limit_order", owner.key().as_ref(), &limit_order.order_id.to_le_bytes()],
+        bump = limit_order.bump,
+        has_one = owner @ ErrorCode::InvalidLimitOrder,
+        has_one = mint @ ErrorCode::InvalidLimitOrder,
+    )]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = limit_order,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// Receives the escrowed tokens of a sell order
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: The order's owner, bound by its seeds; receives the refund
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// The owner, or anyone once the order has expired
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyBasket<'info> {
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        seeds = [b"user_points", buyer.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Box<Account<'info, UserPoints>>,
+
+    /// The buyer's attestation, required when any bought curve is KYC-gated
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Box<Account<'info, GlobalStats>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SwapBetweenCurves<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", sell_mint.key().as_ref()],
+        bump = sell_bonding_curve.load()?.bump,
+    )]
+    pub sell_bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub sell_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", sell_mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the sold curve
+    pub sell_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = sell_mint,
+        associated_token::authority = sell_bonding_curve,
+    )]
+    pub sell_curve_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Price oracle of the sold curve
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", sell_mint.key().as_ref()],
+        bump = sell_curve_oracle.bump,
+    )]
+    pub sell_curve_oracle: Box<Account<'info, CurveOracle>>,
+
+    /// In-progress OHLCV candle of the sold curve
+    #[account(
+        init_if_needed,
+        payer = trader,
+        seeds = [b"live_candle", sell_mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub sell_live_candle: Box<Account<'info, LiveCandle>>,
+
+    /// Slots of the trader's last buy and sell on the sold curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = trader,
+        seeds = [b"trade_guard", sell_mint.key().as_ref(), trader.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub sell_trade_guard: Box<Account<'info, TradeGuard>>,
+
+    #[account(
+        mut,
+        associated_token::mint = sell_mint,
+        associated_token::authority = trader,
+    )]
+    pub seller_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", buy_mint.key().as_ref()],
+        bump = buy_bonding_curve.load()?.bump,
+    )]
+    pub buy_bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(constraint = buy_mint.key() != sell_mint.key() @ ErrorCode::SameCurveSwap)]
+    pub buy_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", buy_mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bought curve
+    pub buy_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = buy_mint,
+        associated_token::authority = buy_bonding_curve,
+    )]
+    pub buy_curve_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Price oracle of the bought curve
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", buy_mint.key().as_ref()],
+        bump = buy_curve_oracle.bump,
+    )]
+    pub buy_curve_oracle: Box<Account<'info, CurveOracle>>,
+
+    /// In-progress OHLCV candle of the bought curve
+    #[account(
+        init_if_needed,
+        payer = trader,
+        seeds = [b"live_candle", buy_mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub buy_live_candle: Box<Account<'info, LiveCandle>>,
+
+    /// Slots of the trader's last buy and sell on the bought curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = trader,
+        seeds = [b"trade_guard", buy_mint.key().as_ref(), trader.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub buy_trade_guard: Box<Account<'info, TradeGuard>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        associated_token::mint = buy_mint,
+        associated_token::authority = trader,
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Per-wallet record on the bought curve, used to count unique buyers (optional; curves
+    /// with a per-wallet cap require it)
+    #[account(
+        init_if_needed,
+        payer = trader,
+        seeds = [b"buyer_record", buy_mint.key().as_ref(), trader.key().as_ref()],
+        bump,
+        space = BuyerRecord::MAX_SIZE,
+    )]
+    pub buyer_record: Option<Box<Account<'info, BuyerRecord>>>,
+
+    /// Loyalty points accrued by the trader
+    #[account(
+        init_if_needed,
+        payer = trader,
+        seeds = [b"user_points", trader.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Box<Account<'info, UserPoints>>,
+
+    /// The trader's attestation, required when the bought curve is KYC-gated
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// The bought mint's compliance gate, required when its curve is compliance-gated
+    #[account(
+        seeds = [b"compliance_gate", buy_mint.key().as_ref()],
+        bump = compliance_gate.bump,
+    )]
+    pub compliance_gate: Option<Account<'info, ComplianceGate>>,
+
+    /// The sold curve's wSOL vault, required when it holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&sell_bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub sell_curve_wsol_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Receives the sale's proceeds when the sold curve holds wSOL
+    #[account(
+        mut,
+        constraint = trader_wsol_account.mint == native_mint::ID @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub trader_wsol_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The bought curve's wSOL vault, required when it holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&buy_bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub buy_curve_wsol_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The sold curve's reflection vault, required when it takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", sell_mint.key().as_ref()],
+        bump,
+    )]
+    pub sell_reflection_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The bought curve's reflection vault, required when it takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", buy_mint.key().as_ref()],
+        bump,
+    )]
+    pub buy_reflection_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The bought curve's jackpot, required when it has one
+    #[account(
+        mut,
+        seeds = [b"jackpot", buy_mint.key().as_ref()],
+        bump = buy_jackpot.bump,
+    )]
+    pub buy_jackpot: Option<Box<Account<'info, Jackpot>>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Box<Account<'info, GlobalStats>>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceConditionalOrder<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Holds the keeper's tip
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"conditional_order", owner.key().as_ref(), &order_id.to_le_bytes()],
+        bump,
+        space = ConditionalOrder::MAX_SIZE,
+    )]
+    pub conditional_order: Account<'info, ConditionalOrder>,
+
+    /// Holds the tokens to sell
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = conditional_order,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteConditionalOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the owner
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"user_points", owner.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the owner's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"trade_guard", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    /// Receives any tokens sent to the escrow from outside
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"conditional_order", owner.key().as_ref(), &conditional_order.order_id.to_le_bytes()],
+        bump = conditional_order.bump,
+        has_one = owner @ ErrorCode::InvalidConditionalOrder,
+        has_one = mint @ ErrorCode::InvalidConditionalOrder,
+    )]
+    pub conditional_order: Account<'info, ConditionalOrder>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = conditional_order,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Receives the owner's payout from a wSOL curve
+    #[account(
+        mut,
+        constraint = owner_wsol_account.mint == native_mint::ID
+            && owner_wsol_account.owner == owner.key() @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub owner_wsol_account: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: The order's owner, bound by its seeds; receives the proceeds and rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConditionalOrder<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"conditional_order", owner.key().as_ref(), &conditional_order.order_id.to_le_bytes()],
+        bump = conditional_order.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+        has_one = mint @ ErrorCode::InvalidConditionalOrder,
+    )]
+    pub conditional_order: Account<'info, ConditionalOrder>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = conditional_order,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDcaSchedule<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Holds the deposit the buys are paid from
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"dca_schedule", owner.key().as_ref(), mint.key().as_ref()],
+        bump,
+        space = DcaSchedule::MAX_SIZE,
+    )]
+    pub dca_schedule: Account<'info, DcaSchedule>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteDcaBuy<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the owner
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"user_points", owner.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the owner's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"trade_guard", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    /// Per-wallet record used to count unique buyers (optional; curves
+    /// with a per-wallet cap require it)
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"buyer_record", mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = BuyerRecord::MAX_SIZE,
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// Pays for the buy and the keeper's tip out of its deposit
+    #[account(
+        mut,
+        seeds = [b"dca_schedule", owner.key().as_ref(), mint.key().as_ref()],
+        bump = dca_schedule.bump,
+    )]
+    pub dca_schedule: Account<'info, DcaSchedule>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The owner's attestation, required on KYC-gated curves
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// The mint's compliance gate, required on compliance-gated curves
+    #[account(
+        seeds = [b"compliance_gate", mint.key().as_ref()],
+        bump = compliance_gate.bump,
+    )]
+    pub compliance_gate: Option<Account<'info, ComplianceGate>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's jackpot, required on curves with one
+    #[account(
+        mut,
+        seeds = [b"jackpot", mint.key().as_ref()],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: The schedule's owner, bound by its seeds; receives the refund once it closes
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDcaSchedule<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"dca_schedule", owner.key().as_ref(), dca_schedule.mint.as_ref()],
+        bump = dca_schedule.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub dca_schedule: Account<'info, DcaSchedule>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    /// The curve and its vaults are required until `close_bonding_curve` closes them
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: Option<AccountLoader<'info, BondingCurve>>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Closed curves only: the graduation archive, which records the pool to route to
+    #[account(
+        seeds = [b"graduation_archive", mint.key().as_ref()],
+        bump = graduation_archive.bump,
+    )]
+    pub graduation_archive: Option<Account<'info, GraduationArchive>>,
+
+    /// Price oracle updated on every trade
+    #[account(
+        mut,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// In-progress OHLCV candle for the current time bucket
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"live_candle", mint.key().as_ref()],
+        bump,
+        space = LiveCandle::MAX_SIZE,
+    )]
+    pub live_candle: Account<'info, LiveCandle>,
+
+    /// Loyalty points accrued by the trader
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"user_points", user.key().as_ref()],
+        bump,
+        space = UserPoints::MAX_SIZE,
+    )]
+    pub user_points: Account<'info, UserPoints>,
+
+    /// Slots of the trader's last buy and sell on this curve (anti-sandwich)
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"trade_guard", mint.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = TradeGuard::MAX_SIZE,
+    )]
+    pub trade_guard: Account<'info, TradeGuard>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Per-wallet record used to count unique buyers (optional; curve buys with a per-wallet
+    /// cap require it). Sells may pass it to credit the payout on curves with such a cap
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"buyer_record", mint.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = BuyerRecord::MAX_SIZE,
+    )]
+    pub buyer_record: Option<Account<'info, BuyerRecord>>,
+
+    /// Optional position tracking for the trader (see `open_trader_position`)
+    #[account(
+        mut,
+        seeds = [b"trader_position", mint.key().as_ref(), user.key().as_ref()],
+        bump = trader_position.bump,
+    )]
+    pub trader_position: Option<Account<'info, TraderPosition>>,
+
+    /// Optional competition the trade counts towards (pass with `competition_entry`)
+    #[account(mut)]
+    pub competition: Option<Account<'info, Competition>>,
+
+    /// The trader's entry in `competition`
+    #[account(mut)]
+    pub competition_entry: Option<Account<'info, CompetitionEntry>>,
+
+    /// The user's attestation, required for buys on KYC-gated curves
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+
+    /// The mint's compliance gate, required for buys on compliance-gated curves
+    #[account(
+        seeds = [b"compliance_gate", mint.key().as_ref()],
+        bump = compliance_gate.bump,
+    )]
+    pub compliance_gate: Option<Account<'info, ComplianceGate>>,
+
+    /// The curve's reflection vault, required when the curve takes reflections on trades
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Option<Account<'info, TokenAccount>>,
+
+    /// The curve's jackpot, required on buys from curves with one
+    #[account(
+        mut,
+        seeds = [b"jackpot", mint.key().as_ref()],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    /// The curve's wSOL vault, required when the curve holds its SOL side as wSOL
+    #[account(
+        mut,
+        constraint = bonding_curve.as_ref().is_some_and(|curve| {
+            curve_wsol_vault.key() == get_associated_token_address(&curve.key(), &native_mint::ID)
+        }) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Receives the payout of sells to a wSOL curve
+    #[account(
+        mut,
+        constraint = user_wsol_account.mint == native_mint::ID @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub user_wsol_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: The user's platform token account for a fee discount on curve trades
+    /// (remaining accounts carry the Raydium route); checked by `holder_fee_discount_bps`
+    pub platform_token_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPlatformFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = global_config.is_fee_destination(&treasury.key()) @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury or secondary payout address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    /// Receives `fee_share_bps` of the withdrawal (required when fee sharing is enabled)
+    #[account(
+        mut,
+        seeds = [b"fee_share_vault"],
+        bump = fee_share_vault.bump,
+    )]
+    pub fee_share_vault: Option<Account<'info, FeeShareVault>>,
+
+    /// wSOL curves only: the curve's wSOL vault, the treasury's wSOL account and the token program
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = treasury_wsol_account.mint == native_mint::ID
+            && treasury_wsol_account.owner == treasury.key() @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub treasury_wsol_account: Option<Account<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Receives `insurance_fee_bps` of the withdrawal (required when it is non-zero)
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPlatformFeesBatch<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = global_config.is_fee_destination(&treasury.key()) @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury or secondary payout address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    /// Receives `fee_share_bps` of the withdrawal (required when fee sharing is enabled)
+    #[account(
+        mut,
+        seeds = [b"fee_share_vault"],
+        bump = fee_share_vault.bump,
+    )]
+    pub fee_share_vault: Option<Account<'info, FeeShareVault>>,
+
+    /// Receives `insurance_fee_bps` of the withdrawal (required when it is non-zero)
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+}
+
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// Migrated curves only: the curve's native-mint ATA, created beforehand, that pays the pool
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeShareVault<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"fee_share_vault"],
+        bump,
+        space = FeeShareVault::MAX_SIZE,
+    )]
+    pub fee_share_vault: Account<'info, FeeShareVault>,
+
+    #[account(
+        constraint = stake_mint.key() == global_config.platform_token_mint @ ErrorCode::InvalidStakeMint,
+    )]
+    pub stake_mint: Account<'info, Mint>,
+
+    /// Holds staked platform tokens
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = fee_share_vault,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct StakePlatformToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_share_vault"],
+        bump = fee_share_vault.bump,
+    )]
+    pub fee_share_vault: Account<'info, FeeShareVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = fee_share_vault.stake_mint,
+        associated_token::authority = fee_share_vault,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// Created on first stake
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"fee_share_stake", owner.key().as_ref()],
+        bump,
+        space = FeeShareStake::MAX_SIZE,
+    )]
+    pub fee_share_stake: Account<'info, FeeShareStake>,
+
+    #[account(
+        mut,
+        token::mint = fee_share_vault.stake_mint,
+        token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_share_vault"],
+        bump = fee_share_vault.bump,
+    )]
+    pub fee_share_vault: Account<'info, FeeShareVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = fee_share_vault.stake_mint,
+        associated_token::authority = fee_share_vault,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_share_stake", owner.key().as_ref()],
+        bump = fee_share_stake.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub fee_share_stake: Account<'info, FeeShareStake>,
+
+    #[account(
+        mut,
+        token::mint = fee_share_vault.stake_mint,
+        token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFeeShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_share_vault"],
+        bump = fee_share_vault.bump,
+    )]
+    pub fee_share_vault: Account<'info, FeeShareVault>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_share_stake", owner.key().as_ref()],
+        bump = fee_share_stake.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+    )]
+    pub fee_share_stake: Account<'info, FeeShareStake>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"insurance_fund"],
+        bump,
+        space = InsuranceFund::MAX_SIZE,
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposePayout<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"insurance_payout".as_ref(), insurance_fund.next_payout_id.to_le_bytes().as_ref()],
+        bump,
+        space = InsurancePayout::MAX_SIZE,
+    )]
+    pub payout: Account<'info, InsurancePayout>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecutePayout<'info> {
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_payout", &payout.id.to_le_bytes()],
+        bump = payout.bump,
+    )]
+    pub payout: Account<'info, InsurancePayout>,
+
+    #[account(
+        mut,
+        address = payout.recipient @ ErrorCode::InvalidPayoutRecipient,
+    )]
+    /// CHECK: Recipient validated against the proposed payout
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelPayout<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_payout", &payout.id.to_le_bytes()],
+        bump = payout.bump,
+        close = authority,
+    )]
+    pub payout: Account<'info, InsurancePayout>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateToRaydium<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// The curve's wSOL vault, required for wSOL curves (unwrapped before migrating)
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<UncheckedAccount<'info>>,
+
+    /// Migration vault to hold SOL before Raydium pool creation
+    #[account(
+        mut,
+        seeds = [b"migration_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for migration
+    pub migration_sol_vault: AccountInfo<'info>,
+
+    /// Migration token account to hold tokens before Raydium pool creation
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_token_account: Account<'info, TokenAccount>,
+
+    /// Authority for the migration vault (a PDA)
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// Launch history and reputation of the curve's creator
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"creator_stats", bonding_curve.load()?.creator.as_ref()],
+        bump,
+        space = CreatorStats::MAX_SIZE,
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    /// Permissionless keeper cranking the migration (receives the keeper reward)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    /// Receives the refundable launch deposit
+    #[account(mut)]
+    /// CHECK: Validated against the bonding curve's creator
+    pub creator: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawMigrationFunds<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Migration vault holding SOL
+    #[account(
+        mut,
+        seeds = [b"migration_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for migration
+    pub migration_sol_vault: AccountInfo<'info>,
+
+    /// Migration token account holding tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_token_account: Account<'info, TokenAccount>,
+
+    /// Authority for the migration vault (a PDA)
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Platform authority who can withdraw
+    pub authority: Signer<'info>,
+
+    /// Recipient for SOL
+    #[account(
+        mut,
+        constraint = recipient.key() == global_config.migration_operator @ ErrorCode::InvalidMigrationRecipient
+    )]
+    /// CHECK: Validated against the configured migration operator
+    pub recipient: AccountInfo<'info>,
+
+    /// Recipient token account
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = recipient,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReturnMigrationFunds<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"migration_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for migration
+    pub migration_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        constraint = operator.key() == global_config.migration_operator @ ErrorCode::InvalidMigrationRecipient
+    )]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = operator,
+    )]
+    pub operator_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleMigrationRemainder<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"migration_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for migration
+    pub migration_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnRaydiumLpTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// LP burn info account to track the burn (new account)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_burn_info", mint.key().as_ref()],
+        bump,
+        space = LpBurnInfo::MAX_SIZE,
+    )]
+    pub lp_burn_info: Account<'info, LpBurnInfo>,
+
+    /// LP token mint from Raydium pool
+    #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// LP token account holding the LP tokens (owned by migration_authority)
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = migration_authority,
+    )]
+    pub lp_token_account: Account<'info, TokenAccount>,
+
+    /// Authority for the migration vault (a PDA)
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: AccountInfo<'info>,
+
+    /// CHECK: Raydium pool address (for recording)
+    pub raydium_pool: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Platform authority who can call this
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LockLpTokens<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// LP lock record (new account)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_lock", mint.key().as_ref()],
+        bump,
+        space = LpLock::MAX_SIZE,
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+
+    /// Escrow holding the locked LP tokens (owned by the lp_lock PDA)
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock,
+    )]
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+
+    /// LP token mint of the recorded Raydium pool
+    #[account(
+        address = raydium_lp_mint(&raydium_pool.key(), &global_config.raydium_amm_program) @ ErrorCode::InvalidLpMint,
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// LP token account holding the LP tokens (owned by migration_authority)
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = migration_authority,
+    )]
+    pub lp_token_account: Account<'info, TokenAccount>,
+
+    /// Authority for the migration vault (a PDA)
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: AccountInfo<'info>,
+
+    /// CHECK: Must be the pool recorded on the curve by `record_raydium_pool`
+    #[account(address = bonding_curve.load()?.raydium_pool @ ErrorCode::InvalidRaydiumPool)]
+    pub raydium_pool: AccountInfo<'info>,
+
+    /// Pool vaults, read to record the liquidity backing each LP token
+    #[account(
+        address = raydium_pool_vault(
+            &raydium_pool.key(),
+            &raydium_pool_mint(&raydium_pool, RAYDIUM_CPMM_TOKEN_0_MINT_OFFSET),
+            &global_config.raydium_amm_program,
+        ) @ ErrorCode::InvalidRaydiumAccounts,
+    )]
+    pub token_0_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        address = raydium_pool_vault(
+            &raydium_pool.key(),
+            &raydium_pool_mint(&raydium_pool, RAYDIUM_CPMM_TOKEN_1_MINT_OFFSET),
+            &global_config.raydium_amm_program,
+        ) @ ErrorCode::InvalidRaydiumAccounts,
+    )]
+    pub token_1_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Platform authority who can call this
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawLockedLp<'info> {
+    #[account(
+        mut,
+        seeds = [b"lp_lock", mint.key().as_ref()],
+        bump = lp_lock.bump,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = lp_mint @ ErrorCode::InvalidMint,
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Escrow holding the locked LP tokens
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock,
+    )]
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Recipient LP token account, owned by the platform authority that holds the lock
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        constraint = recipient_lp_token_account.owner == global_config.authority @ ErrorCode::Unauthorized,
+    )]
+    pub recipient_lp_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Platform authority who can call this
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimLpFees<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock", mint.key().as_ref()],
+        bump = lp_lock.bump,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = lp_mint @ ErrorCode::InvalidMint,
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+
+    /// Escrow holding the locked LP tokens
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock,
+    )]
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+
+    /// LP token mint from Raydium pool
+    #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Token 0 received from the LP withdrawal (owned by lp_lock)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = vault_0_mint,
+        associated_token::authority = lp_lock,
+    )]
+    pub lp_fee_token_0_account: Account<'info, TokenAccount>,
+
+    /// Token 1 received from the LP withdrawal (owned by lp_lock)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = vault_1_mint,
+        associated_token::authority = lp_lock,
+    )]
+    pub lp_fee_token_1_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = vault_0_mint,
+        token::authority = bonding_curve.load()?.creator,
+    )]
+    pub creator_token_0_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = vault_1_mint,
+        token::authority = bonding_curve.load()?.creator,
+    )]
+    pub creator_token_1_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = vault_0_mint,
+        token::authority = global_config.treasury,
+    )]
+    pub treasury_token_0_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = vault_1_mint,
+        token::authority = global_config.treasury,
+    )]
+    pub treasury_token_1_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Raydium CPMM program, validated against global config
+    pub raydium_program: AccountInfo<'info>,
+
+    /// CHECK: Raydium pool authority, validated by the Raydium program
+    pub pool_authority: AccountInfo<'info>,
+
+    /// CHECK: Raydium pool state, validated against the LP lock record
+    #[account(mut)]
+    pub pool_state: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        address = raydium_pool_vault(&pool_state.key(), &vault_0_mint.key(), &global_config.raydium_amm_program)
+            @ ErrorCode::InvalidRaydiumAccounts,
+    )]
+    pub token_0_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = raydium_pool_vault(&pool_state.key(), &vault_1_mint.key(), &global_config.raydium_amm_program)
+            @ ErrorCode::InvalidRaydiumAccounts,
+    )]
+    pub token_1_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        address = raydium_pool_mint(&pool_state, RAYDIUM_CPMM_TOKEN_0_MINT_OFFSET) @ ErrorCode::InvalidRaydiumAccounts,
+    )]
+    pub vault_0_mint: Account<'info, Mint>,
+    #[account(
+        address = raydium_pool_mint(&pool_state, RAYDIUM_CPMM_TOKEN_1_MINT_OFFSET) @ ErrorCode::InvalidRaydiumAccounts,
+    )]
+    pub vault_1_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Creator or platform authority
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: SPL Token-2022 program, validated by the Raydium program
+    pub token_program_2022: AccountInfo<'info>,
+    /// CHECK: SPL Memo program, validated by the Raydium program
+    pub memo_program: AccountInfo<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAndLockRaydiumPool<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Migration vault holding SOL
+    #[account(
+        mut,
+        seeds = [b"migration_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for migration
+    pub migration_sol_vault: AccountInfo<'info>,
+
+    /// Migration token account holding tokens
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_token_account: Account<'info, TokenAccount>,
+
+    /// Authority for the migration vault (a PDA)
+    #[account(
+        seeds = [b"migration_authority"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used as authority for migration accounts
+    pub migration_authority: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Platform authority who can call this
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeMigration<'info> {
+    #[account(
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// Immutable graduation record (new account)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"graduation_archive", mint.key().as_ref()],
+        bump,
+        space = GraduationArchive::MAX_SIZE,
+    )]
+    pub graduation_archive: Account<'info, GraduationArchive>,
+
+    /// CHECK: Must be the pool recorded on the curve by `record_raydium_pool`
+    #[account(address = bonding_curve.load()?.raydium_pool @ ErrorCode::InvalidRaydiumPool)]
+    pub raydium_pool: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Platform authority who can call this
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBuyerRecord<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"buyer_record", mint.key().as_ref(), buyer.key().as_ref()],
+        bump = buyer_record.bump,
+        has_one = buyer @ ErrorCode::Unauthorized,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = rent_payer @ ErrorCode::Unauthorized,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// The archive must exist before buyer records can be closed
+    #[account(
+        seeds = [b"graduation_archive", mint.key().as_ref()],
+        bump = graduation_archive.bump,
+    )]
+    pub graduation_archive: Account<'info, GraduationArchive>,
+
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Receives the record's rent; must be the account that paid it
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBondingCurve<'info> {
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// The archive must exist before the curve can be closed
+    #[account(
+        seeds = [b"graduation_archive", mint.key().as_ref()],
+        bump = graduation_archive.bump,
+    )]
+    pub graduation_archive: Account<'info, GraduationArchive>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Platform authority who can call this
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    /// Receives the launch deposit if it is still held
+    #[account(mut)]
+    /// CHECK: Validated against the bonding curve's creator
+    pub creator: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EnableWsolVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(address = native_mint::ID)]
+    pub native_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = native_mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub curve_wsol_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeLaunch<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// The creator allocation escrow, required when the creator escrowed an allocation
+    #[account(
+        seeds = [b"creator_allocation", mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_allocation_escrow: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct EnableInlineSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableKycGating<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct IssueKycAttestation<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        constraint = global_config.kyc_verifier == verifier.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        seeds = [b"kyc_attestation", verifier.key().as_ref(), wallet.as_ref()],
+        bump,
+        space = KycAttestation::MAX_SIZE,
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableLiquidityBootstrapping<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxSolPerWallet<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorRoyalty<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorRoyalties<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: The curve's SOL vault PDA, holding the royalties
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: Receives the royalties; checked against the curve's creator
+    pub creator: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EscrowCreatorAllocation<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"creator_allocation", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = bonding_curve,
+    )]
+    pub creator_allocation_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorAllocation<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_allocation", mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_allocation_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    /// CHECK: Receives the allocation and the escrow's rent; checked against the curve's creator
+    pub creator: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EnableReflections<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"reflection_pool", mint.key().as_ref()],
+        bump,
+        space = ReflectionPool::MAX_SIZE,
+    )]
+    pub reflection_pool: Account<'info, ReflectionPool>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"reflection_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = reflection_pool,
+    )]
+    pub reflection_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct DistributeReflections<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection_pool", reflection_pool.mint.as_ref()],
+        bump = reflection_pool.bump,
+    )]
+    pub reflection_pool: Account<'info, ReflectionPool>,
+
+    #[account(
+        seeds = [b"reflection_vault", reflection_pool.mint.as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"holder_snapshot", reflection_pool.mint.as_ref(), &epoch.to_le_bytes()],
+        bump = holder_snapshot.bump,
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"reflection_distribution", reflection_pool.mint.as_ref(), &epoch.to_le_bytes()],
+        bump,
+        space = ReflectionDistribution::MAX_SIZE,
+    )]
+    pub reflection_distribution: Account<'info, ReflectionDistribution>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimReflections<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection_pool", reflection_pool.mint.as_ref()],
+        bump = reflection_pool.bump,
+    )]
+    pub reflection_pool: Account<'info, ReflectionPool>,
+
+    #[account(
+        mut,
+        seeds = [b"reflection_vault", reflection_pool.mint.as_ref()],
+        bump,
+    )]
+    pub reflection_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"holder_snapshot", reflection_pool.mint.as_ref(), &epoch.to_le_bytes()],
+        bump = holder_snapshot.bump,
+    )]
+    pub holder_snapshot: Account<'info, HolderSnapshot>,
+
+    #[account(
+        mut,
+        seeds = [b"reflection_distribution", reflection_pool.mint.as_ref(), &epoch.to_le_bytes()],
+        bump = reflection_distribution.bump,
+    )]
+    pub reflection_distribution: Account<'info, ReflectionDistribution>,
+
+    /// Created on claim, so each holder claims an epoch at most once
+    #[account(
+        init,
+        payer = holder,
+        seeds = [b"reflection_claim", reflection_pool.mint.as_ref(), &epoch.to_le_bytes(), holder.key().as_ref()],
+        bump,
+        space = ReflectionClaim::MAX_SIZE,
+    )]
+    pub reflection_claim: Account<'info, ReflectionClaim>,
+
+    /// Any existing token account the holder owns
+    #[account(
+        mut,
+        token::mint = reflection_pool.mint,
+        token::authority = holder,
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeKycAttestation<'info> {
+    #[account(
+        mut,
+        close = verifier,
+        seeds = [b"kyc_attestation", verifier.key().as_ref(), kyc_attestation.wallet.as_ref()],
+        bump = kyc_attestation.bump,
+        has_one = verifier @ ErrorCode::Unauthorized,
+    )]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBondingCurve<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"bonding_curve", mint.key().as_ref()],
+        bump = bonding_curve.load()?.bump,
+        has_one = creator @ ErrorCode::Unauthorized,
+    )]
+    pub bonding_curve: AccountLoader<'info, BondingCurve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This is a PDA used to hold SOL for the bonding curve
+    pub bonding_curve_sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+
+    /// The curve's wSOL vault, required for wSOL curves (unwrapped before refunding)
+    #[account(
+        mut,
+        address = get_associated_token_address(&bonding_curve.key(), &native_mint::ID) @ ErrorCode::InvalidWsolAccount,
+    )]
+    pub curve_wsol_vault: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"curve_oracle", mint.key().as_ref()],
+        bump = curve_oracle.bump,
+    )]
+    pub curve_oracle: Account<'info, CurveOracle>,
+
+    /// The creator allocation escrow, required when the creator escrowed an allocation
+    #[account(
+        mut,
+        seeds = [b"creator_allocation", mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_allocation_escrow: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
+    )]
+    /// CHECK: Treasury address validated against global config
+    pub treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCategoryRegistry<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"category_registry"],
+        bump,
+        space = CategoryRegistry::MAX_SIZE,
+    )]
+    pub category_registry: Box<Account<'info, CategoryRegistry>>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays rent for new accounts (may differ from the authority)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddCategory<'info> {
+    #[account(
+        seeds = [b"global_config"],
+        bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"category_registry"],
+        bump = category_registry.bump,
+    )]
+    pub category_registry: Box<Account<'info, CategoryRegistry>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Slippage tolerance exceeded")]
+    SlippageExceeded,
+    #[msg("Bonding curve is complete")]
+    BondingCurveComplete,
+    #[msg("Insufficient tokens in bonding curve")]
+    InsufficientTokens,
+    #[msg("Insufficient SOL in bonding curve")]
+    InsufficientSOL,
+    #[msg("Already migrated to DEX")]
+    AlreadyMigrated,
+    #[msg("Migration threshold not reached")]
+    ThresholdNotReached,
+    #[msg("Token not migrated yet")]
+    NotMigrated,
+    #[msg("Invalid vesting duration")]
+    InvalidVestingDuration,
+    #[msg("Invalid cliff duration")]
+    InvalidCliffDuration,
+    #[msg("Cliff period not reached yet")]
+    CliffNotReached,
+    #[msg("No tokens available to claim")]
+    NoTokensToCllaim,
+    #[msg("Invalid mint address")]
+    InvalidMint,
+    #[msg("Insufficient fees to withdraw")]
+    InsufficientFees,
+    #[msg("No fees to withdraw")]
+    NoFeesToWithdraw,
+    #[msg("Invalid treasury address")]
+    InvalidTreasury,
+    #[msg("Insufficient SOL for migration (need at least 6 SOL fee + pool liquidity)")]
+    InsufficientSOLForMigration,
+    #[msg("LP tokens have already been burned")]
+    LpAlreadyBurned,
+    #[msg("Feature not yet implemented")]
+    NotImplemented,
+    #[msg("Bonding curve already uses the current layout")]
+    CurveAlreadyCurrent,
+    #[msg("Bonding curve data does not match any known layout")]
+    UnknownCurveLayout,
+    #[msg("Keeper reward cannot exceed the migration fee")]
+    InvalidKeeperReward,
+    #[msg("Invalid Raydium accounts for routed swap")]
+    InvalidRaydiumAccounts,
+    #[msg("Invalid LP lock duration")]
+    InvalidLockDuration,
+    #[msg("LP tokens are still locked")]
+    LpStillLocked,
+    #[msg("Locked LP tokens have already been withdrawn")]
+    LpAlreadyWithdrawn,
+    #[msg("Pool is not a Raydium pool for this curve")]
+    InvalidRaydiumPool,
+    #[msg("LP mint is not the Raydium pool's LP mint")]
+    InvalidLpMint,
+    #[msg("Invalid fee share")]
+    InvalidFeeShare,
+    #[msg("No LP fees available to claim")]
+    NoLpFeesToClaim,
+    #[msg("Bonding curve vaults must be drained before closing")]
+    CurveNotDrained,
+    #[msg("Curve has already graduated")]
+    CurveAlreadyGraduated,
+    #[msg("Pass the bonding curve accounts, or the graduation archive once the curve is closed")]
+    CurveAccountsRequired,
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Too many secondary payout addresses")]
+    TooManyPayoutAddresses,
+    #[msg("Account is not a global config")]
+    InvalidGlobalConfig,
+    #[msg("Global config already uses the current layout")]
+    ConfigAlreadyCurrent,
+    #[msg("Fee exceeds the maximum of 1000 basis points")]
+    FeeTooHigh,
+    #[msg("Virtual reserves must be greater than zero")]
+    InvalidVirtualReserves,
+    #[msg("Token supply must be greater than zero")]
+    InvalidTokenSupply,
+    #[msg("Migration threshold must exceed the migration fee")]
+    InvalidMigrationThreshold,
+    #[msg("This change must be queued through the config timelock")]
+    TimelockRequired,
+    #[msg("Queued config update is still timelocked")]
+    TimelockNotExpired,
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Arithmetic underflow")]
+    MathUnderflow,
+    #[msg("Division by zero")]
+    DivisionByZero,
+    #[msg("TWAP window is invalid or longer than the oracle history")]
+    InvalidTwapWindow,
+    #[msg("No finished candle for this bucket")]
+    CandleNotFinalizable,
+    #[msg("Unknown creator flag bits")]
+    InvalidCreatorFlags,
+    #[msg("Trending score does not beat the current king of the hill")]
+    ScoreTooLow,
+    #[msg("Project boosts are not enabled")]
+    BoostingDisabled,
+    #[msg("Invalid boost duration")]
+    InvalidBoostDuration,
+    #[msg("Competition must end after it starts")]
+    InvalidCompetitionWindow,
+    #[msg("Competition has ended")]
+    CompetitionEnded,
+    #[msg("Competition has not ended yet")]
+    CompetitionNotEnded,
+    #[msg("Competition is already finalized")]
+    CompetitionFinalized,
+    #[msg("Competition is not finalized")]
+    CompetitionNotFinalized,
+    #[msg("Competition entry does not belong to this competition and trader")]
+    InvalidCompetitionEntry,
+    #[msg("Prize already claimed")]
+    PrizeAlreadyClaimed,
+    #[msg("No prize for this trader")]
+    NoPrize,
+    #[msg("Early-buyer badge count exceeds the claim bitmap")]
+    InvalidBadgeCount,
+    #[msg("String exceeds the maximum length")]
+    StringTooLong,
+    #[msg("Buyer is not eligible for this badge")]
+    NotEligibleForBadge,
+    #[msg("Badge already claimed")]
+    BadgeAlreadyClaimed,
+    #[msg("Dev buy would reach the migration threshold")]
+    DevBuyTooLarge,
+    #[msg("Slippage must be at most 10000 basis points")]
+    InvalidSlippage,
+    #[msg("Trade would move the price more than the allowed impact")]
+    PriceImpactExceeded,
+    #[msg("Opposite-side trade on this curve is too soon after the last one")]
+    RoundTripTooSoon,
+    #[msg("Curve has been flagged by the platform; only sells are allowed")]
+    CurveFlagged,
+    #[msg("Fee tiers must be in increasing order of progress with non-increasing fees, unused slots last")]
+    InvalidFeeTiers,
+    #[msg("Discount tiers must be in increasing order of balance, with unused slots last")]
+    InvalidDiscountTiers,
+    #[msg("Discount account must be the trader's platform token account")]
+    InvalidDiscountAccount,
+    #[msg("Fee share vault must be passed while fee sharing is enabled")]
+    FeeShareVaultRequired,
+    #[msg("Stake mint must be the configured platform token")]
+    InvalidStakeMint,
+    #[msg("Unstake amount exceeds the stake")]
+    InsufficientStake,
+    #[msg("No fee share to claim")]
+    NoFeeShare,
+    #[msg("Launch window must be positive when launches are rate limited")]
+    InvalidLaunchWindow,
+    #[msg("Creator has reached the launch limit for the current window")]
+    LaunchRateLimited,
+    #[msg("Trading start time must be between now and the maximum scheduling delay")]
+    InvalidTradingStartTime,
+    #[msg("Trading has not started on this curve yet")]
+    TradingNotStarted,
+    #[msg("Curve has been traded by someone other than the creator's dev buy")]
+    CurveHasTraded,
+    #[msg("Migration can only advance to its next step")]
+    InvalidMigrationTransition,
+    #[msg("The curve's Raydium pool hasn't been recorded yet")]
+    RaydiumPoolNotRecorded,
+    #[msg("Migration funds can only go to the configured migration operator")]
+    InvalidMigrationRecipient,
+    #[msg("Migration funds can only be withdrawn before the pool is recorded")]
+    MigrationWithdrawalClosed,
+    #[msg("Withdrawal exceeds what the curve migrated")]
+    MigrationWithdrawalCapExceeded,
+    #[msg("This curve holds its SOL as wSOL; pass its wSOL accounts")]
+    WsolVaultRequired,
+    #[msg("Invalid wSOL account")]
+    InvalidWsolAccount,
+    #[msg("The wSOL vault can only be enabled before the first trade")]
+    WsolVaultUnavailable,
+    #[msg("Metadata account is not the canonical Metaplex PDA for this mint")]
+    InvalidMetadataAccount,
+    #[msg("Name must be 1-32 printable ASCII characters")]
+    InvalidName,
+    #[msg("Symbol must be 1-10 ASCII letters or digits")]
+    InvalidSymbol,
+    #[msg("Category is not in the registry")]
+    InvalidCategory,
+    #[msg("URI must be 1-200 printable ASCII characters without spaces")]
+    InvalidUri,
+    #[msg("Name and symbol must match the registered project")]
+    ProjectMetadataMismatch,
+    #[msg("Category name must be 1-32 printable ASCII characters")]
+    InvalidCategoryName,
+    #[msg("Category already exists")]
+    DuplicateCategory,
+    #[msg("Category registry is full")]
+    CategoryRegistryFull,
+    #[msg("Lamports can only be debited from accounts owned by this program")]
+    VaultNotProgramOwned,
+    #[msg("Cannot move lamports from an account to itself")]
+    VaultSelfTransfer,
+    #[msg("Treasury split recipients must be distinct, listed first, and weighted to 10,000 bps in total")]
+    InvalidTreasurySplit,
+    #[msg("Insurance fund account required when insurance_fee_bps is non-zero")]
+    InsuranceFundRequired,
+    #[msg("Insurance fund cannot cover this payout")]
+    InsufficientInsuranceFunds,
+    #[msg("Payout reason must be 1-64 printable ASCII characters")]
+    InvalidPayoutReason,
+    #[msg("Payout has already been executed")]
+    PayoutAlreadyExecuted,
+    #[msg("Payout delay has not passed yet")]
+    PayoutNotReady,
+    #[msg("Recipient does not match the proposed payout")]
+    InvalidPayoutRecipient,
+    #[msg("Buybacks are disabled (buyback_bps is 0)")]
+    BuybackDisabled,
+    #[msg("Claim amount exceeds the unlocked, unclaimed tokens")]
+    ClaimExceedsUnlocked,
+    #[msg("Tokens are still locked")]
+    TokensStillLocked,
+    #[msg("Invalid OTC deal counterparty")]
+    InvalidOtcCounterparty,
+    #[msg("This leg of the OTC deal is already funded")]
+    OtcLegAlreadyFunded,
+    #[msg("Both legs of the OTC deal must be funded to settle")]
+    OtcDealNotFunded,
+    #[msg("A fully funded OTC deal can only be settled")]
+    OtcDealFullyFunded,
+    #[msg("Invalid funding round discount or deadline")]
+    InvalidFundingRoundTerms,
+    #[msg("Funding round is closed to new deposits")]
+    FundingRoundClosed,
+    #[msg("Funding round has already converted")]
+    FundingRoundAlreadyConverted,
+    #[msg("Funding round has not converted yet")]
+    FundingRoundNotConverted,
+    #[msg("Curve did not graduate before the funding round deadline")]
+    FundingRoundNotGraduated,
+    #[msg("Funding round has not failed")]
+    FundingRoundNotFailed,
+    #[msg("Snapshot root must be set and its slot newer than the last snapshot's")]
+    InvalidSnapshot,
+    #[msg("Invalid payment stream duration, cliff or payee")]
+    InvalidStreamTerms,
+    #[msg("Nothing has streamed since the last withdrawal")]
+    NothingToWithdraw,
+    #[msg("This payment stream can't be canceled before it ends")]
+    StreamNotCancelable,
+    #[msg("Missing or invalid token accounts for this payment stream")]
+    InvalidStreamAccounts,
+    #[msg("Rage-quit must be enabled before the round has investors")]
+    FundingRoundHasInvestors,
+    #[msg("Rage-quit is not enabled for this funding round")]
+    RageQuitDisabled,
+    #[msg("The next milestone has not been reached")]
+    MilestoneNotReached,
+    #[msg("No escrowed SOL is left to release or withdraw")]
+    NothingEscrowed,
+    #[msg("Update content hash must not be empty")]
+    InvalidContentHash,
+    #[msg("No KYC verifier is configured")]
+    KycVerifierNotSet,
+    #[msg("KYC gating can only be enabled before the first trade")]
+    KycGatingUnavailable,
+    #[msg("Attestation expiry must be in the future")]
+    InvalidKycAttestation,
+    #[msg("This curve requires a valid KYC attestation to buy")]
+    KycAttestationRequired,
+    #[msg("Invalid compliance flags")]
+    InvalidComplianceFlags,
+    #[msg("This curve's compliance gate must be passed to buy")]
+    ComplianceGateRequired,
+    #[msg("Buy exceeds the compliance limit for this launch")]
+    ComplianceLimitExceeded,
+    #[msg("The per-wallet cap can only be set before the first trade")]
+    WalletCapUnavailable,
+    #[msg("Buy would take this wallet over the curve's per-wallet cap")]
+    WalletCapExceeded,
+    #[msg("Buys from a curve with a per-wallet cap must pass the buyer record")]
+    BuyerRecordRequired,
+    #[msg("Creator royalty exceeds the maximum")]
+    InvalidCreatorRoyalty,
+    #[msg("The creator royalty can only be set before the first trade, on lamport vaults")]
+    CreatorRoyaltyUnavailable,
+    #[msg("No royalties to claim")]
+    NoRoyaltiesToClaim,
+    #[msg("Claim the creator's royalties before closing the curve")]
+    RoyaltiesUnclaimed,
+    #[msg("The relayer must be a different wallet from the trader")]
+    InvalidRelayer,
+    #[msg("Invalid trade delegate limits")]
+    InvalidTradeDelegate,
+    #[msg("The session key has expired")]
+    TradeDelegateExpired,
+    #[msg("The session key may not trade this mint")]
+    TradeDelegateMintNotAllowed,
+    #[msg("Trade exceeds the session key's per-trade limit")]
+    TradeDelegateLimitExceeded,
+    #[msg("Trade exceeds the SOL deposited with the session key")]
+    TradeDelegateBudgetExceeded,
+    #[msg("Invalid limit order")]
+    InvalidLimitOrder,
+    #[msg("The owner's token account is required for sell orders")]
+    LimitOrderTokenAccountRequired,
+    #[msg("The limit order has expired")]
+    LimitOrderExpired,
+    #[msg("The curve price has not reached the order's limit")]
+    LimitPriceNotReached,
+    #[msg("Invalid DCA schedule")]
+    InvalidDcaSchedule,
+    #[msg("The next DCA buy is not due yet")]
+    DcaBuyNotDue,
+    #[msg("Invalid conditional order")]
+    InvalidConditionalOrder,
+    #[msg("The curve price has not reached the order's trigger")]
+    OrderNotTriggered,
+    #[msg("Routes must go through the configured Jupiter program")]
+    InvalidJupiterRoute,
+    #[msg("Cannot swap a curve's token for itself")]
+    SameCurveSwap,
+    #[msg("Basket weights must be non-zero, sum to 10000 bps and come with one minimum per curve")]
+    InvalidBasket,
+    #[msg("Inline SOL can only be enabled before the first trade on a curve without a wSOL vault")]
+    InlineSolUnavailable,
+    #[msg("The project's mint has already been created")]
+    MintAlreadyCreated,
+    #[msg("The mint address does not start with the requested vanity prefix")]
+    VanityPrefixMismatch,
+    #[msg("Token decimals exceed the maximum")]
+    InvalidTokenDecimals,
+    #[msg("Curves must launch with the platform's full initial token supply")]
+    NonstandardTokenSupply,
+    #[msg("The mint's decimals do not match the platform's token decimals")]
+    NonstandardTokenDecimals,
+    #[msg("Buys open once finalize_launch has verified the launch")]
+    LaunchNotFinalized,
+    #[msg("The launch has already been finalized")]
+    LaunchAlreadyFinalized,
+    #[msg("The curve does not hold the launch's full token supply")]
+    LaunchSupplyNotDeposited,
+    #[msg("The mint authority must be revoked or held by the curve")]
+    MintAuthorityNotRevoked,
+    #[msg("The freeze authority must be revoked")]
+    FreezeAuthorityNotRevoked,
+    #[msg("Creator allocation must be 1 to 1000 bps of the supply, released at most 365 days after migration")]
+    InvalidCreatorAllocation,
+    #[msg("A creator allocation can only be escrowed once, before the launch is finalized")]
+    CreatorAllocationUnavailable,
+    #[msg("This curve has no escrowed creator allocation")]
+    NoCreatorAllocation,
+    #[msg("The creator allocation is locked until the release delay after migration")]
+    CreatorAllocationLocked,
+    #[msg("The creator allocation escrow account is required")]
+    CreatorAllocationEscrowRequired,
+    #[msg("The creator allocation must be claimed before the curve is closed")]
+    CreatorAllocationUnclaimed,
+    #[msg("Reflections must be 1 to 500 bps of each trade")]
+    InvalidReflectionBps,
+    #[msg("Reflections can only be enabled once, before the launch is finalized")]
+    ReflectionsUnavailable,
+    #[msg("This curve takes reflections: pass its reflection vault")]
+    ReflectionVaultRequired,
+    #[msg("Reflections are distributed against non-empty snapshots, in epoch order")]
+    InvalidReflectionEpoch,
+    #[msg("No reflections have accrued since the last distribution")]
+    NoReflectionsToDistribute,
+    #[msg("The proof doesn't match the holder's balance in the snapshot")]
+    InvalidSnapshotProof,
+    #[msg("The holder's share of this distribution is zero")]
+    NoReflectionsToClaim,
+    #[msg("Claims would exceed the reflections distributed for this epoch")]
+    ReflectionsOverclaimed,
+    #[msg("Jackpot fee share must be 1 to 2000 bps, drawn every 10 or more buys")]
+    InvalidJackpotConfig,
+    #[msg("A jackpot can only be enabled once, on a lamport-vault curve that hasn't migrated")]
+    JackpotUnavailable,
+    #[msg("This curve has a jackpot: pass its jackpot account")]
+    JackpotRequired,
+    #[msg("No jackpot payout is waiting to be claimed")]
+    NoJackpotToClaim,
+    #[msg("Not a Switchboard randomness account, or not the one the request was bound to")]
+    InvalidRandomnessAccount,
+    #[msg("Randomness must be committed in the previous slot")]
+    RandomnessAlreadyRevealed,
+    #[msg("The randomness hasn't been revealed yet")]
+    RandomnessNotRevealed,
+    #[msg("A randomness request is already pending for this consumer")]
+    RandomnessRequestPending,
+    #[msg("No randomness request is pending for this consumer")]
+    NoRandomnessRequest,
+    #[msg("The consumer isn't waiting on a draw")]
+    RandomnessNotNeeded,
+    #[msg("The consumer account doesn't match the requested kind")]
+    InvalidRandomnessConsumer,
+    #[msg("Jackpots need the platform's Switchboard randomness queue")]
+    RandomnessSourceNotSet,
+    #[msg("Not the configured Pyth SOL/USD price update, or not fully verified")]
+    InvalidPriceAccount,
+    #[msg("Oracle price is not positive")]
+    InvalidPrice,
+    #[msg("Oracle price is older than the configured maximum age")]
+    StalePrice,
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("No SOL/USD price account is configured")]
+    SolUsdPriceNotConfigured,
+    #[msg("The launch fee is priced in USD: pass the SOL/USD price account")]
+    SolUsdPriceRequired,
+    #[msg("Invalid oracle configuration")]
+    InvalidOracleConfig,
+    #[msg("Bootstrapping weights must be 1x-5x and non-increasing over a window of up to 7 days")]
+    InvalidLbpSchedule,
+    #[msg("Liquidity bootstrapping can only be enabled once, before the launch is finalized and funded")]
+    LbpUnavailable,
+}
+
+#[account]
+pub struct ProjectState {
+    pub owner: Pubkey,           // 32
+    pub mint: Pubkey,            // 32
+    pub name_len: u8,            // 1 - Bytes of `name` in use
+    pub name: [u8; 32],          // 32 - Zero-padded project name
+    pub symbol_len: u8,          // 1 - Bytes of `symbol` in use
+    pub symbol: [u8; 10],        // 10 - Zero-padded token symbol
+    pub created_at: i64,         // 8
+    pub total_supply: u64,       // 8 - Total token supply
+    pub category: u8,            // 1 - Index into the CategoryRegistry
+    pub boosted_until: i64,      // 8 - Promoted on the platform until this timestamp (0 = never boosted)
+    pub compliance_flags: u16,   // 2 - Platform restrictions on the launch (ComplianceGate::FLAG_*, 0 = none)
+    pub mint_source: MintSource, // 1 - How `mint` was created
+}
+
+impl ProjectState {
+    // Metaplex metadata limits; names and symbols must fit them to be minted
+    pub const MAX_NAME: usize = 32;
+    pub const MAX_SYMBOL: usize = 10;
+    pub const MAX_URI: usize = 200;
+    pub const MAX_SIZE: usize = 8  // discriminator
+        + 32                       // owner
+        + 32                       // mint
+        + 1 + Self::MAX_NAME       // name
+        + 1 + Self::MAX_SYMBOL     // symbol
+        + 8                        // created_at
+        + 8                        // total_supply
+        + 1                        // category
+        + 8                        // boosted_until
+        + 2                        // compliance_flags
+        + 1;                       // mint_source
+
+    // Byte offsets for `getProgramAccounts` memcmp filters
+    pub const NAME_OFFSET: usize = 8 + 32 + 32 + 1;
+    pub const SYMBOL_OFFSET: usize = Self::NAME_OFFSET + Self::MAX_NAME + 1;
+    pub const CATEGORY_OFFSET: usize = Self::SYMBOL_OFFSET + Self::MAX_SYMBOL + 8 + 8;
+
+    pub fn name(&self) -> &str {
+        fixed_str(&self.name, self.name_len)
+    }
+
+    pub fn symbol(&self) -> &str {
+        fixed_str(&self.symbol, self.symbol_len)
+    }
+
+    // Longest boost that can be bought in one go (30 days)
+    pub const MAX_BOOST_DURATION: i64 = 30 * 24 * 60 * 60;
+
+    // Longest vanity prefix `create_mint` will check; grinding more characters is impractical
+    pub const MAX_VANITY_PREFIX: usize = 8;
+}
+
+#[account]
+pub struct ProjectUpdateFeed {
+    pub project: Pubkey,         // 32 - Project the updates belong to (PDA seed)
+    pub update_count: u64,       // 8 - Updates posted; the next one gets this sequence number
+    pub bump: u8,                // 1 - PDA bump seed
+}
+
+impl ProjectUpdateFeed {
+    pub const MAX_SIZE: usize = 8  // discriminator
+        + 32                       // project
+        + 8                        // update_count
+        + 1;                       // bump
+}
+
+/// One investor update, fixed once posted so the project page can check the content it
+/// serves against `content_hash`
+#[account]
+pub struct ProjectUpdate {
+    pub project: Pubkey,         // 32 - Project that posted the update (PDA seed)
+    pub sequence: u64,           // 8 - Position in the project's feed, from 0 (PDA seed)
+    pub content_hash: [u8; 32],  // 32 - Hash of the update's content
+    pub uri_len: u8,             // 1 - Bytes of `uri` in use
+    pub uri: [u8; 200],          // 200 - Zero-padded URI the content is published at
+    pub posted_at: i64,          // 8 - When the update was posted
+    pub bump: u8,                // 1 - PDA bump seed
+}
+
+impl ProjectUpdate {
+    pub const MAX_URI: usize = 200;
+    pub const MAX_SIZE: usize = 8  // discriminator
+        + 32                       // project
+        + 8                        // sequence
+        + 32                       // content_hash
+        + 1 + Self::MAX_URI        // uri
+        + 8                        // posted_at
+        + 1;                       // bump
+
+    pub fn uri(&self) -> &str {
+        fixed_str(&self.uri, self.uri_len)
+    }
+}
+
+/// A verifier's statement that `wallet` passed KYC / accreditation checks
+#[account]
+pub struct KycAttestation {
+    pub wallet: Pubkey,          // 32 - Verified wallet (PDA seed)
+    pub verifier: Pubkey,        // 32 - Authority that issued the attestation (PDA seed)
+    pub issued_at: i64,          // 8 - When the attestation was last issued
+    pub expires_at: i64,         // 8 - Buys on gated curves are rejected from this time on
+    pub bump: u8,                // 1 - PDA bump seed
+}
+
+impl KycAttestation {
+    pub const MAX_SIZE: usize = 8  // discriminator
+        + 32                       // wallet
+        + 32                       // verifier
+        + 8                        // issued_at
+        + 8                        // expires_at
+        + 1;                       // bump
+
+    /// Whether the attestation lets `wallet` buy on curves gated by `verifier` at `now`
+    pub fn is_valid_for(&self, wallet: &Pubkey, verifier: &Pubkey, now: i64) -> bool {
+        self.wallet == *wallet && self.verifier == *verifier && now < self.expires_at
+    }
+}
+
+/// A session key allowed to trade on a wallet's behalf within limits
+/// Holds the lamports delegated buys spend; delegated sells move the owner's tokens with this
+/// account as their SPL delegate and pay the owner
+#[account]
+pub struct TradeDelegate {
+    pub owner: Pubkey,                  // 32 - Wallet traded for (PDA seed)
+    pub session_key: Pubkey,            // 32 - Hot key allowed to sign trades (PDA seed)
+    pub max_sol_per_trade: u64,         // 8 - Largest buy, or sell payout, per trade
+    pub expires_at: i64,                // 8 - The session key can't trade from this time
+    pub mint_count: u8,                 // 1 - Entries used in allowed_mints (0 = any mint)
+    pub allowed_mints: [Pubkey; 4],     // 128 - Mints the session key may trade
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl TradeDelegate {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // owner
+        + 32                        // session_key
+        + 8                         // max_sol_per_trade
+        + 8                         // expires_at
+        + 1                         // mint_count
+        + 32 * Self::MAX_MINTS      // allowed_mints
+        + 1;                        // bump
+
+    pub const MAX_MINTS: usize = 4;
+
+    pub fn allows_mint(&self, mint: &Pubkey) -> bool {
+        self.mint_count == 0 || self.allowed_mints[..self.mint_count as usize].contains(mint)
+    }
+
+    /// Fail unless the session key may trade `sol_amount` lamports of `mint` at `now`
+    pub fn check_trade(&self, mint: &Pubkey, sol_amount: u64, now: i64) -> Result<()> {
+        require!(now < self.expires_at, ErrorCode::TradeDelegateExpired);
+        require!(self.allows_mint(mint), ErrorCode::TradeDelegateMintNotAllowed);
+        require!(sol_amount <= self.max_sol_per_trade, ErrorCode::TradeDelegateLimitExceeded);
+        Ok(())
+    }
+}
+
+/// A resting order against a bonding curve, filled by keepers once the price crosses its limit
+/// Buy orders hold their lamports in this account; sell orders hold their tokens in its ATA
+#[account]
+pub struct LimitOrder {
+    pub owner: Pubkey,                  // 32 - Wallet that placed the order (PDA seed)
+    pub mint: Pubkey,                   // 32 - Mint of the curve traded
+    pub order_id: u64,                  // 8 - Distinguishes the owner's orders (PDA seed)
+    pub side: SwapDirection,            // 1 - Buy or sell
+    pub price: u64,                     // 8 - Limit in lamports per whole token
+    pub amount: u64,                    // 8 - Lamports to spend (buy) or tokens to sell
+    pub expires_at: i64,                // 8 - The order can't be filled from this time
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl LimitOrder {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // owner
+        + 32                        // mint
+        + 8                         // order_id
+        + 1                         // side
+        + 8                         // price
+        + 8                         // amount
+        + 8                         // expires_at
+        + 1;                        // bump
+}
+
+/// A pre-authorized stop-loss or take-profit sell, executed by keepers once the price triggers
+/// The tokens are escrowed in this account's ATA and the keeper's tip in the account itself
+#[account]
+pub struct ConditionalOrder {
+    pub owner: Pubkey,                  // 32 - Wallet selling (PDA seed)
+    pub mint: Pubkey,                   // 32 - Mint of the curve sold into
+    pub order_id: u64,                  // 8 - Distinguishes the owner's orders (PDA seed)
+    pub trigger: OrderTrigger,          // 9 - Stop-loss or take-profit price
+    pub token_amount: u64,              // 8 - Tokens to sell
+    pub slippage_bps: u16,              // 2 - Allowed shortfall from the trigger price
+    pub keeper_tip: u64,                // 8 - Lamports paid to the executing keeper
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl ConditionalOrder {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // owner
+        + 32                        // mint
+        + 8                         // order_id
+        + 9                         // trigger
+        + 8                         // token_amount
+        + 2                         // slippage_bps
+        + 8                         // keeper_tip
+        + 1;                        // bump
+}
+
+/// Recurring buys of a curve's token, executed by keepers out of SOL escrowed in this account
+#[account]
+pub struct DcaSchedule {
+    pub owner: Pubkey,                  // 32 - Wallet buying (PDA seed)
+    pub mint: Pubkey,                   // 32 - Mint of the curve bought (PDA seed)
+    pub amount_per_buy: u64,            // 8 - Lamports spent per buy
+    pub interval: i64,                  // 8 - Seconds between buys
+    pub max_price: u64,                 // 8 - Highest average price per whole token (0 = none)
+    pub keeper_tip: u64,                // 8 - Lamports paid to the keeper per buy
+    pub next_buy_at: i64,               // 8 - When the next buy is due
+    pub buys_executed: u32,             // 4 - Buys made so far
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl DcaSchedule {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // owner
+        + 32                        // mint
+        + 8                         // amount_per_buy
+        + 8                         // interval
+        + 8                         // max_price
+        + 8                         // keeper_tip
+        + 8                         // next_buy_at
+        + 4                         // buys_executed
+        + 1;                        // bump
+}
+
+/// Trade-time copy of a project's compliance flags, keyed by mint so the trade path can find it
+#[account]
+pub struct ComplianceGate {
+    pub mint: Pubkey,            // 32 - Mint of the gated curve (PDA seed)
+    pub project: Pubkey,         // 32 - Project whose compliance_flags this mirrors
+    pub flags: u16,              // 2 - Active restrictions (FLAG_*)
+    pub max_buy_lamports: u64,   // 8 - Largest single buy under FLAG_MAX_BUY
+    pub bump: u8,                // 1 - PDA bump seed
+}
+
+impl ComplianceGate {
+    pub const MAX_SIZE: usize = 8  // discriminator
+        + 32                       // mint
+        + 32                       // project
+        + 2                        // flags
+        + 8                        // max_buy_lamports
+        + 1;                       // bump
+
+    // Buyers must hold a valid attestation from the platform's kyc_verifier
+    pub const FLAG_ATTESTATION: u16 = 1 << 0;
+    // Single buys are capped at max_buy_lamports
+    pub const FLAG_MAX_BUY: u16 = 1 << 1;
+    pub const ALL_FLAGS: u16 = Self::FLAG_ATTESTATION | Self::FLAG_MAX_BUY;
+
+    pub fn has(&self, flag: u16) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+/// Platform-managed list of project categories, referenced by index from ProjectState
+#[account]
+pub struct CategoryRegistry {
+    pub count: u8,                      // 1 - Categories registered so far
+    pub name_lens: [u8; 32],            // 32 - Bytes of each name in use
+    pub names: [[u8; 32]; 32],          // 32 * 32 - Zero-padded category names
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl CategoryRegistry {
+    pub const MAX_CATEGORIES: usize = 32;
+    pub const MAX_NAME: usize = 32;
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 1                         // count
+        + Self::MAX_CATEGORIES      // name_lens
+        + Self::MAX_NAME * Self::MAX_CATEGORIES // names
+        + 1;                        // bump
+
+    pub fn name(&self, index: u8) -> Option<&str> {
+        let slot = index as usize;
+        (index < self.count).then(|| fixed_str(&self.names[slot], self.name_lens[slot]))
+    }
+
+    // Index of `name`, ignoring ASCII case so "DeFi" and "defi" can't both be registered
+    pub fn find(&self, name: &str) -> Option<u8> {
+        (0..self.count).find(|&index| {
+            self.name(index)
+                .is_some_and(|existing| existing.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+/// Buy fee applied once a curve has reached `min_progress_bps` of the migration threshold
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    pub min_progress_bps: u16,          // 2 - Curve progress the tier starts at (0 = unused slot)
+    pub buy_fee_bps: u16,               // 2 - Buy fee charged from that point on
+}
+
+/// Share of withdrawn platform fees paid to `recipient`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TreasurySplit {
+    pub recipient: Pubkey,              // 32 - Wallet receiving this share (default = unused slot)
+    pub weight_bps: u16,                // 2 - Share of the fees, out of 10,000 across all used slots
+}
+
+/// Fee discount for traders holding at least `min_balance` of the platform token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct HolderDiscountTier {
+    pub min_balance: u64,               // 8 - Platform-token balance (raw units) required (0 = unused slot)
+    pub discount_bps: u16,              // 2 - Share of the trading fee waived
+}
+
+#[account]
+pub struct GlobalConfig {
+    pub authority: Pubkey,              // 32
+    pub treasury: Pubkey,               // 32 - Treasury address for automatic fee collection
+    pub virtual_sol_reserves: u64,      // 8 - Virtual SOL reserves for price stability
+    pub virtual_token_reserves: u64,    // 8 - Virtual token reserves for price stability
+    pub initial_token_supply: u64,      // 8 - Default initial token supply for new curves
+    pub buy_fee_bps: u16,               // 2 - Platform fee on buys (e.g., 100 = 1%)
+    pub migration_threshold_sol: u64,   // 8 - SOL threshold to trigger migration (e.g., 85 SOL)
+    pub raydium_amm_program: Pubkey,    // 32 - Raydium AMM program ID
+    pub migration_keeper_reward: u64,   // 8 - Lamports paid to whoever cranks a migration
+    pub lp_fee_creator_share_bps: u16,  // 2 - Share of harvested LP fees paid to the creator (rest to treasury)
+    pub secondary_payouts: [Pubkey; 4], // 32 * 4 - Additional fee payout addresses (default = empty slot)
+    pub version: u8,                    // 1 - Layout version, bumped whenever fields are appended
+    pub config_update_delay: i64,       // 8 - Seconds queued config updates must wait before execution
+    pub pending_authority: Pubkey,      // 32 - Authority nominated by propose_authority
+    pub boost_lamports_per_hour: u64,   // 8 - Price of promoting a project for one hour (0 = boosts disabled)
+    pub points_per_sol_bought: u64,     // 8 - Loyalty points accrued per SOL of buy volume
+    pub points_per_sol_sold: u64,       // 8 - Loyalty points accrued per SOL of sell volume
+    pub points_epoch: u32,              // 4 - Current points epoch, advanced by close_points_epoch
+    pub round_trip_slot_window: u64,    // 8 - Slots a wallet must wait between opposite-side trades on a curve (0 = off)
+    pub fee_tiers: [FeeTier; 4],        // 4 * 4 - Lower buy fees as the curve nears graduation (see buy_fee_bps_for)
+    pub sell_fee_bps: u16,              // 2 - Platform fee on sells (e.g., 100 = 1%)
+    pub platform_token_mint: Pubkey,    // 32 - Token whose holders get trading fee discounts (default = disabled)
+    pub holder_discount_tiers: [HolderDiscountTier; 4], // 10 * 4 - Fee discounts by platform-token balance (see holder_discount_bps)
+    pub fee_share_bps: u16,             // 2 - Share of withdrawn trading fees routed to platform-token stakers
+    pub launch_fee_lamports: u64,       // 8 - Non-refundable fee paid to the treasury per curve launch
+    pub launch_deposit_lamports: u64,   // 8 - Refundable deposit held per curve until it graduates or is closed
+    pub max_launches_per_window: u32,   // 4 - Curves a creator may launch per launch window (0 = unlimited)
+    pub launch_window_seconds: i64,     // 8 - Length of the per-creator launch rate-limit window
+    pub migration_operator: Pubkey,     // 32 - Only wallet migration funds may be withdrawn to
+    pub treasury_splits: [TreasurySplit; 4], // 34 * 4 - Recipients sharing withdrawn fees (empty = all to treasury)
+    pub insurance_fee_bps: u16,         // 2 - Share of withdrawn trading fees routed to the insurance fund
+    pub buyback_bps: u16,               // 2 - Share of a curve's accumulated fees each buyback_and_burn spends (0 = disabled)
+    pub kyc_verifier: Pubkey,           // 32 - Authority whose attestations KYC-gated curves accept (default = none)
+    pub jupiter_program: Pubkey,        // 32 - Jupiter aggregator program non-SOL buys route through (default = disabled)
+    pub token_decimals: u8,             // 1 - Decimals every launched mint must use
+    pub randomness_queue: Pubkey,       // 32 - Switchboard queue random draws must use (default = none)
+    pub randomness_authority: Pubkey,   // 32 - Authority of the Switchboard randomness accounts random draws read
+    pub sol_usd_price_account: Pubkey,  // 32 - Pyth SOL/USD price update account USD features read (default = none)
+    pub max_price_age_seconds: i64,     // 8 - Oldest oracle price USD features accept
+    pub max_price_confidence_bps: u16,  // 2 - Widest oracle confidence interval accepted, relative to the price
+    pub launch_fee_usd: u64,            // 8 - Launch fee in micro-dollars, charged in SOL at the oracle price (0 = use launch_fee_lamports)
+}
+
+impl GlobalConfig {
+    pub const MAX_SIZE: usize = 8  // discriminator
+        + 32                       // authority
+        + 32                       // treasury
+        + 8                        // virtual_sol_reserves
+        + 8                        // virtual_token_reserves
+        + 8                        // initial_token_supply
+        + 2                        // buy_fee_bps
+        + 8                        // migration_threshold_sol
+        + 32                       // raydium_amm_program
+        + 8                        // migration_keeper_reward
+        + 2                        // lp_fee_creator_share_bps
+        + 32 * 4                   // secondary_payouts
+        + 1                        // version
+        + 8                        // config_update_delay
+        + 32                       // pending_authority
+        + 8                        // boost_lamports_per_hour
+        + 8                        // points_per_sol_bought
+        + 8                        // points_per_sol_sold
+        + 4                        // points_epoch
+        + 8                        // round_trip_slot_window
+        + 4 * 4                    // fee_tiers
+        + 2                        // sell_fee_bps
+        + 32                       // platform_token_mint
+        + 10 * 4                   // holder_discount_tiers
+        + 2                        // fee_share_bps
+        + 8                        // launch_fee_lamports
+        + 8                        // launch_deposit_lamports
+        + 4                        // max_launches_per_window
+        + 8                        // launch_window_seconds
+        + 32                       // migration_operator
+        + 34 * 4                   // treasury_splits
+        + 2                        // insurance_fee_bps
+        + 2                        // buyback_bps
+        + 32                       // kyc_verifier
+        + 32                       // jupiter_program
+        + 1                        // token_decimals
+        + 32                       // randomness_queue
+        + 32                       // randomness_authority
+        + 32                       // sol_usd_price_account
+        + 8                        // max_price_age_seconds
+        + 2                        // max_price_confidence_bps
+        + 8;                       // launch_fee_usd
+
+    pub const MAX_SECONDARY_PAYOUTS: usize = 4;
+    pub const MAX_TREASURY_SPLITS: usize = 4;
+
+    // Bump whenever fields are appended, and give them defaults in `apply_upgrade_defaults`
+    pub const CURRENT_VERSION: u8 = 21;
+
+    // Populate fields added after `from_version` whose default isn't zero
+    pub fn apply_upgrade_defaults(&mut self, from_version: u8) {
+        if from_version < 1 && self.lp_fee_creator_share_bps == 0 {
+            // LP fee harvests are split evenly unless configured otherwise
+            self.lp_fee_creator_share_bps = 5_000;
+        }
+        if from_version < 8 {
+            // Sells keep paying what they paid when there was a single fee
+            self.sell_fee_bps = self.buy_fee_bps;
+        }
+        if from_version < 13 {
+            // The authority kept creating pools itself before operators were configured
+            self.migration_operator = self.authority;
+        }
+        if from_version < 19 {
+            // Mints were always created with 6 decimals before this was configurable
+            self.token_decimals = 6;
+        }
+        if from_version < 21 {
+            // Oracle reads stay usable until tightened: a minute old, 2% confidence
+            self.max_price_age_seconds = 60;
+            self.max_price_confidence_bps = 200;
+        }
+    }
+
+    // Maximum platform trading fee (10%)
+    pub const MAX_FEE_BASIS_POINTS: u16 = 1_000;
+
+    // Most decimals a launched mint may use, as for native SOL
+    pub const MAX_TOKEN_DECIMALS: u8 = 9;
+
+    // Reject parameters that would brick the curve math or overcharge traders
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.buy_fee_bps <= Self::MAX_FEE_BASIS_POINTS
+                && self.sell_fee_bps <= Self::MAX_FEE_BASIS_POINTS,
+            ErrorCode::FeeTooHigh
+        );
+        require!(
+            self.virtual_sol_reserves > 0 && self.virtual_token_reserves > 0,
+            ErrorCode::InvalidVirtualReserves
+        );
+        require!(self.initial_token_supply > 0, ErrorCode::InvalidTokenSupply);
+        require!(self.token_decimals <= Self::MAX_TOKEN_DECIMALS, ErrorCode::InvalidTokenDecimals);
+        require!(
+            self.max_price_age_seconds >= 0
+                && self.max_price_confidence_bps <= 10_000
+                && (self.launch_fee_usd == 0 || self.sol_usd_price_account != Pubkey::default()),
+            ErrorCode::InvalidOracleConfig
+        );
+        require!(
+            self.migration_threshold_sol > MIGRATION_FEE_LAMPORTS,
+            ErrorCode::InvalidMigrationThreshold
+        );
+        require!(
+            self.migration_keeper_reward <= MIGRATION_FEE_LAMPORTS,
+            ErrorCode::InvalidKeeperReward
+        );
+        require!(self.lp_fee_creator_share_bps <= 10_000, ErrorCode::InvalidFeeShare);
+        require!(self.treasury != Pubkey::default(), ErrorCode::InvalidTreasury);
+        require!(self.config_update_delay >= 0, ErrorCode::InvalidTimelockDelay);
+        require!(
+            (self.fee_share_bps as u32) + (self.insurance_fee_bps as u32) <= 10_000,
+            ErrorCode::InvalidFeeShare
+        );
+        require!(self.buyback_bps <= 10_000, ErrorCode::InvalidFeeShare);
+        require!(
+            self.max_launches_per_window == 0 || self.launch_window_seconds > 0,
+            ErrorCode::InvalidLaunchWindow
+        );
+
+        // Used tiers come first, in strictly increasing order of progress, and each charges no
+        // more than the fee before it
+        let mut previous_progress = 0;
+        let mut previous_fee = self.buy_fee_bps;
+        let mut seen_unused = false;
+        for tier in self.fee_tiers.iter() {
+            if tier.min_progress_bps == 0 {
+                require!(tier.buy_fee_bps == 0, ErrorCode::InvalidFeeTiers);
+                seen_unused = true;
+                continue;
+            }
+            require!(
+                !seen_unused
+                    && tier.min_progress_bps > previous_progress
+                    && tier.min_progress_bps <= 10_000,
+                ErrorCode::InvalidFeeTiers
+            );
+            require!(tier.buy_fee_bps <= previous_fee, ErrorCode::InvalidFeeTiers);
+            previous_progress = tier.min_progress_bps;
+            previous_fee = tier.buy_fee_bps;
+        }
+
+        // Same shape for holder discounts, ordered by balance
+        let mut previous_balance = 0;
+        let mut seen_unused = false;
+        for tier in self.holder_discount_tiers.iter() {
+            if tier.min_balance == 0 {
+                require!(tier.discount_bps == 0, ErrorCode::InvalidDiscountTiers);
+                seen_unused = true;
+                continue;
+            }
+            require!(
+                !seen_unused
+                    && tier.min_balance > previous_balance
+                    && tier.discount_bps <= 10_000,
+                ErrorCode::InvalidDiscountTiers
+            );
+            previous_balance = tier.min_balance;
+        }
+
+        // Used split slots come first, with distinct recipients, and share out exactly 100%
+        let mut total_weight = 0u32;
+        let mut seen_unused = false;
+        for (index, split) in self.treasury_splits.iter().enumerate() {
+            if split.weight_bps == 0 {
+                require!(split.recipient == Pubkey::default(), ErrorCode::InvalidTreasurySplit);
+                seen_unused = true;
+                continue;
+            }
+            require!(
+                !seen_unused
+                    && split.recipient != Pubkey::default()
+                    && self.treasury_splits[..index].iter().all(|other| other.recipient != split.recipient),
+                ErrorCode::InvalidTreasurySplit
+            );
+            total_weight = total_weight.safe_add(split.weight_bps as u32)?;
+        }
+        require!(
+            total_weight == 0 || total_weight == 10_000,
+            ErrorCode::InvalidTreasurySplit
+        );
+        Ok(())
+    }
+
+    // Configured treasury split slots; empty when all fees go to the treasury
+    pub fn active_treasury_splits(&self) -> &[TreasurySplit] {
+        let used = self.treasury_splits.iter().take_while(|split| split.weight_bps != 0).count();
+        &self.treasury_splits[..used]
+    }
+
+    // How far `bonding_curve` is towards the migration threshold, capped at 100%
+    pub fn graduation_progress_bps(&self, bonding_curve: &BondingCurve) -> Result<u16> {
+        Ok((bonding_curve.real_sol_reserves as u128)
+            .safe_mul(10_000)?
+            .safe_div(self.migration_threshold_sol as u128)?
+            .min(10_000) as u16)
+    }
+
+    // Buy fee for `bonding_curve`: its override, else the last tier reached, else the base fee
+    pub fn buy_fee_bps_for(&self, bonding_curve: &BondingCurve) -> Result<u16> {
+        if let Some(fee) = bonding_curve.buy_fee_override() {
+            return Ok(fee);
+        }
+        let progress_bps = self.graduation_progress_bps(bonding_curve)?;
+        Ok(self
+            .fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_progress_bps != 0 && tier.min_progress_bps <= progress_bps)
+            .map_or(self.buy_fee_bps, |tier| tier.buy_fee_bps))
+    }
+
+    // Fee discount for a platform-token balance: the highest tier reached, or none
+    pub fn holder_discount_bps(&self, balance: u64) -> u16 {
+        self.holder_discount_tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_balance != 0 && tier.min_balance <= balance)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+
+    // Sell fee for `bonding_curve`: its override, else the base fee (tiers only discount buys)
+    pub fn sell_fee_bps_for(&self, bonding_curve: &BondingCurve) -> u16 {
+        bonding_curve.sell_fee_override().unwrap_or(self.sell_fee_bps)
+    }
+
+    // Launch fee in lamports: launch_fee_usd converted at the SOL/USD price when set,
+    // else launch_fee_lamports
+    pub fn launch_fee_lamports_at(&self, sol_usd_price: Option<&AccountInfo>, now: i64) -> Result<u64> {
+        if self.launch_fee_usd == 0 {
+            return Ok(self.launch_fee_lamports);
+        }
+        let sol_usd_price = sol_usd_price.ok_or(ErrorCode::SolUsdPriceRequired)?;
+        oracle::lamports_for_usd(self.launch_fee_usd, oracle::sol_usd(sol_usd_price, self, now)?)
+    }
+
+    // Fees may only be paid to the treasury or a configured secondary payout address
+    pub fn is_fee_destination(&self, destination: &Pubkey) -> bool {
+        *destination == self.treasury
+            || (*destination != Pubkey::default() && self.secondary_payouts.contains(destination))
+    }
+}
+
+/// Parameters of `initialize_global_config`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct GlobalConfigParams {
+    pub treasury: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub initial_token_supply: u64,
+    pub buy_fee_bps: u16,
+    pub migration_threshold_sol: u64,
+    pub raydium_amm_program: Pubkey,
+    pub migration_keeper_reward: u64,
+    pub lp_fee_creator_share_bps: u16,
+    pub config_update_delay: i64,
+    pub boost_lamports_per_hour: u64,
+    pub points_per_sol_bought: u64,
+    pub points_per_sol_sold: u64,
+    pub round_trip_slot_window: u64,
+    pub fee_tiers: [FeeTier; 4],
+    pub sell_fee_bps: u16,
+    pub platform_token_mint: Pubkey,
+    pub holder_discount_tiers: [HolderDiscountTier; 4],
+    pub fee_share_bps: u16,
+    pub launch_fee_lamports: u64,
+    pub launch_deposit_lamports: u64,
+    pub max_launches_per_window: u32,
+    pub launch_window_seconds: i64,
+    pub migration_operator: Pubkey,
+    pub insurance_fee_bps: u16,
+    pub buyback_bps: u16,
+    pub kyc_verifier: Pubkey,
+    pub jupiter_program: Pubkey,
+    pub token_decimals: u8,
+    pub randomness_queue: Pubkey,
+    pub randomness_authority: Pubkey,
+    pub sol_usd_price_account: Pubkey,
+    pub max_price_age_seconds: i64,
+    pub max_price_confidence_bps: u16,
+    pub launch_fee_usd: u64,
+}
+
+/// Fields to change in `update_global_config`; `None` leaves a field as it is
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ConfigUpdate {
+    pub treasury: Option<Pubkey>,
+    pub virtual_sol_reserves: Option<u64>,
+    pub virtual_token_reserves: Option<u64>,
+    pub initial_token_supply: Option<u64>,
+    pub buy_fee_bps: Option<u16>,
+    pub migration_threshold_sol: Option<u64>,
+    pub raydium_amm_program: Option<Pubkey>,
+    pub migration_keeper_reward: Option<u64>,
+    pub lp_fee_creator_share_bps: Option<u16>,
+    pub config_update_delay: Option<i64>,
+    pub boost_lamports_per_hour: Option<u64>,
+    pub points_per_sol_bought: Option<u64>,
+    pub points_per_sol_sold: Option<u64>,
+    pub round_trip_slot_window: Option<u64>,
+    pub fee_tiers: Option<[FeeTier; 4]>,
+    pub sell_fee_bps: Option<u16>,
+    pub platform_token_mint: Option<Pubkey>,
+    pub holder_discount_tiers: Option<[HolderDiscountTier; 4]>,
+    pub fee_share_bps: Option<u16>,
+    pub launch_fee_lamports: Option<u64>,
+    pub launch_deposit_lamports: Option<u64>,
+    pub max_launches_per_window: Option<u32>,
+    pub launch_window_seconds: Option<i64>,
+    pub migration_operator: Option<Pubkey>,
+    pub insurance_fee_bps: Option<u16>,
+    pub buyback_bps: Option<u16>,
+    pub kyc_verifier: Option<Pubkey>,
+    pub jupiter_program: Option<Pubkey>,
+    pub token_decimals: Option<u8>,
+    pub randomness_queue: Option<Pubkey>,
+    pub randomness_authority: Option<Pubkey>,
+    pub sol_usd_price_account: Option<Pubkey>,
+    pub max_price_age_seconds: Option<i64>,
+    pub max_price_confidence_bps: Option<u16>,
+    pub launch_fee_usd: Option<u64>,
+}
+
+/// Parameters changed through `queue_config_update`; `None` leaves a field as it is
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TimelockedConfigUpdate {
+    pub treasury: Option<Pubkey>,
+    pub virtual_sol_reserves: Option<u64>,
+    pub virtual_token_reserves: Option<u64>,
+    pub initial_token_supply: Option<u64>,
+    pub buy_fee_bps: Option<u16>,
+    pub migration_threshold_sol: Option<u64>,
+    pub raydium_amm_program: Option<Pubkey>,
+    pub migration_keeper_reward: Option<u64>,
+    pub lp_fee_creator_share_bps: Option<u16>,
+    pub config_update_delay: Option<i64>,
+    pub fee_tiers: Option<[FeeTier; 4]>,
+    pub sell_fee_bps: Option<u16>,
+    pub launch_fee_lamports: Option<u64>,
+    pub launch_deposit_lamports: Option<u64>,
+}
+
+#[account]
+pub struct PendingConfigUpdate {
+    pub treasury: Option<Pubkey>,               // 1 + 32
+    pub virtual_sol_reserves: Option<u64>,      // 1 + 8
+    pub virtual_token_reserves: Option<u64>,    // 1 + 8
+    pub initial_token_supply: Option<u64>,      // 1 + 8
+    pub buy_fee_bps: Option<u16>,               // 1 + 2
+    pub migration_threshold_sol: Option<u64>,   // 1 + 8
+    pub raydium_amm_program: Option<Pubkey>,    // 1 + 32
+    pub migration_keeper_reward: Option<u64>,   // 1 + 8
+    pub lp_fee_creator_share_bps: Option<u16>,  // 1 + 2
+    pub config_update_delay: Option<i64>,       // 1 + 8
+    pub fee_tiers: Option<[FeeTier; 4]>,        // 1 + 4 * 4
+    pub sell_fee_bps: Option<u16>,              // 1 + 2
+    pub launch_fee_lamports: Option<u64>,       // 1 + 8
+    pub launch_deposit_lamports: Option<u64>,   // 1 + 8
+    pub queued_at: i64,                         // 8 - When the update was queued
+    pub eta: i64,                               // 8 - Earliest execution time
+    pub bump: u8,                               // 1 - PDA bump seed
+}
+
+impl PendingConfigUpdate {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 1 + 32                    // treasury
+        + 1 + 8                     // virtual_sol_reserves
+        + 1 + 8                     // virtual_token_reserves
+        + 1 + 8                     // initial_token_supply
+        + 1 + 2                     // buy_fee_bps
+        + 1 + 8                     // migration_threshold_sol
+        + 1 + 32                    // raydium_amm_program
+        + 1 + 8                     // migration_keeper_reward
+        + 1 + 2                     // lp_fee_creator_share_bps
+        + 1 + 8                     // config_update_delay
+        + 1 + 4 * 4                 // fee_tiers
+        + 1 + 2                     // sell_fee_bps
+        + 1 + 8                     // launch_fee_lamports
+        + 1 + 8                     // launch_deposit_lamports
+        + 8                         // queued_at
+        + 8                         // eta
+        + 1;                        // bump
+}
+
+#[account]
+pub struct GlobalStats {
+    pub total_curves_created: u64,      // 8 - Bonding curves ever initialized
+    pub total_migrated: u64,            // 8 - Curves that graduated to Raydium
+    pub active_curves: u64,             // 8 - Curves still trading on the bonding curve
+    pub total_volume_sol: u64,          // 8 - Lamports traded through all curves (excluding fees)
+    pub total_fees_collected: u64,      // 8 - Trading and migration fees across all curves
+    pub initialized_at: i64,            // 8 - When tracking started (earlier activity is not counted)
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl GlobalStats {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 8                         // total_curves_created
+        + 8                         // total_migrated
+        + 8                         // active_curves
+        + 8                         // total_volume_sol
+        + 8                         // total_fees_collected
+        + 8                         // initialized_at
+        + 1;                        // bump
+
+    pub fn record_trade(&mut self, sol_volume: u64, fee: u64) -> Result<()> {
+        self.total_volume_sol = self.total_volume_sol.safe_add(sol_volume)?;
+        self.total_fees_collected = self.total_fees_collected.safe_add(fee)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct CreatorStats {
+    pub creator: Pubkey,                // 32 - Wallet that launched the curves
+    pub launches: u64,                  // 8 - Bonding curves initialized
+    pub migrations: u64,                // 8 - Curves that graduated to Raydium
+    pub total_sol_raised: u64,          // 8 - Real SOL reserves of graduated curves at migration
+    pub last_launch_at: i64,            // 8 - When the most recent curve was initialized
+    pub flags: u8,                      // 1 - Reputation flags set by the platform (see FLAG_*)
+    pub bump: u8,                       // 1 - PDA bump seed
+    pub window_started_at: i64,         // 8 - Start of the current launch rate-limit window
+    pub window_launches: u32,           // 4 - Curves launched in the current window
+}
+
+impl CreatorStats {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // creator
+        + 8                         // launches
+        + 8                         // migrations
+        + 8                         // total_sol_raised
+        + 8                         // last_launch_at
+        + 1                         // flags
+        + 1                         // bump
+        + 8                         // window_started_at
+        + 4;                        // window_launches
+
+    // Creator pulled liquidity or dumped on buyers
+    pub const FLAG_RUG: u8 = 1 << 0;
+    // Creator walked away from a project before it graduated
+    pub const FLAG_ABANDONED: u8 = 1 << 1;
+    pub const ALL_FLAGS: u8 = Self::FLAG_RUG | Self::FLAG_ABANDONED;
+
+    // Created on first use, so fill in identity fields if this is a fresh account
+    pub fn ensure_initialized(&mut self, creator: Pubkey, bump: u8) {
+        if self.creator == Pubkey::default() {
+            self.creator = creator;
+            self.bump = bump;
+        }
+    }
+
+    // Count a launch at `now`, rejecting it if the creator already used up the window
+    // A window opens with the first launch after the previous one expired
+    pub fn record_launch(&mut self, now: i64, max_launches: u32, window: i64) -> Result<()> {
+        if now >= self.window_started_at.safe_add(window)? {
+            self.window_started_at = now;
+            self.window_launches = 0;
+        }
+        require!(
+            max_launches == 0 || self.window_launches < max_launches,
+            ErrorCode::LaunchRateLimited
+        );
+        self.window_launches = self.window_launches.safe_add(1)?;
+        self.launches = self.launches.safe_add(1)?;
+        self.last_launch_at = now;
+        Ok(())
+    }
+}
+
+/// Hot-path trading state, read without deserialization (`AccountLoader`)
+/// Fields are ordered by size so the `repr(C)` layout has no implicit padding. New fields
+/// take their space from the reserved arrays and padding, so the account never changes size
+#[account(zero_copy)]
+pub struct BondingCurve {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub creator: Pubkey,                // 32 - Creator of the bonding curve
+    pub raydium_pool: Pubkey,           // 32 - Raydium pool address (if migrated)
+    pub virtual_sol_reserves: u64,      // 8 - Virtual SOL for price calculation
+    pub virtual_token_reserves: u64,    // 8 - Virtual tokens for price calculation
+    pub real_sol_reserves: u64,         // 8 - Actual SOL in the curve
+    pub real_token_reserves: u64,       // 8 - Actual tokens in the curve
+    pub created_at: i64,                // 8 - When the curve was initialized
+    pub graduated_at: i64,              // 8 - When the curve migrated (0 if not migrated)
+    pub unique_buyers: u64,             // 8 - Number of distinct wallets that bought with a buyer record
+    pub total_fees_collected: u64,      // 8 - Lifetime fees paid to the platform (trading + migration)
+    pub graduation_sol_reserves: u64,   // 8 - Real SOL reserves at migration
+    pub graduation_token_reserves: u64, // 8 - Real token reserves at migration
+    pub accumulated_fees: u64,          // 8 - Trading fees held in the SOL vault awaiting withdrawal
+    pub trade_count: u64,               // 8 - Number of buys and sells executed on the curve
+    pub holder_count: u64,              // 8 - Wallets whose token balance is non-zero (tracked on curve trades)
+    pub trending_score: u64,            // 8 - Trade volume (lamports) decayed with a half-life of TRENDING_HALF_LIFE
+    pub trending_updated_at: i64,       // 8 - When trending_score was last decayed
+    pub dev_buy_sol: u64,               // 8 - Lamports the creator spent on the launch dev buy (0 = none)
+    pub dev_buy_tokens: u64,            // 8 - Tokens the creator received from the dev buy
+    pub flagged_at: i64,                // 8 - When the curve was last flagged
+    pub launch_deposit: u64,            // 8 - Refundable launch deposit held in this account (0 once refunded)
+    pub trading_start_time: i64,        // 8 - Trades before this time are rejected (0 = open at launch)
+    pub migration_sol_withdrawn: u64,   // 8 - Lamports withdrawn from the migration vault and not returned
+    pub migration_tokens_withdrawn: u64, // 8 - Tokens withdrawn from the migration token account and not returned
+    pub vault_rent_reserve: u64,        // 8 - Lamports kept in the SOL vault for rent exemption, never tradable
+    pub max_sol_per_wallet: u64,        // 8 - Cap on any wallet's net SOL contributed (0 = uncapped)
+    pub creator_royalties: u64,         // 8 - Royalties held in the SOL vault awaiting claim by the creator
+    pub creator_allocation: u64,        // 8 - Creator's tokens held in the allocation escrow until released (0 = none)
+    pub allocation_release_delay: i64,  // 8 - Seconds after migration before the creator allocation can be claimed
+    pub lbp_start_time: i64,            // 8 - Start of the liquidity bootstrapping window, set by finalize_launch
+    pub lbp_duration: i64,              // 8 - Length of the liquidity bootstrapping window (0 = fixed constant product)
+    pub reserved_u64: [u64; 2],         // 16 - Space for new u64 fields, taken from the front
+    pub buy_fee_bps_override: u16,      // 2 - Per-curve buy fee replacing the global schedule (NO_FEE_OVERRIDE = none)
+    pub sell_fee_bps_override: u16,     // 2 - Per-curve sell fee replacing the global one (NO_FEE_OVERRIDE = none)
+    pub creator_royalty_bps: u16,       // 2 - Creator's cut of every buy and sell, on top of the platform fee
+    pub reflection_bps: u16,            // 2 - Share of each trade's tokens sent to the reflection vault for holders (0 = off)
+    pub lbp_weights_bps: [u16; 4],      // 2 * 4 - Virtual SOL weight at each quarter of the bootstrapping window (10,000 = 1x)
+    pub reserved_u16: [u16; 2],         // 4 - Space for new u16 fields, taken from the front
+    pub complete: u8,                   // 1 - Whether all tokens have been sold (0/1)
+    pub migrated: u8,                   // 1 - Whether migrated to DEX (0/1)
+    pub bump: u8,                       // 1 - PDA bump seed
+    pub flag_reason: u8,                // 1 - Reason code set by flag_curve; non-zero blocks buys (0 = not flagged)
+    pub migration_status: u8,           // 1 - Post-migration progress as a MigrationStatus
+    pub wsol_vault: u8,                 // 1 - Whether the SOL side is held as wSOL in the curve's native-mint ATA (0/1)
+    pub kyc_required: u8,               // 1 - Whether buys need a KycAttestation from the platform's verifier (0/1)
+    pub compliance_gated: u8,           // 1 - Whether buys must pass the curve's ComplianceGate (0/1)
+    pub inline_sol: u8,                 // 1 - Whether the SOL side is held in this account's lamports instead of the sol_vault (0/1)
+    pub launch_finalized: u8,           // 1 - Whether finalize_launch verified the supply deposit and revoked authorities (0/1)
+    pub jackpot: u8,                    // 1 - Whether a share of buy fees feeds the curve's Jackpot (0/1)
+    pub padding: [u8; 1],               // 1 - Keeps the size a multiple of 8; new u8 fields are taken from here
+}
+
+impl BondingCurve {
+    pub const MAX_SIZE: usize = 8 + std::mem::size_of::<BondingCurve>();
+
+    // Stored in the fee override fields when the curve follows the global fees
+    pub const NO_FEE_OVERRIDE: u16 = u16::MAX;
+
+    // Highest royalty a creator can set on their curve
+    pub const MAX_CREATOR_ROYALTY_BPS: u16 = 500;
+
+    // Bootstrapping weight of a plain constant-product curve (1x)
+    pub const LBP_BASE_WEIGHT_BPS: u64 = 10_000;
+
+    // Highest opening bootstrapping weight (5x the base price)
+    pub const MAX_LBP_WEIGHT_BPS: u16 = 50_000;
+
+    // Longest bootstrapping window
+    pub const MAX_LBP_DURATION: i64 = 7 * 24 * 60 * 60;
+
+    pub fn is_complete(&self) -> bool {
+        self.complete != 0
+    }
+
+    pub fn is_migrated(&self) -> bool {
+        self.migrated != 0
+    }
+
+    pub fn has_wsol_vault(&self) -> bool {
+        self.wsol_vault != 0
+    }
+
+    pub fn kyc_required(&self) -> bool {
+        self.kyc_required != 0
+    }
+
+    pub fn compliance_gated(&self) -> bool {
+        self.compliance_gated != 0
+    }
+
+    pub fn has_inline_sol(&self) -> bool {
+        self.inline_sol != 0
+    }
+
+    pub fn is_launch_finalized(&self) -> bool {
+        self.launch_finalized != 0
+    }
+
+    pub fn has_reflections(&self) -> bool {
+        self.reflection_bps != 0
+    }
+
+    pub fn has_jackpot(&self) -> bool {
+        self.jackpot != 0
+    }
+
+    pub fn has_liquidity_bootstrapping(&self) -> bool {
+        self.lbp_duration != 0
+    }
+
+    // Weight applied to the virtual SOL reserves at `now`: interpolated linearly between the
+    // quarterly checkpoints, reaching 1x when the window ends. The window's opening weight
+    // holds until finalize_launch starts it
+    pub fn lbp_weight_bps(&self, now: i64) -> Result<u64> {
+        if !self.has_liquidity_bootstrapping() {
+            return Ok(Self::LBP_BASE_WEIGHT_BPS);
+        }
+        let elapsed = if self.lbp_start_time == 0 {
+            0
+        } else {
+            now.safe_sub(self.lbp_start_time)?.max(0)
+        };
+        if elapsed >= self.lbp_duration {
+            return Ok(Self::LBP_BASE_WEIGHT_BPS);
+        }
+        let checkpoints = self.lbp_weights_bps.len() as u128;
+        let position = (elapsed as u128).safe_mul(checkpoints)?;
+        let duration = self.lbp_duration as u128;
+        let index = position.safe_div(duration)?.to_u64()? as usize;
+        let from = self.lbp_weights_bps[index] as u128;
+        let to = self
+            .lbp_weights_bps
+            .get(index + 1)
+            .map_or(Self::LBP_BASE_WEIGHT_BPS as u128, |weight| *weight as u128);
+        let decay = mul_div(from.safe_sub(to)?, position % duration, duration, Rounding::Down)?;
+        from.safe_sub(decay)?.to_u64()
+    }
+
+    // Reserves trades are priced over at `now`, with the virtual SOL scaled by the
+    // bootstrapping weight. Weights only fall over time, so tokens bought at a higher
+    // weight can never be sold back for more SOL than they paid in
+    pub fn reserves_at(&self, now: i64) -> Result<Reserves> {
+        let virtual_sol = mul_div(
+            self.virtual_sol_reserves as u128,
+            self.lbp_weight_bps(now)? as u128,
+            Self::LBP_BASE_WEIGHT_BPS as u128,
+            Rounding::Down,
+        )?
+        .to_u64()?;
+        Reserves::new(
+            virtual_sol,
+            self.real_sol_reserves,
+            self.virtual_token_reserves,
+            self.real_token_reserves,
+        )
+    }
+
+    // The account holding the curve's SOL side in lamports: the curve itself for inline-SOL
+    // curves, otherwise its sol_vault PDA
+    pub fn sol_holder<'info>(
+        &self,
+        curve: AccountInfo<'info>,
+        sol_vault: &AccountInfo<'info>,
+    ) -> AccountInfo<'info> {
+        if self.has_inline_sol() {
+            curve
+        } else {
+            sol_vault.clone()
+        }
+    }
+
+    // Lamports the SOL holder keeps back from trades and withdrawals: the vault's rent
+    // reserve, or for inline-SOL curves the curve account's own rent and unrefunded launch
+    // deposit. Curves created before the vault reserve was tracked use the current rent minimum
+    pub fn sol_floor_lamports(&self) -> Result<u64> {
+        if self.has_inline_sol() {
+            Rent::get()?.minimum_balance(Self::MAX_SIZE).safe_add(self.launch_deposit)
+        } else if self.vault_rent_reserve > 0 {
+            Ok(self.vault_rent_reserve)
+        } else {
+            Ok(Rent::get()?.minimum_balance(0))
+        }
+    }
+
+    pub fn buy_fee_override(&self) -> Option<u16> {
+        (self.buy_fee_bps_override != Self::NO_FEE_OVERRIDE).then_some(self.buy_fee_bps_override)
+    }
+
+    pub fn sell_fee_override(&self) -> Option<u16> {
+        (self.sell_fee_bps_override != Self::NO_FEE_OVERRIDE).then_some(self.sell_fee_bps_override)
+    }
+
+    /// The creator's royalty on a trade of `amount` lamports
+    pub fn creator_royalty_for(&self, amount: u64) -> Result<u64> {
+        curve::fee_for_amount(amount, self.creator_royalty_bps)
+    }
+
+    /// The tokens reflected to holders from a trade of `amount` tokens
+    pub fn reflection_for(&self, amount: u64) -> Result<u64> {
+        curve::fee_for_amount(amount, self.reflection_bps)
+    }
+
+    // Lamports in the SOL holder backing the real reserves, i.e. excluding its floor and
+    // unwithdrawn fees and royalties
+    pub fn vault_tradable_lamports(&self, vault: &AccountInfo) -> Result<u64> {
+        Ok(vault
+            .lamports()
+            .saturating_sub(self.sol_floor_lamports()?)
+            .saturating_sub(self.accumulated_fees)
+            .saturating_sub(self.creator_royalties))
+    }
+
+    // Curves migrated before status tracking never recorded `FundsMoved`
+    pub fn migration_stage(&self) -> MigrationStatus {
+        let status = MigrationStatus::from_u8(self.migration_status);
+        if self.is_migrated() && status == MigrationStatus::NotStarted {
+            MigrationStatus::FundsMoved
+        } else {
+            status
+        }
+    }
+
+    // Furthest ahead a launch can schedule trading to open (7 days)
+    pub const MAX_TRADING_START_DELAY: i64 = 7 * 24 * 60 * 60;
+
+    // Largest share of the supply a creator can escrow for themselves, so the curve keeps
+    // enough tokens to reach the migration threshold
+    pub const MAX_CREATOR_ALLOCATION_BPS: u16 = 1_000;
+
+    // Longest timelock after migration on the creator allocation (365 days)
+    pub const MAX_ALLOCATION_RELEASE_DELAY: i64 = 365 * 24 * 60 * 60;
+
+    // Highest share of each trade's tokens a curve can reflect to holders
+    pub const MAX_REFLECTION_BPS: u16 = 500;
+
+    // Seconds for the trending score to halve without new volume
+    pub const TRENDING_HALF_LIFE: i64 = 3_600;
+
+    // Trending score as of `now`, with decay applied
+    pub fn trending_score_at(&self, now: i64) -> Result<u64> {
+        decay_trending_score(self.trending_score, now.saturating_sub(self.trending_updated_at))
+    }
+
+    // Decay the score to `now` and add the lamports traded
+    pub fn record_trending_volume(&mut self, now: i64, sol_volume: u64) -> Result<()> {
+        self.trending_score = self.trending_score_at(now)?.safe_add(sol_volume)?;
+        self.trending_updated_at = now;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct OracleObservation {
+    pub timestamp: i64,                 // 8 - When the observation was written
+    pub price_cumulative: u128,         // 16 - Cumulative price at that time
+}
+
+#[account]
+pub struct CurveOracle {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub last_price: u64,                // 8 - Spot price after the most recent trade
+    pub last_update_timestamp: i64,     // 8 - When the cumulative price was last updated
+    pub price_cumulative: u128,         // 16 - Sum of spot price * seconds (Uniswap-style)
+    pub observation_index: u8,          // 1 - Slot holding the most recent observation
+    pub observation_count: u8,          // 1 - Number of populated observation slots
+    pub bump: u8,                       // 1 - PDA bump seed
+    pub observations: [OracleObservation; 24], // 24 * 24 - Ring buffer of cumulative prices
+}
+
+impl CurveOracle {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 8                         // last_price
+        + 8                         // last_update_timestamp
+        + 16                        // price_cumulative
+        + 1                         // observation_index
+        + 1                         // observation_count
+        + 1                         // bump
+        + 24 * Self::MAX_OBSERVATIONS; // observations
+
+    pub const MAX_OBSERVATIONS: usize = 24;
+
+    // Minimum spacing between observations (24 slots cover at least 2 hours)
+    pub const OBSERVATION_INTERVAL: i64 = 300;
+
+    pub fn initialize(&mut self, mint: Pubkey, bump: u8, timestamp: i64, price: u64) {
+        self.mint = mint;
+        self.last_price = price;
+        self.last_update_timestamp = timestamp;
+        self.price_cumulative = 0;
+        self.observation_index = 0;
+        self.observation_count = 1;
+        self.bump = bump;
+        self.observations = [OracleObservation::default(); Self::MAX_OBSERVATIONS];
+        self.observations[0] = OracleObservation { timestamp, price_cumulative: 0 };
+    }
+
+    // Cumulative price extrapolated to `timestamp` using the last traded price
+    pub fn cumulative_at(&self, timestamp: i64) -> Result<u128> {
+        let elapsed = timestamp.safe_sub(self.last_update_timestamp)?.max(0) as u128;
+        self.price_cumulative.safe_add((self.last_price as u128).safe_mul(elapsed)?)
+    }
+
+    // Accumulate the previous price up to `timestamp`, then switch to `price`
+    pub fn record(&mut self, timestamp: i64, price: u64) -> Result<()> {
+        if timestamp > self.last_update_timestamp {
+            self.price_cumulative = self.cumulative_at(timestamp)?;
+            self.last_update_timestamp = timestamp;
+        }
+        self.last_price = price;
+
+        let latest = self.observations[self.observation_index as usize];
+        if timestamp.safe_sub(latest.timestamp)? >= Self::OBSERVATION_INTERVAL {
+            let next = (self.observation_index as usize + 1) % Self::MAX_OBSERVATIONS;
+            self.observations[next] = OracleObservation {
+                timestamp,
+                price_cumulative: self.price_cumulative,
+            };
+            self.observation_index = next as u8;
+            if (self.observation_count as usize) < Self::MAX_OBSERVATIONS {
+                self.observation_count = self.observation_count.safe_add(1)?;
+            }
+        }
+        Ok(())
+    }
+
+    // TWAP from the newest observation at least `window` seconds old until `now`
+    pub fn twap(&self, now: i64, window: i64) -> Result<u64> {
+        require!(window > 0, ErrorCode::InvalidTwapWindow);
+        let target = now.safe_sub(window)?;
+
+        let mut start = None;
+        for offset in 0..self.observation_count as usize {
+            let slot = (self.observation_index as usize + Self::MAX_OBSERVATIONS - offset)
+                % Self::MAX_OBSERVATIONS;
+            let observation = self.observations[slot];
+            if observation.timestamp <= target {
+                start = Some(observation);
+                break;
+            }
+        }
+        let start = start.ok_or(ErrorCode::InvalidTwapWindow)?;
+
+        let elapsed = now.safe_sub(start.timestamp)? as u128;
+        self.cumulative_at(now)?
+            .safe_sub(start.price_cumulative)?
+            .safe_div(elapsed)?
+            .to_u64()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CandleData {
+    pub bucket_start: i64,              // 8 - Start of the time bucket
+    pub open: u64,                      // 8 - First traded price in the bucket
+    pub high: u64,                      // 8 - Highest traded price
+    pub low: u64,                       // 8 - Lowest traded price
+    pub close: u64,                     // 8 - Last traded price
+    pub volume_sol: u64,                // 8 - Lamports traded through the curve (excluding fees)
+    pub volume_tokens: u64,             // 8 - Tokens traded
+    pub trade_count: u32,               // 4 - Trades in the bucket (0 = empty)
+}
+
+impl CandleData {
+    pub const SIZE: usize = 8 * 7 + 4;
+}
+
+#[account]
+pub struct LiveCandle {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub current: CandleData,            // 60 - Bucket currently being traded
+    pub completed: CandleData,          // 60 - Last finished bucket awaiting finalize_candle
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl LiveCandle {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + CandleData::SIZE          // current
+        + CandleData::SIZE          // completed
+        + 1;                        // bump
+
+    // Candle width in seconds (5 minutes)
+    pub const INTERVAL: i64 = 300;
+
+    pub fn bucket_for(timestamp: i64) -> i64 {
+        timestamp - timestamp.rem_euclid(Self::INTERVAL)
+    }
+
+    // Fold a trade at `price` into the bucket containing `timestamp`
+    // If the previous bucket was never finalized it is overwritten by the one that just closed
+    pub fn record(&mut self, timestamp: i64, price: u64, sol_volume: u64, token_volume: u64) -> Result<()> {
+        let bucket_start = Self::bucket_for(timestamp);
+        if self.current.trade_count > 0 && self.current.bucket_start != bucket_start {
+            self.completed = self.current;
+            self.current = CandleData::default();
+        }
+
+        let candle = &mut self.current;
+        if candle.trade_count == 0 {
+            candle.bucket_start = bucket_start;
+            candle.open = price;
+            candle.high = price;
+            candle.low = price;
+        }
+        candle.high = candle.high.max(price);
+        candle.low = candle.low.min(price);
+        candle.close = price;
+        candle.volume_sol = candle.volume_sol.safe_add(sol_volume)?;
+        candle.volume_tokens = candle.volume_tokens.safe_add(token_volume)?;
+        candle.trade_count = candle.trade_count.safe_add(1)?;
+        Ok(())
+    }
+
+    // Remove and return the finished bucket starting at `bucket_start`
+    pub fn take_finished(&mut self, bucket_start: i64, now: i64) -> Result<CandleData> {
+        if self.completed.trade_count > 0 && self.completed.bucket_start == bucket_start {
+            let data = self.completed;
+            self.completed = CandleData::default();
+            return Ok(data);
+        }
+
+        let bucket_end = self.current.bucket_start.safe_add(Self::INTERVAL)?;
+        if self.current.trade_count > 0
+            && self.current.bucket_start == bucket_start
+            && now >= bucket_end
+        {
+            let data = self.current;
+            self.current = CandleData::default();
+            return Ok(data);
+        }
+
+        err!(ErrorCode::CandleNotFinalizable)
+    }
+}
+
+#[account]
+pub struct Candle {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub data: CandleData,               // 60 - OHLCV for the bucket
+    pub finalized_at: i64,              // 8 - When the crank persisted this candle
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl Candle {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + CandleData::SIZE          // data
+        + 8                         // finalized_at
+        + 1;                        // bump
+}
+
+#[account]
+pub struct BuyerRecord {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub buyer: Pubkey,                  // 32 - Wallet that bought
+    pub first_buy_at: i64,              // 8 - Timestamp of the first buy
+    pub bump: u8,                       // 1 - PDA bump seed
+    pub rent_payer: Pubkey,             // 32 - Account that paid the rent, refunded on close
+    pub buyer_index: u64,               // 8 - Order of the first buy among unique buyers (0 = first)
+    pub net_sol_contributed: u64,       // 8 - Lamports spent buying less lamports received selling, floored at 0
+}
+
+impl BuyerRecord {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // buyer
+        + 8                         // first_buy_at
+        + 1                         // bump
+        + 32                        // rent_payer
+        + 8                         // buyer_index
+        + 8;                        // net_sol_contributed
+}
+
+#[account]
+pub struct BadgeCollection {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub creator: Pubkey,                // 32 - Curve creator who set up the badges
+    pub early_buyer_count: u16,         // 2 - How many of the first buyers get an early-buyer badge
+    pub name: String,                   // 4 + up to 32 - Badge NFT name
+    pub symbol: String,                 // 4 + up to 10 - Badge NFT symbol
+    pub uri: String,                    // 4 + up to 200 - Badge NFT metadata URI
+    pub early_buyer_claims: [u8; 256],  // 256 - Claim bitmap indexed by buyer_index
+    pub graduate_claims: [u8; 256],     // 256 - Claim bitmap indexed by buyer_index
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl BadgeCollection {
+    pub const MAX_NAME: usize = 32;
+    pub const MAX_SYMBOL: usize = 10;
+    pub const MAX_URI: usize = 200;
+    pub const BITMAP_BYTES: usize = 256;
+    // Buyers beyond this index can't claim badges
+    pub const MAX_HOLDERS: usize = Self::BITMAP_BYTES * 8;
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // creator
+        + 2                         // early_buyer_count
+        + 4 + Self::MAX_NAME        // name
+        + 4 + Self::MAX_SYMBOL      // symbol
+        + 4 + Self::MAX_URI         // uri
+        + Self::BITMAP_BYTES        // early_buyer_claims
+        + Self::BITMAP_BYTES        // graduate_claims
+        + 1;                        // bump
+
+    // Flip the claim bit for `buyer_index`, failing if it was already set
+    pub fn mark_claimed(&mut self, kind: BadgeKind, buyer_index: u64) -> Result<()> {
+        require!(
+            buyer_index < Self::MAX_HOLDERS as u64,
+            ErrorCode::NotEligibleForBadge
+        );
+        let bitmap = match kind {
+            BadgeKind::EarlyBuyer => &mut self.early_buyer_claims,
+            BadgeKind::Graduate => &mut self.graduate_claims,
+        };
+        let byte = (buyer_index / 8) as usize;
+        let bit = 1u8 << (buyer_index % 8);
+        require!(bitmap[byte] & bit == 0, ErrorCode::BadgeAlreadyClaimed);
+        bitmap[byte] |= bit;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct KingOfTheHill {
+    pub mint: Pubkey,                   // 32 - Curve with the highest trending score (default = none yet)
+    pub score: u64,                     // 8 - Its trending score when last refreshed
+    pub crowned_at: i64,                // 8 - When the current king took the spot
+    pub updated_at: i64,                // 8 - When the score was last refreshed
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl KingOfTheHill {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 8                         // score
+        + 8                         // crowned_at
+        + 8                         // updated_at
+        + 1;                        // bump
+
+    // The king's score decays like the curve's own trending score
+    pub fn score_at(&self, now: i64) -> Result<u64> {
+        decay_trending_score(self.score, now.saturating_sub(self.updated_at))
+    }
+}
+
+#[account]
+pub struct Competition {
+    pub id: u64,                        // 8 - Competition identifier (PDA seed)
+    pub authority: Pubkey,              // 32 - Platform authority that created it
+    pub mint: Pubkey,                   // 32 - Curve the competition is scoped to (default = platform-wide)
+    pub start_time: i64,                // 8 - When volume starts counting
+    pub end_time: i64,                  // 8 - When volume stops counting
+    pub prize_pool: u64,                // 8 - Lamports deposited for prizes
+    pub total_volume: u64,              // 8 - Volume traded by all participants
+    pub participant_count: u64,         // 8 - Registered entries
+    pub finalized: bool,                // 1 - Whether prizes have been fixed
+    pub leaders: [Pubkey; 3],           // 32 * 3 - Top traders by volume (default = empty slot)
+    pub leader_volumes: [u64; 3],       // 8 * 3 - Their volumes
+    pub prizes: [u64; 3],               // 8 * 3 - Prize per rank, set at finalization
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl Competition {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 8                         // id
+        + 32                        // authority
+        + 32                        // mint
+        + 8                         // start_time
+        + 8                         // end_time
+        + 8                         // prize_pool
+        + 8                         // total_volume
+        + 8                         // participant_count
+        + 1                         // finalized
+        + 32 * Self::WINNERS        // leaders
+        + 8 * Self::WINNERS         // leader_volumes
+        + 8 * Self::WINNERS         // prizes
+        + 1;                        // bump
+
+    pub const WINNERS: usize = 3;
+
+    // Share of the prize pool per rank; unfilled ranks are redistributed pro rata
+    pub const PRIZE_SHARES_BPS: [u64; 3] = [5_000, 3_000, 2_000];
+
+    pub fn is_active(&self, now: i64) -> bool {
+        !self.finalized && now >= self.start_time && now < self.end_time
+    }
+
+    pub fn covers_mint(&self, mint: &Pubkey) -> bool {
+        self.mint == Pubkey::default() || self.mint == *mint
+    }
+
+    // Insert or move `trader` on the leaderboard given their cumulative volume
+    pub fn update_leaderboard(&mut self, trader: Pubkey, volume: u64) {
+        // Ranks are kept sorted, so the last slot is either empty or the lowest volume
+        let last = Self::WINNERS - 1;
+        let slot = match self.leaders.iter().position(|leader| *leader == trader) {
+            Some(slot) => slot,
+            None if self.leaders[last] == Pubkey::default() || volume > self.leader_volumes[last] => {
+                self.leaders[last] = trader;
+                last
+            }
+            None => return,
+        };
+        self.leader_volumes[slot] = volume;
+
+        // Keep ranks ordered by volume, highest first
+        let mut ranked: Vec<(Pubkey, u64)> = self
+            .leaders
+            .iter()
+            .copied()
+            .zip(self.leader_volumes.iter().copied())
+            .collect();
+        ranked.sort_by_key(|&(_, volume)| std::cmp::Reverse(volume));
+        for (i, (leader, volume)) in ranked.into_iter().enumerate() {
+            self.leaders[i] = leader;
+            self.leader_volumes[i] = volume;
+        }
+    }
+
+    // Split the prize pool across filled ranks
+    pub fn compute_prizes(&mut self) -> Result<()> {
+        let filled = self
+            .leaders
+            .iter()
+            .take_while(|leader| **leader != Pubkey::default())
+            .count();
+        let total_shares: u64 = Self::PRIZE_SHARES_BPS[..filled].iter().sum();
+        for rank in 0..filled {
+            self.prizes[rank] = (self.prize_pool as u128)
+                .safe_mul(Self::PRIZE_SHARES_BPS[rank] as u128)?
+                .safe_div(total_shares as u128)?
+                .to_u64()?;
+        }
+        Ok(())
+    }
+}
+
+#[account]
+pub struct CompetitionEntry {
+    pub competition: Pubkey,            // 32 - Competition entered
+    pub trader: Pubkey,                 // 32 - Participating wallet
+    pub volume: u64,                    // 8 - Lamports traded while the competition was active
+    pub claimed: bool,                  // 1 - Whether the prize was claimed
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl CompetitionEntry {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // competition
+        + 32                        // trader
+        + 8                         // volume
+        + 1                         // claimed
+        + 1;                        // bump
+}
+
+#[account]
+pub struct UserPoints {
+    pub user: Pubkey,                   // 32 - Wallet earning points
+    pub total_points: u64,              // 8 - Lifetime points
+    pub epoch: u32,                     // 4 - Epoch `epoch_points` belongs to
+    pub epoch_points: u64,              // 8 - Points earned in `epoch`
+    pub snapshot_epoch: u32,            // 4 - Last closed epoch the wallet earned points in
+    pub snapshot_points: u64,           // 8 - Points earned in `snapshot_epoch`
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl UserPoints {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // user
+        + 8                         // total_points
+        + 4                         // epoch
+        + 8                         // epoch_points
+        + 4                         // snapshot_epoch
+        + 8                         // snapshot_points
+        + 1;                        // bump
+
+    // Roll a finished epoch into the snapshot, then add `points` to `current_epoch`
+    pub fn accrue(&mut self, current_epoch: u32, points: u64) -> Result<()> {
+        if self.epoch != current_epoch {
+            if self.epoch_points > 0 {
+                self.snapshot_epoch = self.epoch;
+                self.snapshot_points = self.epoch_points;
+            }
+            self.epoch = current_epoch;
+            self.epoch_points = 0;
+        }
+        self.epoch_points = self.epoch_points.safe_add(points)?;
+        self.total_points = self.total_points.safe_add(points)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct FeeShareVault {
+    pub stake_mint: Pubkey,             // 32 - Platform token stakers deposit
+    pub total_staked: u64,              // 8 - Tokens currently staked
+    pub acc_fee_per_share: u128,        // 16 - Lamports earned per staked token, scaled by ACC_PRECISION
+    pub undistributed: u64,             // 8 - Fees received while nothing was staked
+    pub total_distributed: u64,         // 8 - Lamports ever credited to stakers
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl FeeShareVault {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // stake_mint
+        + 8                         // total_staked
+        + 16                        // acc_fee_per_share
+        + 8                         // undistributed
+        + 8                         // total_distributed
+        + 1;                        // bump
+
+    pub const ACC_PRECISION: u128 = 1_000_000_000_000;
+
+    // Credit `amount` lamports (plus anything held back) to current stakers
+    pub fn distribute(&mut self, amount: u64) -> Result<()> {
+        let amount = amount.safe_add(self.undistributed)?;
+        if self.total_staked == 0 {
+            self.undistributed = amount;
+            return Ok(());
+        }
+        let per_share = (amount as u128)
+            .safe_mul(Self::ACC_PRECISION)?
+            .safe_div(self.total_staked as u128)?;
+        self.acc_fee_per_share = self.acc_fee_per_share.safe_add(per_share)?;
+        self.total_distributed = self.total_distributed.safe_add(amount)?;
+        self.undistributed = 0;
+        Ok(())
+    }
+
+    // Lamports accrued by `staked` tokens since the accumulator started
+    pub fn accrued_for(&self, staked: u64) -> Result<u128> {
+        (staked as u128)
+            .safe_mul(self.acc_fee_per_share)?
+            .safe_div(Self::ACC_PRECISION)
+    }
+}
+
+#[account]
+pub struct FeeShareStake {
+    pub owner: Pubkey,                  // 32 - Staker
+    pub amount: u64,                    // 8 - Platform tokens staked
+    pub reward_debt: u128,              // 16 - Accrued fees already accounted for
+    pub claimed: u64,                   // 8 - Lamports ever paid out
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl FeeShareStake {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // owner
+        + 8                         // amount
+        + 16                        // reward_debt
+        + 8                         // claimed
+        + 1;                        // bump
+}
+
+#[account]
+pub struct InsuranceFund {
+    pub payout_delay: i64,              // 8 - Seconds a proposed payout waits before it can be executed
+    pub total_deposited: u64,           // 8 - Lamports ever received from trading fees
+    pub total_paid_out: u64,            // 8 - Lamports ever paid to compensated users
+    pub pending_amount: u64,            // 8 - Lamports reserved by proposed, unexecuted payouts
+    pub next_payout_id: u64,            // 8 - Id given to the next proposed payout
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl InsuranceFund {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 8                         // payout_delay
+        + 8                         // total_deposited
+        + 8                         // total_paid_out
+        + 8                         // pending_amount
+        + 8                         // next_payout_id
+        + 1;                        // bump
+
+    // Lamports not needed for rent or already reserved by pending payouts
+    pub fn available_lamports(&self, info: &AccountInfo) -> Result<u64> {
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(Self::MAX_SIZE);
+        Ok(info
+            .lamports()
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(self.pending_amount))
+    }
+}
+
+#[account]
+pub struct InsurancePayout {
+    pub id: u64,                        // 8 - Sequential payout id (PDA seed)
+    pub recipient: Pubkey,              // 32 - Wallet being compensated
+    pub amount: u64,                    // 8 - Lamports to pay
+    pub reason_len: u8,                 // 1 - Bytes of `reason` in use
+    pub reason: [u8; 64],               // 64 - Zero-padded description of the incident
+    pub proposed_at: i64,               // 8
+    pub executable_at: i64,             // 8 - Earliest time the payout can be executed
+    pub executed_at: i64,               // 8 - When it was paid (0 = pending)
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl InsurancePayout {
+    pub const MAX_REASON: usize = 64;
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 8                         // id
+        + 32                        // recipient
+        + 8                         // amount
+        + 1                         // reason_len
+        + 64                        // reason
+        + 8                         // proposed_at
+        + 8                         // executable_at
+        + 8                         // executed_at
+        + 1;                        // bump
+
+    pub fn reason(&self) -> &str {
+        fixed_str(&self.reason, self.reason_len)
+    }
+}
+
+#[account]
+pub struct TradeGuard {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub trader: Pubkey,                 // 32 - Wallet whose trades are guarded
+    pub last_buy_slot: u64,             // 8 - Slot of the last buy (0 = never)
+    pub last_sell_slot: u64,            // 8 - Slot of the last sell (0 = never)
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl TradeGuard {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // trader
+        + 8                         // last_buy_slot
+        + 8                         // last_sell_slot
+        + 1;                        // bump
+
+    // Reject a trade within `window` slots of an opposite-side trade, then record it
+    // A buy and sell landing in the same slot is the signature of a sandwich
+    pub fn record(&mut self, is_buy: bool, slot: u64, window: u64) -> Result<()> {
+        let opposite = if is_buy { self.last_sell_slot } else { self.last_buy_slot };
+        require!(
+            window == 0 || opposite == 0 || slot >= opposite.safe_add(window)?,
+            ErrorCode::RoundTripTooSoon
+        );
+        if is_buy {
+            self.last_buy_slot = slot;
+        } else {
+            self.last_sell_slot = slot;
+        }
+        Ok(())
+    }
+}
+
+#[account]
+pub struct TraderPosition {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub owner: Pubkey,                  // 32 - Wallet whose trades are tracked
+    pub net_tokens: u64,                // 8 - Tokens bought minus tokens sold (floored at 0)
+    pub tokens_bought: u64,             // 8 - Tokens received from the curve
+    pub tokens_sold: u64,               // 8 - Tokens sold back to the curve
+    pub sol_spent: u64,                 // 8 - Lamports paid for buys (including fees)
+    pub sol_received: u64,              // 8 - Lamports received from sells (after fees)
+    pub opened_at: i64,                 // 8 - When tracking started
+    pub last_trade_at: i64,             // 8 - Timestamp of the last tracked trade
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl TraderPosition {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // owner
+        + 8                         // net_tokens
+        + 8                         // tokens_bought
+        + 8                         // tokens_sold
+        + 8                         // sol_spent
+        + 8                         // sol_received
+        + 8                         // opened_at
+        + 8                         // last_trade_at
+        + 1;                        // bump
+
+    pub fn record_buy(&mut self, sol_spent: u64, tokens_out: u64, timestamp: i64) -> Result<()> {
+        self.net_tokens = self.net_tokens.safe_add(tokens_out)?;
+        self.tokens_bought = self.tokens_bought.safe_add(tokens_out)?;
+        self.sol_spent = self.sol_spent.safe_add(sol_spent)?;
+        self.last_trade_at = timestamp;
+        Ok(())
+    }
+
+    // Tokens acquired outside the curve (transfers, pre-tracking buys) aren't counted,
+    // so sells can exceed the tracked balance
+    pub fn record_sell(&mut self, tokens_in: u64, sol_received: u64, timestamp: i64) -> Result<()> {
+        self.net_tokens = self.net_tokens.saturating_sub(tokens_in);
+        self.tokens_sold = self.tokens_sold.safe_add(tokens_in)?;
+        self.sol_received = self.sol_received.safe_add(sol_received)?;
+        self.last_trade_at = timestamp;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct GraduationArchive {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub creator: Pubkey,                // 32 - Creator of the bonding curve
+    pub virtual_sol_reserves: u64,      // 8 - Virtual SOL used by the curve
+    pub virtual_token_reserves: u64,    // 8 - Virtual tokens used by the curve
+    pub final_sol_reserves: u64,        // 8 - Real SOL reserves at migration
+    pub final_token_reserves: u64,      // 8 - Real token reserves at migration
+    pub unique_buyers: u64,             // 8 - Distinct wallets that bought on the curve
+    pub created_at: i64,                // 8 - When the curve was initialized
+    pub graduated_at: i64,              // 8 - When the curve migrated
+    pub duration: i64,                  // 8 - Seconds from launch to graduation
+    pub total_fees_paid: u64,           // 8 - Lifetime fees paid to the platform
+    pub raydium_pool: Pubkey,           // 32 - Raydium pool address
+    pub archived_at: i64,               // 8 - When this archive was written
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl GraduationArchive {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // creator
+        + 8                         // virtual_sol_reserves
+        + 8                         // virtual_token_reserves
+        + 8                         // final_sol_reserves
+        + 8                         // final_token_reserves
+        + 8                         // unique_buyers
+        + 8                         // created_at
+        + 8                         // graduated_at
+        + 8                         // duration
+        + 8                         // total_fees_paid
+        + 32                        // raydium_pool
+        + 8                         // archived_at
+        + 1;                        // bump
+}
+
+#[account]
+pub struct LpBurnInfo {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub lp_mint: Pubkey,                // 32 - LP token mint address
+    pub raydium_pool: Pubkey,           // 32 - Raydium pool address
+    pub lp_burned_amount: u64,          // 8 - Amount of LP tokens burned
+    pub burn_timestamp: i64,            // 8 - When LP tokens were burned
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl LpBurnInfo {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // lp_mint
+        + 32                        // raydium_pool
+        + 8                         // lp_burned_amount
+        + 8                         // burn_timestamp
+        + 1;                        // bump
+}
+
+#[account]
+pub struct LpLock {
+    pub mint: Pubkey,                   // 32 - Token mint address
+    pub lp_mint: Pubkey,                // 32 - LP token mint address
+    pub raydium_pool: Pubkey,           // 32 - Raydium pool address
+    pub lp_amount: u64,                 // 8 - Amount of LP tokens locked
+    pub locked_at: i64,                 // 8 - When LP tokens were locked
+    pub unlock_time: i64,               // 8 - When LP tokens can be withdrawn
+    pub withdrawn: bool,                // 1 - Whether LP tokens were withdrawn
+    pub bump: u8,                       // 1 - PDA bump seed
+    pub liquidity_per_lp: u128,         // 16 - Pool liquidity backing each LP token (1e12 scale) at lock or last harvest
+}
+
+impl LpLock {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // lp_mint
+        + 32                        // raydium_pool
+        + 8                         // lp_amount
+        + 8                         // locked_at
+        + 8                         // unlock_time
+        + 1                         // withdrawn
+        + 1                         // bump
+        + 16;                       // liquidity_per_lp
+}
+
+#[account]
+#[derive(Debug)]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey,        // 32 - Who receives the vested tokens
+    pub mint: Pubkey,               // 32 - Token mint address
+    pub total_amount: u64,          // 8 - Total tokens to vest
+    pub claimed_amount: u64,        // 8 - Amount already claimed
+    pub start_time: i64,            // 8 - When vesting starts
+    pub cliff_time: i64,            // 8 - When cliff period ends
+    pub end_time: i64,              // 8 - When vesting fully completes
+    pub release_interval: i64,      // 8 - How often tokens unlock (e.g., monthly = 2592000 seconds)
+    pub last_claim_time: i64,       // 8 - Last time tokens were claimed
+    pub bump: u8,                   // 1 - PDA bump seed
+    pub claim_delegate: Pubkey,     // 32 - May trigger claims for the beneficiary (default: none)
+}
+
+impl VestingSchedule {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // beneficiary
+        + 32                        // mint
+        + 8                         // total_amount
+        + 8                         // claimed_amount
+        + 8                         // start_time
+        + 8                         // cliff_time
+        + 8                         // end_time
+        + 8                         // release_interval
+        + 8                         // last_claim_time
+        + 1                         // bump
+        + 32;                       // claim_delegate
+
+    /// Whether `claimer` may trigger a claim: the beneficiary or, if set, the claim delegate
+    pub fn can_claim(&self, claimer: &Pubkey) -> bool {
+        *claimer == self.beneficiary
+            || (self.claim_delegate != Pubkey::default() && *claimer == self.claim_delegate)
+    }
+
+    /// Tokens unlocked at `current_time`: nothing before the cliff, linear from the start
+    /// time after it, everything from the end time. Never exceeds `total_amount`
+    pub fn unlocked_amount(&self, current_time: i64) -> Result<u64> {
+        vested_amount(self.total_amount, self.start_time, self.cliff_time, self.end_time, current_time)
+    }
+}
+
+#[account]
+pub struct SolVestingSchedule {
+    pub funder: Pubkey,             // 32 - Who deposited the lamports (PDA seed)
+    pub beneficiary: Pubkey,        // 32 - Who receives the vested lamports
+    pub total_amount: u64,          // 8 - Total lamports to vest
+    pub claimed_amount: u64,        // 8 - Lamports already claimed
+    pub start_time: i64,            // 8 - When vesting starts
+    pub cliff_time: i64,            // 8 - When cliff period ends
+    pub end_time: i64,              // 8 - When vesting fully completes
+    pub release_interval: i64,      // 8 - How often lamports unlock
+    pub last_claim_time: i64,       // 8 - Last time lamports were claimed
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl SolVestingSchedule {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // funder
+        + 32                        // beneficiary
+        + 8                         // total_amount
+        + 8                         // claimed_amount
+        + 8                         // start_time
+        + 8                         // cliff_time
+        + 8                         // end_time
+        + 8                         // release_interval
+        + 8                         // last_claim_time
+        + 1;                        // bump
+
+    /// Lamports unlocked at `current_time`, on the same schedule as `VestingSchedule`
+    pub fn unlocked_amount(&self, current_time: i64) -> Result<u64> {
+        vested_amount(self.total_amount, self.start_time, self.cliff_time, self.end_time, current_time)
+    }
+}
+
+// Helper function to compute how much of `total_amount` has vested at `current_time`:
+// nothing before the cliff, linear from the start time after it, everything from the end time
+fn vested_amount(
+    total_amount: u64,
+    start_time: i64,
+    cliff_time: i64,
+    end_time: i64,
+    current_time: i64,
+) -> Result<u64> {
+    // If we haven't reached the cliff, nothing is unlocked
+    if current_time < cliff_time {
+        return Ok(0);
+    }
+
+    // If we're past the end time, everything is unlocked
+    if current_time >= end_time {
+        return Ok(total_amount);
+    }
+
+    // Linear vesting between start and end
+    let vesting_duration = (end_time as i128).safe_sub(start_time as i128)?;
+    // Clamped so a cliff before the start never yields a negative elapsed time
+    let elapsed_time = (current_time as i128)
+        .safe_sub(start_time as i128)?
+        .max(0);
+
+    // Calculate unlocked amount proportionally
+    let unlocked = (total_amount as u128)
+        .safe_mul(elapsed_time as u128)?
+        .safe_div(vesting_duration as u128)?
+        .to_u64()?;
+
+    Ok(unlocked.min(total_amount))
+}
+
+#[account]
+pub struct TokenLock {
+    pub owner: Pubkey,              // 32 - Who locked the tokens and may withdraw them (PDA seed)
+    pub mint: Pubkey,               // 32 - Mint of the locked tokens (PDA seed)
+    pub index: u64,                 // 8 - Distinguishes an owner's locks of the same mint (PDA seed)
+    pub amount: u64,                // 8 - Tokens locked
+    pub locked_at: i64,             // 8 - When the tokens were locked
+    pub unlock_time: i64,           // 8 - When the tokens can be withdrawn
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl TokenLock {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // owner
+        + 32                        // mint
+        + 8                         // index
+        + 8                         // amount
+        + 8                         // locked_at
+        + 8                         // unlock_time
+        + 1;                        // bump
+}
+
+#[account]
+pub struct OtcDeal {
+    pub id: u64,                    // 8 - Distinguishes a seller's deals (PDA seed)
+    pub seller: Pubkey,             // 32 - Deposits the tokens, receives the SOL (PDA seed)
+    pub buyer: Pubkey,              // 32 - Deposits the SOL, receives the tokens
+    pub mint: Pubkey,               // 32 - Mint of the tokens sold
+    pub token_amount: u64,          // 8 - Tokens sold
+    pub sol_amount: u64,            // 8 - Lamports paid for them
+    pub tokens_deposited: bool,     // 1 - Whether the token leg is escrowed
+    pub sol_deposited: bool,        // 1 - Whether the SOL leg is escrowed
+    pub created_at: i64,            // 8 - When the deal was opened
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl OtcDeal {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 8                         // id
+        + 32                        // seller
+        + 32                        // buyer
+        + 32                        // mint
+        + 8                         // token_amount
+        + 8                         // sol_amount
+        + 1                         // tokens_deposited
+        + 1                         // sol_deposited
+        + 8                         // created_at
+        + 1;                        // bump
+}
+
+/// Payee and schedule of a payment stream
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamTerms {
+    pub payee: Pubkey,
+    pub rate_per_second: u64,
+    pub start_time: i64,
+    pub cliff_duration: i64,
+    pub duration: i64,
+    pub cancelable: bool,
+}
+
+#[account]
+pub struct PaymentStream {
+    pub id: u64,                    // 8 - Distinguishes a payer's streams (PDA seed)
+    pub payer: Pubkey,              // 32 - Funded the stream (PDA seed)
+    pub payee: Pubkey,              // 32 - Receives the streamed funds
+    pub mint: Pubkey,               // 32 - Streamed token (default: lamports)
+    pub rate_per_second: u64,       // 8 - Amount streamed per second
+    pub start_time: i64,            // 8 - When streaming starts
+    pub cliff_time: i64,            // 8 - Nothing is withdrawable before this
+    pub end_time: i64,              // 8 - When the full amount has streamed
+    pub withdrawn: u64,             // 8 - Amount the payee has withdrawn
+    pub cancelable: bool,           // 1 - Whether the payer may stop the stream before it ends
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl PaymentStream {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 8                         // id
+        + 32                        // payer
+        + 32                        // payee
+        + 32                        // mint
+        + 8                         // rate_per_second
+        + 8                         // start_time
+        + 8                         // cliff_time
+        + 8                         // end_time
+        + 8                         // withdrawn
+        + 1                         // cancelable
+        + 1;                        // bump
+
+    // Record a new stream's terms, rejecting ones that can never stream
+    fn open(&mut self, id: u64, payer: Pubkey, mint: Pubkey, terms: StreamTerms, bump: u8) -> Result<()> {
+        require!(terms.rate_per_second > 0, ErrorCode::InvalidAmount);
+        require!(
+            terms.duration > 0
+                && terms.cliff_duration >= 0
+                && terms.cliff_duration <= terms.duration
+                && terms.payee != payer,
+            ErrorCode::InvalidStreamTerms
+        );
+
+        self.id = id;
+        self.payer = payer;
+        self.payee = terms.payee;
+        self.mint = mint;
+        self.rate_per_second = terms.rate_per_second;
+        self.start_time = terms.start_time;
+        self.cliff_time = terms.start_time.safe_add(terms.cliff_duration)?;
+        self.end_time = terms.start_time.safe_add(terms.duration)?;
+        self.withdrawn = 0;
+        self.cancelable = terms.cancelable;
+        self.bump = bump;
+        // Fails now rather than at withdrawal if the total doesn't fit
+        self.total_amount()?;
+        Ok(())
+    }
+
+    pub fn is_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    /// Amount escrowed for the whole stream
+    pub fn total_amount(&self) -> Result<u64> {
+        self.streamed_between(self.end_time)
+    }
+
+    /// Amount streamed by `current_time`: nothing before the cliff, then `rate_per_second`
+    /// for every second since the start, up to the end time
+    pub fn streamed_amount(&self, current_time: i64) -> Result<u64> {
+        if current_time < self.cliff_time {
+            return Ok(0);
+        }
+        self.streamed_between(current_time.min(self.end_time))
+    }
+
+    fn streamed_between(&self, until: i64) -> Result<u64> {
+        let elapsed = (until as i128).safe_sub(self.start_time as i128)?.max(0);
+        (self.rate_per_second as u128).safe_mul(elapsed as u128)?.to_u64()
+    }
+}
+
+#[account]
+pub struct SnapshotRegistry {
+    pub mint: Pubkey,               // 32 - Mint whose holders are snapshotted (PDA seed)
+    pub next_epoch: u64,            // 8 - Epoch the next snapshot is recorded as
+    pub latest_slot: u64,           // 8 - Slot of the latest snapshot; later ones must be newer
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl SnapshotRegistry {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 8                         // next_epoch
+        + 8                         // latest_slot
+        + 1;                        // bump
+}
+
+#[account]
+pub struct HolderSnapshot {
+    pub mint: Pubkey,               // 32 - Mint whose holders are snapshotted (PDA seed)
+    pub epoch: u64,                 // 8 - Sequential snapshot number for the mint (PDA seed)
+    pub merkle_root: [u8; 32],      // 32 - Root over `snapshot::leaf(holder, balance)` leaves
+    pub slot: u64,                  // 8 - Slot the balances were read at
+    pub holder_count: u64,          // 8 - Leaves in the tree
+    pub total_balance: u64,         // 8 - Sum of the snapshotted balances
+    pub created_at: i64,            // 8 - When the snapshot was committed
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl HolderSnapshot {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 8                         // epoch
+        + 32                        // merkle_root
+        + 8                         // slot
+        + 8                         // holder_count
+        + 8                         // total_balance
+        + 8                         // created_at
+        + 1;                        // bump
+
+    /// Whether `proof` shows `holder` held `balance` in this snapshot
+    pub fn verify_balance(&self, holder: &Pubkey, balance: u64, proof: &[[u8; 32]]) -> bool {
+        snapshot::verify(&self.merkle_root, snapshot::leaf(holder, balance), proof)
+    }
+}
+
+#[account]
+pub struct Jackpot {
+    pub mint: Pubkey,                   // 32 - Mint of the curve feeding the jackpot (PDA seed)
+    pub fee_share_bps: u16,             // 2 - Share of each buy's fee added to the pot
+    pub buys_per_draw: u64,             // 8 - Every this many buys, a draw among the recent buyers is due
+    pub buy_count: u64,                 // 8 - Buys counted since the jackpot was enabled
+    pub total_contributed: u64,         // 8 - Lamports ever added from fees
+    pub total_paid: u64,                // 8 - Lamports ever paid to winners
+    pub draws: u64,                     // 8 - Pots paid out
+    pub last_winner: Pubkey,            // 32 - Winner of the latest draw
+    pub last_payout: u64,               // 8 - Lamports paid in the latest draw
+    pub last_draw_at: i64,              // 8 - When the latest draw happened (0 = never)
+    pub draw_pending: bool,             // 1 - Whether a draw is due and waiting on randomness
+    pub recent_buyers: [Pubkey; 8],     // 32 * 8 - Latest buyers, the pool draws pick from
+    pub recent_cursor: u8,              // 1 - Index in recent_buyers the next buyer is written to
+    pub pending_winner: Pubkey,         // 32 - Draw winner yet to be paid (default = none)
+    pub pending_payout: u64,            // 8 - Lamports reserved for pending_winner
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl Jackpot {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 2                         // fee_share_bps
+        + 8                         // buys_per_draw
+        + 8                         // buy_count
+        + 8                         // total_contributed
+        + 8                         // total_paid
+        + 8                         // draws
+        + 32                        // last_winner
+        + 8                         // last_payout
+        + 8                         // last_draw_at
+        + 1                         // draw_pending
+        + 32 * Self::RECENT_BUYERS  // recent_buyers
+        + 1                         // recent_cursor
+        + 32                        // pending_winner
+        + 8                         // pending_payout
+        + 1;                        // bump
+
+    pub const RECENT_BUYERS: usize = 8;
+
+    // Highest share of the platform's buy fee a jackpot can take
+    pub const MAX_FEE_SHARE_BPS: u16 = 2_000;
+
+    // Fewest buys between draws, so a single wallet can't farm every pot
+    pub const MIN_BUYS_PER_DRAW: u64 = 10;
+
+    // Lamports waiting for the next winner
+    pub fn pot(&self) -> Result<u64> {
+        self.total_contributed.safe_sub(self.total_paid)
+    }
+
+    // Overwrite the oldest recent buyer with `buyer`
+    pub fn record_recent_buyer(&mut self, buyer: Pubkey) {
+        let index = self.recent_cursor as usize % Self::RECENT_BUYERS;
+        self.recent_buyers[index] = buyer;
+        self.recent_cursor = ((index + 1) % Self::RECENT_BUYERS) as u8;
+    }
+}
+
+impl RandomnessConsumer for Jackpot {
+    // Only one payout is reserved at a time, so the previous winner must be paid first
+    fn awaiting_randomness(&self) -> bool {
+        self.draw_pending && self.pending_payout == 0
+    }
+
+    fn consume_randomness(&mut self, value: &[u8; 32], now: i64) -> Result<()> {
+        require!(self.awaiting_randomness(), ErrorCode::RandomnessNotNeeded);
+        let filled = (self.buy_count as usize).min(Self::RECENT_BUYERS);
+        let winner = self.recent_buyers[randomness::pick_index(value, filled)];
+        let amount = self.pot()?;
+        self.total_paid = self.total_paid.safe_add(amount)?;
+        self.draws = self.draws.safe_add(1)?;
+        self.last_winner = winner;
+        self.last_payout = amount;
+        self.last_draw_at = now;
+        self.pending_winner = winner;
+        self.pending_payout = amount;
+        self.draw_pending = false;
+
+        emit!(JackpotWon {
+            mint: self.mint,
+            winner,
+            amount,
+            draw: self.draws,
+            buy_count: self.buy_count,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// Features that can draw from verifiable randomness, each backed by a `RandomnessConsumer`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RandomnessConsumerKind {
+    /// A curve's `Jackpot`
+    Jackpot,
+}
+
+#[account]
+pub struct RandomnessRequest {
+    pub consumer: Pubkey,               // 32 - Account the draw is for (PDA seed)
+    pub kind: RandomnessConsumerKind,   // 1 - Type of the consumer account
+    pub randomness_account: Pubkey,     // 32 - Switchboard randomness account the draw reads
+    pub seed_slot: u64,                 // 8 - Slot the randomness was committed in
+    pub requested_slot: u64,            // 8 - Slot the request was opened in
+    pub requested_at: i64,              // 8 - When the request was opened
+    pub fulfilled_at: i64,              // 8 - When the value was delivered (0 while pending)
+    pub value: [u8; 32],                // 32 - The delivered value
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl RandomnessRequest {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // consumer
+        + 1                         // kind
+        + 32                        // randomness_account
+        + 8                         // seed_slot
+        + 8                         // requested_slot
+        + 8                         // requested_at
+        + 8                         // fulfilled_at
+        + 32                        // value
+        + 1;                        // bump
+
+    // Slots after which an unfulfilled request can be replaced
+    pub const EXPIRY_SLOTS: u64 = 150;
+
+    pub fn is_pending(&self) -> bool {
+        self.requested_slot != 0 && self.fulfilled_at == 0
+    }
+
+    pub fn blocks_new_request(&self, slot: u64) -> Result<bool> {
+        Ok(self.is_pending() && slot <= self.requested_slot.safe_add(Self::EXPIRY_SLOTS)?)
+    }
+}
+
+#[account]
+pub struct ReflectionPool {
+    pub mint: Pubkey,                   // 32 - Mint whose trades feed the pool (PDA seed)
+    pub acc_tokens_per_share: u128,     // 16 - Tokens distributed per snapshotted token, scaled by ACC_PRECISION
+    pub total_distributed: u64,         // 8 - Tokens ever allotted to snapshot holders
+    pub total_claimed: u64,             // 8 - Tokens ever paid out to holders
+    pub next_epoch: u64,                // 8 - Lowest snapshot epoch that can still be distributed
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl ReflectionPool {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 16                        // acc_tokens_per_share
+        + 8                         // total_distributed
+        + 8                         // total_claimed
+        + 8                         // next_epoch
+        + 1;                        // bump
+
+    pub const ACC_PRECISION: u128 = 1_000_000_000_000;
+
+    // Tokens in the vault already allotted to holders but not yet claimed
+    pub fn outstanding(&self) -> Result<u64> {
+        self.total_distributed.safe_sub(self.total_claimed)
+    }
+}
+
+#[account]
+pub struct ReflectionDistribution {
+    pub mint: Pubkey,                   // 32 - Mint of the pool (PDA seed)
+    pub epoch: u64,                     // 8 - Snapshot epoch the tokens were shared against (PDA seed)
+    pub tokens_per_share: u128,         // 16 - Tokens per snapshotted token, scaled by ReflectionPool::ACC_PRECISION
+    pub amount: u64,                    // 8 - Tokens allotted to the snapshot's holders
+    pub distributed_at: i64,            // 8 - When the distribution was made
+    pub bump: u8,                       // 1 - PDA bump seed
+    pub claimed: u64,                   // 8 - Tokens holders have claimed so far, never more than amount
+}
+
+impl ReflectionDistribution {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 8                         // epoch
+        + 16                        // tokens_per_share
+        + 8                         // amount
+        + 8                         // distributed_at
+        + 1                         // bump
+        + 8;                        // claimed
+
+    /// Tokens owed to a holder with `balance` in the snapshot
+    pub fn share_of(&self, balance: u64) -> Result<u64> {
+        (balance as u128)
+            .safe_mul(self.tokens_per_share)?
+            .safe_div(ReflectionPool::ACC_PRECISION)?
+            .to_u64()
+    }
+}
+
+#[account]
+pub struct ReflectionClaim {
+    pub holder: Pubkey,                 // 32 - Holder who claimed (PDA seed)
+    pub epoch: u64,                     // 8 - Snapshot epoch claimed (PDA seed)
+    pub amount: u64,                    // 8 - Tokens paid out
+    pub claimed_at: i64,                // 8 - When the claim was made
+    pub bump: u8,                       // 1 - PDA bump seed
+}
+
+impl ReflectionClaim {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // holder
+        + 8                         // epoch
+        + 8                         // amount
+        + 8                         // claimed_at
+        + 1;                        // bump
+}
+
+#[account]
+pub struct FundingRound {
+    pub mint: Pubkey,               // 32 - Mint of the curve the round converts into (PDA seed)
+    pub creator: Pubkey,            // 32 - Curve creator; provides the allocation, receives the SOL
+    pub discount_bps: u16,          // 2 - Discount on the migration price investors convert at
+    pub price_cap: u64,             // 8 - Highest conversion price, lamports per whole token (0 = uncapped)
+    pub token_allocation: u64,      // 8 - Tokens escrowed to back conversions
+    pub deadline: i64,              // 8 - The curve must graduate by this time or investors are refunded
+    pub total_raised: u64,          // 8 - Lamports deposited and not refunded
+    pub investor_count: u64,        // 8 - Investors who have deposited
+    pub conversion_price: u64,      // 8 - Lamports per whole token deposits convert at (0 until converted)
+    pub tokens_owed: u64,           // 8 - Tokens reserved for investors at conversion
+    pub converted_at: i64,          // 8 - When the round converted (0 until converted)
+    pub milestone_count: u8,        // 1 - Tranches the raised SOL is released in (0 = rage-quit off, paid at conversion)
+    pub milestones_released: u8,    // 1 - Tranches released to the creator so far
+    pub milestone_interval: i64,    // 8 - Seconds between tranches, counted from conversion
+    pub escrowed_sol: u64,          // 8 - Raised lamports neither released nor rage-quit
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl FundingRound {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // creator
+        + 2                         // discount_bps
+        + 8                         // price_cap
+        + 8                         // token_allocation
+        + 8                         // deadline
+        + 8                         // total_raised
+        + 8                         // investor_count
+        + 8                         // conversion_price
+        + 8                         // tokens_owed
+        + 8                         // converted_at
+        + 1                         // milestone_count
+        + 1                         // milestones_released
+        + 8                         // milestone_interval
+        + 8                         // escrowed_sol
+        + 1;                        // bump
+
+    pub fn is_converted(&self) -> bool {
+        self.conversion_price > 0
+    }
+
+    pub fn rage_quit_enabled(&self) -> bool {
+        self.milestone_count > 0
+    }
+
+    /// When the next tranche of escrowed SOL can be released
+    pub fn next_milestone_time(&self) -> Result<i64> {
+        let elapsed = self.milestone_interval.safe_mul(self.milestones_released as i64 + 1)?;
+        self.converted_at.safe_add(elapsed)
+    }
+
+    /// Lamports a holder burning `amount` of the mint's `supply` withdraws from the escrow.
+    /// Rounds down so the last holders out can always be paid
+    pub fn rage_quit_share(&self, amount: u64, supply: u64) -> Result<u64> {
+        mul_div(self.escrowed_sol as u128, amount as u128, supply as u128, Rounding::Down)?.to_u64()
+    }
+
+    /// Whether the curve migrated by the round's deadline, so deposits convert
+    pub fn graduated_in_time(&self, bonding_curve: &BondingCurve) -> bool {
+        bonding_curve.is_migrated() && bonding_curve.graduated_at <= self.deadline
+    }
+
+    /// Whether the deadline passed without the curve graduating, so deposits are refunded
+    pub fn has_failed(&self, bonding_curve: &BondingCurve, current_time: i64) -> bool {
+        current_time >= self.deadline && !self.graduated_in_time(bonding_curve)
+    }
+}
+
+#[account]
+pub struct RoundPosition {
+    pub mint: Pubkey,               // 32 - Mint of the funding round (PDA seed)
+    pub investor: Pubkey,           // 32 - Who deposited (PDA seed)
+    pub deposited: u64,             // 8 - Lamports deposited into the round
+    pub bump: u8,                   // 1 - PDA bump seed
+}
+
+impl RoundPosition {
+    pub const MAX_SIZE: usize = 8   // discriminator
+        + 32                        // mint
+        + 32                        // investor
+        + 8                         // deposited
+        + 1;                        // bump
+}
+
+/// Achievement a badge is minted for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BadgeKind {
+    EarlyBuyer,
+    Graduate,
+}
+
+/// Progress of a curve's migration to Raydium, advanced one step at a time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigrationStatus {
+    NotStarted,
+    /// Reserves moved into the migration vaults
+    FundsMoved,
+    /// Raydium pool created from the migration vaults
+    PoolCreated,
+    /// Pool LP tokens burned, liquidity permanently locked
+    LpBurned,
+}
+
+impl MigrationStatus {
+    // Decode the value stored on a zero-copy `BondingCurve`
+    pub fn from_u8(value: u8) -> MigrationStatus {
+        match value {
+            1 => MigrationStatus::FundsMoved,
+            2 => MigrationStatus::PoolCreated,
+            3 => MigrationStatus::LpBurned,
+            _ => MigrationStatus::NotStarted,
+        }
+    }
+
+    // The step that follows this one, if any
+    pub fn next(self) -> Option<MigrationStatus> {
+        match self {
+            MigrationStatus::NotStarted => Some(MigrationStatus::FundsMoved),
+            MigrationStatus::FundsMoved => Some(MigrationStatus::PoolCreated),
+            MigrationStatus::PoolCreated => Some(MigrationStatus::LpBurned),
+            MigrationStatus::LpBurned => None,
+        }
+    }
+}
+
+/// How a project's mint address was chosen, recorded so integrators know how to derive it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintSource {
+    Unminted,
+    /// A client-generated keypair, e.g. one ground for a vanity prefix
+    Keypair,
+    /// The PDA ["project_mint", project_state]
+    ProjectPda,
+}
+
+/// When a conditional order sells, by spot price in lamports per whole token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderTrigger {
+    /// Sell once the price falls to `price`
+    StopLoss { price: u64 },
+    /// Sell once the price rises to `price`
+    TakeProfit { price: u64 },
+}
+
+impl OrderTrigger {
+    pub fn price(self) -> u64 {
+        match self {
+            OrderTrigger::StopLoss { price } | OrderTrigger::TakeProfit { price } => price,
+        }
+    }
+
+    pub fn is_triggered(self, spot_price: u64) -> bool {
+        match self {
+            OrderTrigger::StopLoss { price } => spot_price <= price,
+            OrderTrigger::TakeProfit { price } => spot_price >= price,
+        }
+    }
+}
+
+/// Side of a `swap`, from the user's point of view
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapDirection {
+    Buy,
+    Sell,
+}
+
+// Anchor discriminator of Raydium CPMM's `swap_base_input` instruction
+const RAYDIUM_CPMM_SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+
+// Anchor discriminator of Raydium CPMM's `withdraw` instruction
+const RAYDIUM_CPMM_WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+// Raydium CPMM derives each pool's LP mint from this seed and the pool state address
+const RAYDIUM_CPMM_LP_MINT_SEED: &[u8] = b"pool_lp_mint";
+
+// Raydium CPMM derives each pool vault from this seed, the pool state and the vault's mint
+const RAYDIUM_CPMM_POOL_VAULT_SEED: &[u8] = b"pool_vault";
+
+// Offsets of the two token mints in a Raydium CPMM `PoolState`, after the discriminator,
+// amm_config, pool_creator, both vaults and the LP mint
+const RAYDIUM_CPMM_TOKEN_0_MINT_OFFSET: usize = 168;
+const RAYDIUM_CPMM_TOKEN_1_MINT_OFFSET: usize = 200;
+
+// Helper function to derive the LP mint of a Raydium CPMM pool
+fn raydium_lp_mint(pool: &Pubkey, raydium_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[RAYDIUM_CPMM_LP_MINT_SEED, pool.as_ref()], raydium_program).0
+}
+
+// Helper function to derive the vault holding one of a Raydium CPMM pool's tokens
+fn raydium_pool_vault(pool: &Pubkey, mint: &Pubkey, raydium_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[RAYDIUM_CPMM_POOL_VAULT_SEED, pool.as_ref(), mint.as_ref()],
+        raydium_program,
+    )
+    .0
+}
+
+// Helper function to read the token mint stored at `offset` in a Raydium CPMM pool
+// (the default pubkey if the account is too short to be a pool)
+fn raydium_pool_mint(pool: &AccountInfo, offset: usize) -> Pubkey {
+    pool.try_borrow_data()
+        .ok()
+        .and_then(|data| data.get(offset..offset + 32).map(Pubkey::try_from))
+        .and_then(|key| key.ok())
+        .unwrap_or_default()
+}
+
+// Helper function to check that a Raydium CPMM pool trades `mint` on either side
+fn raydium_pool_trades_mint(pool: &AccountInfo, mint: &Pubkey) -> bool {
+    [RAYDIUM_CPMM_TOKEN_0_MINT_OFFSET, RAYDIUM_CPMM_TOKEN_1_MINT_OFFSET]
+        .iter()
+        .any(|&offset| raydium_pool_mint(pool, offset) == *mint)
+}
+
+// Helper function to compute the pool liquidity backing each LP token, scaled by 1e12
+fn pool_liquidity_per_lp(
+    token_0_vault: &TokenAccount,
+    token_1_vault: &TokenAccount,
+    lp_mint: &Mint,
+) -> Result<u128> {
+    require!(lp_mint.supply > 0, ErrorCode::InvalidAmount);
+    let pool_liquidity = integer_sqrt(
+        (token_0_vault.amount as u128).safe_mul(token_1_vault.amount as u128)?,
+    )?;
+    pool_liquidity
+        .safe_mul(1_000_000_000_000)?
+        .safe_div(lp_mint.supply as u128)
+}
+
+/// Example quotes returned by `simulate_global_config`
+/// Prices are lamports per whole token (10^6 raw units)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConfigSimulation {
+    pub price_at_start: u64,
+    pub price_at_half: u64,
+    pub price_at_graduation: u64,
+    pub graduation_market_cap: u64,
+    pub fee_on_one_sol: u64,
+}
+
+/// Curve valuation returned by `get_curve_usd_value`, all in micro-dollars
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CurveUsdValue {
+    pub mint: Pubkey,
+    pub sol_usd: u64,
+    // Per whole token (10^6 raw units)
+    pub price_per_token: u64,
+    pub market_cap: u64,
+    pub real_sol_reserves: u64,
+}
+
+/// Curve snapshot returned by `get_curve_state`
+/// Prices are lamports per whole token (10^6 raw units)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CurveState {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub price_lamports_per_token: u64,
+    // Liquidity bootstrapping weight on the virtual SOL reserves (10_000 = plain curve)
+    pub lbp_weight_bps: u64,
+    pub migration_threshold_sol: u64,
+    // Real SOL reserves as a share of the migration threshold, capped at 10_000
+    pub graduation_progress_bps: u16,
+    // Fees currently charged, after per-curve overrides and tiers but before holder discounts
+    pub buy_fee_bps: u16,
+    pub sell_fee_bps: u16,
+    pub trade_count: u64,
+    pub unique_buyers: u64,
+    pub holder_count: u64,
+    pub created_at: i64,
+    pub trading_start_time: i64,
+    pub complete: bool,
+    pub migrated: bool,
+    pub migration_status: MigrationStatus,
+}
+
+// Migration fee collected from the curve's SOL at graduation (6 SOL)
+pub const MIGRATION_FEE_LAMPORTS: u64 = 6_000_000_000;
+
+// Compute-unit ceilings for a single curve trade, including a wallet's first buy where
+// its per-trader accounts are created; enforced by the client's `compute_units` test
+pub const BUY_TOKENS_CU_LIMIT: u32 = 150_000;
+pub const SELL_TOKENS_CU_LIMIT: u32 = 100_000;
+
+// Ceiling for a threshold-crossing `buy_tokens` that also migrates the curve, kept within
+// the default per-instruction budget so the buy needs no compute budget instruction
+pub const BUY_AND_MIGRATE_CU_LIMIT: u32 = 200_000;
+
+// Curves a single `buy_basket` may split across, bounded by a transaction's compute budget
+pub const MAX_BASKET_CURVES: usize = 5;
+// Remaining accounts `buy_basket` takes per curve
+pub const BASKET_LEG_ACCOUNTS: usize = 12;
+
+// Accounts needed to trade against a bonding curve, shared by every entrypoint
+// that routes into the curve (buy_tokens, sell_tokens, swap)
+// The curve is zero-copy and also signs CPIs, so helpers work on a copy of its data
+// and write it back once all CPIs are done
+struct CurveTrade<'a, 'info> {
+    bonding_curve: &'a AccountLoader<'info, BondingCurve>,
+    mint: Pubkey,
+    bonding_curve_sol_vault: &'a AccountInfo<'info>,
+    bonding_curve_token_account: &'a Account<'info, TokenAccount>,
+    trader_token_account: &'a mut Account<'info, TokenAccount>,
+    curve_oracle: &'a mut Account<'info, CurveOracle>,
+    live_candle: &'a mut Account<'info, LiveCandle>,
+    live_candle_bump: u8,
+    user_points: &'a mut Account<'info, UserPoints>,
+    user_points_bump: u8,
+    trade_guard: &'a mut Account<'info, TradeGuard>,
+    trade_guard_bump: u8,
+    // The wallet the trade is for; it signs unless `escrow` trades on its behalf
+    trader: &'a AccountInfo<'info>,
+    escrow: Option<TradeEscrow<'a, 'info>>,
+    // Only used by wSOL curves
+    curve_wsol_vault: Option<&'a Account<'info, TokenAccount>>,
+    trader_wsol_account: Option<&'a Account<'info, TokenAccount>>,
+    // Platform-token holder discount on the trading fee
+    fee_discount_bps: u16,
+    trader_position: Option<&'a mut Account<'info, TraderPosition>>,
+    // Only checked on buys from KYC-gated curves
+    kyc_attestation: Option<&'a Account<'info, KycAttestation>>,
+    // Only checked on buys from compliance-gated curves
+    compliance_gate: Option<&'a Account<'info, ComplianceGate>>,
+    // Only used by curves that take reflections on trades
+    reflection_vault: Option<&'a Account<'info, TokenAccount>>,
+    // Only used on buys from curves with a jackpot
+    jackpot: Option<&'a mut Account<'info, Jackpot>>,
+    // Only counted when both the competition and the trader's entry are passed
+    competition: Option<(&'a mut Account<'info, Competition>, &'a mut Account<'info, CompetitionEntry>)>,
+    global_config: &'a Account<'info, GlobalConfig>,
+    global_stats: &'a mut Account<'info, GlobalStats>,
+    system_program: &'a Program<'info, System>,
+    token_program: &'a Program<'info, Token>,
+}
+
+// A program account trading on the trader's behalf (a session key's delegate or a limit
+// order): buys spend its lamports and sells move tokens under its signature
+struct TradeEscrow<'a, 'info> {
+    account: AccountInfo<'info>,
+    signer_seeds: &'a [&'a [u8]],
+}
+
+// The curve's reflection vault if it takes reflections on trades, `None` otherwise
+fn trade_reflection_vault<'a, 'info>(
+    trade: &CurveTrade<'a, 'info>,
+    bonding_curve: &BondingCurve,
+) -> Result<Option<&'a Account<'info, TokenAccount>>> {
+    if !bonding_curve.has_reflections() {
+        return Ok(None);
+    }
+    Ok(Some(trade.reflection_vault.ok_or(ErrorCode::ReflectionVaultRequired)?))
+}
+
+// Helper function to feed a buy's share of the fee into the curve's jackpot and, on every
+// Nth buy, mark a draw among the recent buyers as due. The winner is only picked once the
+// draw's randomness is fulfilled, so no buy can be timed to win. Returns the lamports taken
+// out of the fee
+fn record_jackpot_buy<'info>(
+    jackpot: &mut Account<'info, Jackpot>,
+    sol_holder: &AccountInfo<'info>,
+    buyer: Pubkey,
+    fee: u64,
+    now: i64,
+) -> Result<u64> {
+    let contribution = mul_div(fee as u128, jackpot.fee_share_bps as u128, 10_000, Rounding::Down)?.to_u64()?;
+    move_lamports(sol_holder, &jackpot.to_account_info(), contribution)?;
+    jackpot.total_contributed = jackpot.total_contributed.safe_add(contribution)?;
+    jackpot.buy_count = jackpot.buy_count.safe_add(1)?;
+    // The candidates are frozen once a draw is due, so buys can't steer who it picks
+    if !jackpot.draw_pending {
+        jackpot.record_recent_buyer(buyer);
+    }
+
+    emit!(JackpotContributed {
+        mint: jackpot.mint,
+        buyer,
+        amount: contribution,
+        pot: jackpot.pot()?,
+        buy_count: jackpot.buy_count,
+        timestamp: now,
+    });
+
+    let pot = jackpot.pot()?;
+    // A draw that's already due keeps waiting on its randomness
+    if !jackpot.buy_count.is_multiple_of(jackpot.buys_per_draw) || pot == 0 || jackpot.draw_pending {
+        return Ok(contribution);
+    }
+    jackpot.draw_pending = true;
+    emit!(JackpotDrawDue {
+        mint: jackpot.mint,
+        buy_count: jackpot.buy_count,
+        pot,
+        timestamp: now,
+    });
+
+    Ok(contribution)
+}
+
+// Helper function to check whether the consumer account `info` of type `T` needs a draw
+fn consumer_awaiting_randomness<T>(info: &AccountInfo) -> Result<bool>
+where
+    T: RandomnessConsumer + AccountDeserialize + Owner,
+{
+    require!(info.owner == &T::owner(), ErrorCode::InvalidRandomnessConsumer);
+    let consumer = T::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(consumer.awaiting_randomness())
+}
+
+// Helper function to hand a fulfilled draw to the consumer account `info` of type `T`
+fn deliver_randomness<T>(info: &AccountInfo, value: &[u8; 32], now: i64) -> Result<()>
+where
+    T: RandomnessConsumer + AccountSerialize + AccountDeserialize + Owner,
+{
+    require!(info.owner == &T::owner(), ErrorCode::InvalidRandomnessConsumer);
+    let mut consumer = T::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    consumer.consume_randomness(value, now)?;
+    let mut data = info.try_borrow_mut_data()?;
+    consumer.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+// Helper function to move `amount` of the trader's tokens to `to`, signed by the trader or
+// by the escrow trading on their behalf (as the token account's owner or SPL delegate)
+fn transfer_from_trader<'info>(
+    trade: &CurveTrade<'_, 'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_program = trade.token_program.to_account_info();
+    let from = trade.trader_token_account.to_account_info();
+    match &trade.escrow {
+        Some(escrow) => transfer(
+            CpiContext::new_with_signer(
+                cpi_program,
+                Transfer { from, to, authority: escrow.account.clone() },
+                &[escrow.signer_seeds],
+            ),
+            amount,
+        ),
+        None => transfer(
+            CpiContext::new(cpi_program, Transfer { from, to, authority: trade.trader.to_account_info() }),
+            amount,
+        ),
+    }
+}
+
+// The curve's wSOL vault if it holds its SOL side as wSOL, `None` for lamport vaults
+fn trade_wsol_vault<'a, 'info>(
+    trade: &CurveTrade<'a, 'info>,
+    bonding_curve: &BondingCurve,
+) -> Result<Option<&'a Account<'info, TokenAccount>>> {
+    if !bonding_curve.has_wsol_vault() {
+        return Ok(None);
+    }
+    Ok(Some(trade.curve_wsol_vault.ok_or(ErrorCode::WsolVaultRequired)?))
+}
+
+// Helper function to fold a trade into the curve's live candle
+fn record_candle_trade(
+    trade: &mut CurveTrade,
+    timestamp: i64,
+    price: u64,
+    sol_volume: u64,
+    token_volume: u64,
+) -> Result<()> {
+    // Created on first use so curves launched before candles existed keep trading
+    if trade.live_candle.mint == Pubkey::default() {
+        trade.live_candle.mint = trade.mint;
+        trade.live_candle.bump = trade.live_candle_bump;
+    }
+    trade.live_candle.record(timestamp, price, sol_volume, token_volume)
+}
+
+// Helper function to credit a trade's volume to the trader's competition entry
+fn record_competition_volume(trade: &mut CurveTrade, timestamp: i64, sol_volume: u64) -> Result<()> {
+    let mint = trade.mint;
+    let trader = trade.trader.key();
+    let Some((competition, entry)) = trade.competition.as_mut() else {
+        return Ok(());
+    };
+
+    require!(
+        entry.competition == competition.key() && entry.trader == trader,
+        ErrorCode::InvalidCompetitionEntry
+    );
+    if !competition.is_active(timestamp) || !competition.covers_mint(&mint) {
+        return Ok(());
+    }
+
+    entry.volume = entry.volume.safe_add(sol_volume)?;
+    competition.total_volume = competition.total_volume.safe_add(sol_volume)?;
+    competition.update_leaderboard(trader, entry.volume);
+    Ok(())
+}
+
+// Helper function to reject empty or overlong text and characters outside `allowed`
+fn validate_text(value: &str, max_len: usize, allowed: fn(u8) -> bool, error: ErrorCode) -> Result<()> {
+    if value.is_empty() || value.len() > max_len || !value.bytes().all(allowed) {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+// Zero-padded copy of `value` plus its length; callers validate the length first
+fn fixed_bytes<const N: usize>(value: &str) -> ([u8; N], u8) {
+    let mut bytes = [0u8; N];
+    bytes[..value.len()].copy_from_slice(value.as_bytes());
+    (bytes, value.len() as u8)
+}
+
+// The used part of a zero-padded field; fields only ever hold validated ASCII
+fn fixed_str(bytes: &[u8], len: u8) -> &str {
+    let len = (len as usize).min(bytes.len());
+    core::str::from_utf8(&bytes[..len]).unwrap_or_default()
+}
+
+// Plain printable ASCII (no control characters, no multi-byte UTF-8 such as emoji)
+fn is_printable_ascii(byte: u8) -> bool {
+    (b' '..=b'~').contains(&byte)
+}
+
+fn is_symbol_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric()
+}
+
+// Printable ASCII without whitespace
+fn is_uri_char(byte: u8) -> bool {
+    byte.is_ascii_graphic()
+}
+
+// Canonical Metaplex metadata account of `mint`
+fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref()],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+// Canonical Metaplex master edition account of `mint`
+fn master_edition_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), mint.as_ref(), b"edition"],
+        &mpl_token_metadata::ID,
+    )
+    .0
+}
+
+// Accounts needed to move a graduated curve's reserves into the migration vaults
+struct CurveMigration<'a, 'info> {
+    bonding_curve: &'a AccountLoader<'info, BondingCurve>,
+    bonding_curve_sol_vault: &'a AccountInfo<'info>,
+    bonding_curve_token_account: &'a Account<'info, TokenAccount>,
+    // Required for wSOL curves, which are unwrapped before migrating
+    curve_wsol_vault: Option<&'a AccountInfo<'info>>,
+    migration_sol_vault: &'a AccountInfo<'info>,
+    migration_token_account: &'a Account<'info, TokenAccount>,
+    creator_stats: &'a mut Account<'info, CreatorStats>,
+    creator_stats_bump: u8,
+    treasury: &'a AccountInfo<'info>,
+    // Recipients of the treasury split in slot order (empty when none is configured)
+    treasury_split: &'a [AccountInfo<'info>],
+    keeper: &'a AccountInfo<'info>,
+    global_config: &'a Account<'info, GlobalConfig>,
+    global_stats: &'a mut Account<'info, GlobalStats>,
+    token_program: &'a Program<'info, Token>,
+}
+
+// Helper function to migrate a curve that reached the threshold, shared by the keeper
+// crank and the threshold-crossing buy; the launch deposit is refunded by the caller
+fn migrate_curve(m: CurveMigration) -> Result<MigrationComplete> {
+    unwrap_wsol_vault(
+        m.bonding_curve,
+        m.curve_wsol_vault,
+        m.bonding_curve_sol_vault,
+        &m.token_program.to_account_info(),
+    )?;
+    // Copied out so the curve isn't borrowed while it signs the token transfer
+    let bonding_curve = *m.bonding_curve.load()?;
+    let global_config = m.global_config;
+
+    // Verify migration conditions
+    require!(!bonding_curve.is_migrated(), ErrorCode::AlreadyMigrated);
+    require!(
+        bonding_curve.real_sol_reserves >= global_config.migration_threshold_sol,
+        ErrorCode::ThresholdNotReached
+    );
+
+    let total_sol = bonding_curve.real_sol_reserves;
+    let tokens_to_migrate = bonding_curve.real_token_reserves;
+
+    require!(total_sol > 0, ErrorCode::InsufficientSOL);
+    require!(tokens_to_migrate > 0, ErrorCode::InsufficientTokens);
+
+    // Migration fee: 6 SOL goes to treasury
+    let migration_fee = MIGRATION_FEE_LAMPORTS;
+    require!(total_sol > migration_fee, ErrorCode::InsufficientSOLForMigration);
+
+    let sol_to_migrate = total_sol.safe_sub(migration_fee)?;
+
+    // Keeper reward is carved out of the migration fee, never out of pool liquidity
+    let keeper_reward = global_config.migration_keeper_reward;
+    require!(keeper_reward <= migration_fee, ErrorCode::InvalidKeeperReward);
+    let treasury_fee = migration_fee.safe_sub(keeper_reward)?;
+
+    msg!("Starting migration with {} total SOL", total_sol);
+    msg!("Migration fee: {} SOL (6 SOL)", migration_fee);
+    msg!("SOL to pool: {} lamports", sol_to_migrate);
+    msg!("Tokens to pool: {} tokens", tokens_to_migrate);
+
+    // Verify vault has enough balance
+    let sol_holder = bonding_curve.sol_holder(m.bonding_curve.to_account_info(), m.bonding_curve_sol_vault);
+    require!(sol_holder.lamports() >= total_sol, ErrorCode::InsufficientSOL);
+
+    // Transfer migration fee to treasury, or across the treasury split
+    pay_treasury(global_config, &sol_holder, m.treasury, m.treasury_split, treasury_fee)?;
+    msg!("Transferred {} lamports migration fee to treasury", treasury_fee);
+
+    // Pay the keeper that cranked the migration
+    if keeper_reward > 0 {
+        move_lamports(&sol_holder, m.keeper, keeper_reward)?;
+        msg!("Paid {} lamports keeper reward to {}", keeper_reward, m.keeper.key());
+    }
+
+    // Transfer remaining SOL to migration vault (for liquidity pool)
+    move_lamports(&sol_holder, m.migration_sol_vault, sol_to_migrate)?;
+
+    msg!("Transferred {} lamports to migration vault for pool", sol_to_migrate);
+
+    // Transfer tokens from bonding curve token account to migration token account
+    let mint_key = bonding_curve.mint;
+    let seeds = &[
+        b"bonding_curve",
+        mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: m.bonding_curve_token_account.to_account_info(),
+        to: m.migration_token_account.to_account_info(),
+        authority: m.bonding_curve.to_account_info(),
+    };
+    let cpi_program = m.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+    transfer(cpi_ctx, tokens_to_migrate)?;
+
+    msg!("Transferred {} tokens to migration vault", tokens_to_migrate);
+
+    // Update bonding curve state
+    let now = Clock::get()?.unix_timestamp;
+    let mut bonding_curve = m.bonding_curve.load_mut()?;
+    bonding_curve.migrated = 1;
+    bonding_curve.raydium_pool = m.migration_sol_vault.key(); // Store migration vault for now
+    bonding_curve.graduation_sol_reserves = total_sol;
+    bonding_curve.graduation_token_reserves = tokens_to_migrate;
+    bonding_curve.graduated_at = now;
+    bonding_curve.total_fees_collected = bonding_curve.total_fees_collected
+        .safe_add(migration_fee)?;
+    bonding_curve.real_sol_reserves = 0;
+    bonding_curve.real_token_reserves = 0;
+    advance_migration(&mut bonding_curve, MigrationStatus::FundsMoved)?;
+
+    let global_stats = m.global_stats;
+    global_stats.total_migrated = global_stats.total_migrated.safe_add(1)?;
+    // Curves launched before stats were tracked were never counted as active
+    global_stats.active_curves = global_stats.active_curves.saturating_sub(1);
+    global_stats.total_fees_collected = global_stats.total_fees_collected
+        .safe_add(migration_fee)?;
+
+    let creator_stats = m.creator_stats;
+    creator_stats.ensure_initialized(bonding_curve.creator, m.creator_stats_bump);
+    creator_stats.migrations = creator_stats.migrations.safe_add(1)?;
+    creator_stats.total_sol_raised = creator_stats.total_sol_raised.safe_add(total_sol)?;
+
+    msg!("Migration state updated - bonding curve is now locked");
+    msg!("Migration complete!");
+    msg!("  - Migration fee collected: {} SOL", migration_fee.safe_div(1_000_000_000)?);
+    msg!("  - SOL for pool: {} lamports", sol_to_migrate);
+    msg!("  - Tokens for pool: {}", tokens_to_migrate);
+
+    Ok(MigrationComplete {
+        mint: bonding_curve.mint,
+        raydium_pool: m.migration_sol_vault.key(),
+        sol_migrated: sol_to_migrate,
+        tokens_migrated: tokens_to_migrate,
+        migration_fee,
+        keeper: m.keeper.key(),
+        keeper_reward,
+        timestamp: now,
+    })
+}
+
+// Helper function to turn a wSOL curve back into a lamport curve by closing its wSOL vault
+// into the SOL vault; everything the vault held (wSOL and its rent) lands there
+fn unwrap_wsol_vault<'info>(
+    bonding_curve: &AccountLoader<'info, BondingCurve>,
+    curve_wsol_vault: Option<&AccountInfo<'info>>,
+    sol_vault: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let (has_wsol_vault, mint_key, bump) = {
+        let curve = bonding_curve.load()?;
+        (curve.has_wsol_vault(), curve.mint, curve.bump)
+    };
+    if !has_wsol_vault {
+        return Ok(());
+    }
+    let curve_wsol_vault = curve_wsol_vault.ok_or(ErrorCode::WsolVaultRequired)?;
+    let seeds = &[
+        b"bonding_curve",
+        mint_key.as_ref(),
+        &[bump],
+    ];
+    close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: curve_wsol_vault.clone(),
+            destination: sol_vault.clone(),
+            authority: bonding_curve.to_account_info(),
+        },
+        &[&seeds[..]],
+    ))?;
+    bonding_curve.load_mut()?.wsol_vault = 0;
+    Ok(())
+}
+
+// Helper function to move a curve to the next migration step
+// Re-applying the current step is a no-op so off-chain retries are safe
+fn advance_migration(bonding_curve: &mut BondingCurve, status: MigrationStatus) -> Result<()> {
+    let from = bonding_curve.migration_stage();
+    if from == status {
+        msg!("Migration already at {:?}", status);
+        return Ok(());
+    }
+    require!(from.next() == Some(status), ErrorCode::InvalidMigrationTransition);
+    bonding_curve.migration_status = status as u8;
+
+    emit!(MigrationStatusChanged {
+        mint: bonding_curve.mint,
+        from,
+        to: status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Helper function to return a curve's launch deposit to its creator (no-op once refunded)
+fn refund_launch_deposit<'info>(
+    bonding_curve: &AccountLoader<'info, BondingCurve>,
+    creator: &AccountInfo<'info>,
+) -> Result<()> {
+    let mut curve = bonding_curve.load_mut()?;
+    let deposit = curve.launch_deposit;
+    if deposit == 0 {
+        return Ok(());
+    }
+    // Lamports live outside the account data, so moving them doesn't conflict with the load
+    move_lamports(&bonding_curve.to_account_info(), creator, deposit)?;
+    curve.launch_deposit = 0;
+
+    emit!(LaunchDepositRefunded {
+        mint: curve.mint,
+        creator: creator.key(),
+        amount: deposit,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Helper function to pay out a curve's creator allocation escrow and close it; the curve
+// owns the escrow and signs with `signer`
+fn release_creator_allocation<'info>(
+    escrow: &Account<'info, TokenAccount>,
+    creator_token_account: &Account<'info, TokenAccount>,
+    bonding_curve: &AccountLoader<'info, BondingCurve>,
+    creator: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    if escrow.amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: escrow.to_account_info(),
+                    to: creator_token_account.to_account_info(),
+                    authority: bonding_curve.to_account_info(),
+                },
+                signer,
+            ),
+            escrow.amount,
+        )?;
+    }
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: creator.clone(),
+            authority: bonding_curve.to_account_info(),
+        },
+        signer,
+    ))
+}
+
+// Accounts needed to create a project's mint, shared by the keypair and PDA paths
+struct ProjectMint<'a, 'info> {
+    project_state: &'a mut Account<'info, ProjectState>,
+    global_config: &'a Account<'info, GlobalConfig>,
+    mint: &'a Account<'info, Mint>,
+    metadata: &'a UncheckedAccount<'info>,
+    owner_token_account: &'a Account<'info, TokenAccount>,
+    owner: &'a Signer<'info>,
+    system_program: &'a Program<'info, System>,
+    token_program: &'a Program<'info, Token>,
+    token_metadata_program: &'a Program<'info, Metadata>,
+    rent: &'a Sysvar<'info, Rent>,
+}
+
+// Helper function to record a freshly created mint on its project, attach the metadata and
+// mint the project's supply to its owner
+fn mint_project_supply(
+    m: ProjectMint,
+    name: String,
+    symbol: String,
+    uri: String,
+    source: MintSource,
+) -> Result<()> {
+    // Record the mint on the state account
+    let state = m.project_state;
+    require!(state.mint == Pubkey::default(), ErrorCode::MintAlreadyCreated);
+    // Curves only launch with the standard supply, so projects are minted with exactly that
+    require!(
+        state.total_supply == m.global_config.initial_token_supply,
+        ErrorCode::NonstandardTokenSupply
+    );
+    require!(
+        name == state.name() && symbol == state.symbol(),
+        ErrorCode::ProjectMetadataMismatch
+    );
+    validate_text(&uri, ProjectState::MAX_URI, is_uri_char, ErrorCode::InvalidUri)?;
+    state.mint = m.mint.key();
+    state.mint_source = source;
+
+    // Create metadata account
+    let data_v2 = DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    let cpi_context = CpiContext::new(
+        m.token_metadata_program.to_account_info(),
+        CreateMetadataAccountsV3 {
+            metadata: m.metadata.to_account_info(),
+            mint: m.mint.to_account_info(),
+            mint_authority: m.owner.to_account_info(),
+            payer: m.owner.to_account_info(),
+            update_authority: m.owner.to_account_info(),
+            system_program: m.system_program.to_account_info(),
+            rent: m.rent.to_account_info(),
+        },
+    );
+
+    create_metadata_accounts_v3(cpi_context, data_v2, true, true, None)?;
+
+    // Mint the initial supply (999,999,999.999999 tokens with 6 decimals)
+    // The total_supply is already in raw token units (includes decimals)
+    let amount_with_decimals = state.total_supply;
+
+    let mint_cpi_context = CpiContext::new(
+        m.token_program.to_account_info(),
+        MintTo {
+            mint: m.mint.to_account_info(),
+            to: m.owner_token_account.to_account_info(),
+            authority: m.owner.to_account_info(),
+        },
+    );
+
+    mint_to(mint_cpi_context, amount_with_decimals)?;
 
-    /// CHECK: This account is initialized by the Metaplex Token Metadata program
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
+    emit!(MintCreated {
+        project: state.key(),
+        owner: state.owner,
+        mint: state.mint,
+        name: state.name().to_string(),
+        symbol: state.symbol().to_string(),
+        total_supply: state.total_supply,
+        decimals: m.mint.decimals,
+        project_created_at: state.created_at,
+        source,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
-    #[account(
-        init,
-        payer = owner,
-        associated_token::mint = mint,
-        associated_token::authority = owner,
-    )]
-    pub owner_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+    Ok(())
+}
 
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_metadata_program: Program<'info, Metadata>,
-    pub rent: Sysvar<'info, Rent>,
+// Helper function to run the creator's dev buy against a freshly initialized curve
+// Mirrors `execute_curve_buy` for the accounts that exist at launch
+// `bonding_curve` is the curve's data before it's written to the account
+fn execute_dev_buy(
+    accounts: &mut InitializeBondingCurve,
+    bonding_curve: &mut BondingCurve,
+    sol_amount: u64,
+) -> Result<()> {
+    let global_config = &accounts.global_config;
+    let fee_bps = global_config.buy_fee_bps_for(bonding_curve)?;
+    let fee = curve::fee_for_amount(sol_amount, fee_bps)?;
+    let sol_after_fee = sol_amount.safe_sub(fee)?;
+
+    // The dev buy alone must not graduate the curve
+    require!(
+        sol_after_fee < global_config.migration_threshold_sol,
+        ErrorCode::DevBuyTooLarge
+    );
+
+    let reserves = curve_reserves(bonding_curve)?;
+    let tokens_out = curve::tokens_out_for_sol(reserves, sol_after_fee)?;
+    require!(tokens_out <= bonding_curve.real_token_reserves, ErrorCode::InsufficientTokens);
+
+    // SOL (including fee) from creator to the curve vault
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: accounts.creator.to_account_info(),
+                to: accounts.sol_vault.to_account_info(),
+            },
+        ),
+        sol_amount,
+    )?;
+
+    // Tokens from the curve back to the creator
+    let mint_key = accounts.mint.key();
+    let seeds = &[
+        b"bonding_curve",
+        mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: accounts.bonding_curve_token_account.to_account_info(),
+        to: accounts.creator_token_account.to_account_info(),
+        authority: accounts.bonding_curve.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    transfer(cpi_ctx, tokens_out)?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves.safe_add(sol_after_fee)?;
+    bonding_curve.real_token_reserves = bonding_curve.real_token_reserves.safe_sub(tokens_out)?;
+    bonding_curve.total_fees_collected = bonding_curve.total_fees_collected.safe_add(fee)?;
+    bonding_curve.accumulated_fees = bonding_curve.accumulated_fees.safe_add(fee)?;
+    bonding_curve.trade_count = bonding_curve.trade_count.safe_add(1)?;
+    bonding_curve.dev_buy_sol = sol_amount;
+    bonding_curve.dev_buy_tokens = tokens_out;
+    bonding_curve.record_trending_volume(current_time, sol_after_fee)?;
+
+    accounts.creator_token_account.reload()?;
+    if accounts.creator_token_account.amount == tokens_out {
+        bonding_curve.holder_count = bonding_curve.holder_count.safe_add(1)?;
+    }
+
+    let price = curve_spot_price(bonding_curve)?;
+    accounts.curve_oracle.record(current_time, price)?;
+    accounts.global_stats.record_trade(sol_after_fee, fee)?;
+
+    emit!(DevBuyEvent {
+        creator: accounts.creator.key(),
+        mint: mint_key,
+        sol_amount,
+        tokens_out,
+        fee,
+        price_lamports_per_token: price,
+        timestamp: current_time,
+    });
+
+    Ok(())
 }
 
-#[derive(Accounts)]
-pub struct InitializeGlobalConfig<'info> {
-    #[account(
-        init,
-        payer = authority,
-        seeds = [b"global_config"],
-        bump,
-        space = GlobalConfig::MAX_SIZE,
-    )]
-    pub global_config: Account<'info, GlobalConfig>,
+// Helper function to pay out an OTC deal's escrowed tokens (if any) to `to` and close the
+// escrow, returning its rent to the seller
+fn otc_deal_release<'info>(
+    otc_deal: &Account<'info, OtcDeal>,
+    escrow_vault: &Account<'info, TokenAccount>,
+    to: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seller_key = otc_deal.seller;
+    let id_bytes = otc_deal.id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"otc_deal",
+        seller_key.as_ref(),
+        &id_bytes,
+        &[otc_deal.bump],
+    ];
+    let signer = &[seeds];
+
+    if escrow_vault.amount > 0 {
+        let cpi_accounts = Transfer {
+            from: escrow_vault.to_account_info(),
+            to: to.clone(),
+            authority: otc_deal.to_account_info(),
+        };
+        transfer(
+            CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
+            escrow_vault.amount,
+        )?;
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: escrow_vault.to_account_info(),
+        destination: seller.clone(),
+        authority: otc_deal.to_account_info(),
+    };
+    close_account(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer))
+}
+
+// Helper function to pay `amount` out of a payment stream: lamports straight from the stream
+// PDA, tokens from its vault into `recipient_token_account`
+fn pay_from_stream<'info>(
+    stream: &Account<'info, PaymentStream>,
+    stream_vault: Option<&Account<'info, TokenAccount>>,
+    recipient_token_account: Option<AccountInfo<'info>>,
+    recipient: &AccountInfo<'info>,
+    token_program: Option<&Program<'info, Token>>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    if stream.is_sol() {
+        return move_lamports(&stream.to_account_info(), recipient, amount);
+    }
+
+    let (Some(stream_vault), Some(recipient_token_account), Some(token_program)) =
+        (stream_vault, recipient_token_account, token_program)
+    else {
+        return err!(ErrorCode::InvalidStreamAccounts);
+    };
+    let payer_key = stream.payer;
+    let id_bytes = stream.id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"payment_stream",
+        payer_key.as_ref(),
+        &id_bytes,
+        &[stream.bump],
+    ];
+    let cpi_accounts = Transfer {
+        from: stream_vault.to_account_info(),
+        to: recipient_token_account,
+        authority: stream.to_account_info(),
+    };
+    transfer(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, &[seeds]),
+        amount,
+    )
+}
+
+// Helper function to close a token stream's emptied vault, returning its rent to `destination`
+fn close_stream_vault<'info>(
+    stream: &Account<'info, PaymentStream>,
+    stream_vault: Option<&Account<'info, TokenAccount>>,
+    destination: &AccountInfo<'info>,
+    token_program: Option<&Program<'info, Token>>,
+) -> Result<()> {
+    let (Some(stream_vault), Some(token_program)) = (stream_vault, token_program) else {
+        return err!(ErrorCode::InvalidStreamAccounts);
+    };
+    let payer_key = stream.payer;
+    let id_bytes = stream.id.to_le_bytes();
+    let seeds: &[&[u8]] = &[
+        b"payment_stream",
+        payer_key.as_ref(),
+        &id_bytes,
+        &[stream.bump],
+    ];
+    let cpi_accounts = CloseAccount {
+        account: stream_vault.to_account_info(),
+        destination: destination.clone(),
+        authority: stream.to_account_info(),
+    };
+    close_account(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, &[seeds]))
+}
+
+// Helper function to find the fewest tokens a buy of `sol_amount` may return at
+// `quoted_price` (lamports per whole token) less `slippage_bps`, after the fee and royalty
+fn min_tokens_at_price(
+    global_config: &GlobalConfig,
+    bonding_curve: &BondingCurve,
+    sol_amount: u64,
+    quoted_price: u64,
+    slippage_bps: u16,
+) -> Result<u64> {
+    let fee = curve::fee_for_amount(sol_amount, global_config.buy_fee_bps_for(bonding_curve)?)?;
+    let royalty = bonding_curve.creator_royalty_for(sol_amount)?;
+    curve::min_tokens_for_quote(
+        sol_amount.safe_sub(fee)?.safe_sub(royalty)?,
+        quoted_price,
+        slippage_bps,
+    )
+}
+
+// Helper function to find the least SOL a sale of `token_amount` may pay at `quoted_price`
+// (lamports per whole token) less `slippage_bps`, after the fee and royalty
+fn min_sol_at_price(
+    global_config: &GlobalConfig,
+    bonding_curve: &BondingCurve,
+    token_amount: u64,
+    quoted_price: u64,
+    slippage_bps: u16,
+) -> Result<u64> {
+    curve::min_sol_for_quote(
+        token_amount,
+        quoted_price,
+        global_config.sell_fee_bps_for(bonding_curve)
+            .safe_add(bonding_curve.creator_royalty_bps)?,
+        slippage_bps,
+    )
+}
+
+// Helper function to close a finished DCA schedule, refunding what is left to the owner
+fn close_dca_schedule<'info>(
+    schedule: &Account<'info, DcaSchedule>,
+    owner: &AccountInfo<'info>,
+    now: i64,
+) -> Result<()> {
+    emit!(DcaScheduleClosed {
+        owner: schedule.owner,
+        mint: schedule.mint,
+        buys_executed: schedule.buys_executed,
+        refunded: schedule.to_account_info().lamports(),
+        timestamp: now,
+    });
+    schedule.close(owner.clone())
+}
+
+// Helper function to check that the trader presented an unexpired attestation from the
+// platform's KYC verifier
+fn has_valid_attestation(trade: &CurveTrade, now: i64) -> bool {
+    trade.kyc_attestation.is_some_and(|attestation| {
+        attestation.is_valid_for(&trade.trader.key(), &trade.global_config.kyc_verifier, now)
+    })
+}
+
+// Helper function to enforce a compliance-gated curve's restrictions on a buy of `sol_amount`
+fn check_compliance_gate(trade: &CurveTrade, sol_amount: u64, now: i64) -> Result<()> {
+    let gate = trade.compliance_gate.ok_or(ErrorCode::ComplianceGateRequired)?;
+    if gate.has(ComplianceGate::FLAG_ATTESTATION) {
+        require!(has_valid_attestation(trade, now), ErrorCode::KycAttestationRequired);
+    }
+    if gate.has(ComplianceGate::FLAG_MAX_BUY) {
+        require!(sol_amount <= gate.max_buy_lamports, ErrorCode::ComplianceLimitExceeded);
+    }
+    Ok(())
+}
+
+// Helper function to accrue loyalty points for a trade's volume
+fn record_points(trade: &mut CurveTrade, sol_volume: u64, points_per_sol: u64) -> Result<()> {
+    // Created on first use
+    if trade.user_points.user == Pubkey::default() {
+        trade.user_points.user = trade.trader.key();
+        trade.user_points.bump = trade.user_points_bump;
+    }
+    let points = (sol_volume as u128)
+        .safe_mul(points_per_sol as u128)?
+        .safe_div(1_000_000_000)?
+        .to_u64()?;
+    trade.user_points.accrue(trade.global_config.points_epoch, points)
+}
+
+// Helper function to move the stakers' share of withdrawn fees into the fee share vault
+// Returns the lamports routed (0 when fee sharing is disabled)
+fn route_fee_share<'info>(
+    global_config: &GlobalConfig,
+    fee_share_vault: Option<&mut Account<'info, FeeShareVault>>,
+    from: &AccountInfo<'info>,
+    fees: u64,
+) -> Result<u64> {
+    let fee_share = (fees as u128)
+        .safe_mul(global_config.fee_share_bps as u128)?
+        .safe_div(10_000)?
+        .to_u64()?;
+    if fee_share == 0 {
+        return Ok(0);
+    }
+    let fee_share_vault = fee_share_vault.ok_or(ErrorCode::FeeShareVaultRequired)?;
+    move_lamports(from, &fee_share_vault.to_account_info(), fee_share)?;
+    fee_share_vault.distribute(fee_share)?;
+    Ok(fee_share)
+}
+
+// Helper function to route `insurance_fee_bps` of withdrawn fees into the insurance fund
+// Returns the amount routed
+fn route_insurance<'info>(
+    global_config: &GlobalConfig,
+    insurance_fund: Option<&mut Account<'info, InsuranceFund>>,
+    from: &AccountInfo<'info>,
+    fees: u64,
+) -> Result<u64> {
+    let insurance = (fees as u128)
+        .safe_mul(global_config.insurance_fee_bps as u128)?
+        .safe_div(10_000)?
+        .to_u64()?;
+    if insurance == 0 {
+        return Ok(0);
+    }
+    let insurance_fund = insurance_fund.ok_or(ErrorCode::InsuranceFundRequired)?;
+    move_lamports(from, &insurance_fund.to_account_info(), insurance)?;
+    insurance_fund.total_deposited = insurance_fund.total_deposited.safe_add(insurance)?;
+    Ok(insurance)
+}
+
+// Helper function to pay withdrawn platform fees to the treasury, or across the treasury
+// split when one is configured. `recipients` are the split's accounts in slot order
+fn pay_treasury(
+    global_config: &GlobalConfig,
+    from: &AccountInfo,
+    treasury: &AccountInfo,
+    recipients: &[AccountInfo],
+    amount: u64,
+) -> Result<()> {
+    let splits = global_config.active_treasury_splits();
+    if splits.is_empty() {
+        return move_lamports(from, treasury, amount);
+    }
+    require!(recipients.len() >= splits.len(), ErrorCode::InvalidRemainingAccounts);
+
+    let mut unpaid = amount;
+    for (index, (split, recipient)) in splits.iter().zip(recipients).enumerate() {
+        require_keys_eq!(recipient.key(), split.recipient, ErrorCode::InvalidRemainingAccounts);
+        // The last recipient takes the rounding remainder so the full amount is paid out
+        let share = if index == splits.len() - 1 {
+            unpaid
+        } else {
+            (amount as u128)
+                .safe_mul(split.weight_bps as u128)?
+                .safe_div(10_000)?
+                .to_u64()?
+        };
+        move_lamports(from, recipient, share)?;
+        unpaid = unpaid.safe_sub(share)?;
+    }
+    Ok(())
+}
+
+// Helper function to pay out a stake's unclaimed fee share and mark it as accounted for
+fn settle_fee_share<'info>(
+    fee_share_vault: &Account<'info, FeeShareVault>,
+    fee_share_stake: &mut Account<'info, FeeShareStake>,
+    owner: &AccountInfo<'info>,
+) -> Result<u64> {
+    let accrued = fee_share_vault.accrued_for(fee_share_stake.amount)?;
+    let pending = accrued.safe_sub(fee_share_stake.reward_debt)?.to_u64()?;
+    fee_share_stake.reward_debt = accrued;
+    if pending > 0 {
+        move_lamports(&fee_share_vault.to_account_info(), owner, pending)?;
+        fee_share_stake.claimed = fee_share_stake.claimed.safe_add(pending)?;
+
+        emit!(FeeShareClaimed {
+            owner: fee_share_stake.owner,
+            amount: pending,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+    Ok(pending)
+}
+
+// Helper function to read the trader's platform-token discount from an optional account
+// The account must be a token account of the platform mint owned by the trader
+fn holder_fee_discount_bps(
+    global_config: &GlobalConfig,
+    trader: &Pubkey,
+    holding: Option<&AccountInfo>,
+) -> Result<u16> {
+    let Some(holding) = holding else {
+        return Ok(0);
+    };
+    // The program ID stands in for no account, so accounts after it can still be passed
+    if global_config.platform_token_mint == Pubkey::default() || holding.key == &crate::ID {
+        return Ok(0);
+    }
+    require!(holding.owner == &anchor_spl::token::ID, ErrorCode::InvalidDiscountAccount);
+    let token_account = TokenAccount::try_deserialize(&mut &holding.try_borrow_data()?[..])?;
+    require!(
+        token_account.mint == global_config.platform_token_mint && token_account.owner == *trader,
+        ErrorCode::InvalidDiscountAccount
+    );
+    Ok(global_config.holder_discount_bps(token_account.amount))
+}
+
+// A relayer's tip for sponsoring a trade, paid by the trader once the trade has gone through
+// (for sells, out of the proceeds)
+struct RelayerTip<'info> {
+    trader: AccountInfo<'info>,
+    relayer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+}
+
+impl<'info> RelayerTip<'info> {
+    fn new(
+        trader: &Signer<'info>,
+        relayer: &Signer<'info>,
+        system_program: &Program<'info, System>,
+        amount: u64,
+    ) -> Result<Self> {
+        require_keys_neq!(trader.key(), relayer.key(), ErrorCode::InvalidRelayer);
+        Ok(Self {
+            trader: trader.to_account_info(),
+            relayer: relayer.to_account_info(),
+            system_program: system_program.to_account_info(),
+            amount,
+        })
+    }
+
+    fn pay(self, mint: Pubkey) -> Result<()> {
+        if self.amount == 0 {
+            return Ok(());
+        }
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program,
+                system_program::Transfer {
+                    from: self.trader.clone(),
+                    to: self.relayer.clone(),
+                },
+            ),
+            self.amount,
+        )?;
+
+        emit!(RelayerTipPaid {
+            mint,
+            trader: self.trader.key(),
+            relayer: self.relayer.key(),
+            amount: self.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+// Helper function to enforce and record the trader's round-trip window on this curve
+fn record_trade_guard(trade: &mut CurveTrade, is_buy: bool, slot: u64) -> Result<()> {
+    // Created on first use
+    if trade.trade_guard.trader == Pubkey::default() {
+        trade.trade_guard.mint = trade.mint;
+        trade.trade_guard.trader = trade.trader.key();
+        trade.trade_guard.bump = trade.trade_guard_bump;
+    }
+    trade.trade_guard.record(is_buy, slot, trade.global_config.round_trip_slot_window)
+}
+
+// Helper function to read an optional account passed in remaining accounts, where the
+// program ID stands in for an account that isn't passed, as Anchor does for `Option` accounts
+fn optional_remaining_account<'a, 'info>(
+    info: &'a AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Option<&'a AccountInfo<'info>> {
+    (info.key != program_id).then_some(info)
+}
+
+// Helper function to load a PDA passed in remaining accounts, first creating it with
+// `payer` funding the rent if it doesn't exist yet, as `init_if_needed` would
+fn load_or_init_pda<'info, T>(
+    info: &'info AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system: &Program<'info, System>,
+) -> Result<(Account<'info, T>, u8)>
+where
+    T: AccountSerialize + AccountDeserialize + Owner + Discriminator + Clone,
+{
+    let (address, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(address, info.key(), ErrorCode::InvalidRemainingAccounts);
+    if info.owner == &system_program::ID {
+        let bump_seed = [bump];
+        let mut signer_seeds = seeds.to_vec();
+        signer_seeds.push(&bump_seed);
+        let signer = &[signer_seeds.as_slice()];
+
+        // Top up rather than create, so lamports sent to the address beforehand can't block it
+        let rent = Rent::get()?.minimum_balance(space);
+        let top_up = rent.saturating_sub(info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system.to_account_info(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system.to_account_info(),
+                system_program::Allocate { account_to_allocate: info.clone() },
+                signer,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system.to_account_info(),
+                system_program::Assign { account_to_assign: info.clone() },
+                signer,
+            ),
+            &crate::ID,
+        )?;
+        info.try_borrow_mut_data()?[..T::DISCRIMINATOR.len()].copy_from_slice(T::DISCRIMINATOR);
+    }
+    Ok((Account::try_from(info)?, bump))
+}
+
+// Events produced by a curve buy, emitted by the calling instruction via self-CPI
+struct CurveBuyEvents {
+    buy: BuyEvent,
+    threshold_reached: Option<MigrationThresholdReached>,
+}
+
+// Helper function to buy tokens from the bonding curve with `sol_amount` lamports
+fn execute_curve_buy<'a, 'info>(
+    mut trade: CurveTrade<'a, 'info>,
+    buyer_record: Option<&mut Account<'info, BuyerRecord>>,
+    buyer_record_bump: u8,
+    buyer_record_payer: Pubkey,
+    sol_amount: u64,
+    min_tokens_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Result<CurveBuyEvents> {
+    cu_checkpoint!("buy: start");
+    let mut bonding_curve = *trade.bonding_curve.load()?;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    require!(!bonding_curve.is_complete(), ErrorCode::BondingCurveComplete);
+    require!(!bonding_curve.is_migrated(), ErrorCode::AlreadyMigrated);
+    require!(bonding_curve.flag_reason == 0, ErrorCode::CurveFlagged);
+    // Curves that already had buyers before launches were finalized stay open
+    require!(
+        bonding_curve.is_launch_finalized() || bonding_curve.unique_buyers > 0,
+        ErrorCode::LaunchNotFinalized
+    );
+    require!(
+        current_time >= bonding_curve.trading_start_time,
+        ErrorCode::TradingNotStarted
+    );
+    require!(sol_amount > 0, ErrorCode::InvalidAmount);
+    if bonding_curve.kyc_required() {
+        require!(has_valid_attestation(&trade, current_time), ErrorCode::KycAttestationRequired);
+    }
+    if bonding_curve.compliance_gated() {
+        check_compliance_gate(&trade, sol_amount, current_time)?;
+    }
+    if bonding_curve.max_sol_per_wallet > 0 {
+        let record = buyer_record.as_deref().ok_or(ErrorCode::BuyerRecordRequired)?;
+        require!(
+            record.net_sol_contributed.safe_add(sol_amount)? <= bonding_curve.max_sol_per_wallet,
+            ErrorCode::WalletCapExceeded
+        );
+    }
+    record_trade_guard(&mut trade, true, clock.slot)?;
+
+    // Calculate fee (tiered by how close the curve is to graduating)
+    let fee_bps = curve::discounted_fee_bps(
+        trade.global_config.buy_fee_bps_for(&bonding_curve)?,
+        trade.fee_discount_bps,
+    )?;
+    let fee = curve::fee_for_amount(sol_amount, fee_bps)?;
+    let royalty = bonding_curve.creator_royalty_for(sol_amount)?;
+    let sol_after_fee = sol_amount.safe_sub(fee)?.safe_sub(royalty)?;
+
+    // Calculate tokens out using constant product formula
+    let real_token = bonding_curve.real_token_reserves;
+    let reserves = bonding_curve.reserves_at(current_time)?;
+    let tokens_out = curve::tokens_out_for_sol(reserves, sol_after_fee)?;
+    // Reflections come out of the tokens bought, so slippage applies to what the buyer receives
+    let reflection_vault = trade_reflection_vault(&trade, &bonding_curve)?;
+    let reflection = bonding_curve.reflection_for(tokens_out)?;
+    let tokens_received = tokens_out.safe_sub(reflection)?;
+
+    require!(tokens_received >= min_tokens_out, ErrorCode::SlippageExceeded);
+    require!(tokens_out <= real_token, ErrorCode::InsufficientTokens);
+    if let Some(max_impact) = max_price_impact_bps {
+        let impact = curve::price_impact_bps(
+            curve::spot_price(reserves)?,
+            curve::price_after_trade(reserves, Trade::Buy { sol_in: sol_after_fee })?,
+        )?;
+        require!(impact <= max_impact as u64, ErrorCode::PriceImpactExceeded);
+    }
+
+    // Transfer SOL (including fee) from buyer to bonding curve vault
+    // The fee stays in the vault and is tracked in `accumulated_fees` until withdrawn, and
+    // the royalty likewise in `creator_royalties` until claimed
+    // wSOL vaults are paid in lamports too, then synced so the token balance reflects them
+    let wsol_vault = trade_wsol_vault(&trade, &bonding_curve)?;
+    let sol_destination = match wsol_vault {
+        Some(vault) => vault.to_account_info(),
+        None => bonding_curve.sol_holder(trade.bonding_curve.to_account_info(), trade.bonding_curve_sol_vault),
+    };
+    let mut jackpot_cut = 0;
+    match &trade.escrow {
+        // Escrowed buys spend the SOL deposited with the escrow
+        Some(escrow) => move_lamports(&escrow.account, &sol_destination, sol_amount)?,
+        None => {
+            let cpi_context = CpiContext::new(
+                trade.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: trade.trader.to_account_info(),
+                    to: sol_destination.clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_context, sol_amount)?;
+        }
+    }
+    if wsol_vault.is_some() {
+        sync_native(CpiContext::new(
+            trade.token_program.to_account_info(),
+            SyncNative { account: sol_destination },
+        ))?;
+    } else if bonding_curve.has_jackpot() {
+        // Jackpots are never enabled on wSOL curves
+        let jackpot = trade.jackpot.as_deref_mut().ok_or(ErrorCode::JackpotRequired)?;
+        jackpot_cut = record_jackpot_buy(jackpot, &sol_destination, trade.trader.key(), fee, current_time)?;
+    }
+
+    cu_checkpoint!("buy: quoted");
+
+    // Transfer tokens from bonding curve to buyer
+    let balance_before = trade.trader_token_account.amount;
+    let bump = [bonding_curve.bump];
+    let seeds: &[&[u8]] = &[b"bonding_curve", trade.mint.as_ref(), &bump];
+    let signer = &[seeds];
+
+    let cpi_accounts = Transfer {
+        from: trade.bonding_curve_token_account.to_account_info(),
+        to: trade.trader_token_account.to_account_info(),
+        authority: trade.bonding_curve.to_account_info(),
+    };
+    let cpi_program = trade.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    transfer(cpi_ctx, tokens_received)?;
+    if let Some(vault) = reflection_vault.filter(|_| reflection > 0) {
+        transfer(
+            CpiContext::new_with_signer(
+                trade.token_program.to_account_info(),
+                Transfer {
+                    from: trade.bonding_curve_token_account.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: trade.bonding_curve.to_account_info(),
+                },
+                signer,
+            ),
+            reflection,
+        )?;
+        emit!(ReflectionAccrued {
+            mint: trade.mint,
+            trader: trade.trader.key(),
+            amount: reflection,
+            timestamp: current_time,
+        });
+    }
+    cu_checkpoint!("buy: transferred");
+
+    // A wallet going from zero to a positive balance is a new holder
+    trade.trader_token_account.reload()?;
+    if balance_before == 0 && trade.trader_token_account.amount > 0 {
+        bonding_curve.holder_count = bonding_curve.holder_count.safe_add(1)?;
+    }
+
+    // Update reserves
+    bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves.safe_add(sol_after_fee)?;
+    bonding_curve.real_token_reserves = bonding_curve.real_token_reserves.safe_sub(tokens_out)?;
+    bonding_curve.total_fees_collected = bonding_curve.total_fees_collected.safe_add(fee)?;
+    bonding_curve.accumulated_fees = bonding_curve.accumulated_fees.safe_add(fee.safe_sub(jackpot_cut)?)?;
+    bonding_curve.creator_royalties = bonding_curve.creator_royalties.safe_add(royalty)?;
+    bonding_curve.trade_count = bonding_curve.trade_count.safe_add(1)?;
+
+    let price = curve_spot_price(&bonding_curve)?;
+    trade.curve_oracle.record(current_time, price)?;
+    record_candle_trade(&mut trade, current_time, price, sol_after_fee, tokens_out)?;
+    trade.global_stats.record_trade(sol_after_fee, fee)?;
+    bonding_curve.record_trending_volume(current_time, sol_after_fee)?;
+    if let Some(position) = trade.trader_position.as_deref_mut() {
+        position.record_buy(sol_amount, tokens_received, current_time)?;
+    }
+    record_competition_volume(&mut trade, current_time, sol_after_fee)?;
+    let rate = trade.global_config.points_per_sol_bought;
+    record_points(&mut trade, sol_after_fee, rate)?;
+
+    // First buy from this wallet - record it so unique buyers can be counted
+    if let Some(buyer_record) = buyer_record {
+        if buyer_record.first_buy_at == 0 {
+            buyer_record.mint = trade.mint;
+            buyer_record.buyer = trade.trader.key();
+            buyer_record.first_buy_at = current_time;
+            buyer_record.buyer_index = bonding_curve.unique_buyers;
+            buyer_record.bump = buyer_record_bump;
+            buyer_record.rent_payer = buyer_record_payer;
+
+            bonding_curve.unique_buyers = bonding_curve.unique_buyers.safe_add(1)?;
+        }
+        buyer_record.net_sol_contributed = buyer_record.net_sol_contributed.safe_add(sol_amount)?;
+    }
+
+    // Check if bonding curve is complete (all tokens sold)
+    if bonding_curve.real_token_reserves == 0 {
+        bonding_curve.complete = 1;
+    }
+
+    // Check if migration threshold has been reached
+    let migration_threshold = trade.global_config.migration_threshold_sol;
+    let mut threshold_reached = None;
+    if !bonding_curve.is_migrated()
+        && bonding_curve.real_sol_reserves >= migration_threshold {
+        // Signal that threshold is reached - migration should be triggered
+        threshold_reached = Some(MigrationThresholdReached {
+            mint: trade.mint,
+            sol_reserves: bonding_curve.real_sol_reserves,
+            token_reserves: bonding_curve.real_token_reserves,
+            timestamp: current_time,
+        });
+    }
+
+    *trade.bonding_curve.load_mut()? = bonding_curve;
+    cu_checkpoint!("buy: end");
+
+    Ok(CurveBuyEvents {
+        buy: BuyEvent {
+            buyer: trade.trader.key(),
+            mint: trade.mint,
+            sol_amount,
+            tokens_out: tokens_received,
+            fee,
+            virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+            real_sol_reserves: bonding_curve.real_sol_reserves,
+            real_token_reserves: bonding_curve.real_token_reserves,
+            price_lamports_per_token: price,
+            trade_count: bonding_curve.trade_count,
+            graduation_progress_bps: trade.global_config.graduation_progress_bps(&bonding_curve)?,
+        },
+        threshold_reached,
+    })
+}
+
+// Helper function to sell `token_amount` tokens back to the bonding curve
+// The seller's buyer record, when passed, is credited with the payout so it frees up
+// room under the curve's per-wallet cap
+fn execute_curve_sell<'a, 'info>(
+    mut trade: CurveTrade<'a, 'info>,
+    buyer_record: Option<&mut Account<'info, BuyerRecord>>,
+    token_amount: u64,
+    min_sol_out: u64,
+    max_price_impact_bps: Option<u16>,
+) -> Result<SellEvent> {
+    cu_checkpoint!("sell: start");
+    let mut bonding_curve = *trade.bonding_curve.load()?;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    // Sells stay open on a complete curve that hasn't migrated so holders are never trapped
+    // They are still bounded by the real SOL in the vault
+    require!(!bonding_curve.is_migrated(), ErrorCode::AlreadyMigrated);
+    require!(
+        current_time >= bonding_curve.trading_start_time,
+        ErrorCode::TradingNotStarted
+    );
+    require!(token_amount > 0, ErrorCode::InvalidAmount);
+    record_trade_guard(&mut trade, false, clock.slot)?;
+
+    // Reflections come out of the tokens sold, before they reach the curve
+    let reflection_vault = trade_reflection_vault(&trade, &bonding_curve)?;
+    let reflection = bonding_curve.reflection_for(token_amount)?;
+    let tokens_in = token_amount.safe_sub(reflection)?;
+
+    // Calculate SOL out using constant product formula
+    let real_sol = bonding_curve.real_sol_reserves;
+    let reserves = bonding_curve.reserves_at(current_time)?;
+    let sol_out_before_fee = curve::sol_out_for_tokens(reserves, tokens_in)?;
+
+    // Calculate fee
+    let fee_bps = curve::discounted_fee_bps(
+        trade.global_config.sell_fee_bps_for(&bonding_curve),
+        trade.fee_discount_bps,
+    )?;
+    let fee = curve::fee_for_amount(sol_out_before_fee, fee_bps)?;
+    let royalty = bonding_curve.creator_royalty_for(sol_out_before_fee)?;
+    let sol_out = sol_out_before_fee.safe_sub(fee)?.safe_sub(royalty)?;
+
+    require!(sol_out >= min_sol_out, ErrorCode::SlippageExceeded);
+    // Check that we have enough real SOL to cover the full amount (before fees are taken)
+    require!(sol_out_before_fee <= real_sol, ErrorCode::InsufficientSOL);
+    // ...and that paying out never dips into the vault's rent reserve or unwithdrawn fees
+    let wsol_vault = trade_wsol_vault(&trade, &bonding_curve)?;
+    let sol_holder = bonding_curve.sol_holder(trade.bonding_curve.to_account_info(), trade.bonding_curve_sol_vault);
+    let tradable = match wsol_vault {
+        Some(vault) => vault.amount.saturating_sub(bonding_curve.accumulated_fees),
+        None => bonding_curve.vault_tradable_lamports(&sol_holder)?,
+    };
+    require!(sol_out <= tradable, ErrorCode::InsufficientSOL);
+    if let Some(max_impact) = max_price_impact_bps {
+        let impact = curve::price_impact_bps(
+            curve::spot_price(reserves)?,
+            curve::price_after_trade(reserves, Trade::Sell { tokens_in })?,
+        )?;
+        require!(impact <= max_impact as u64, ErrorCode::PriceImpactExceeded);
+    }
+
+    cu_checkpoint!("sell: quoted");
+
+    // Transfer tokens from seller to bonding curve, and the reflection to its vault
+    transfer_from_trader(&trade, trade.bonding_curve_token_account.to_account_info(), tokens_in)?;
+    if let Some(vault) = reflection_vault.filter(|_| reflection > 0) {
+        transfer_from_trader(&trade, vault.to_account_info(), reflection)?;
+        emit!(ReflectionAccrued {
+            mint: trade.mint,
+            trader: trade.trader.key(),
+            amount: reflection,
+            timestamp: current_time,
+        });
+    }
+
+    // A wallet selling down to zero is no longer a holder
+    // Saturating: wallets that bought before holders were tracked were never counted
+    trade.trader_token_account.reload()?;
+    if trade.trader_token_account.amount == 0 {
+        bonding_curve.holder_count = bonding_curve.holder_count.saturating_sub(1);
+    }
+
+    // Transfer SOL from bonding curve vault to seller (after fee)
+    // The fee stays in the vault and is tracked in `accumulated_fees` until withdrawn
+    match wsol_vault {
+        Some(vault) => {
+            let seller_wsol = trade.trader_wsol_account.ok_or(ErrorCode::WsolVaultRequired)?;
+            let bump = [bonding_curve.bump];
+            let seeds: &[&[u8]] = &[b"bonding_curve", trade.mint.as_ref(), &bump];
+            transfer(
+                CpiContext::new_with_signer(
+                    trade.token_program.to_account_info(),
+                    Transfer {
+                        from: vault.to_account_info(),
+                        to: seller_wsol.to_account_info(),
+                        authority: trade.bonding_curve.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                sol_out,
+            )?;
+        }
+        None => move_lamports(&sol_holder, &trade.trader.to_account_info(), sol_out)?,
+    }
+    cu_checkpoint!("sell: transferred");
+
+    // Update reserves
+    // Subtract the full amount calculated by the constant product (before fee)
+    bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
+        .checked_sub(sol_out_before_fee)
+        .ok_or(ErrorCode::InsufficientSOL)?;
+    bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
+        .safe_add(tokens_in)?;
+    // Tokens are back on the curve, so it reopens for buys
+    bonding_curve.complete = 0;
+    bonding_curve.total_fees_collected = bonding_curve.total_fees_collected
+        .safe_add(fee)?;
+    bonding_curve.accumulated_fees = bonding_curve.accumulated_fees
+        .safe_add(fee)?;
+    bonding_curve.creator_royalties = bonding_curve.creator_royalties
+        .safe_add(royalty)?;
+    bonding_curve.trade_count = bonding_curve.trade_count
+        .safe_add(1)?;
+
+    let price = curve_spot_price(&bonding_curve)?;
+    trade.curve_oracle.record(current_time, price)?;
+    record_candle_trade(&mut trade, current_time, price, sol_out_before_fee, tokens_in)?;
+    trade.global_stats.record_trade(sol_out_before_fee, fee)?;
+    bonding_curve.record_trending_volume(current_time, sol_out_before_fee)?;
+    if let Some(position) = trade.trader_position.as_deref_mut() {
+        position.record_sell(token_amount, sol_out, current_time)?;
+    }
+    if let Some(buyer_record) = buyer_record {
+        buyer_record.net_sol_contributed = buyer_record.net_sol_contributed.saturating_sub(sol_out);
+    }
+    record_competition_volume(&mut trade, current_time, sol_out_before_fee)?;
+    let rate = trade.global_config.points_per_sol_sold;
+    record_points(&mut trade, sol_out_before_fee, rate)?;
+
+    *trade.bonding_curve.load_mut()? = bonding_curve;
+    cu_checkpoint!("sell: end");
+
+    Ok(SellEvent {
+        seller: trade.trader.key(),
+        mint: trade.mint,
+        token_amount,
+        sol_out,
+        fee,
+        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        real_token_reserves: bonding_curve.real_token_reserves,
+        price_lamports_per_token: price,
+        trade_count: bonding_curve.trade_count,
+        graduation_progress_bps: trade.global_config.graduation_progress_bps(&bonding_curve)?,
+    })
+}
+
+// Helper function to build the reserves a curve prices trades over right now
+fn curve_reserves(bonding_curve: &BondingCurve) -> Result<Reserves> {
+    bonding_curve.reserves_at(Clock::get()?.unix_timestamp)
+}
+
+// Helper function to compute a curve's current spot price (lamports per whole token)
+fn curve_spot_price(bonding_curve: &BondingCurve) -> Result<u64> {
+    curve::spot_price(curve_reserves(bonding_curve)?)
+}
+
+// Helper function to decay a trending score over `elapsed` seconds
+// Halves once per full half-life, then decays linearly towards the next halving
+fn decay_trending_score(score: u64, elapsed: i64) -> Result<u64> {
+    if elapsed <= 0 {
+        return Ok(score);
+    }
+    let half_life = BondingCurve::TRENDING_HALF_LIFE;
+    let halvings = elapsed.safe_div(half_life)?;
+    if halvings >= 64 {
+        return Ok(0);
+    }
+    let halved = score >> halvings;
+    let remainder = elapsed.safe_sub(halvings.safe_mul(half_life)?)? as u128;
+    let partial = (halved as u128)
+        .safe_mul(remainder)?
+        .safe_div((half_life as u128).safe_mul(2)?)?;
+    halved.safe_sub(partial.to_u64()?)
+}
+
+// Helper function to read the authority of a global config of any layout version
+// Every layout starts with the discriminator followed by the authority pubkey
+fn read_global_config_authority(config_info: &AccountInfo) -> Result<Pubkey> {
+    require!(config_info.owner == &crate::ID, ErrorCode::InvalidGlobalConfig);
+
+    let data = config_info.try_borrow_data()?;
+    require!(data.len() >= 8 + 32, ErrorCode::InvalidGlobalConfig);
+    require!(&data[..8] == GlobalConfig::DISCRIMINATOR, ErrorCode::InvalidGlobalConfig);
+
+    let authority_bytes: [u8; 32] = data[8..40].try_into().unwrap();
+    Ok(Pubkey::new_from_array(authority_bytes))
+}
+
+// Offset of `config_update_delay`, which no later layout version moves
+const GLOBAL_CONFIG_UPDATE_DELAY_OFFSET: usize = 8 + 32 + 32 + 8 + 8 + 8 + 2 + 8 + 32 + 8 + 2 + 32 * 4 + 1;
+
+// Helper function to read the update delay of a global config of any layout version
+// Layouts from before the timelock are too short to hold one and have no delay
+fn read_global_config_update_delay(config_info: &AccountInfo) -> Result<i64> {
+    let data = config_info.try_borrow_data()?;
+    let delay_bytes = data.get(GLOBAL_CONFIG_UPDATE_DELAY_OFFSET..GLOBAL_CONFIG_UPDATE_DELAY_OFFSET + 8);
+    Ok(delay_bytes.map_or(0, |bytes| i64::from_le_bytes(bytes.try_into().unwrap())))
+}
+
+// Helper function to compute floor(sqrt(value)) with Newton's method
+fn integer_sqrt(value: u128) -> Result<u128> {
+    if value < 2 {
+        return Ok(value);
+    }
+    let mut x = value;
+    let mut y = value.safe_div(2)?.safe_add(1)?;
+    while y < x {
+        x = y;
+        y = x.safe_add(value.safe_div(x)?)?.safe_div(2)?;
+    }
+    Ok(x)
+}
+
+// Helper function to calculate unlocked tokens based on vesting schedule
+#[event]
+pub struct BuyEvent {
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub tokens_out: u64,
+    pub fee: u64,
+    // Curve state after the trade
+    pub virtual_sol_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub price_lamports_per_token: u64,
+    // Sequence number of this trade on the curve (starts at 1)
+    pub trade_count: u64,
+    // Real SOL reserves as a share of the migration threshold, capped at 10_000
+    pub graduation_progress_bps: u16,
+}
+
+#[event]
+pub struct DevBuyEvent {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub tokens_out: u64,
+    pub fee: u64,
+    pub price_lamports_per_token: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeClaimed {
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub badge_mint: Pubkey,
+    pub kind: BadgeKind,
+    pub buyer_index: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PointsEpochClosed {
+    pub epoch: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompetitionCreated {
+    pub competition: Pubkey,
+    pub id: u64,
+    pub mint: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct CompetitionFinalized {
+    pub competition: Pubkey,
+    pub winners: [Pubkey; 3],
+    pub volumes: [u64; 3],
+    pub prizes: [u64; 3],
+}
+
+#[event]
+pub struct PrizeClaimed {
+    pub competition: Pubkey,
+    pub trader: Pubkey,
+    pub rank: u8,
+    pub prize: u64,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ProjectCreated {
+    pub project: Pubkey,
+    pub owner: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub total_supply: u64,
+    pub category: u8,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct UpdateGlobalConfig<'info> {
-    #[account(
-        mut,
-        seeds = [b"global_config"],
-        bump,
-        has_one = authority @ ErrorCode::Unauthorized,
-    )]
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct MintCreated {
+    pub project: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub total_supply: u64,
+    pub decimals: u8,
+    pub project_created_at: i64,
+    pub source: MintSource,
+    pub timestamp: i64,
+}
 
-    pub authority: Signer<'info>,
+#[event]
+pub struct ProjectBoosted {
+    pub project: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub duration: i64,
+    pub cost: u64,
+    pub boosted_until: i64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct CloseGlobalConfig<'info> {
-    #[account(
-        mut,
-        seeds = [b"global_config"],
-        bump,
-    )]
-    /// CHECK: We're closing this account without deserializing it - manual lamport transfer
-    pub global_config: UncheckedAccount<'info>,
+#[event]
+pub struct KingOfTheHillCrowned {
+    pub mint: Pubkey,
+    pub previous_mint: Pubkey,
+    pub score: u64,
+    pub previous_score: u64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct CategoryAdded {
+    pub index: u8,
+    pub name: String,
+    pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct InitializeVesting<'info> {
-    #[account(
-        init,
-        payer = creator,
-        seeds = [b"vesting", mint.key().as_ref(), creator.key().as_ref()],
-        bump,
-        space = VestingSchedule::MAX_SIZE,
-    )]
-    pub vesting_schedule: Account<'info, VestingSchedule>,
+#[event]
+pub struct WsolVaultEnabled {
+    pub mint: Pubkey,
+    pub wsol_vault: Pubkey,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct LaunchFinalized {
+    pub mint: Pubkey,
+    pub supply: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        init,
-        payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = vesting_schedule,
-    )]
-    pub vesting_vault: Account<'info, TokenAccount>,
+#[event]
+pub struct InlineSolEnabled {
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+#[event]
+pub struct RaydiumPoolRecorded {
+    pub mint: Pubkey,
+    pub raydium_pool: Pubkey,
+    pub previous_pool: Pubkey,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct ClaimVestedTokens<'info> {
-    #[account(
-        mut,
-        seeds = [b"vesting", mint.key().as_ref(), beneficiary.key().as_ref()],
-        bump = vesting_schedule.bump,
-        has_one = beneficiary @ ErrorCode::Unauthorized,
-        has_one = mint @ ErrorCode::InvalidMint,
-    )]
-    pub vesting_schedule: Account<'info, VestingSchedule>,
+#[event]
+pub struct MigrationStatusChanged {
+    pub mint: Pubkey,
+    pub from: MigrationStatus,
+    pub to: MigrationStatus,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct BondingCurveCancelled {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub tokens_returned: u64,
+    pub sol_returned: u64,
+    pub fees_to_treasury: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = vesting_schedule,
-    )]
-    pub vesting_vault: Account<'info, TokenAccount>,
+#[event]
+pub struct LaunchDepositRefunded {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        init_if_needed,
-        payer = beneficiary,
-        associated_token::mint = mint,
-        associated_token::authority = beneficiary,
-    )]
-    pub beneficiary_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct PlatformTokenStaked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub staked: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub beneficiary: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+#[event]
+pub struct PlatformTokenUnstaked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub staked: u64,
+    pub total_staked: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct GetClaimableAmount<'info> {
-    #[account(
-        seeds = [b"vesting", mint.key().as_ref(), vesting_schedule.beneficiary.as_ref()],
-        bump = vesting_schedule.bump,
-    )]
-    pub vesting_schedule: Account<'info, VestingSchedule>,
+#[event]
+pub struct FeeShareClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct CurveFeesUpdated {
+    pub mint: Pubkey,
+    pub buy_fee_bps: Option<u16>,
+    pub sell_fee_bps: Option<u16>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct InitializeBondingCurve<'info> {
-    #[account(
-        init,
-        payer = creator,
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump,
-        space = BondingCurve::MAX_SIZE,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct CurveFlagged {
+    pub mint: Pubkey,
+    pub old_reason: u8,
+    pub reason: u8,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct CreatorFlagsUpdated {
+    pub creator: Pubkey,
+    pub old_flags: u8,
+    pub new_flags: u8,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(
-        init,
-        payer = creator,
-        seeds = [b"sol_vault", mint.key().as_ref()],
-        bump,
-        space = 0,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for the bonding curve
-    pub sol_vault: AccountInfo<'info>,
+#[event]
+pub struct CandleFinalized {
+    pub mint: Pubkey,
+    pub bucket_start: i64,
+    pub open: u64,
+    pub high: u64,
+    pub low: u64,
+    pub close: u64,
+    pub volume_sol: u64,
+    pub volume_tokens: u64,
+    pub trade_count: u32,
+}
 
-    #[account(
-        init,
-        payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
-    )]
-    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct SellEvent {
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+    pub sol_out: u64,
+    pub fee: u64,
+    // Curve state after the trade
+    pub virtual_sol_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub price_lamports_per_token: u64,
+    // Sequence number of this trade on the curve (starts at 1)
+    pub trade_count: u64,
+    // Real SOL reserves as a share of the migration threshold, capped at 10_000
+    pub graduation_progress_bps: u16,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = creator,
-    )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct MigrationThresholdReached {
+    pub mint: Pubkey,
+    pub sol_reserves: u64,
+    pub token_reserves: u64,
+    pub timestamp: i64,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct MigrationComplete {
+    pub mint: Pubkey,
+    pub raydium_pool: Pubkey,
+    pub sol_migrated: u64,
+    pub tokens_migrated: u64,
+    pub migration_fee: u64,
+    pub keeper: Pubkey,
+    pub keeper_reward: u64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct MigrationFundsWithdrawn {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub total_sol_withdrawn: u64,
+    pub total_tokens_withdrawn: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct BuyTokens<'info> {
-    #[account(
-        mut,
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct MigrationFundsReturned {
+    pub mint: Pubkey,
+    pub operator: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub total_sol_withdrawn: u64,
+    pub total_tokens_withdrawn: u64,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct MigrationRemainderSettled {
+    pub mint: Pubkey,
+    pub sol_to_treasury: u64,
+    pub tokens_burned: u64,
+    pub total_sol_withdrawn: u64,
+    pub total_tokens_withdrawn: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"sol_vault", mint.key().as_ref()],
-        bump,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for the bonding curve
-    pub bonding_curve_sol_vault: AccountInfo<'info>,
+#[event]
+pub struct VestingClaimEvent {
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub claimer: Pubkey,
+    pub amount_claimed: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
-    )]
-    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct SolVestingCreated {
+    pub sol_vesting_schedule: Pubkey,
+    pub funder: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
 
-    #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = mint,
-        associated_token::authority = buyer,
-    )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct SolVestingClaimEvent {
+    pub sol_vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount_claimed: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct TokensLocked {
+    pub token_lock: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub unlock_time: i64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+#[event]
+pub struct LockExtended {
+    pub token_lock: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub index: u64,
+    pub previous_unlock_time: i64,
+    pub unlock_time: i64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
-    )]
-    /// CHECK: Treasury address validated against global config
-    pub treasury: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+#[event]
+pub struct LockWithdrawn {
+    pub token_lock: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct SellTokens<'info> {
-    #[account(
-        mut,
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct OtcDealCreated {
+    pub otc_deal: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct OtcDealFunded {
+    pub otc_deal: Pubkey,
+    pub depositor: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"sol_vault", mint.key().as_ref()],
-        bump,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for the bonding curve
-    pub bonding_curve_sol_vault: AccountInfo<'info>,
+#[event]
+pub struct OtcDealSettled {
+    pub otc_deal: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
-    )]
-    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct OtcDealCancelled {
+    pub otc_deal: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = seller,
-    )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct FundingRoundCreated {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub discount_bps: u16,
+    pub price_cap: u64,
+    pub token_allocation: u64,
+    pub deadline: i64,
+    pub timestamp: i64,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct RoundInvestment {
+    pub mint: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub deposited: u64,
+    pub total_raised: u64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub seller: Signer<'info>,
+#[event]
+pub struct FundingRoundConverted {
+    pub mint: Pubkey,
+    pub migration_price: u64,
+    pub conversion_price: u64,
+    pub total_raised: u64,
+    pub tokens_owed: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
-    )]
-    /// CHECK: Treasury address validated against global config
-    pub treasury: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct RoundTokensClaimed {
+    pub mint: Pubkey,
+    pub investor: Pubkey,
+    pub deposited: u64,
+    pub tokens: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct WithdrawPlatformFees<'info> {
-    #[account(
-        mut,
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct RoundDepositRefunded {
+    pub mint: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct SnapshotCreated {
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub merkle_root: [u8; 32],
+    pub slot: u64,
+    pub holder_count: u64,
+    pub total_balance: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"sol_vault", mint.key().as_ref()],
-        bump,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for the bonding curve
-    pub bonding_curve_sol_vault: AccountInfo<'info>,
+#[event]
+pub struct PaymentStreamCreated {
+    pub payment_stream: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub mint: Pubkey,
+    pub rate_per_second: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub cancelable: bool,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct StreamWithdrawn {
+    pub payment_stream: Pubkey,
+    pub payee: Pubkey,
+    pub amount: u64,
+    pub withdrawn: u64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct PaymentStreamCanceled {
+    pub payment_stream: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub payee_amount: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    /// CHECK: Treasury account to receive fees (validated by authority)
-    pub treasury: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct RageQuitEnabled {
+    pub mint: Pubkey,
+    pub milestone_count: u8,
+    pub milestone_interval: i64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct MigrateToRaydium<'info> {
-    #[account(
-        mut,
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct RoundMilestoneReleased {
+    pub mint: Pubkey,
+    pub milestone: u8,
+    pub amount: u64,
+    pub escrowed_sol: u64,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct RageQuitEvent {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub tokens_burned: u64,
+    pub lamports: u64,
+    pub escrowed_sol: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"sol_vault", mint.key().as_ref()],
-        bump,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for the bonding curve
-    pub bonding_curve_sol_vault: AccountInfo<'info>,
+#[event]
+pub struct ProjectUpdatePosted {
+    pub project: Pubkey,
+    pub owner: Pubkey,
+    pub sequence: u64,
+    pub content_hash: [u8; 32],
+    pub uri: String,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
-    )]
-    pub bonding_curve_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct WalletCapSet {
+    pub mint: Pubkey,
+    pub max_sol_per_wallet: u64,
+    pub timestamp: i64,
+}
 
-    /// Migration vault to hold SOL before Raydium pool creation
-    #[account(
-        mut,
-        seeds = [b"migration_vault", mint.key().as_ref()],
-        bump,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for migration
-    pub migration_sol_vault: AccountInfo<'info>,
+#[event]
+pub struct CreatorRoyaltySet {
+    pub mint: Pubkey,
+    pub royalty_bps: u16,
+    pub timestamp: i64,
+}
 
-    /// Migration token account to hold tokens before Raydium pool creation
-    #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = migration_authority,
-    )]
-    pub migration_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct CreatorRoyaltiesClaimed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    /// Authority for the migration vault (a PDA)
-    #[account(
-        seeds = [b"migration_authority"],
-        bump,
-    )]
-    /// CHECK: This is a PDA used as authority for migration accounts
-    pub migration_authority: AccountInfo<'info>,
+#[event]
+pub struct CreatorAllocationEscrowed {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub release_delay: i64,
+    pub timestamp: i64,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct CreatorAllocationReleased {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct JackpotEnabled {
+    pub mint: Pubkey,
+    pub fee_share_bps: u16,
+    pub buys_per_draw: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        constraint = treasury.key() == global_config.treasury @ ErrorCode::InvalidTreasury
-    )]
-    /// CHECK: Treasury address validated against global config
-    pub treasury: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct JackpotContributed {
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub pot: u64,
+    pub buy_count: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct WithdrawMigrationFunds<'info> {
-    #[account(
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct JackpotWon {
+    pub mint: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub draw: u64,
+    pub buy_count: u64,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct JackpotDrawDue {
+    pub mint: Pubkey,
+    pub buy_count: u64,
+    pub pot: u64,
+    pub timestamp: i64,
+}
 
-    /// Migration vault holding SOL
-    #[account(
-        mut,
-        seeds = [b"migration_vault", mint.key().as_ref()],
-        bump,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for migration
-    pub migration_sol_vault: AccountInfo<'info>,
+#[event]
+pub struct JackpotClaimed {
+    pub mint: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    /// Migration token account holding tokens
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = migration_authority,
-    )]
-    pub migration_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct RandomnessRequested {
+    pub consumer: Pubkey,
+    pub kind: RandomnessConsumerKind,
+    pub randomness_account: Pubkey,
+    pub seed_slot: u64,
+    pub timestamp: i64,
+}
 
-    /// Authority for the migration vault (a PDA)
-    #[account(
-        seeds = [b"migration_authority"],
-        bump,
-    )]
-    /// CHECK: This is a PDA used as authority for migration accounts
-    pub migration_authority: AccountInfo<'info>,
+#[event]
+pub struct RandomnessFulfilled {
+    pub consumer: Pubkey,
+    pub kind: RandomnessConsumerKind,
+    pub value: [u8; 32],
+    pub timestamp: i64,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct LiquidityBootstrappingEnabled {
+    pub mint: Pubkey,
+    pub weights_bps: [u16; 4],
+    pub duration: i64,
+    pub timestamp: i64,
+}
 
-    /// Platform authority who can withdraw
-    pub authority: Signer<'info>,
+#[event]
+pub struct ReflectionsEnabled {
+    pub mint: Pubkey,
+    pub reflection_bps: u16,
+    pub timestamp: i64,
+}
 
-    /// Recipient for SOL
-    #[account(mut)]
-    /// CHECK: Recipient account (usually authority's wallet for pool creation)
-    pub recipient: AccountInfo<'info>,
+#[event]
+pub struct ReflectionAccrued {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    /// Recipient token account
-    #[account(
-        mut,
-        token::mint = mint,
-        token::authority = recipient,
-    )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct ReflectionsDistributed {
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub total_balance: u64,
+    pub acc_tokens_per_share: u128,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ReflectionsClaimed {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct BurnRaydiumLpTokens<'info> {
-    #[account(
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct KycGatingEnabled {
+    pub mint: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct KycAttestationIssued {
+    pub wallet: Pubkey,
+    pub verifier: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
 
-    /// LP burn info account to track the burn (new account)
-    #[account(
-        init,
-        payer = authority,
-        seeds = [b"lp_burn_info", mint.key().as_ref()],
-        bump,
-        space = LpBurnInfo::MAX_SIZE,
-    )]
-    pub lp_burn_info: Account<'info, LpBurnInfo>,
+#[event]
+pub struct KycAttestationRevoked {
+    pub wallet: Pubkey,
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
 
-    /// LP token mint from Raydium pool
-    #[account(mut)]
-    pub lp_mint: Account<'info, Mint>,
+#[event]
+pub struct ComplianceFlagsUpdated {
+    pub project: Pubkey,
+    pub mint: Pubkey,
+    pub old_flags: u16,
+    pub new_flags: u16,
+    pub max_buy_lamports: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    /// LP token account holding the LP tokens (owned by migration_authority)
-    #[account(
-        mut,
-        token::mint = lp_mint,
-        token::authority = migration_authority,
-    )]
-    pub lp_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct RelayerTipPaid {
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub relayer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    /// Authority for the migration vault (a PDA)
-    #[account(
-        seeds = [b"migration_authority"],
-        bump,
-    )]
-    /// CHECK: This is a PDA used as authority for migration accounts
-    pub migration_authority: AccountInfo<'info>,
+#[event]
+pub struct TradeDelegateAuthorized {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub max_sol_per_trade: u64,
+    pub expires_at: i64,
+    pub allowed_mints: Vec<Pubkey>,
+    pub deposit: u64,
+    pub timestamp: i64,
+}
 
-    /// CHECK: Raydium pool address (for recording)
-    pub raydium_pool: AccountInfo<'info>,
+#[event]
+pub struct TradeDelegateRevoked {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct LimitOrderPlaced {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u64,
+    pub side: SwapDirection,
+    pub price: u64,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
 
-    /// Platform authority who can call this
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct LimitOrderFilled {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u64,
+    pub side: SwapDirection,
+    pub price: u64,
+    pub amount: u64,
+    pub spot_price: u64,
+    pub keeper: Pubkey,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct LimitOrderCancelled {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u64,
+    pub side: SwapDirection,
+    pub amount: u64,
+    pub expired: bool,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct CreateAndLockRaydiumPool<'info> {
-    #[account(
-        mut,
-        seeds = [b"bonding_curve", mint.key().as_ref()],
-        bump = bonding_curve.bump,
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+#[event]
+pub struct ConditionalOrderPlaced {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u64,
+    pub trigger: OrderTrigger,
+    pub token_amount: u64,
+    pub slippage_bps: u16,
+    pub keeper_tip: u64,
+    pub timestamp: i64,
+}
 
-    pub mint: Account<'info, Mint>,
+#[event]
+pub struct ConditionalOrderExecuted {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u64,
+    pub trigger: OrderTrigger,
+    pub spot_price: u64,
+    pub token_amount: u64,
+    pub keeper: Pubkey,
+    pub keeper_tip: u64,
+    pub timestamp: i64,
+}
 
-    /// Migration vault holding SOL
-    #[account(
-        mut,
-        seeds = [b"migration_vault", mint.key().as_ref()],
-        bump,
-    )]
-    /// CHECK: This is a PDA used to hold SOL for migration
-    pub migration_sol_vault: AccountInfo<'info>,
+#[event]
+pub struct ConditionalOrderCancelled {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u64,
+    pub token_amount: u64,
+    pub timestamp: i64,
+}
 
-    /// Migration token account holding tokens
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = migration_authority,
-    )]
-    pub migration_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct DcaScheduleCreated {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount_per_buy: u64,
+    pub interval: i64,
+    pub max_price: u64,
+    pub keeper_tip: u64,
+    pub deposit: u64,
+    pub timestamp: i64,
+}
 
-    /// Authority for the migration vault (a PDA)
-    #[account(
-        seeds = [b"migration_authority"],
-        bump,
-    )]
-    /// CHECK: This is a PDA used as authority for migration accounts
-    pub migration_authority: AccountInfo<'info>,
+#[event]
+pub struct DcaBuyExecuted {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub sol_amount: u64,
+    pub keeper: Pubkey,
+    pub keeper_tip: u64,
+    pub buys_executed: u32,
+    pub next_buy_at: i64,
+    pub timestamp: i64,
+}
 
-    pub global_config: Account<'info, GlobalConfig>,
+#[event]
+pub struct DcaScheduleClosed {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub buys_executed: u32,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
 
-    /// Platform authority who can call this
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct JupiterBuyRouted {
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub jupiter_program: Pubkey,
+    pub sol_received: u64,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct CurvesSwapped {
+    pub trader: Pubkey,
+    pub sell_mint: Pubkey,
+    pub buy_mint: Pubkey,
+    pub token_amount: u64,
+    pub sol_routed: u64,
+    pub tokens_out: u64,
+    pub timestamp: i64,
 }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Unauthorized")]
-    Unauthorized,
-    #[msg("Invalid amount")]
-    InvalidAmount,
-    #[msg("Slippage tolerance exceeded")]
-    SlippageExceeded,
-    #[msg("Bonding curve is complete")]
-    BondingCurveComplete,
-    #[msg("Insufficient tokens in bonding curve")]
-    InsufficientTokens,
-    #[msg("Insufficient SOL in bonding curve")]
-    InsufficientSOL,
-    #[msg("Already migrated to DEX")]
-    AlreadyMigrated,
-    #[msg("Migration threshold not reached")]
-    ThresholdNotReached,
-    #[msg("Token not migrated yet")]
-    NotMigrated,
-    #[msg("Invalid vesting duration")]
-    InvalidVestingDuration,
-    #[msg("Invalid cliff duration")]
-    InvalidCliffDuration,
-    #[msg("Cliff period not reached yet")]
-    CliffNotReached,
-    #[msg("No tokens available to claim")]
-    NoTokensToCllaim,
-    #[msg("Invalid mint address")]
-    InvalidMint,
-    #[msg("Insufficient fees to withdraw")]
-    InsufficientFees,
-    #[msg("No fees to withdraw")]
-    NoFeesToWithdraw,
-    #[msg("Invalid treasury address")]
-    InvalidTreasury,
-    #[msg("Insufficient SOL for migration (need at least 6 SOL fee + pool liquidity)")]
-    InsufficientSOLForMigration,
-    #[msg("LP tokens have already been burned")]
-    LpAlreadyBurned,
-    #[msg("Feature not yet implemented")]
-    NotImplemented,
+#[event]
+pub struct BasketBought {
+    pub buyer: Pubkey,
+    pub curves: u8,
+    pub sol_amount: u64,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct ProjectState {
-    pub owner: Pubkey,           // 32
-    pub mint: Pubkey,            // 32
-    pub name: String,            // 4 + up to 64
-    pub symbol: String,          // 4 + up to 16
-    pub created_at: i64,         // 8
-    pub total_supply: u64,       // 8 - Total token supply
-    pub category: String,        // 4 + up to 32 - Startup category/industry
+#[event]
+pub struct ClaimDelegateUpdated {
+    pub vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub delegate: Option<Pubkey>,
+    pub timestamp: i64,
 }
 
-impl ProjectState {
-    pub const MAX_NAME: usize = 64;
-    pub const MAX_SYMBOL: usize = 16;
-    pub const MAX_CATEGORY: usize = 32;
-    pub const MAX_SIZE: usize = 8  // discriminator
-        + 32                       // owner
-        + 32                       // mint
-        + 4 + Self::MAX_NAME       // name
-        + 4 + Self::MAX_SYMBOL     // symbol
-        + 8                        // created_at
-        + 8                        // total_supply
-        + 4 + Self::MAX_CATEGORY;  // category
+#[event]
+pub struct FeeWithdrawalEvent {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub fee_share: u64,
+    pub insurance: u64,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct GlobalConfig {
-    pub authority: Pubkey,              // 32
-    pub treasury: Pubkey,               // 32 - Treasury address for automatic fee collection
-    pub virtual_sol_reserves: u64,      // 8 - Virtual SOL reserves for price stability
-    pub virtual_token_reserves: u64,    // 8 - Virtual token reserves for price stability
-    pub initial_token_supply: u64,      // 8 - Default initial token supply for new curves
-    pub fee_basis_points: u16,          // 2 - Platform fee (e.g., 100 = 1%)
-    pub migration_threshold_sol: u64,   // 8 - SOL threshold to trigger migration (e.g., 85 SOL)
-    pub raydium_amm_program: Pubkey,    // 32 - Raydium AMM program ID
+#[event]
+pub struct BatchFeeWithdrawalEvent {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub curves_swept: u32,
+    pub amount: u64,
+    pub fee_share: u64,
+    pub insurance: u64,
+    pub timestamp: i64,
 }
 
-impl GlobalConfig {
-    pub const MAX_SIZE: usize = 8  // discriminator
-        + 32                       // authority
-        + 32                       // treasury
-        + 8                        // virtual_sol_reserves
-        + 8                        // virtual_token_reserves
-        + 8                        // initial_token_supply
-        + 2                        // fee_basis_points
-        + 8                        // migration_threshold_sol
-        + 32;                      // raydium_amm_program
+#[event]
+pub struct GlobalConfigClosed {
+    pub authority: Pubkey,
+    pub lamports_recovered: u64,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct BondingCurve {
-    pub mint: Pubkey,                   // 32 - Token mint address
-    pub creator: Pubkey,                // 32 - Creator of the bonding curve
-    pub virtual_sol_reserves: u64,      // 8 - Virtual SOL for price calculation
-    pub virtual_token_reserves: u64,    // 8 - Virtual tokens for price calculation
-    pub real_sol_reserves: u64,         // 8 - Actual SOL in the curve
-    pub real_token_reserves: u64,       // 8 - Actual tokens in the curve
-    pub complete: bool,                 // 1 - Whether all tokens have been sold
-    pub migrated: bool,                 // 1 - Whether migrated to DEX
-    pub raydium_pool: Pubkey,           // 32 - Raydium pool address (if migrated)
-    pub bump: u8,                       // 1 - PDA bump seed
+#[event]
+pub struct BondingCurveUpgraded {
+    pub mint: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
 }
 
-impl BondingCurve {
-    pub const MAX_SIZE: usize = 8  // discriminator
-        + 32                       // mint
-        + 32                       // creator
-        + 8                        // virtual_sol_reserves
-        + 8                        // virtual_token_reserves
-        + 8                        // real_sol_reserves
-        + 8                        // real_token_reserves
-        + 1                        // complete
-        + 1                        // migrated
-        + 32                       // raydium_pool
-        + 1;                       // bump
+#[event]
+pub struct GlobalConfigUpgraded {
+    pub authority: Pubkey,
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct LpBurnInfo {
-    pub mint: Pubkey,                   // 32 - Token mint address
-    pub lp_mint: Pubkey,                // 32 - LP token mint address
-    pub raydium_pool: Pubkey,           // 32 - Raydium pool address
-    pub lp_burned_amount: u64,          // 8 - Amount of LP tokens burned
-    pub burn_timestamp: i64,            // 8 - When LP tokens were burned
-    pub bump: u8,                       // 1 - PDA bump seed
+#[event]
+pub struct ConfigUpdateQueued {
+    pub authority: Pubkey,
+    pub buy_fee_bps: Option<u16>,
+    pub migration_threshold_sol: Option<u64>,
+    pub eta: i64,
+    pub timestamp: i64,
 }
 
-impl LpBurnInfo {
-    pub const MAX_SIZE: usize = 8   // discriminator
-        + 32                        // mint
-        + 32                        // lp_mint
-        + 32                        // raydium_pool
-        + 8                         // lp_burned_amount
-        + 8                         // burn_timestamp
-        + 1;                        // bump
+#[event]
+pub struct ConfigUpdateExecuted {
+    pub authority: Pubkey,
+    pub buy_fee_bps: u16,
+    pub migration_threshold_sol: u64,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct VestingSchedule {
-    pub beneficiary: Pubkey,        // 32 - Who receives the vested tokens
-    pub mint: Pubkey,               // 32 - Token mint address
-    pub total_amount: u64,          // 8 - Total tokens to vest
-    pub claimed_amount: u64,        // 8 - Amount already claimed
-    pub start_time: i64,            // 8 - When vesting starts
-    pub cliff_time: i64,            // 8 - When cliff period ends
-    pub end_time: i64,              // 8 - When vesting fully completes
-    pub release_interval: i64,      // 8 - How often tokens unlock (e.g., monthly = 2592000 seconds)
-    pub last_claim_time: i64,       // 8 - Last time tokens were claimed
-    pub bump: u8,                   // 1 - PDA bump seed
+#[event]
+pub struct ConfigUpdateCancelled {
+    pub authority: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
 }
 
-impl VestingSchedule {
-    pub const MAX_SIZE: usize = 8   // discriminator
-        + 32                        // beneficiary
-        + 32                        // mint
-        + 8                         // total_amount
-        + 8                         // claimed_amount
-        + 8                         // start_time
-        + 8                         // cliff_time
-        + 8                         // end_time
-        + 8                         // release_interval
-        + 8                         // last_claim_time
-        + 1;                        // bump
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
 }
 
-// Helper function to calculate unlocked tokens based on vesting schedule
-fn calculate_unlocked_amount(schedule: &VestingSchedule, current_time: i64) -> Result<u64> {
-    // If we haven't reached the cliff, nothing is unlocked
-    if current_time < schedule.cliff_time {
-        return Ok(0);
-    }
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
 
-    // If we're past the end time, everything is unlocked
-    if current_time >= schedule.end_time {
-        return Ok(schedule.total_amount);
-    }
+#[event]
+pub struct SecondaryPayoutsUpdated {
+    pub authority: Pubkey,
+    pub payouts: Vec<Pubkey>,
+    pub timestamp: i64,
+}
 
-    // Linear vesting between cliff and end
-    let vesting_duration = schedule.end_time
-        .checked_sub(schedule.start_time)
-        .ok_or(ErrorCode::InvalidVestingDuration)?;
-    
-    let elapsed_time = current_time
-        .checked_sub(schedule.start_time)
-        .ok_or(ErrorCode::InvalidVestingDuration)?;
+#[event]
+pub struct BuybackBurned {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub sol_spent: u64,
+    pub tokens_burned: u64,
+    pub via_raydium: bool,
+    pub accumulated_fees: u64,
+    pub timestamp: i64,
+}
 
-    // Calculate unlocked amount proportionally
-    let unlocked = (schedule.total_amount as u128)
-        .checked_mul(elapsed_time as u128)
-        .unwrap()
-        .checked_div(vesting_duration as u128)
-        .unwrap() as u64;
+#[event]
+pub struct InsurancePayoutProposed {
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub reason: String,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
 
-    Ok(unlocked)
+#[event]
+pub struct InsurancePayoutExecuted {
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_paid_out: u64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct BuyEvent {
-    pub buyer: Pubkey,
-    pub mint: Pubkey,
-    pub sol_amount: u64,
-    pub tokens_out: u64,
-    pub fee: u64,
+pub struct InsurancePayoutCancelled {
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct SellEvent {
-    pub seller: Pubkey,
-    pub mint: Pubkey,
-    pub token_amount: u64,
-    pub sol_out: u64,
-    pub fee: u64,
+pub struct TreasurySplitUpdated {
+    pub authority: Pubkey,
+    pub splits: Vec<TreasurySplit>,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct MigrationThresholdReached {
+pub struct LpTokensBurnedEvent {
     pub mint: Pubkey,
-    pub sol_reserves: u64,
-    pub token_reserves: u64,
+    pub raydium_pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount_burned: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct MigrationComplete {
+pub struct LpTokensLockedEvent {
     pub mint: Pubkey,
     pub raydium_pool: Pubkey,
-    pub sol_migrated: u64,
-    pub tokens_migrated: u64,
-    pub migration_fee: u64,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub unlock_time: i64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct MigrationFundsWithdrawn {
+pub struct LockedLpWithdrawnEvent {
     pub mint: Pubkey,
-    pub authority: Pubkey,
+    pub lp_mint: Pubkey,
     pub recipient: Pubkey,
-    pub sol_amount: u64,
-    pub token_amount: u64,
+    pub lp_amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct VestingClaimEvent {
-    pub beneficiary: Pubkey,
+pub struct LpFeesClaimedEvent {
     pub mint: Pubkey,
-    pub amount_claimed: u64,
-    pub total_claimed: u64,
+    pub raydium_pool: Pubkey,
+    pub lp_amount_withdrawn: u64,
+    pub token_0_mint: Pubkey,
+    pub token_0_to_creator: u64,
+    pub token_0_to_treasury: u64,
+    pub token_1_mint: Pubkey,
+    pub token_1_to_creator: u64,
+    pub token_1_to_treasury: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct FeeWithdrawalEvent {
+pub struct RaydiumSwapRouted {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub raydium_pool: Pubkey,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondingCurveClosed {
     pub mint: Pubkey,
-    pub authority: Pubkey,
     pub treasury: Pubkey,
-    pub amount: u64,
+    pub lamports_recovered: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct LpTokensBurnedEvent {
+pub struct GraduationArchived {
     pub mint: Pubkey,
     pub raydium_pool: Pubkey,
-    pub lp_mint: Pubkey,
-    pub lp_amount_burned: u64,
+    pub unique_buyers: u64,
+    pub duration: i64,
+    pub total_fees_paid: u64,
     pub timestamp: i64,
 }
 
-
+#[event]
+pub struct BuyerRecordClosed {
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub rent_payer: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::ErrorCode;
+
+/// Checked arithmetic that fails with a typed program error instead of panicking
+pub trait SafeMath: Sized {
+    fn safe_add(self, rhs: Self) -> Result<Self>;
+    fn safe_sub(self, rhs: Self) -> Result<Self>;
+    fn safe_mul(self, rhs: Self) -> Result<Self>;
+    fn safe_div(self, rhs: Self) -> Result<Self>;
+}
+
+macro_rules! impl_safe_math {
+    ($($t:ty),*) => {
+        $(
+            impl SafeMath for $t {
+                #[inline(always)]
+                fn safe_add(self, rhs: Self) -> Result<Self> {
+                    self.checked_add(rhs).ok_or_else(|| error!(ErrorCode::MathOverflow))
+                }
+
+                #[inline(always)]
+                fn safe_sub(self, rhs: Self) -> Result<Self> {
+                    self.checked_sub(rhs).ok_or_else(|| error!(ErrorCode::MathUnderflow))
+                }
+
+                #[inline(always)]
+                fn safe_mul(self, rhs: Self) -> Result<Self> {
+                    self.checked_mul(rhs).ok_or_else(|| error!(ErrorCode::MathOverflow))
+                }
+
+                #[inline(always)]
+                fn safe_div(self, rhs: Self) -> Result<Self> {
+                    self.checked_div(rhs).ok_or_else(|| error!(ErrorCode::DivisionByZero))
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_math!(u8, u16, u32, u64, u128, i64, i128);
+
+/// Narrowing conversion that fails instead of silently truncating
+pub trait SafeCast {
+    fn to_u64(self) -> Result<u64>;
+}
+
+impl SafeCast for u128 {
+    #[inline(always)]
+    fn to_u64(self) -> Result<u64> {
+        u64::try_from(self).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
+
+/// Direction to round a division whose remainder can't be represented
+///
+/// Policy: amounts paid to a trader (tokens out, SOL out) round down and amounts charged
+/// to a trader (fees) round up, so every remainder stays with the protocol and repeated
+/// dust trades can't extract value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// `numerator / denominator`, rounded in the given direction
+pub fn div_rounded(numerator: u128, denominator: u128, rounding: Rounding) -> Result<u128> {
+    let quotient = numerator.safe_div(denominator)?;
+    match rounding {
+        Rounding::Up if !numerator.is_multiple_of(denominator) => quotient.safe_add(1),
+        _ => Ok(quotient),
+    }
+}
+
+/// `value * numerator / denominator`, rounded in the given direction
+pub fn mul_div(value: u128, numerator: u128, denominator: u128, rounding: Rounding) -> Result<u128> {
+    div_rounded(value.safe_mul(numerator)?, denominator, rounding)
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::math::{mul_div, Rounding, SafeCast, SafeMath};
+use crate::{ErrorCode, GlobalConfig};
+
+/// Pyth pull-oracle receiver program, which owns `PriceUpdateV2` accounts
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of the receiver's `PriceUpdateV2`
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Pyth feed id of SOL/USD
+pub const SOL_USD_FEED_ID: [u8; 32] = [
+    239, 13, 139, 111, 218, 44, 235, 164, 29, 161, 93, 64, 149, 209, 218, 57, 42, 13, 47, 142, 208,
+    198, 199, 188, 15, 76, 250, 200, 194, 128, 181, 109,
+];
+
+/// USD amounts are carried in micro-dollars
+pub const USD_DECIMALS: u32 = 6;
+
+const LAMPORTS_PER_SOL: u128 = 1_000_000_000;
+
+// `PriceUpdateV2` starts with the discriminator and write authority, then the verification
+// level: tag 1 (`Full`) alone, or tag 0 (`Partial`) followed by a signature count
+const VERIFICATION_LEVEL_OFFSET: usize = 8 + 32;
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+// The price message follows a `Full` level: feed id, price, conf, exponent, publish time
+const MESSAGE_OFFSET: usize = VERIFICATION_LEVEL_OFFSET + 1;
+const MESSAGE_LEN: usize = 32 + 8 + 8 + 4 + 8;
+
+/// A Pyth price read from a fully verified `PriceUpdateV2`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PythPrice {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Parse `info`, rejecting accounts the receiver doesn't own and partially verified updates
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require!(info.owner == &PYTH_RECEIVER_ID, ErrorCode::InvalidPriceAccount);
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= MESSAGE_OFFSET + MESSAGE_LEN && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+            ErrorCode::InvalidPriceAccount
+        );
+        require!(data[VERIFICATION_LEVEL_OFFSET] == VERIFICATION_LEVEL_FULL, ErrorCode::InvalidPriceAccount);
+        let field = |offset: usize, len: usize| &data[MESSAGE_OFFSET + offset..MESSAGE_OFFSET + offset + len];
+        Ok(Self {
+            feed_id: field(0, 32).try_into().unwrap(),
+            price: i64::from_le_bytes(field(32, 8).try_into().unwrap()),
+            conf: u64::from_le_bytes(field(40, 8).try_into().unwrap()),
+            exponent: i32::from_le_bytes(field(48, 4).try_into().unwrap()),
+            publish_time: i64::from_le_bytes(field(52, 8).try_into().unwrap()),
+        })
+    }
+
+    /// Fail unless this is a positive `feed_id` price published within `max_age` seconds of
+    /// `now` whose confidence interval is at most `max_confidence_bps` of the price
+    pub fn validate(&self, feed_id: &[u8; 32], now: i64, max_age: i64, max_confidence_bps: u16) -> Result<()> {
+        require!(self.feed_id == *feed_id, ErrorCode::InvalidPriceAccount);
+        require!(self.price > 0, ErrorCode::InvalidPrice);
+        require!(now.safe_sub(self.publish_time)? <= max_age, ErrorCode::StalePrice);
+        require!(
+            (self.conf as u128).safe_mul(10_000)? <= (self.price as u128).safe_mul(max_confidence_bps as u128)?,
+            ErrorCode::PriceConfidenceTooWide
+        );
+        Ok(())
+    }
+
+    /// The price in micro-dollars, rounded down
+    pub fn usd_micros(&self) -> Result<u64> {
+        let price = self.price as u128;
+        let scale = USD_DECIMALS as i32 + self.exponent;
+        let micros = if scale >= 0 {
+            price.safe_mul(10u128.checked_pow(scale as u32).ok_or(ErrorCode::MathOverflow)?)?
+        } else {
+            price.safe_div(10u128.checked_pow(scale.unsigned_abs()).ok_or(ErrorCode::MathOverflow)?)?
+        };
+        require!(micros > 0, ErrorCode::InvalidPrice);
+        micros.to_u64()
+    }
+}
+
+/// Micro-dollars per SOL from the configured SOL/USD price account, after staleness and
+/// confidence checks. Every USD-denominated feature reads the price through here
+pub fn sol_usd(info: &AccountInfo, global_config: &GlobalConfig, now: i64) -> Result<u64> {
+    require!(
+        global_config.sol_usd_price_account != Pubkey::default(),
+        ErrorCode::SolUsdPriceNotConfigured
+    );
+    require_keys_eq!(info.key(), global_config.sol_usd_price_account, ErrorCode::InvalidPriceAccount);
+    let price = PythPrice::load(info)?;
+    price.validate(
+        &SOL_USD_FEED_ID,
+        now,
+        global_config.max_price_age_seconds,
+        global_config.max_price_confidence_bps,
+    )?;
+    price.usd_micros()
+}
+
+/// Lamports worth `usd_micros` at `sol_usd`, rounded up as it's charged
+pub fn lamports_for_usd(usd_micros: u64, sol_usd: u64) -> Result<u64> {
+    mul_div(usd_micros as u128, LAMPORTS_PER_SOL, sol_usd as u128, Rounding::Up)?.to_u64()
+}
+
+/// Micro-dollars `lamports` are worth at `sol_usd`, rounded down
+pub fn usd_for_lamports(lamports: u64, sol_usd: u64) -> Result<u64> {
+    mul_div(lamports as u128, sol_usd as u128, LAMPORTS_PER_SOL, Rounding::Down)?.to_u64()
+}
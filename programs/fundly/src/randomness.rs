@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+use crate::ErrorCode;
+
+/// Switchboard On-Demand program, which owns the randomness accounts draws are read from
+pub const SWITCHBOARD_ON_DEMAND_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Anchor discriminator of Switchboard's `RandomnessAccountData`
+pub const RANDOMNESS_ACCOUNT_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+
+// Offsets into `RandomnessAccountData`: discriminator, authority, queue and seed slothash
+// come first, then the seed slot, the oracle, the reveal slot and the value
+const AUTHORITY_OFFSET: usize = 8;
+const QUEUE_OFFSET: usize = AUTHORITY_OFFSET + 32;
+const SEED_SLOT_OFFSET: usize = QUEUE_OFFSET + 32 + 32;
+const REVEAL_SLOT_OFFSET: usize = SEED_SLOT_OFFSET + 8 + 32;
+const VALUE_OFFSET: usize = REVEAL_SLOT_OFFSET + 8;
+
+/// The parts of a Switchboard randomness account a draw needs
+/// Switchboard commits to a seed slot and later reveals `value` in `reveal_slot`, so a
+/// request is bound at commit time, before the value can be known, and fulfilled once it's
+/// revealed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwitchboardRandomness {
+    pub authority: Pubkey,
+    pub queue: Pubkey,
+    pub seed_slot: u64,
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
+
+impl SwitchboardRandomness {
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require!(info.owner == &SWITCHBOARD_ON_DEMAND_ID, ErrorCode::InvalidRandomnessAccount);
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= VALUE_OFFSET + 32 && data[..8] == RANDOMNESS_ACCOUNT_DISCRIMINATOR,
+            ErrorCode::InvalidRandomnessAccount
+        );
+        let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let read_key = |offset: usize| Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap());
+        Ok(Self {
+            authority: read_key(AUTHORITY_OFFSET),
+            queue: read_key(QUEUE_OFFSET),
+            seed_slot: read_u64(SEED_SLOT_OFFSET),
+            reveal_slot: read_u64(REVEAL_SLOT_OFFSET),
+            value: data[VALUE_OFFSET..VALUE_OFFSET + 32].try_into().unwrap(),
+        })
+    }
+
+    /// Whether the account was committed in the previous slot, so its value can't be known yet
+    pub fn is_fresh_commit(&self, slot: u64) -> bool {
+        self.seed_slot == slot.saturating_sub(1)
+    }
+
+    /// Whether the account belongs to the platform's randomness `authority` on `queue`, so
+    /// no one else can commit it or choose when it's revealed
+    pub fn is_from(&self, queue: &Pubkey, authority: &Pubkey) -> bool {
+        self.queue == *queue && self.authority == *authority
+    }
+
+    /// The value revealed for the current commit, if any. Re-committing clears the reveal
+    /// and moves the seed slot, so a value read against a known seed slot can't change
+    pub fn revealed_value(&self) -> Option<[u8; 32]> {
+        (self.reveal_slot != 0).then_some(self.value)
+    }
+}
+
+/// A feature that draws from verifiable randomness. A consumer account has at most one
+/// open `RandomnessRequest`, keyed by its address, and is handed the value on fulfillment
+pub trait RandomnessConsumer {
+    /// Whether the consumer needs a draw, so a request may be opened for it
+    fn awaiting_randomness(&self) -> bool;
+
+    /// Apply a fulfilled draw
+    fn consume_randomness(&mut self, value: &[u8; 32], now: i64) -> Result<()>;
+}
+
+/// Index below `len` picked by `value`; the modulo bias is negligible for small `len`
+pub fn pick_index(value: &[u8; 32], len: usize) -> usize {
+    let word = u64::from_le_bytes(value[..8].try_into().unwrap());
+    (word % len.max(1) as u64) as usize
+}
+
+/// Fisher-Yates shuffle of `items`, each step drawing from `value` hashed with its position
+pub fn shuffle<T>(items: &mut [T], value: &[u8; 32]) {
+    for i in (1..items.len()).rev() {
+        let step = hashv(&[value.as_ref(), &(i as u64).to_le_bytes()]).to_bytes();
+        items.swap(i, pick_index(&step, i + 1));
+    }
+}
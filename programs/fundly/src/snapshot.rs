@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+// Domain prefixes so a leaf can never be passed off as an inner node
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf committing `holder`'s `balance`: `sha256(0x00 || holder || balance as u64 LE)`
+pub fn leaf(holder: &Pubkey, balance: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, holder.as_ref(), &balance.to_le_bytes()]).to_bytes()
+}
+
+/// Parent of two nodes, hashed in sorted order so proofs need no left/right flags
+pub fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, low, high]).to_bytes()
+}
+
+/// Whether `proof` (sibling hashes from the leaf upwards) links `leaf` to `root`
+pub fn verify(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| parent(&node, sibling));
+    computed == *root
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::math::SafeMath;
+use crate::ErrorCode;
+
+/// Fail unless `account` is owned by this program, i.e. one the program may debit
+pub fn require_program_owned(account: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::VaultNotProgramOwned);
+    Ok(())
+}
+
+/// Move lamports out of a program-owned account
+///
+/// Checks ownership up front so a bad account fails with a program error instead of a
+/// runtime abort, and works out both balances before writing either, so an overdraw or
+/// overflow leaves the pair untouched. Rent exemption of `from` is left to the runtime,
+/// which rejects the transaction if it ends with `from` neither rent-exempt nor empty
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    require_program_owned(from)?;
+    // The same account on both sides would be credited after being debited
+    require_keys_neq!(*from.key, *to.key, ErrorCode::VaultSelfTransfer);
+
+    let from_after = from.lamports().safe_sub(amount)?;
+    let to_after = to.lamports().safe_add(amount)?;
+    **from.try_borrow_mut_lamports()? = from_after;
+    **to.try_borrow_mut_lamports()? = to_after;
+    Ok(())
+}
+
+/// Move every lamport out of a program-owned account, returning the amount moved
+pub fn drain_lamports(from: &AccountInfo, to: &AccountInfo) -> Result<u64> {
+    let amount = from.lamports();
+    move_lamports(from, to, amount)?;
+    Ok(amount)
+}
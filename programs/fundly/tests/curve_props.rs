@@ -0,0 +1,232 @@
+//! Property tests for the shared constant-product pricing math.
+
+use fundly::curve::{
+    discounted_fee_bps, fee_for_amount, price_after_trade, price_impact_bps, reserves_after_trade,
+    round_conversion_price, sol_out_for_tokens, spot_price, tokens_for_deposit, tokens_out_for_sol,
+    Reserves, Trade, TOKEN_DECIMALS_FACTOR,
+};
+use fundly::math::{div_rounded, Rounding};
+use proptest::prelude::*;
+
+// Reserve ranges around the production config (30 SOL / ~1.07B tokens virtual),
+// stretched far enough to cover graduation and badly tuned configs
+fn reserves() -> impl Strategy<Value = Reserves> {
+    (1_000_000_000u64..1_000_000_000_000, 1_000_000_000u64..u64::MAX / 2)
+        .prop_map(|(sol, token)| Reserves { sol: sol as u128, token: token as u128 })
+}
+
+// The same reserves, limited to those priced at one lamport per whole token or more
+fn priced_reserves() -> impl Strategy<Value = Reserves> {
+    (1_000_000_000u64..1_000_000_000_000)
+        .prop_flat_map(|sol| (Just(sol), 1_000_000_000u64..=sol * TOKEN_DECIMALS_FACTOR as u64))
+        .prop_map(|(sol, token)| Reserves { sol: sol as u128, token: token as u128 })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2_000))]
+
+    #[test]
+    fn buy_never_decreases_k(reserves in reserves(), sol_in in 0u64..500_000_000_000) {
+        let after = reserves_after_trade(reserves, Trade::Buy { sol_in }).unwrap();
+        prop_assert!(after.k().unwrap() >= reserves.k().unwrap());
+    }
+
+    #[test]
+    fn sell_never_decreases_k(reserves in reserves(), tokens_in in 0u64..u64::MAX / 4) {
+        let after = reserves_after_trade(reserves, Trade::Sell { tokens_in }).unwrap();
+        prop_assert!(after.k().unwrap() >= reserves.k().unwrap());
+    }
+
+    #[test]
+    fn buy_then_sell_never_profits(reserves in reserves(), sol_in in 0u64..500_000_000_000) {
+        let tokens_out = tokens_out_for_sol(reserves, sol_in).unwrap();
+        let after_buy = reserves_after_trade(reserves, Trade::Buy { sol_in }).unwrap();
+        let sol_back = sol_out_for_tokens(after_buy, tokens_out).unwrap();
+        prop_assert!(sol_back <= sol_in);
+    }
+
+    #[test]
+    fn sell_then_buy_never_profits(reserves in reserves(), tokens_in in 0u64..u64::MAX / 4) {
+        let sol_out = sol_out_for_tokens(reserves, tokens_in).unwrap();
+        let after_sell = reserves_after_trade(reserves, Trade::Sell { tokens_in }).unwrap();
+        let tokens_back = tokens_out_for_sol(after_sell, sol_out).unwrap();
+        prop_assert!(tokens_back <= tokens_in);
+    }
+
+    #[test]
+    fn draining_a_curve_never_pays_out_more_than_went_in(
+        reserves in reserves(),
+        buys in prop::collection::vec(1u64..50_000_000_000, 1..8),
+    ) {
+        // Sell every buy back, so the vault only ever holds its rent reserve plus what came in
+        let mut current = reserves;
+        let mut sol_in_total = 0u128;
+        let mut bought = Vec::new();
+        for sol_in in buys {
+            bought.push(tokens_out_for_sol(current, sol_in).unwrap());
+            current = reserves_after_trade(current, Trade::Buy { sol_in }).unwrap();
+            sol_in_total += sol_in as u128;
+        }
+        let mut sol_out_total = 0u128;
+        for tokens_in in bought {
+            sol_out_total += sol_out_for_tokens(current, tokens_in).unwrap() as u128;
+            current = reserves_after_trade(current, Trade::Sell { tokens_in }).unwrap();
+        }
+        prop_assert!(sol_out_total <= sol_in_total);
+        prop_assert!(current.sol >= reserves.sol);
+    }
+
+    #[test]
+    fn outputs_never_exceed_reserves(reserves in reserves(), amount in 0u64..u64::MAX / 4) {
+        prop_assert!((tokens_out_for_sol(reserves, amount).unwrap() as u128) < reserves.token);
+        prop_assert!((sol_out_for_tokens(reserves, amount).unwrap() as u128) < reserves.sol);
+    }
+
+    #[test]
+    fn buys_raise_and_sells_lower_price(reserves in reserves(), amount in 1u64..500_000_000_000) {
+        let price = spot_price(reserves).unwrap();
+        let buy = Trade::Buy { sol_in: amount };
+        let sell = Trade::Sell { tokens_in: amount };
+        prop_assert!(price_after_trade(reserves, buy).unwrap() >= price);
+        prop_assert!(price_after_trade(reserves, sell).unwrap() <= price);
+    }
+
+    #[test]
+    fn larger_buys_get_more_tokens(reserves in reserves(), a in 0u64..250_000_000_000, b in 0u64..250_000_000_000) {
+        let (small, large) = if a <= b { (a, b) } else { (b, a) };
+        prop_assert!(
+            tokens_out_for_sol(reserves, small).unwrap() <= tokens_out_for_sol(reserves, large).unwrap()
+        );
+    }
+
+    #[test]
+    fn larger_buys_have_more_price_impact(reserves in priced_reserves(), a in 0u64..250_000_000_000, b in 0u64..250_000_000_000) {
+        let price = spot_price(reserves).unwrap();
+        let (small, large) = if a <= b { (a, b) } else { (b, a) };
+        let impact = |sol_in| {
+            price_impact_bps(price, price_after_trade(reserves, Trade::Buy { sol_in }).unwrap()).unwrap()
+        };
+        prop_assert!(impact(small) <= impact(large));
+    }
+
+    #[test]
+    fn fee_never_exceeds_amount(amount in any::<u64>(), fee_bps in 0u16..=10_000) {
+        let fee = fee_for_amount(amount, fee_bps).unwrap();
+        prop_assert!(fee <= amount);
+        // Rounded up: never less than the exact fraction, and by less than one lamport
+        let exact = amount as u128 * fee_bps as u128;
+        prop_assert!(fee as u128 * 10_000 >= exact);
+        prop_assert!((fee as u128 * 10_000) < exact + 10_000);
+    }
+
+    #[test]
+    fn fee_is_monotonic(a in any::<u64>(), b in any::<u64>(), fee_bps in 0u16..=10_000) {
+        let (small, large) = if a <= b { (a, b) } else { (b, a) };
+        prop_assert!(fee_for_amount(small, fee_bps).unwrap() <= fee_for_amount(large, fee_bps).unwrap());
+    }
+
+    #[test]
+    fn splitting_a_trade_never_lowers_the_fee(a in any::<u32>(), b in any::<u32>(), fee_bps in 0u16..=10_000) {
+        // Rounding up means splitting can only cost the trader, by at most one lamport
+        let whole = fee_for_amount(a as u64 + b as u64, fee_bps).unwrap();
+        let split = fee_for_amount(a as u64, fee_bps).unwrap() + fee_for_amount(b as u64, fee_bps).unwrap();
+        prop_assert!(split >= whole && split - whole <= 1);
+    }
+
+    #[test]
+    fn discounts_never_round_the_fee_below_exact(fee_bps in 0u16..=10_000, discount_bps in 0u16..=10_000) {
+        let discounted = discounted_fee_bps(fee_bps, discount_bps).unwrap();
+        prop_assert!(discounted <= fee_bps);
+        prop_assert!(discounted as u128 * 10_000 >= fee_bps as u128 * (10_000 - discount_bps as u128));
+    }
+
+    #[test]
+    fn rounding_brackets_the_exact_quotient(numerator in any::<u128>(), denominator in 1u128..) {
+        let down = div_rounded(numerator, denominator, Rounding::Down).unwrap();
+        let up = div_rounded(numerator, denominator, Rounding::Up).unwrap();
+        prop_assert!(down <= up && up - down <= 1);
+        prop_assert_eq!(up == down, numerator % denominator == 0);
+    }
+
+    #[test]
+    fn round_trip_with_fees_never_profits(
+        reserves in reserves(),
+        sol_amount in 1u64..500_000_000_000,
+        fee_bps in 0u16..=1_000,
+    ) {
+        let fee = fee_for_amount(sol_amount, fee_bps).unwrap();
+        let sol_in = sol_amount - fee;
+        let tokens = tokens_out_for_sol(reserves, sol_in).unwrap();
+        let after_buy = reserves_after_trade(reserves, Trade::Buy { sol_in }).unwrap();
+        let sol_out_before_fee = sol_out_for_tokens(after_buy, tokens).unwrap();
+        let sol_back = sol_out_before_fee - fee_for_amount(sol_out_before_fee, fee_bps).unwrap();
+        prop_assert!(sol_back <= sol_amount);
+        // Any non-zero fee makes a non-empty round trip strictly lose
+        if fee_bps > 0 && tokens > 0 {
+            prop_assert!(sol_back < sol_amount);
+        }
+    }
+
+    #[test]
+    fn dust_round_trips_never_extract_value(
+        reserves in reserves(),
+        sol_amount in 1u64..10_000,
+        trips in 1usize..64,
+        fee_bps in 0u16..=1_000,
+    ) {
+        // Many tiny round trips against the same curve: rounding alone must not pay out
+        let mut current = reserves;
+        let mut spent = 0u128;
+        let mut received = 0u128;
+        for _ in 0..trips {
+            let sol_in = sol_amount - fee_for_amount(sol_amount, fee_bps).unwrap();
+            let tokens = tokens_out_for_sol(current, sol_in).unwrap();
+            current = reserves_after_trade(current, Trade::Buy { sol_in }).unwrap();
+            let sol_out = sol_out_for_tokens(current, tokens).unwrap();
+            current = reserves_after_trade(current, Trade::Sell { tokens_in: tokens }).unwrap();
+            spent += sol_amount as u128;
+            received += (sol_out - fee_for_amount(sol_out, fee_bps).unwrap()) as u128;
+        }
+        prop_assert!(received <= spent);
+        prop_assert!(current.sol >= reserves.sol);
+    }
+
+    #[test]
+    fn round_conversions_fit_the_allocation(
+        migration_price in 1u64..1_000_000_000,
+        discount_bps in 0u16..10_000,
+        price_cap in any::<u64>(),
+        deposits in prop::collection::vec(1u64..1_000_000_000_000, 1..16),
+        allocation in 1u64..u64::MAX / 2,
+    ) {
+        // Every investor's claim together never exceeds the tokens set aside
+        let raised: u64 = deposits.iter().sum();
+        let price = round_conversion_price(migration_price, discount_bps, price_cap, raised, allocation).unwrap();
+        let claimed: u128 = deposits.iter().map(|&deposit| tokens_for_deposit(deposit, price).unwrap() as u128).sum();
+        prop_assert!(claimed <= tokens_for_deposit(raised, price).unwrap() as u128);
+        prop_assert!(claimed <= allocation as u128);
+        // The discount and cap only ever lower the price, unless the allocation runs short
+        let discounted = migration_price as u128 * (10_000 - discount_bps as u128) / 10_000;
+        if raised as u128 * 1_000_000 <= discounted * allocation as u128 {
+            prop_assert!(price as u128 <= discounted.max(1));
+        }
+    }
+
+    #[test]
+    fn pricing_never_panics(
+        virtual_sol in any::<u64>(),
+        real_sol in any::<u64>(),
+        virtual_token in any::<u64>(),
+        real_token in any::<u64>(),
+        amount in any::<u64>(),
+    ) {
+        // Any input either prices or fails with a program error
+        if let Ok(reserves) = Reserves::new(virtual_sol, real_sol, virtual_token, real_token) {
+            let _ = tokens_out_for_sol(reserves, amount);
+            let _ = sol_out_for_tokens(reserves, amount);
+            let _ = spot_price(reserves);
+            let _ = price_after_trade(reserves, Trade::Buy { sol_in: amount });
+            let _ = price_after_trade(reserves, Trade::Sell { tokens_in: amount });
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Admin instructions executed by a PDA authority through a mock multisig.
+//!
+//! These tests load the compiled programs, so build them first:
+//! `anchor build && cargo test -p fundly --test multisig`
+
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use fundly::GlobalConfig;
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+const VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
+const VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+const INITIAL_TOKEN_SUPPLY: u64 = 1_000_000_000_000_000;
+const MIGRATION_THRESHOLD_SOL: u64 = 85_000_000_000;
+
+fn global_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_config"], &fundly::ID).0
+}
+
+fn multisig_vault_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"vault"], &mock_multisig::ID).0
+}
+
+async fn start() -> ProgramTestContext {
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(true);
+    program_test.add_program("fundly", fundly::ID, None);
+    program_test.add_program("mock_multisig", mock_multisig::ID, None);
+    program_test.start_with_context().await
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+// Wrap a fundly instruction so the multisig vault signs it via CPI
+fn through_multisig(member: &Pubkey, instruction: Instruction) -> Instruction {
+    let mut accounts = mock_multisig::accounts::Execute {
+        vault: multisig_vault_pda(),
+        target_program: fundly::ID,
+        member: *member,
+    }
+    .to_account_metas(None);
+    accounts.extend(instruction.accounts.into_iter().map(|meta| AccountMeta {
+        // The vault can't sign the outer transaction; it signs inside the CPI
+        is_signer: meta.is_signer && meta.pubkey != multisig_vault_pda(),
+        ..meta
+    }));
+
+    Instruction {
+        program_id: mock_multisig::ID,
+        accounts,
+        data: mock_multisig::instruction::Execute { data: instruction.data }.data(),
+    }
+}
+
+fn initialize_global_config_ix(authority: &Pubkey, treasury: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::InitializeGlobalConfig {
+            global_config: global_config_pda(),
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::InitializeGlobalConfig {
+            params: fundly::GlobalConfigParams {
+                treasury: *treasury,
+                virtual_sol_reserves: VIRTUAL_SOL_RESERVES,
+                virtual_token_reserves: VIRTUAL_TOKEN_RESERVES,
+                initial_token_supply: INITIAL_TOKEN_SUPPLY,
+                buy_fee_bps: 100,
+                migration_threshold_sol: MIGRATION_THRESHOLD_SOL,
+                raydium_amm_program: Pubkey::new_unique(),
+                migration_keeper_reward: 0,
+                lp_fee_creator_share_bps: 5_000,
+                config_update_delay: 0,
+                boost_lamports_per_hour: 0,
+                points_per_sol_bought: 0,
+                points_per_sol_sold: 0,
+                round_trip_slot_window: 0,
+                fee_tiers: [fundly::FeeTier::default(); 4],
+                sell_fee_bps: 100,
+                platform_token_mint: Pubkey::default(),
+                holder_discount_tiers: [fundly::HolderDiscountTier::default(); 4],
+                fee_share_bps: 0,
+                launch_fee_lamports: 0,
+                launch_deposit_lamports: 0,
+                max_launches_per_window: 0,
+                launch_window_seconds: 0,
+                migration_operator: Pubkey::default(),
+                insurance_fee_bps: 0,
+                buyback_bps: 0,
+                kyc_verifier: Pubkey::default(),
+                jupiter_program: Pubkey::default(),
+                token_decimals: 6,
+                randomness_queue: Pubkey::default(),
+                randomness_authority: Pubkey::default(),
+                sol_usd_price_account: Pubkey::default(),
+                max_price_age_seconds: 60,
+                max_price_confidence_bps: 200,
+                launch_fee_usd: 0,
+            },
+        }
+        .data(),
+    }
+}
+
+fn propose_authority_ix(authority: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::UpdateGlobalConfig {
+            global_config: global_config_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::ProposeAuthority {
+            new_authority: *new_authority,
+        }
+        .data(),
+    }
+}
+
+fn accept_authority_ix(new_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::AcceptAuthority {
+            global_config: global_config_pda(),
+            new_authority: *new_authority,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::AcceptAuthority {}.data(),
+    }
+}
+
+fn update_treasury_ix(authority: &Pubkey, treasury: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::UpdateGlobalConfig {
+            global_config: global_config_pda(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::UpdateGlobalConfig {
+            update: fundly::ConfigUpdate {
+                treasury: Some(*treasury),
+                ..Default::default()
+            },
+        }
+        .data(),
+    }
+}
+
+async fn fetch_global_config(context: &mut ProgramTestContext) -> GlobalConfig {
+    let account = context
+        .banks_client
+        .get_account(global_config_pda())
+        .await
+        .unwrap()
+        .expect("global config exists");
+    anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+// Hand the platform over to the multisig vault and return the approving member
+async fn transfer_to_multisig(context: &mut ProgramTestContext, admin: &Keypair) -> Keypair {
+    let treasury = Pubkey::new_unique();
+    send(context, initialize_global_config_ix(&admin.pubkey(), &treasury), &[admin])
+        .await
+        .unwrap();
+    send(context, propose_authority_ix(&admin.pubkey(), &multisig_vault_pda()), &[admin])
+        .await
+        .unwrap();
+
+    let member = Keypair::new();
+    send(
+        context,
+        through_multisig(&member.pubkey(), accept_authority_ix(&multisig_vault_pda())),
+        &[&member],
+    )
+    .await
+    .unwrap();
+    member
+}
+
+async fn funded_keypair(context: &mut ProgramTestContext) -> Keypair {
+    let keypair = Keypair::new();
+    let transfer = solana_sdk::system_instruction::transfer(
+        &context.payer.pubkey(),
+        &keypair.pubkey(),
+        1_000_000_000,
+    );
+    send(context, transfer, &[]).await.unwrap();
+    keypair
+}
+
+#[tokio::test]
+async fn multisig_vault_accepts_authority() {
+    let mut context = start().await;
+    let admin = funded_keypair(&mut context).await;
+    transfer_to_multisig(&mut context, &admin).await;
+
+    let global_config = fetch_global_config(&mut context).await;
+    assert_eq!(global_config.authority, multisig_vault_pda());
+    assert_eq!(global_config.pending_authority, Pubkey::default());
+}
+
+#[tokio::test]
+async fn multisig_vault_can_update_config() {
+    let mut context = start().await;
+    let admin = funded_keypair(&mut context).await;
+    let member = transfer_to_multisig(&mut context, &admin).await;
+
+    let new_treasury = Pubkey::new_unique();
+    send(
+        &mut context,
+        through_multisig(&member.pubkey(), update_treasury_ix(&multisig_vault_pda(), &new_treasury)),
+        &[&member],
+    )
+    .await
+    .unwrap();
+
+    let global_config = fetch_global_config(&mut context).await;
+    assert_eq!(global_config.treasury, new_treasury);
+}
+
+#[tokio::test]
+async fn previous_authority_is_locked_out() {
+    let mut context = start().await;
+    let admin = funded_keypair(&mut context).await;
+    transfer_to_multisig(&mut context, &admin).await;
+
+    let result = send(
+        &mut context,
+        update_treasury_ix(&admin.pubkey(), &Pubkey::new_unique()),
+        &[&admin],
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn only_the_nominee_can_accept() {
+    let mut context = start().await;
+    let admin = funded_keypair(&mut context).await;
+    send(
+        &mut context,
+        initialize_global_config_ix(&admin.pubkey(), &Pubkey::new_unique()),
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    send(
+        &mut context,
+        propose_authority_ix(&admin.pubkey(), &multisig_vault_pda()),
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    let impostor = Keypair::new();
+    let result = send(&mut context, accept_authority_ix(&impostor.pubkey()), &[&impostor]).await;
+    assert!(result.is_err());
+
+    let global_config = fetch_global_config(&mut context).await;
+    assert_eq!(global_config.authority, admin.pubkey());
+}
+
+#[tokio::test]
+async fn multisig_vault_queues_update_with_separate_payer() {
+    let mut context = start().await;
+    let admin = funded_keypair(&mut context).await;
+    let member = transfer_to_multisig(&mut context, &admin).await;
+
+    // The vault holds no lamports; rent for the pending update comes from the payer
+    let pending_config_update =
+        Pubkey::find_program_address(&[b"pending_config_update"], &fundly::ID).0;
+    let queue = Instruction {
+        program_id: fundly::ID,
+        accounts: fundly::accounts::QueueConfigUpdate {
+            global_config: global_config_pda(),
+            pending_config_update,
+            authority: multisig_vault_pda(),
+            payer: context.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: fundly::instruction::QueueConfigUpdate {
+            update: fundly::TimelockedConfigUpdate {
+                buy_fee_bps: Some(50),
+                ..Default::default()
+            },
+        }
+        .data(),
+    };
+    send(&mut context, through_multisig(&member.pubkey(), queue), &[&member])
+        .await
+        .unwrap();
+
+    let pending = context
+        .banks_client
+        .get_account(pending_config_update)
+        .await
+        .unwrap();
+    assert!(pending.is_some());
+}
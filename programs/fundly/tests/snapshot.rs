@@ -0,0 +1,59 @@
+//! Merkle proofs against holder snapshots, as built off-chain for `create_snapshot`.
+
+use anchor_lang::prelude::Pubkey;
+use fundly::snapshot::{leaf, parent, verify};
+
+// Build every level of the tree, carrying an odd node up unchanged
+fn levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| if pair.len() == 2 { parent(&pair[0], &pair[1]) } else { pair[0] })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+fn proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+fn holders(count: usize) -> Vec<(Pubkey, u64)> {
+    (0..count).map(|i| (Pubkey::new_unique(), 1_000 * (i as u64 + 1))).collect()
+}
+
+#[test]
+fn every_holder_proves_their_balance() {
+    for count in 1..=9 {
+        let holders = holders(count);
+        let levels = levels(holders.iter().map(|(holder, balance)| leaf(holder, *balance)).collect());
+        let root = levels.last().unwrap()[0];
+        for (index, (holder, balance)) in holders.iter().enumerate() {
+            assert!(verify(&root, leaf(holder, *balance), &proof(&levels, index)));
+        }
+    }
+}
+
+#[test]
+fn rejects_wrong_balance_or_holder() {
+    let holders = holders(5);
+    let levels = levels(holders.iter().map(|(holder, balance)| leaf(holder, *balance)).collect());
+    let root = levels.last().unwrap()[0];
+    let (holder, balance) = holders[2];
+    let proof = proof(&levels, 2);
+
+    assert!(!verify(&root, leaf(&holder, balance + 1), &proof));
+    assert!(!verify(&root, leaf(&Pubkey::new_unique(), balance), &proof));
+    assert!(!verify(&root, leaf(&holder, balance), &proof[1..]));
+}
@@ -0,0 +1,178 @@
+//! Lamport moves out of program-owned vaults, as done by sells, fee withdrawals,
+//! migration, deposit refunds, prize claims and account closes.
+
+use anchor_lang::error::{Error, ERROR_CODE_OFFSET};
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::system_program;
+use fundly::vault::{drain_lamports, move_lamports, require_program_owned};
+use fundly::ErrorCode;
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    fn program_owned(lamports: u64) -> Self {
+        Self { key: Pubkey::new_unique(), owner: fundly::ID, lamports, data: vec![0; 8] }
+    }
+
+    fn wallet(lamports: u64) -> Self {
+        Self { key: Pubkey::new_unique(), owner: system_program::ID, lamports, data: vec![] }
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            false,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+fn error_code(result: anchor_lang::Result<impl std::fmt::Debug>) -> u32 {
+    match result.unwrap_err() {
+        Error::AnchorError(error) => error.error_code_number,
+        Error::ProgramError(error) => panic!("unexpected program error: {error:?}"),
+    }
+}
+
+fn program_error(code: ErrorCode) -> u32 {
+    ERROR_CODE_OFFSET + code as u32
+}
+
+#[test]
+fn moves_between_vault_and_wallet() {
+    let mut vault = TestAccount::program_owned(5_000);
+    let mut trader = TestAccount::wallet(100);
+
+    move_lamports(&vault.info(), &trader.info(), 1_200).unwrap();
+
+    assert_eq!(vault.lamports, 3_800);
+    assert_eq!(trader.lamports, 1_300);
+}
+
+#[test]
+fn moves_between_program_accounts() {
+    // Migration: SOL vault into the migration vault
+    let mut sol_vault = TestAccount::program_owned(90_000);
+    let mut migration_vault = TestAccount::program_owned(1_000);
+
+    move_lamports(&sol_vault.info(), &migration_vault.info(), 84_000).unwrap();
+
+    assert_eq!(sol_vault.lamports, 6_000);
+    assert_eq!(migration_vault.lamports, 85_000);
+}
+
+#[test]
+fn zero_amount_is_a_no_op() {
+    let mut vault = TestAccount::program_owned(5_000);
+    let mut treasury = TestAccount::wallet(0);
+
+    move_lamports(&vault.info(), &treasury.info(), 0).unwrap();
+
+    assert_eq!(vault.lamports, 5_000);
+    assert_eq!(treasury.lamports, 0);
+}
+
+#[test]
+fn rejects_debit_from_foreign_account() {
+    let mut wallet = TestAccount::wallet(5_000);
+    let mut attacker = TestAccount::wallet(0);
+
+    let result = move_lamports(&wallet.info(), &attacker.info(), 1_000);
+
+    assert_eq!(error_code(result), program_error(ErrorCode::VaultNotProgramOwned));
+    assert_eq!(wallet.lamports, 5_000);
+    assert_eq!(attacker.lamports, 0);
+}
+
+#[test]
+fn rejects_debit_from_account_owned_by_another_program() {
+    let mut foreign = TestAccount::program_owned(5_000);
+    foreign.owner = Pubkey::new_unique();
+    let mut trader = TestAccount::wallet(0);
+
+    let result = move_lamports(&foreign.info(), &trader.info(), 1_000);
+
+    assert_eq!(error_code(result), program_error(ErrorCode::VaultNotProgramOwned));
+}
+
+#[test]
+fn rejects_self_transfer() {
+    let mut vault = TestAccount::program_owned(5_000);
+    let key = vault.key;
+    let mut alias = TestAccount::program_owned(5_000);
+    alias.key = key;
+
+    let result = move_lamports(&vault.info(), &alias.info(), 1_000);
+
+    assert_eq!(error_code(result), program_error(ErrorCode::VaultSelfTransfer));
+    assert_eq!(vault.lamports, 5_000);
+}
+
+#[test]
+fn rejects_overdraw() {
+    let mut vault = TestAccount::program_owned(5_000);
+    let mut trader = TestAccount::wallet(0);
+
+    let result = move_lamports(&vault.info(), &trader.info(), 5_001);
+
+    assert_eq!(error_code(result), program_error(ErrorCode::MathUnderflow));
+    assert_eq!(vault.lamports, 5_000);
+    assert_eq!(trader.lamports, 0);
+}
+
+#[test]
+fn rejects_credit_overflow() {
+    let mut vault = TestAccount::program_owned(5_000);
+    let mut whale = TestAccount::wallet(u64::MAX - 10);
+
+    let result = move_lamports(&vault.info(), &whale.info(), 11);
+
+    assert_eq!(error_code(result), program_error(ErrorCode::MathOverflow));
+    assert_eq!(vault.lamports, 5_000);
+}
+
+#[test]
+fn drain_empties_the_vault() {
+    // Closing a curve's SOL vault or the global config
+    let mut vault = TestAccount::program_owned(7_777);
+    let mut treasury = TestAccount::wallet(1);
+
+    let moved = drain_lamports(&vault.info(), &treasury.info()).unwrap();
+
+    assert_eq!(moved, 7_777);
+    assert_eq!(vault.lamports, 0);
+    assert_eq!(treasury.lamports, 7_778);
+}
+
+#[test]
+fn drain_rejects_foreign_account() {
+    let mut wallet = TestAccount::wallet(7_777);
+    let mut attacker = TestAccount::wallet(0);
+
+    let result = drain_lamports(&wallet.info(), &attacker.info());
+
+    assert_eq!(error_code(result), program_error(ErrorCode::VaultNotProgramOwned));
+    assert_eq!(wallet.lamports, 7_777);
+}
+
+#[test]
+fn ownership_check() {
+    let mut vault = TestAccount::program_owned(0);
+    let mut wallet = TestAccount::wallet(0);
+
+    assert!(require_program_owned(&vault.info()).is_ok());
+    assert_eq!(
+        error_code(require_program_owned(&wallet.info())),
+        program_error(ErrorCode::VaultNotProgramOwned)
+    );
+}
@@ -0,0 +1,93 @@
+//! Property tests for the vesting unlock schedule.
+
+use anchor_lang::prelude::Pubkey;
+use fundly::VestingSchedule;
+use proptest::prelude::*;
+
+// Schedules as `initialize_vesting` accepts them: 0 <= cliff < duration
+fn schedule() -> impl Strategy<Value = VestingSchedule> {
+    (any::<u64>(), -1_000_000_000i64..1_000_000_000, 1i64..100_000_000)
+        .prop_flat_map(|(total_amount, start_time, duration)| {
+            (Just(total_amount), Just(start_time), Just(duration), 0..duration)
+        })
+        .prop_map(|(total_amount, start_time, duration, cliff)| VestingSchedule {
+            beneficiary: Pubkey::default(),
+            mint: Pubkey::default(),
+            total_amount,
+            claimed_amount: 0,
+            start_time,
+            cliff_time: start_time + cliff,
+            end_time: start_time + duration,
+            release_interval: 1,
+            last_claim_time: start_time,
+            bump: 0,
+            claim_delegate: Pubkey::default(),
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2_000))]
+
+    #[test]
+    fn unlocked_never_exceeds_total(schedule in schedule(), now in any::<i64>()) {
+        prop_assert!(schedule.unlocked_amount(now).unwrap() <= schedule.total_amount);
+    }
+
+    #[test]
+    fn nothing_unlocks_before_cliff(schedule in schedule(), before in 1i64..1_000_000_000) {
+        prop_assert_eq!(schedule.unlocked_amount(schedule.cliff_time - before).unwrap(), 0);
+    }
+
+    #[test]
+    fn everything_unlocks_at_end(schedule in schedule(), after in 0i64..1_000_000_000) {
+        prop_assert_eq!(schedule.unlocked_amount(schedule.end_time + after).unwrap(), schedule.total_amount);
+    }
+
+    #[test]
+    fn unlocking_is_monotonic(schedule in schedule(), a in any::<i64>(), b in any::<i64>()) {
+        let (earlier, later) = if a <= b { (a, b) } else { (b, a) };
+        prop_assert!(schedule.unlocked_amount(earlier).unwrap() <= schedule.unlocked_amount(later).unwrap());
+    }
+
+    #[test]
+    fn repeated_claims_never_exceed_total(
+        schedule in schedule(),
+        mut claim_times in prop::collection::vec(any::<i64>(), 1..16),
+    ) {
+        // Claim the difference at each time, as `claim_vested_tokens` does
+        claim_times.sort();
+        let mut claimed = 0u64;
+        for now in claim_times {
+            let unlocked = schedule.unlocked_amount(now).unwrap();
+            claimed += unlocked.saturating_sub(claimed);
+            prop_assert!(claimed <= schedule.total_amount);
+        }
+    }
+
+    #[test]
+    fn never_panics_on_arbitrary_schedules(
+        total_amount in any::<u64>(),
+        start_time in any::<i64>(),
+        cliff_time in any::<i64>(),
+        end_time in any::<i64>(),
+        now in any::<i64>(),
+    ) {
+        // Even schedules `initialize_vesting` would reject fail cleanly instead of panicking
+        let schedule = VestingSchedule {
+            beneficiary: Pubkey::default(),
+            mint: Pubkey::default(),
+            total_amount,
+            claimed_amount: 0,
+            start_time,
+            cliff_time,
+            end_time,
+            release_interval: 1,
+            last_claim_time: start_time,
+            bump: 0,
+            claim_delegate: Pubkey::default(),
+        };
+        if let Ok(unlocked) = schedule.unlocked_amount(now) {
+            prop_assert!(unlocked <= total_amount);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+declare_id!("7Sw8qzC19vUGbFZLmjh99QjzenFubkjXyYnuVdua4pk");
+
+/// Minimal stand-in for a multisig (e.g., Squads) used by the fundly integration tests.
+/// The vault PDA signs arbitrary instructions via CPI, exactly like a real multisig
+/// vault does once a proposal is approved.
+#[program]
+pub mod mock_multisig {
+    use super::*;
+
+    /// Execute an instruction against `target_program` with the vault PDA as signer
+    /// The target's accounts are passed as remaining accounts, in order
+    pub fn execute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Execute<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == vault_key,
+                is_writable: account.is_writable,
+            })
+            .collect();
+
+        let instruction = Instruction {
+            program_id: ctx.accounts.target_program.key(),
+            accounts,
+            data,
+        };
+
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.target_program.to_account_info());
+
+        let vault_seeds: &[&[u8]] = &[b"vault", &[ctx.bumps.vault]];
+        invoke_signed(&instruction, &account_infos, &[vault_seeds])?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    /// Vault PDA acting as the multisig's signing authority
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump,
+    )]
+    /// CHECK: This is a PDA used only as a CPI signer
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: Program being invoked on behalf of the vault
+    #[account(executable)]
+    pub target_program: AccountInfo<'info>,
+
+    /// Stand-in for the approving members
+    pub member: Signer<'info>,
+}